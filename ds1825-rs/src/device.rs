@@ -0,0 +1,52 @@
+use crate::location;
+use core::ops::{Deref, DerefMut};
+use ds18b20::Ds18b20;
+use embedded_onewire::{OneWire, OneWireResult};
+
+/// A DS1825 digital thermometer on a 1-Wire bus.
+///
+/// The DS1825 is operated like a DS18B20: the driver wraps a [`Ds18b20`] driver, whose
+/// methods are available through [`Deref`], and adds the hardware address of the device.
+#[derive(Debug)]
+pub struct Ds1825<O> {
+    pub(crate) inner: Ds18b20<O>,
+}
+
+impl<O> Ds1825<O> {
+    /// Creates a driver for the device with ROM `rom` on `bus`.
+    ///
+    /// See [`Ds18b20::new`].
+    pub fn new(bus: O, rom: Option<u64>) -> Self {
+        Ds1825 {
+            inner: Ds18b20::new(bus, rom),
+        }
+    }
+
+    /// Release the bus master.
+    pub fn release(self) -> O {
+        self.inner.release()
+    }
+}
+
+impl<O> Deref for Ds1825<O> {
+    type Target = Ds18b20<O>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+impl<O> DerefMut for Ds1825<O> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.inner
+    }
+}
+
+impl<O: OneWire> Ds1825<O> {
+    /// Read the hardware address set by the AD0 to AD3 pins.
+    ///
+    /// The address is read from the configuration register in the scratchpad.
+    pub fn location(&mut self) -> OneWireResult<u8, O::BusError> {
+        Ok(location(self.inner.read_scratchpad()?.config))
+    }
+}
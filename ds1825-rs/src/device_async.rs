@@ -0,0 +1,11 @@
+use crate::{Ds1825, location};
+use embedded_onewire::{OneWireAsync, OneWireResult};
+
+impl<O: OneWireAsync> Ds1825<O> {
+    /// Read the hardware address set by the AD0 to AD3 pins.
+    ///
+    /// See [`Ds1825::location`].
+    pub async fn location_async(&mut self) -> OneWireResult<u8, O::BusError> {
+        Ok(location(self.inner.read_scratchpad_async().await?.config))
+    }
+}
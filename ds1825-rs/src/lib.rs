@@ -0,0 +1,66 @@
+#![no_std]
+#![deny(missing_docs)]
+#![cfg_attr(docsrs, feature(doc_cfg))]
+#![doc = include_str!("../README.md")]
+
+mod device;
+mod device_async;
+
+pub use device::Ds1825;
+pub use ds18b20::{Ds18b20, Resolution, Scratchpad, Temperature};
+pub use embedded_onewire::{OneWireError, OneWireResult};
+
+/// Family code of the DS1825, the low byte of its ROM.
+///
+/// Shared with the MAX31826 and the MAX31850.
+pub const FAMILY_CODE: u8 = 0x3b;
+
+/// Hardware address set by the AD0 to AD3 pins, from the configuration register.
+///
+/// The resolution bits of the register are shared with the DS18B20, and the address pins
+/// are read in the low bits.
+pub(crate) fn location(config: u8) -> u8 {
+    config & 0xf
+}
+
+mod test {
+    #[test]
+    fn test_config() {
+        use super::*;
+        assert_eq!(location(0x73), 3);
+        assert_eq!(location(0x3a), 10);
+        assert_eq!(location(0x1f), 15);
+    }
+
+    #[test]
+    fn test_location() {
+        use super::*;
+        use embedded_hal_mock::eh1::delay::NoopDelay;
+        use embedded_onewire::testing::{Mock, Transaction};
+        extern crate std;
+
+        let rom = 0x2e00_0000_1234_563b;
+        let bus = Mock::new(&[
+            Transaction::reset(true),
+            Transaction::match_rom(rom),
+            Transaction::write(&[0x4e, 0x4b, 0x46, 0x3f]),
+            Transaction::reset(true),
+            Transaction::match_rom(rom),
+            Transaction::write(&[0xbe]),
+            Transaction::read(&[0x5e, 0xff, 0x4b, 0x46, 0x3a, 0xff, 0xff, 0xff, 0x6f]),
+            Transaction::reset(true),
+            Transaction::match_rom(rom),
+            Transaction::write(&[0x44]),
+            Transaction::reset(true),
+            Transaction::match_rom(rom),
+            Transaction::write(&[0xbe]),
+            Transaction::read(&[0x5e, 0xff, 0x4b, 0x46, 0x3a, 0xff, 0xff, 0xff, 0x6f]),
+        ]);
+        let mut sensor = Ds1825::new(bus, Some(rom));
+        sensor.configure(75, 70, Resolution::Bits10).unwrap();
+        assert_eq!(sensor.location().unwrap(), 10);
+        let temperature = sensor.measure(&mut NoopDelay::new()).unwrap();
+        assert_eq!(temperature.millidegrees(), -10_250);
+        sensor.release().done();
+    }
+}
@@ -0,0 +1,204 @@
+use crate::{
+    CONVERT_T_CMD, COPY_SCRATCHPAD_CMD, COPY_SCRATCHPAD_US, READ_POWER_SUPPLY_CMD,
+    READ_SCRATCHPAD_CMD, RECALL_E2_CMD, Resolution, Scratchpad, Temperature, WRITE_SCRATCHPAD_CMD,
+};
+use embedded_hal::delay::DelayNs;
+use embedded_onewire::{OneWire, OneWireError, OneWireResult, OneWireStrongPullup};
+
+/// Number of read time slots to wait for a recall of the EEPROM to complete.
+pub(crate) const RECALL_E2_POLLS: usize = 32;
+
+/// A DS18B20 digital thermometer on a 1-Wire bus.
+///
+/// The driver owns the bus master, which can be a `&mut` reference to one or a handle to a
+/// shared one. Every operation addresses the device first. Operations that write the
+/// configuration keep track of the resolution, which sets the time waited for a conversion.
+#[derive(Debug)]
+pub struct Ds18b20<O> {
+    pub(crate) bus: O,
+    pub(crate) rom: Option<u64>,
+    pub(crate) resolution: Resolution,
+}
+
+impl<O> Ds18b20<O> {
+    /// Creates a driver for the device with ROM `rom` on `bus`.
+    ///
+    /// Pass [`None`] to skip ROM addressing on a bus with a single device. On a bus with
+    /// several devices, this addresses all of them at once, which is only useful to start
+    /// a conversion on all of them or to write the same configuration to all of them.
+    ///
+    /// The resolution is assumed to be the power-on default of 12 bits until the device is
+    /// configured or its scratchpad is read.
+    pub fn new(bus: O, rom: Option<u64>) -> Self {
+        Ds18b20 {
+            bus,
+            rom,
+            resolution: Resolution::default(),
+        }
+    }
+
+    /// ROM of the device, if it is addressed by ROM.
+    pub fn rom(&self) -> Option<u64> {
+        self.rom
+    }
+
+    /// The resolution last configured or read from the device.
+    pub fn resolution(&self) -> Resolution {
+        self.resolution
+    }
+
+    /// Release the bus master.
+    pub fn release(self) -> O {
+        self.bus
+    }
+}
+
+impl<O: OneWire> Ds18b20<O> {
+    fn command(&mut self, cmd: u8) -> OneWireResult<(), O::BusError> {
+        self.bus.address(self.rom)?;
+        self.bus.write_byte(cmd)
+    }
+
+    /// Start a temperature conversion.
+    ///
+    /// An externally powered device signals the end of the conversion in the read time
+    /// slots that follow, see [`Ds18b20::conversion_done`]. A parasitically powered device
+    /// needs [`Ds18b20::start_conversion_powered`] instead.
+    pub fn start_conversion(&mut self) -> OneWireResult<(), O::BusError> {
+        self.command(CONVERT_T_CMD)
+    }
+
+    /// Whether the conversion started by [`Ds18b20::start_conversion`] is complete.
+    ///
+    /// Only valid for an externally powered device, right after the conversion is started
+    /// and before any other operation on the bus.
+    pub fn conversion_done(&mut self) -> OneWireResult<bool, O::BusError> {
+        self.bus.read_bit()
+    }
+
+    /// Read the scratchpad, and update the resolution from it.
+    ///
+    /// # Errors
+    /// Returns [`OneWireError::InvalidCrc`] if the CRC of the scratchpad does not match,
+    /// e.g. because no device responded.
+    pub fn read_scratchpad(&mut self) -> OneWireResult<Scratchpad, O::BusError> {
+        self.command(READ_SCRATCHPAD_CMD)?;
+        let mut buf = [0; Scratchpad::LEN];
+        self.bus.read_bytes(&mut buf)?;
+        let scratchpad = Scratchpad::parse(&buf)?;
+        self.resolution = scratchpad.resolution;
+        Ok(scratchpad)
+    }
+
+    /// Read the result of the last temperature conversion.
+    pub fn read_temperature(&mut self) -> OneWireResult<Temperature, O::BusError> {
+        Ok(self.read_scratchpad()?.temperature)
+    }
+
+    /// Start a temperature conversion, wait for it to complete and read the result.
+    ///
+    /// Waits for the maximum conversion time at the current [`Ds18b20::resolution`].
+    pub fn measure<D: DelayNs>(
+        &mut self,
+        delay: &mut D,
+    ) -> OneWireResult<Temperature, O::BusError> {
+        self.start_conversion()?;
+        delay.delay_us(self.resolution.conversion_time_us());
+        self.read_temperature()
+    }
+
+    /// Write the alarm thresholds, in °C, and the resolution to the scratchpad.
+    ///
+    /// The configuration is lost at power-off unless saved with [`Ds18b20::save_config`].
+    pub fn configure(
+        &mut self,
+        alarm_high: i8,
+        alarm_low: i8,
+        resolution: Resolution,
+    ) -> OneWireResult<(), O::BusError> {
+        if alarm_low > alarm_high {
+            return Err(OneWireError::InvalidValue("alarm_low"));
+        }
+        self.command(WRITE_SCRATCHPAD_CMD)?;
+        self.bus
+            .write_bytes(&[alarm_high as u8, alarm_low as u8, resolution.config()])?;
+        self.resolution = resolution;
+        Ok(())
+    }
+
+    /// Copy the alarm thresholds and the resolution from the scratchpad to the EEPROM, and
+    /// wait for the copy to complete.
+    ///
+    /// A parasitically powered device needs [`Ds18b20::save_config_powered`] instead.
+    pub fn save_config<D: DelayNs>(&mut self, delay: &mut D) -> OneWireResult<(), O::BusError> {
+        self.command(COPY_SCRATCHPAD_CMD)?;
+        delay.delay_us(COPY_SCRATCHPAD_US);
+        Ok(())
+    }
+
+    /// Recall the alarm thresholds and the resolution from the EEPROM to the scratchpad,
+    /// and read the scratchpad.
+    ///
+    /// # Errors
+    /// Returns [`OneWireError::BusInUse`] if the device does not signal the end of the
+    /// recall.
+    pub fn recall_config(&mut self) -> OneWireResult<Scratchpad, O::BusError> {
+        self.command(RECALL_E2_CMD)?;
+        for _ in 0..RECALL_E2_POLLS {
+            if self.bus.read_bit()? {
+                return self.read_scratchpad();
+            }
+        }
+        Err(OneWireError::BusInUse)
+    }
+
+    /// Whether the device is parasitically powered.
+    ///
+    /// When the device is addressed with [`None`], this is true if any device on the bus
+    /// is parasitically powered.
+    pub fn is_parasite_powered(&mut self) -> OneWireResult<bool, O::BusError> {
+        self.command(READ_POWER_SUPPLY_CMD)?;
+        // Parasitically powered devices pull the bus low
+        Ok(!self.bus.read_bit()?)
+    }
+}
+
+impl<O: OneWireStrongPullup> Ds18b20<O> {
+    /// Start a temperature conversion, powering a parasitically powered device with the
+    /// strong pullup of the bus master.
+    ///
+    /// The strong pullup lasts until the next operation on the bus, which must wait for
+    /// [`Resolution::conversion_time_us`].
+    pub fn start_conversion_powered(&mut self) -> OneWireResult<(), O::BusError> {
+        self.bus.address(self.rom)?;
+        self.bus.write_byte_powered(CONVERT_T_CMD)
+    }
+
+    /// Start a temperature conversion with the strong pullup, wait for it to complete and
+    /// read the result.
+    ///
+    /// See [`Ds18b20::measure`] and [`Ds18b20::start_conversion_powered`].
+    pub fn measure_powered<D: DelayNs>(
+        &mut self,
+        delay: &mut D,
+    ) -> OneWireResult<Temperature, O::BusError> {
+        self.start_conversion_powered()?;
+        delay.delay_us(self.resolution.conversion_time_us());
+        self.read_temperature()
+    }
+
+    /// Copy the alarm thresholds and the resolution from the scratchpad to the EEPROM,
+    /// powering a parasitically powered device with the strong pullup of the bus master
+    /// until the copy completes.
+    pub fn save_config_powered<D: DelayNs>(
+        &mut self,
+        delay: &mut D,
+    ) -> OneWireResult<(), O::BusError> {
+        self.bus.address(self.rom)?;
+        self.bus.write_byte_powered(COPY_SCRATCHPAD_CMD)?;
+        delay.delay_us(COPY_SCRATCHPAD_US);
+        // End the strong pullup
+        self.bus.reset()?;
+        Ok(())
+    }
+}
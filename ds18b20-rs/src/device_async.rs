@@ -0,0 +1,155 @@
+use crate::{
+    CONVERT_T_CMD, COPY_SCRATCHPAD_CMD, COPY_SCRATCHPAD_US, Ds18b20, READ_POWER_SUPPLY_CMD,
+    READ_SCRATCHPAD_CMD, RECALL_E2_CMD, Resolution, Scratchpad, Temperature, WRITE_SCRATCHPAD_CMD,
+    device::RECALL_E2_POLLS,
+};
+use embedded_hal_async::delay::DelayNs as DelayNsAsync;
+use embedded_onewire::{OneWireAsync, OneWireError, OneWireResult, OneWireStrongPullupAsync};
+
+impl<O: OneWireAsync> Ds18b20<O> {
+    async fn command_async(&mut self, cmd: u8) -> OneWireResult<(), O::BusError> {
+        OneWireAsync::address(&mut self.bus, self.rom).await?;
+        OneWireAsync::write_byte(&mut self.bus, cmd).await
+    }
+
+    /// Start a temperature conversion.
+    ///
+    /// See [`Ds18b20::start_conversion`].
+    pub async fn start_conversion_async(&mut self) -> OneWireResult<(), O::BusError> {
+        self.command_async(CONVERT_T_CMD).await
+    }
+
+    /// Whether the conversion started by [`Ds18b20::start_conversion_async`] is complete.
+    ///
+    /// See [`Ds18b20::conversion_done`].
+    pub async fn conversion_done_async(&mut self) -> OneWireResult<bool, O::BusError> {
+        OneWireAsync::read_bit(&mut self.bus).await
+    }
+
+    /// Read the scratchpad, and update the resolution from it.
+    ///
+    /// See [`Ds18b20::read_scratchpad`].
+    pub async fn read_scratchpad_async(&mut self) -> OneWireResult<Scratchpad, O::BusError> {
+        self.command_async(READ_SCRATCHPAD_CMD).await?;
+        let mut buf = [0; Scratchpad::LEN];
+        OneWireAsync::read_bytes(&mut self.bus, &mut buf).await?;
+        let scratchpad = Scratchpad::parse(&buf)?;
+        self.resolution = scratchpad.resolution;
+        Ok(scratchpad)
+    }
+
+    /// Read the result of the last temperature conversion.
+    pub async fn read_temperature_async(&mut self) -> OneWireResult<Temperature, O::BusError> {
+        Ok(self.read_scratchpad_async().await?.temperature)
+    }
+
+    /// Start a temperature conversion, wait for it to complete and read the result.
+    ///
+    /// See [`Ds18b20::measure`].
+    pub async fn measure_async<D: DelayNsAsync>(
+        &mut self,
+        delay: &mut D,
+    ) -> OneWireResult<Temperature, O::BusError> {
+        self.start_conversion_async().await?;
+        delay.delay_us(self.resolution.conversion_time_us()).await;
+        self.read_temperature_async().await
+    }
+
+    /// Write the alarm thresholds, in °C, and the resolution to the scratchpad.
+    ///
+    /// See [`Ds18b20::configure`].
+    pub async fn configure_async(
+        &mut self,
+        alarm_high: i8,
+        alarm_low: i8,
+        resolution: Resolution,
+    ) -> OneWireResult<(), O::BusError> {
+        if alarm_low > alarm_high {
+            return Err(OneWireError::InvalidValue("alarm_low"));
+        }
+        self.command_async(WRITE_SCRATCHPAD_CMD).await?;
+        OneWireAsync::write_bytes(
+            &mut self.bus,
+            &[alarm_high as u8, alarm_low as u8, resolution.config()],
+        )
+        .await?;
+        self.resolution = resolution;
+        Ok(())
+    }
+
+    /// Copy the alarm thresholds and the resolution from the scratchpad to the EEPROM, and
+    /// wait for the copy to complete.
+    ///
+    /// See [`Ds18b20::save_config`].
+    pub async fn save_config_async<D: DelayNsAsync>(
+        &mut self,
+        delay: &mut D,
+    ) -> OneWireResult<(), O::BusError> {
+        self.command_async(COPY_SCRATCHPAD_CMD).await?;
+        delay.delay_us(COPY_SCRATCHPAD_US).await;
+        Ok(())
+    }
+
+    /// Recall the alarm thresholds and the resolution from the EEPROM to the scratchpad,
+    /// and read the scratchpad.
+    ///
+    /// See [`Ds18b20::recall_config`].
+    pub async fn recall_config_async(&mut self) -> OneWireResult<Scratchpad, O::BusError> {
+        self.command_async(RECALL_E2_CMD).await?;
+        for _ in 0..RECALL_E2_POLLS {
+            if OneWireAsync::read_bit(&mut self.bus).await? {
+                return self.read_scratchpad_async().await;
+            }
+        }
+        Err(OneWireError::BusInUse)
+    }
+
+    /// Whether the device is parasitically powered.
+    ///
+    /// See [`Ds18b20::is_parasite_powered`].
+    pub async fn is_parasite_powered_async(&mut self) -> OneWireResult<bool, O::BusError> {
+        self.command_async(READ_POWER_SUPPLY_CMD).await?;
+        Ok(!OneWireAsync::read_bit(&mut self.bus).await?)
+    }
+}
+
+impl<O: OneWireStrongPullupAsync> Ds18b20<O> {
+    /// Start a temperature conversion, powering a parasitically powered device with the
+    /// strong pullup of the bus master.
+    ///
+    /// See [`Ds18b20::start_conversion_powered`].
+    pub async fn start_conversion_powered_async(&mut self) -> OneWireResult<(), O::BusError> {
+        OneWireAsync::address(&mut self.bus, self.rom).await?;
+        OneWireStrongPullupAsync::write_byte_powered(&mut self.bus, CONVERT_T_CMD).await
+    }
+
+    /// Start a temperature conversion with the strong pullup, wait for it to complete and
+    /// read the result.
+    ///
+    /// See [`Ds18b20::measure_powered`].
+    pub async fn measure_powered_async<D: DelayNsAsync>(
+        &mut self,
+        delay: &mut D,
+    ) -> OneWireResult<Temperature, O::BusError> {
+        self.start_conversion_powered_async().await?;
+        delay.delay_us(self.resolution.conversion_time_us()).await;
+        self.read_temperature_async().await
+    }
+
+    /// Copy the alarm thresholds and the resolution from the scratchpad to the EEPROM,
+    /// powering a parasitically powered device with the strong pullup of the bus master
+    /// until the copy completes.
+    ///
+    /// See [`Ds18b20::save_config_powered`].
+    pub async fn save_config_powered_async<D: DelayNsAsync>(
+        &mut self,
+        delay: &mut D,
+    ) -> OneWireResult<(), O::BusError> {
+        OneWireAsync::address(&mut self.bus, self.rom).await?;
+        OneWireStrongPullupAsync::write_byte_powered(&mut self.bus, COPY_SCRATCHPAD_CMD).await?;
+        delay.delay_us(COPY_SCRATCHPAD_US).await;
+        // End the strong pullup
+        OneWireAsync::reset(&mut self.bus).await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,257 @@
+#![no_std]
+#![deny(missing_docs)]
+#![cfg_attr(docsrs, feature(doc_cfg))]
+#![doc = include_str!("../README.md")]
+
+mod device;
+mod device_async;
+
+pub use device::Ds18b20;
+pub use embedded_onewire::{OneWireError, OneWireResult};
+
+/// Family code of the DS18B20, the low byte of its ROM.
+pub const FAMILY_CODE: u8 = 0x28;
+
+pub(crate) const CONVERT_T_CMD: u8 = 0x44;
+pub(crate) const WRITE_SCRATCHPAD_CMD: u8 = 0x4e;
+pub(crate) const READ_SCRATCHPAD_CMD: u8 = 0xbe;
+pub(crate) const COPY_SCRATCHPAD_CMD: u8 = 0x48;
+pub(crate) const RECALL_E2_CMD: u8 = 0xb8;
+pub(crate) const READ_POWER_SUPPLY_CMD: u8 = 0xb4;
+
+/// Time for the scratchpad to be copied to the EEPROM, in microseconds.
+pub(crate) const COPY_SCRATCHPAD_US: u32 = 10_000;
+
+/// Resolution of the temperature conversion.
+///
+/// A finer resolution takes longer to convert, see [`Resolution::conversion_time_us`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum Resolution {
+    /// 9 bits, 0.5 °C.
+    Bits9 = 0,
+    /// 10 bits, 0.25 °C.
+    Bits10 = 1,
+    /// 11 bits, 0.125 °C.
+    Bits11 = 2,
+    /// 12 bits, 0.0625 °C. Power-on default.
+    #[default]
+    Bits12 = 3,
+}
+
+impl Resolution {
+    /// Maximum conversion time at this resolution, in microseconds.
+    pub fn conversion_time_us(&self) -> u32 {
+        750_000 >> (3 - *self as u8)
+    }
+
+    /// Value of the configuration register.
+    pub(crate) fn config(&self) -> u8 {
+        ((*self as u8) << 5) | 0x1f
+    }
+
+    pub(crate) fn from_config(config: u8) -> Self {
+        match (config >> 5) & 0x3 {
+            0 => Resolution::Bits9,
+            1 => Resolution::Bits10,
+            2 => Resolution::Bits11,
+            _ => Resolution::Bits12,
+        }
+    }
+}
+
+/// A temperature reading, in units of 1/16 °C.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Temperature(i16);
+
+impl Temperature {
+    /// The value of the temperature register at power-on, 85 °C.
+    ///
+    /// Reading it usually means that no conversion took place, e.g. because the device lost
+    /// power during the conversion.
+    pub const POWER_ON_RESET: Temperature = Temperature(0x0550);
+
+    /// Temperature from the raw register value.
+    pub fn from_raw(raw: i16) -> Self {
+        Temperature(raw)
+    }
+
+    /// The raw register value, in units of 1/16 °C.
+    pub fn raw(&self) -> i16 {
+        self.0
+    }
+
+    /// The temperature in thousandths of a degree Celsius, truncated.
+    pub fn millidegrees(&self) -> i32 {
+        self.0 as i32 * 125 / 2
+    }
+
+    /// The temperature in degrees Celsius.
+    pub fn celsius(&self) -> f32 {
+        self.0 as f32 / 16.0
+    }
+}
+
+/// Contents of the DS18B20 scratchpad.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Scratchpad {
+    /// Result of the last temperature conversion. The bits below the configured resolution
+    /// are cleared.
+    pub temperature: Temperature,
+    /// Upper alarm threshold, in °C. The device responds to an alarm search if the
+    /// temperature is higher than or equal to this value.
+    pub alarm_high: i8,
+    /// Lower alarm threshold, in °C. The device responds to an alarm search if the
+    /// temperature is lower than or equal to this value.
+    pub alarm_low: i8,
+    /// Resolution of the temperature conversion.
+    pub resolution: Resolution,
+    /// Raw value of the configuration register, which holds the resolution. Compatible
+    /// devices report more in the other bits, e.g. the address pins of the DS1825.
+    pub config: u8,
+}
+
+impl Scratchpad {
+    /// Length of the scratchpad including its CRC.
+    pub(crate) const LEN: usize = 9;
+
+    /// Parse the scratchpad read from the device.
+    ///
+    /// # Errors
+    /// Returns [`OneWireError::InvalidCrc`] if the CRC does not match the contents.
+    pub(crate) fn parse<E>(bytes: &[u8; Self::LEN]) -> OneWireResult<Self, E> {
+        if !embedded_onewire::OneWireCrc::validate(bytes) {
+            return Err(OneWireError::InvalidCrc);
+        }
+        let resolution = Resolution::from_config(bytes[4]);
+        // The low bits are undefined at lower resolutions
+        let mask = !((1i16 << (3 - resolution as u8)) - 1);
+        Ok(Scratchpad {
+            temperature: Temperature(i16::from_le_bytes([bytes[0], bytes[1]]) & mask),
+            alarm_high: bytes[2] as i8,
+            alarm_low: bytes[3] as i8,
+            resolution,
+            config: bytes[4],
+        })
+    }
+}
+
+mod test {
+    #[test]
+    fn test_temperature() {
+        use super::*;
+        // Examples from the datasheet
+        assert_eq!(Temperature::from_raw(0x07d0).millidegrees(), 125_000);
+        assert_eq!(Temperature::from_raw(0x0191).millidegrees(), 25_062);
+        assert_eq!(Temperature::from_raw(0x0008).celsius(), 0.5);
+        assert_eq!(Temperature::from_raw(0xff5e_u16 as i16).celsius(), -10.125);
+        assert_eq!(
+            Temperature::from_raw(0xfc90_u16 as i16).millidegrees(),
+            -55_000
+        );
+        assert_eq!(Temperature::POWER_ON_RESET.celsius(), 85.0);
+    }
+
+    #[test]
+    fn test_resolution() {
+        use super::*;
+        assert_eq!(Resolution::Bits9.conversion_time_us(), 93_750);
+        assert_eq!(Resolution::Bits12.conversion_time_us(), 750_000);
+        assert_eq!(Resolution::Bits9.config(), 0x1f);
+        assert_eq!(Resolution::Bits12.config(), 0x7f);
+        for res in [
+            Resolution::Bits9,
+            Resolution::Bits10,
+            Resolution::Bits11,
+            Resolution::Bits12,
+        ] {
+            assert_eq!(Resolution::from_config(res.config()), res);
+        }
+    }
+
+    #[test]
+    fn test_scratchpad() {
+        use super::*;
+        let bytes = [0x91, 0x01, 0x4b, 0x46, 0x7f, 0xff, 0x0f, 0x10, 0x25];
+        let scratchpad = Scratchpad::parse::<()>(&bytes).unwrap();
+        assert_eq!(scratchpad.temperature, Temperature::from_raw(0x0191));
+        assert_eq!(scratchpad.alarm_high, 75);
+        assert_eq!(scratchpad.alarm_low, 70);
+        assert_eq!(scratchpad.resolution, Resolution::Bits12);
+        assert_eq!(scratchpad.config, 0x7f);
+        // 10 bits, the two lowest bits are undefined
+        let bytes = [0x5e, 0xff, 0x4b, 0x46, 0x3f, 0xff, 0x02, 0x10, 0x56];
+        let scratchpad = Scratchpad::parse::<()>(&bytes).unwrap();
+        assert_eq!(scratchpad.temperature.celsius(), -10.25);
+        assert_eq!(scratchpad.resolution, Resolution::Bits10);
+        let mut bytes = bytes;
+        bytes[2] = 0x4c;
+        assert!(matches!(
+            Scratchpad::parse::<()>(&bytes),
+            Err(OneWireError::InvalidCrc)
+        ));
+    }
+
+    #[test]
+    fn test_measure() {
+        use super::*;
+        use embedded_hal_mock::eh1::delay::NoopDelay;
+        use embedded_onewire::testing::{Mock, Transaction};
+        extern crate std;
+
+        let rom = 0x9e06050403020128;
+        let bus = Mock::new(&[
+            Transaction::reset(true),
+            Transaction::match_rom(rom),
+            Transaction::write(&[CONVERT_T_CMD]),
+            Transaction::reset(true),
+            Transaction::match_rom(rom),
+            Transaction::write(&[READ_SCRATCHPAD_CMD]),
+            Transaction::read(&[0x91, 0x01, 0x4b, 0x46, 0x7f, 0xff, 0x0f, 0x10, 0x25]),
+        ]);
+        let mut sensor = Ds18b20::new(bus, Some(rom));
+        let temperature = sensor.measure(&mut NoopDelay::new()).unwrap();
+        assert_eq!(temperature.millidegrees(), 25_062);
+        sensor.release().done();
+    }
+
+    #[test]
+    fn test_measure_powered() {
+        use super::*;
+        use embedded_hal_mock::eh1::delay::NoopDelay;
+        use embedded_onewire::testing::{Mock, Transaction};
+        extern crate std;
+
+        let bus = Mock::new(&[
+            // Parasite powered
+            Transaction::reset(true),
+            Transaction::skip_rom(),
+            Transaction::write(&[READ_POWER_SUPPLY_CMD]),
+            Transaction::read_bit(false),
+            // Set up 9 bits, and save to the EEPROM
+            Transaction::reset(true),
+            Transaction::skip_rom(),
+            Transaction::write(&[WRITE_SCRATCHPAD_CMD, 0x4b, 0xf6, 0x1f]),
+            Transaction::reset(true),
+            Transaction::skip_rom(),
+            Transaction::write_powered(COPY_SCRATCHPAD_CMD),
+            Transaction::reset(true),
+            // Convert
+            Transaction::reset(true),
+            Transaction::skip_rom(),
+            Transaction::write_powered(CONVERT_T_CMD),
+            Transaction::reset(true),
+            Transaction::skip_rom(),
+            Transaction::write(&[READ_SCRATCHPAD_CMD]),
+            Transaction::read(&[0x50, 0x05, 0x4b, 0xf6, 0x1f, 0xff, 0x0c, 0x10, 0xc3]),
+        ]);
+        let mut sensor = Ds18b20::new(bus, None);
+        assert!(sensor.is_parasite_powered().unwrap());
+        sensor.configure(75, -10, Resolution::Bits9).unwrap();
+        sensor.save_config_powered(&mut NoopDelay::new()).unwrap();
+        assert_eq!(sensor.resolution(), Resolution::Bits9);
+        let temperature = sensor.measure_powered(&mut NoopDelay::new()).unwrap();
+        assert_eq!(temperature, Temperature::POWER_ON_RESET);
+        sensor.release().done();
+    }
+}
@@ -0,0 +1,318 @@
+use crate::{
+    CLEAR_MEMORY_CMD, CLEAR_TIME_US, CLOCK_ADDRESS, COPY_DONE, COPY_SCRATCHPAD_PASSWORD_CMD,
+    COPY_TIME_US, DATALOG_ADDRESS, DATALOG_SIZE, DateTime, GENERAL_MEMORY_SIZE, MEMORY_END,
+    MISSION_ADDRESS, MISSION_SIZE, Mission, PAGE_SIZE, PASSWORD_CONTROL_ADDRESS, PASSWORD_SIZE,
+    PASSWORDS_ENABLED, PASSWORDS_SIZE, Password, READ_MEMORY_PASSWORD_CMD, RTC_EOSC,
+    START_MISSION_CMD, STATUS_SIZE, STOP_MISSION_CMD, Status,
+};
+use embedded_hal::delay::DelayNs;
+use embedded_onewire::{OneWire, OneWireCrc16, OneWireError, OneWireResult};
+use onewire_eeprom::scratchpad::{self, Scratchpad};
+
+/// A DS1922 Thermochron or DS1923 Hygrochron logger iButton on a 1-Wire bus.
+///
+/// The driver owns the bus master, which can be a `&mut` reference to one or a handle to a
+/// shared one. Every operation addresses the device first.
+///
+/// Every access to the memory carries a password. The loggers run from their own battery,
+/// so the bus does not need to be powered.
+#[derive(Debug)]
+pub struct Ds1922<O> {
+    pub(crate) bus: O,
+    pub(crate) rom: Option<u64>,
+}
+
+impl<O> Ds1922<O> {
+    /// Creates a driver for the device with ROM `rom` on `bus`.
+    ///
+    /// Pass [`None`] to skip ROM addressing on a bus with a single device.
+    pub fn new(bus: O, rom: Option<u64>) -> Self {
+        Ds1922 { bus, rom }
+    }
+
+    /// ROM of the device, if it is addressed by ROM.
+    pub fn rom(&self) -> Option<u64> {
+        self.rom
+    }
+
+    /// Release the bus master.
+    pub fn release(self) -> O {
+        self.bus
+    }
+}
+
+impl<O: OneWire> Ds1922<O> {
+    /// Read the memory from `address` into `buf`, with the read access or the full access
+    /// password.
+    ///
+    /// The device sends the CRC-16 of every page it reads, so the read always continues to
+    /// the end of the last page touched by `buf`.
+    ///
+    /// # Errors
+    /// Returns [`OneWireError::InvalidValue`] if the read goes past the end of the datalog
+    /// memory, and [`OneWireError::InvalidCrc`] if the CRC-16 of a page does not match. A
+    /// device that rejects the password stops responding, which also fails the CRC-16.
+    pub fn read(
+        &mut self,
+        address: u16,
+        buf: &mut [u8],
+        password: &Password,
+    ) -> OneWireResult<(), O::BusError> {
+        if address as usize + buf.len() > MEMORY_END {
+            return Err(OneWireError::InvalidValue("address"));
+        }
+        let [lo, hi] = address.to_le_bytes();
+        let cmd = [READ_MEMORY_PASSWORD_CMD, lo, hi];
+        self.bus.address(self.rom)?;
+        self.bus.write_bytes(&cmd)?;
+        self.bus.write_bytes(password)?;
+        // The CRC of the first page includes the command and the address
+        let mut crc = OneWireCrc16::default();
+        crc.update_slice(&cmd);
+        let mut offset = address as usize % PAGE_SIZE;
+        let mut buf = buf;
+        while !buf.is_empty() {
+            let mut page = [0; PAGE_SIZE];
+            let page = &mut page[offset..];
+            self.bus.read_bytes(page)?;
+            let mut check = [0; 2];
+            self.bus.read_bytes(&mut check)?;
+            crc.update_slice(page);
+            crc.update_slice(&check);
+            if !crc.is_valid() {
+                return Err(OneWireError::InvalidCrc);
+            }
+            let len = page.len().min(buf.len());
+            buf[..len].copy_from_slice(&page[..len]);
+            buf = &mut buf[len..];
+            crc = OneWireCrc16::default();
+            offset = 0;
+        }
+        Ok(())
+    }
+
+    /// Read the datalog memory from `offset` into `buf`.
+    ///
+    /// # Errors
+    /// Returns [`OneWireError::InvalidValue`] if the read goes past the end of the datalog
+    /// memory, and the errors of [`Ds1922::read`].
+    pub fn read_datalog(
+        &mut self,
+        offset: usize,
+        buf: &mut [u8],
+        password: &Password,
+    ) -> OneWireResult<(), O::BusError> {
+        if offset + buf.len() > DATALOG_SIZE {
+            return Err(OneWireError::InvalidValue("offset"));
+        }
+        self.read(DATALOG_ADDRESS + offset as u16, buf, password)
+    }
+
+    /// Read the state of the logger: the clock, the mission and the counters.
+    ///
+    /// # Errors
+    /// See [`Ds1922::read`].
+    pub fn status(&mut self, password: &Password) -> OneWireResult<Status, O::BusError> {
+        let mut regs = [0; STATUS_SIZE];
+        self.read(CLOCK_ADDRESS, &mut regs, password)?;
+        Ok(Status::parse(&regs))
+    }
+
+    /// Read the real-time clock.
+    ///
+    /// # Errors
+    /// See [`Ds1922::read`].
+    pub fn clock(&mut self, password: &Password) -> OneWireResult<DateTime, O::BusError> {
+        let mut regs = [0; 6];
+        self.read(CLOCK_ADDRESS, &mut regs, password)?;
+        Ok(DateTime::from_registers(&regs))
+    }
+
+    /// Set the real-time clock to `time`, and start its oscillator, with the full access
+    /// password.
+    ///
+    /// # Errors
+    /// Returns [`OneWireError::InvalidValue`] if `time` is not a valid date and time, and the
+    /// errors of [`Ds1922::write`]. The clock can not be set during a mission.
+    pub fn set_clock<D: DelayNs>(
+        &mut self,
+        time: &DateTime,
+        password: &Password,
+        delay: &mut D,
+    ) -> OneWireResult<(), O::BusError> {
+        if !time.is_valid() {
+            return Err(OneWireError::InvalidValue("time"));
+        }
+        let mut page = [0; PAGE_SIZE];
+        self.read(CLOCK_ADDRESS, &mut page, password)?;
+        page[..6].copy_from_slice(&time.into_registers());
+        page[0x12] |= RTC_EOSC;
+        self.program(CLOCK_ADDRESS, &page, password, delay)
+    }
+
+    /// Write `data` to the general purpose memory at `address`, with the full access
+    /// password.
+    ///
+    /// Every page touched by `data` is programmed in full: pages that are only partially
+    /// covered are read first, so that the rest of their bytes are written back unchanged.
+    ///
+    /// # Errors
+    /// Returns [`OneWireError::InvalidValue`] if the write goes past the end of the general
+    /// purpose memory, [`OneWireError::InvalidCrc`] if a transfer of the scratchpad is
+    /// corrupted, and [`OneWireError::VerificationFailed`] if the scratchpad read back does
+    /// not match, or the device does not confirm the copy, e.g. because it rejected the
+    /// password or a mission is in progress. The pages before the failed one are already
+    /// programmed.
+    pub fn write<D: DelayNs>(
+        &mut self,
+        address: u16,
+        data: &[u8],
+        password: &Password,
+        delay: &mut D,
+    ) -> OneWireResult<(), O::BusError> {
+        if address as usize + data.len() > GENERAL_MEMORY_SIZE {
+            return Err(OneWireError::InvalidValue("address"));
+        }
+        let mut address = address as usize;
+        let mut data = data;
+        while !data.is_empty() {
+            let offset = address % PAGE_SIZE;
+            let start = (address - offset) as u16;
+            let len = (PAGE_SIZE - offset).min(data.len());
+            let mut page = [0; PAGE_SIZE];
+            if len != PAGE_SIZE {
+                self.read(start, &mut page, password)?;
+            }
+            page[offset..offset + len].copy_from_slice(&data[..len]);
+            self.program(start, &page, password, delay)?;
+            address += len;
+            data = &data[len..];
+        }
+        Ok(())
+    }
+
+    /// Set the read access and the full access passwords, and enable or disable them, with
+    /// the current full access password.
+    ///
+    /// The passwords can not be read back, and a forgotten full access password locks the
+    /// logger for good.
+    ///
+    /// # Errors
+    /// See [`Ds1922::write`].
+    pub fn set_passwords<D: DelayNs>(
+        &mut self,
+        read: &Password,
+        full: &Password,
+        enable: bool,
+        password: &Password,
+        delay: &mut D,
+    ) -> OneWireResult<(), O::BusError> {
+        let mut regs = [0; PASSWORDS_SIZE];
+        self.read(PASSWORD_CONTROL_ADDRESS, &mut regs, password)?;
+        regs[0] = if enable { PASSWORDS_ENABLED } else { 0x00 };
+        regs[1..1 + PASSWORD_SIZE].copy_from_slice(read);
+        regs[1 + PASSWORD_SIZE..1 + 2 * PASSWORD_SIZE].copy_from_slice(full);
+        self.program(PASSWORD_CONTROL_ADDRESS, &regs, password, delay)
+    }
+
+    /// Clear the mission memory and the mission registers, with the full access password.
+    ///
+    /// The memory must be cleared before a mission is started.
+    ///
+    /// # Errors
+    /// Returns [`OneWireError::VerificationFailed`] if the memory is not cleared, e.g.
+    /// because a mission is in progress or the device rejected the password.
+    pub fn clear_memory<D: DelayNs>(
+        &mut self,
+        password: &Password,
+        delay: &mut D,
+    ) -> OneWireResult<(), O::BusError> {
+        self.command(CLEAR_MEMORY_CMD, password)?;
+        delay.delay_us(CLEAR_TIME_US);
+        if !self.status(password)?.memory_cleared {
+            return Err(OneWireError::VerificationFailed);
+        }
+        Ok(())
+    }
+
+    /// Configure `mission` and start it, with the full access password.
+    ///
+    /// The memory must be cleared first with [`Ds1922::clear_memory`]. The oscillator of the
+    /// real-time clock is started, and the alarms are left as configured.
+    ///
+    /// # Errors
+    /// Returns [`OneWireError::InvalidValue`] if the sample rate or the start delay is out
+    /// of range, and the errors of [`Ds1922::write`].
+    pub fn start_mission<D: DelayNs>(
+        &mut self,
+        mission: &Mission,
+        password: &Password,
+        delay: &mut D,
+    ) -> OneWireResult<(), O::BusError> {
+        if !mission.is_valid() {
+            return Err(OneWireError::InvalidValue("mission"));
+        }
+        let mut regs = [0; MISSION_SIZE];
+        self.read(MISSION_ADDRESS, &mut regs, password)?;
+        mission.write_registers(&mut regs);
+        self.program(MISSION_ADDRESS, &regs, password, delay)?;
+        self.command(START_MISSION_CMD, password)
+    }
+
+    /// Stop the mission in progress, with the full access password.
+    ///
+    /// The logged samples stay in the datalog memory until it is cleared.
+    pub fn stop_mission(&mut self, password: &Password) -> OneWireResult<(), O::BusError> {
+        self.command(STOP_MISSION_CMD, password)
+    }
+
+    /// Send a mission command followed by `password` and the dummy byte.
+    fn command(&mut self, cmd: u8, password: &Password) -> OneWireResult<(), O::BusError> {
+        self.bus.address(self.rom)?;
+        self.bus.write_bytes(&command_bytes(cmd, password))
+    }
+
+    /// Write `data` at `address` up to the end of its page, and copy it to the memory.
+    fn program<D: DelayNs, const N: usize>(
+        &mut self,
+        address: u16,
+        data: &[u8; N],
+        password: &Password,
+        delay: &mut D,
+    ) -> OneWireResult<(), O::BusError> {
+        scratchpad::write_scratchpad(&mut self.bus, self.rom, address, data)?;
+        let scratchpad = scratchpad::read_scratchpad(&mut self.bus, self.rom)?;
+        verify_scratchpad(&scratchpad, address, data)?;
+        let [lo, hi] = address.to_le_bytes();
+        self.bus.address(self.rom)?;
+        self.bus
+            .write_bytes(&[COPY_SCRATCHPAD_PASSWORD_CMD, lo, hi, scratchpad.es])?;
+        self.bus.write_bytes(password)?;
+        delay.delay_us(COPY_TIME_US);
+        if self.bus.read_byte()? != COPY_DONE {
+            return Err(OneWireError::VerificationFailed);
+        }
+        Ok(())
+    }
+}
+
+/// A mission command, followed by the password and the dummy byte.
+pub(crate) fn command_bytes(cmd: u8, password: &Password) -> [u8; PASSWORD_SIZE + 2] {
+    let mut bytes = [0xff; PASSWORD_SIZE + 2];
+    bytes[0] = cmd;
+    bytes[1..1 + PASSWORD_SIZE].copy_from_slice(password);
+    bytes
+}
+
+pub(crate) fn verify_scratchpad<E, const N: usize>(
+    scratchpad: &Scratchpad<N>,
+    address: u16,
+    data: &[u8; N],
+) -> OneWireResult<(), E> {
+    let ending = (PAGE_SIZE - 1) as u8;
+    if scratchpad.address != address || scratchpad.es & ending != ending || scratchpad.data != *data
+    {
+        return Err(OneWireError::VerificationFailed);
+    }
+    Ok(())
+}
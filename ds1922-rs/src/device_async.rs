@@ -0,0 +1,248 @@
+use crate::{
+    CLEAR_MEMORY_CMD, CLEAR_TIME_US, CLOCK_ADDRESS, COPY_DONE, COPY_SCRATCHPAD_PASSWORD_CMD,
+    COPY_TIME_US, DATALOG_ADDRESS, DATALOG_SIZE, DateTime, Ds1922, GENERAL_MEMORY_SIZE, MEMORY_END,
+    MISSION_ADDRESS, MISSION_SIZE, Mission, PAGE_SIZE, PASSWORD_CONTROL_ADDRESS, PASSWORD_SIZE,
+    PASSWORDS_ENABLED, PASSWORDS_SIZE, Password, READ_MEMORY_PASSWORD_CMD, RTC_EOSC,
+    START_MISSION_CMD, STATUS_SIZE, STOP_MISSION_CMD, Status,
+    device::{command_bytes, verify_scratchpad},
+};
+use embedded_hal_async::delay::DelayNs;
+use embedded_onewire::{OneWireAsync, OneWireCrc16, OneWireError, OneWireResult};
+use onewire_eeprom::scratchpad;
+
+impl<O: OneWireAsync> Ds1922<O> {
+    /// Read the memory from `address` into `buf`, with the read access or the full access
+    /// password.
+    ///
+    /// See [`Ds1922::read`].
+    pub async fn read_async(
+        &mut self,
+        address: u16,
+        buf: &mut [u8],
+        password: &Password,
+    ) -> OneWireResult<(), O::BusError> {
+        if address as usize + buf.len() > MEMORY_END {
+            return Err(OneWireError::InvalidValue("address"));
+        }
+        let [lo, hi] = address.to_le_bytes();
+        let cmd = [READ_MEMORY_PASSWORD_CMD, lo, hi];
+        OneWireAsync::address(&mut self.bus, self.rom).await?;
+        OneWireAsync::write_bytes(&mut self.bus, &cmd).await?;
+        OneWireAsync::write_bytes(&mut self.bus, password).await?;
+        let mut crc = OneWireCrc16::default();
+        crc.update_slice(&cmd);
+        let mut offset = address as usize % PAGE_SIZE;
+        let mut buf = buf;
+        while !buf.is_empty() {
+            let mut page = [0; PAGE_SIZE];
+            let page = &mut page[offset..];
+            OneWireAsync::read_bytes(&mut self.bus, page).await?;
+            let mut check = [0; 2];
+            OneWireAsync::read_bytes(&mut self.bus, &mut check).await?;
+            crc.update_slice(page);
+            crc.update_slice(&check);
+            if !crc.is_valid() {
+                return Err(OneWireError::InvalidCrc);
+            }
+            let len = page.len().min(buf.len());
+            buf[..len].copy_from_slice(&page[..len]);
+            buf = &mut buf[len..];
+            crc = OneWireCrc16::default();
+            offset = 0;
+        }
+        Ok(())
+    }
+
+    /// Read the datalog memory from `offset` into `buf`.
+    ///
+    /// See [`Ds1922::read_datalog`].
+    pub async fn read_datalog_async(
+        &mut self,
+        offset: usize,
+        buf: &mut [u8],
+        password: &Password,
+    ) -> OneWireResult<(), O::BusError> {
+        if offset + buf.len() > DATALOG_SIZE {
+            return Err(OneWireError::InvalidValue("offset"));
+        }
+        self.read_async(DATALOG_ADDRESS + offset as u16, buf, password)
+            .await
+    }
+
+    /// Read the state of the logger.
+    ///
+    /// See [`Ds1922::status`].
+    pub async fn status_async(
+        &mut self,
+        password: &Password,
+    ) -> OneWireResult<Status, O::BusError> {
+        let mut regs = [0; STATUS_SIZE];
+        self.read_async(CLOCK_ADDRESS, &mut regs, password).await?;
+        Ok(Status::parse(&regs))
+    }
+
+    /// Read the real-time clock.
+    ///
+    /// See [`Ds1922::clock`].
+    pub async fn clock_async(
+        &mut self,
+        password: &Password,
+    ) -> OneWireResult<DateTime, O::BusError> {
+        let mut regs = [0; 6];
+        self.read_async(CLOCK_ADDRESS, &mut regs, password).await?;
+        Ok(DateTime::from_registers(&regs))
+    }
+
+    /// Set the real-time clock to `time`, and start its oscillator.
+    ///
+    /// See [`Ds1922::set_clock`].
+    pub async fn set_clock_async<D: DelayNs>(
+        &mut self,
+        time: &DateTime,
+        password: &Password,
+        delay: &mut D,
+    ) -> OneWireResult<(), O::BusError> {
+        if !time.is_valid() {
+            return Err(OneWireError::InvalidValue("time"));
+        }
+        let mut page = [0; PAGE_SIZE];
+        self.read_async(CLOCK_ADDRESS, &mut page, password).await?;
+        page[..6].copy_from_slice(&time.into_registers());
+        page[0x12] |= RTC_EOSC;
+        self.program_async(CLOCK_ADDRESS, &page, password, delay)
+            .await
+    }
+
+    /// Write `data` to the general purpose memory at `address`.
+    ///
+    /// See [`Ds1922::write`].
+    pub async fn write_async<D: DelayNs>(
+        &mut self,
+        address: u16,
+        data: &[u8],
+        password: &Password,
+        delay: &mut D,
+    ) -> OneWireResult<(), O::BusError> {
+        if address as usize + data.len() > GENERAL_MEMORY_SIZE {
+            return Err(OneWireError::InvalidValue("address"));
+        }
+        let mut address = address as usize;
+        let mut data = data;
+        while !data.is_empty() {
+            let offset = address % PAGE_SIZE;
+            let start = (address - offset) as u16;
+            let len = (PAGE_SIZE - offset).min(data.len());
+            let mut page = [0; PAGE_SIZE];
+            if len != PAGE_SIZE {
+                self.read_async(start, &mut page, password).await?;
+            }
+            page[offset..offset + len].copy_from_slice(&data[..len]);
+            self.program_async(start, &page, password, delay).await?;
+            address += len;
+            data = &data[len..];
+        }
+        Ok(())
+    }
+
+    /// Set the read access and the full access passwords, and enable or disable them.
+    ///
+    /// See [`Ds1922::set_passwords`].
+    pub async fn set_passwords_async<D: DelayNs>(
+        &mut self,
+        read: &Password,
+        full: &Password,
+        enable: bool,
+        password: &Password,
+        delay: &mut D,
+    ) -> OneWireResult<(), O::BusError> {
+        let mut regs = [0; PASSWORDS_SIZE];
+        self.read_async(PASSWORD_CONTROL_ADDRESS, &mut regs, password)
+            .await?;
+        regs[0] = if enable { PASSWORDS_ENABLED } else { 0x00 };
+        regs[1..1 + PASSWORD_SIZE].copy_from_slice(read);
+        regs[1 + PASSWORD_SIZE..1 + 2 * PASSWORD_SIZE].copy_from_slice(full);
+        self.program_async(PASSWORD_CONTROL_ADDRESS, &regs, password, delay)
+            .await
+    }
+
+    /// Clear the mission memory and the mission registers.
+    ///
+    /// See [`Ds1922::clear_memory`].
+    pub async fn clear_memory_async<D: DelayNs>(
+        &mut self,
+        password: &Password,
+        delay: &mut D,
+    ) -> OneWireResult<(), O::BusError> {
+        self.command_async(CLEAR_MEMORY_CMD, password).await?;
+        delay.delay_us(CLEAR_TIME_US).await;
+        if !self.status_async(password).await?.memory_cleared {
+            return Err(OneWireError::VerificationFailed);
+        }
+        Ok(())
+    }
+
+    /// Configure `mission` and start it.
+    ///
+    /// See [`Ds1922::start_mission`].
+    pub async fn start_mission_async<D: DelayNs>(
+        &mut self,
+        mission: &Mission,
+        password: &Password,
+        delay: &mut D,
+    ) -> OneWireResult<(), O::BusError> {
+        if !mission.is_valid() {
+            return Err(OneWireError::InvalidValue("mission"));
+        }
+        let mut regs = [0; MISSION_SIZE];
+        self.read_async(MISSION_ADDRESS, &mut regs, password)
+            .await?;
+        mission.write_registers(&mut regs);
+        self.program_async(MISSION_ADDRESS, &regs, password, delay)
+            .await?;
+        self.command_async(START_MISSION_CMD, password).await
+    }
+
+    /// Stop the mission in progress.
+    ///
+    /// See [`Ds1922::stop_mission`].
+    pub async fn stop_mission_async(
+        &mut self,
+        password: &Password,
+    ) -> OneWireResult<(), O::BusError> {
+        self.command_async(STOP_MISSION_CMD, password).await
+    }
+
+    async fn command_async(
+        &mut self,
+        cmd: u8,
+        password: &Password,
+    ) -> OneWireResult<(), O::BusError> {
+        OneWireAsync::address(&mut self.bus, self.rom).await?;
+        OneWireAsync::write_bytes(&mut self.bus, &command_bytes(cmd, password)).await
+    }
+
+    async fn program_async<D: DelayNs, const N: usize>(
+        &mut self,
+        address: u16,
+        data: &[u8; N],
+        password: &Password,
+        delay: &mut D,
+    ) -> OneWireResult<(), O::BusError> {
+        scratchpad::write_scratchpad_async(&mut self.bus, self.rom, address, data).await?;
+        let scratchpad = scratchpad::read_scratchpad_async(&mut self.bus, self.rom).await?;
+        verify_scratchpad(&scratchpad, address, data)?;
+        let [lo, hi] = address.to_le_bytes();
+        OneWireAsync::address(&mut self.bus, self.rom).await?;
+        OneWireAsync::write_bytes(
+            &mut self.bus,
+            &[COPY_SCRATCHPAD_PASSWORD_CMD, lo, hi, scratchpad.es],
+        )
+        .await?;
+        OneWireAsync::write_bytes(&mut self.bus, password).await?;
+        delay.delay_us(COPY_TIME_US).await;
+        if OneWireAsync::read_byte(&mut self.bus).await? != COPY_DONE {
+            return Err(OneWireError::VerificationFailed);
+        }
+        Ok(())
+    }
+}
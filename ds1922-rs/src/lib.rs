@@ -0,0 +1,448 @@
+#![no_std]
+#![deny(missing_docs)]
+#![cfg_attr(docsrs, feature(doc_cfg))]
+#![doc = include_str!("../README.md")]
+
+mod device;
+mod device_async;
+
+pub use device::Ds1922;
+pub use embedded_onewire::{OneWireError, OneWireResult};
+
+/// Family code of the DS1922 and the DS1923, the low byte of their ROM.
+pub const FAMILY_CODE: u8 = 0x41;
+/// Size of a page of the memory, and of the scratchpad.
+pub const PAGE_SIZE: usize = 32;
+/// Size of a password.
+pub const PASSWORD_SIZE: usize = 8;
+/// Size of the general purpose memory, at address `0x0000`.
+pub const GENERAL_MEMORY_SIZE: usize = 0x0200;
+/// Address of the datalog memory.
+pub const DATALOG_ADDRESS: u16 = 0x1000;
+/// Size of the datalog memory. When both temperature and humidity are logged, the
+/// temperature samples take the first half and the humidity samples the second half.
+pub const DATALOG_SIZE: usize = 0x2000;
+
+/// Address of the real-time clock, the start of the first register page.
+pub(crate) const CLOCK_ADDRESS: u16 = 0x0200;
+/// Address of the mission registers: the sample rate, up to the end of the page.
+pub(crate) const MISSION_ADDRESS: u16 = 0x0206;
+/// Size of the mission registers.
+pub(crate) const MISSION_SIZE: usize = 26;
+/// Address of the password control register, followed by the passwords up to the end of
+/// the page.
+pub(crate) const PASSWORD_CONTROL_ADDRESS: u16 = 0x0227;
+/// Size of the password registers.
+pub(crate) const PASSWORDS_SIZE: usize = 25;
+/// Size of the registers read by [`Ds1922::status`].
+pub(crate) const STATUS_SIZE: usize = 0x28;
+/// End of the memory.
+pub(crate) const MEMORY_END: usize = DATALOG_ADDRESS as usize + DATALOG_SIZE;
+
+pub(crate) const READ_MEMORY_PASSWORD_CMD: u8 = 0x69;
+pub(crate) const COPY_SCRATCHPAD_PASSWORD_CMD: u8 = 0x99;
+pub(crate) const CLEAR_MEMORY_CMD: u8 = 0x96;
+pub(crate) const START_MISSION_CMD: u8 = 0xcc;
+pub(crate) const STOP_MISSION_CMD: u8 = 0x33;
+
+/// Pattern of alternating ones and zeros sent by the device after a successful copy.
+pub(crate) const COPY_DONE: u8 = 0xaa;
+/// Value of the password control register that enables the passwords.
+pub(crate) const PASSWORDS_ENABLED: u8 = 0xaa;
+
+/// Oscillator enable bit of the RTC control register.
+pub(crate) const RTC_EOSC: u8 = 0x01;
+/// High speed sample bit of the RTC control register: the sample rate is in seconds.
+pub(crate) const RTC_EHSS: u8 = 0x02;
+/// Mission in progress bit of the general status register.
+pub(crate) const STATUS_MIP: u8 = 0x02;
+/// Memory cleared bit of the general status register.
+pub(crate) const STATUS_MEMCLR: u8 = 0x08;
+
+/// Time for the device to copy the scratchpad, with a margin.
+pub(crate) const COPY_TIME_US: u32 = 1_000;
+/// Time for the device to clear the mission memory.
+pub(crate) const CLEAR_TIME_US: u32 = 500;
+
+/// A password of the device.
+///
+/// The read access password only grants reads, and the full access password grants reads,
+/// writes and the mission commands. While the passwords are disabled, any password is
+/// accepted.
+pub type Password = [u8; PASSWORD_SIZE];
+
+/// Model of the logger, from its configuration code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Model {
+    /// DS1922L Thermochron, -40 °C to +85 °C.
+    Ds1922l,
+    /// DS1922T Thermochron, 0 °C to +125 °C.
+    Ds1922t,
+    /// DS1922E Thermochron, +15 °C to +140 °C.
+    Ds1922e,
+    /// DS1923 Hygrochron, -20 °C to +85 °C, with a humidity sensor.
+    Ds1923,
+    /// An unknown configuration code.
+    Unknown(u8),
+}
+
+impl Model {
+    pub(crate) fn from_code(code: u8) -> Self {
+        match code {
+            0x40 => Model::Ds1922l,
+            0x60 => Model::Ds1922t,
+            0x80 => Model::Ds1922e,
+            0x20 => Model::Ds1923,
+            code => Model::Unknown(code),
+        }
+    }
+
+    /// Temperature of a reading, from its high byte `trh` and its low byte `trl`.
+    ///
+    /// Samples logged at 8-bit resolution only hold the high byte, and `trl` is 0 for them.
+    /// A 16-bit sample in the datalog stores the high byte first.
+    ///
+    /// Returns [`None`] for an unknown model.
+    pub fn temperature(&self, trh: u8, trl: u8) -> Option<Temperature> {
+        let offset = match self {
+            Model::Ds1922l | Model::Ds1923 => -41,
+            Model::Ds1922t => -1,
+            Model::Ds1922e => 14,
+            Model::Unknown(_) => return None,
+        };
+        let raw = u16::from_be_bytes([trh, trl & 0xe0]) as i32 + offset * 512;
+        Some(Temperature(raw))
+    }
+}
+
+/// A temperature reading, in units of 1/512 °C.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Temperature(i32);
+
+impl Temperature {
+    /// The raw value, in units of 1/512 °C.
+    pub fn raw(&self) -> i32 {
+        self.0
+    }
+
+    /// The temperature in thousandths of a degree Celsius, truncated.
+    pub fn millidegrees(&self) -> i32 {
+        self.0 * 125 / 64
+    }
+
+    /// The temperature in degrees Celsius.
+    pub fn celsius(&self) -> f32 {
+        self.0 as f32 / 512.0
+    }
+}
+
+/// A date and time of the real-time clock, from 2000 to 2199.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct DateTime {
+    /// Year, from 2000 to 2199.
+    pub year: u16,
+    /// Month, from 1 to 12.
+    pub month: u8,
+    /// Day of the month, from 1 to 31.
+    pub day: u8,
+    /// Hour, from 0 to 23.
+    pub hour: u8,
+    /// Minute, from 0 to 59.
+    pub minute: u8,
+    /// Second, from 0 to 59.
+    pub second: u8,
+}
+
+impl DateTime {
+    pub(crate) fn is_valid(&self) -> bool {
+        (2000..2200).contains(&self.year)
+            && (1..=12).contains(&self.month)
+            && (1..=31).contains(&self.day)
+            && self.hour < 24
+            && self.minute < 60
+            && self.second < 60
+    }
+
+    /// Parse the BCD clock registers. The hours may be in the 12-hour format.
+    pub(crate) fn from_registers(regs: &[u8]) -> Self {
+        let hour = if regs[2] & 0x40 != 0 {
+            from_bcd(regs[2] & 0x1f) % 12 + if regs[2] & 0x20 != 0 { 12 } else { 0 }
+        } else {
+            from_bcd(regs[2] & 0x3f)
+        };
+        let century = if regs[4] & 0x80 != 0 { 100 } else { 0 };
+        DateTime {
+            year: 2000 + century + from_bcd(regs[5]) as u16,
+            month: from_bcd(regs[4] & 0x1f),
+            day: from_bcd(regs[3] & 0x3f),
+            hour,
+            minute: from_bcd(regs[1] & 0x7f),
+            second: from_bcd(regs[0] & 0x7f),
+        }
+    }
+
+    /// The BCD clock registers, with the hours in the 24-hour format.
+    pub(crate) fn into_registers(self) -> [u8; 6] {
+        let year = self.year - 2000;
+        let century = if year >= 100 { 0x80 } else { 0 };
+        [
+            to_bcd(self.second),
+            to_bcd(self.minute),
+            to_bcd(self.hour),
+            to_bcd(self.day),
+            to_bcd(self.month) | century,
+            to_bcd((year % 100) as u8),
+        ]
+    }
+}
+
+fn from_bcd(bcd: u8) -> u8 {
+    (bcd >> 4) * 10 + (bcd & 0x0f)
+}
+
+fn to_bcd(value: u8) -> u8 {
+    ((value / 10) << 4) | (value % 10)
+}
+
+/// Resolution of a logged quantity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Resolution {
+    /// One byte per sample.
+    Bits8,
+    /// Two bytes per sample, the high byte first.
+    Bits16,
+}
+
+/// Interval between two samples of a mission.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SampleRate {
+    /// Seconds between two samples, from 1 to 16383.
+    Seconds(u16),
+    /// Minutes between two samples, from 1 to 16383.
+    Minutes(u16),
+}
+
+/// Settings of a mission.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Mission {
+    /// Interval between two samples.
+    pub sample_rate: SampleRate,
+    /// Resolution of the logged temperature, or [`None`] to not log the temperature.
+    pub temperature: Option<Resolution>,
+    /// Resolution of the logged humidity, or [`None`] to not log the humidity. Only the
+    /// DS1923 logs the humidity.
+    pub humidity: Option<Resolution>,
+    /// Whether the oldest samples are overwritten once the datalog memory is full. Otherwise,
+    /// logging stops.
+    pub rollover: bool,
+    /// Minutes from the start of the mission to the first sample, up to 2^24 - 1.
+    pub start_delay: u32,
+}
+
+impl Mission {
+    pub(crate) fn is_valid(&self) -> bool {
+        let (SampleRate::Seconds(rate) | SampleRate::Minutes(rate)) = self.sample_rate;
+        (1..0x4000).contains(&rate) && self.start_delay < 1 << 24
+    }
+
+    /// Write the mission to the mission registers `regs`, from the sample rate on.
+    pub(crate) fn write_registers(&self, regs: &mut [u8; MISSION_SIZE]) {
+        let (rate, ehss) = match self.sample_rate {
+            SampleRate::Seconds(rate) => (rate, RTC_EHSS),
+            SampleRate::Minutes(rate) => (rate, 0),
+        };
+        regs[0..2].copy_from_slice(&rate.to_le_bytes());
+        regs[0x0c] = RTC_EOSC | ehss;
+        let mut control = 0;
+        if let Some(resolution) = self.temperature {
+            control |= 0x01;
+            if resolution == Resolution::Bits16 {
+                control |= 0x04;
+            }
+        }
+        if let Some(resolution) = self.humidity {
+            control |= 0x02;
+            if resolution == Resolution::Bits16 {
+                control |= 0x08;
+            }
+        }
+        if self.rollover {
+            control |= 0x10;
+        }
+        regs[0x0d] = control;
+        regs[0x10..0x13].copy_from_slice(&self.start_delay.to_le_bytes()[..3]);
+    }
+
+    /// Parse the mission registers `regs`, from the sample rate on.
+    pub(crate) fn from_registers(regs: &[u8]) -> Self {
+        let rate = u16::from_le_bytes([regs[0], regs[1]]) & 0x3fff;
+        let sample_rate = if regs[0x0c] & RTC_EHSS != 0 {
+            SampleRate::Seconds(rate)
+        } else {
+            SampleRate::Minutes(rate)
+        };
+        let control = regs[0x0d];
+        let resolution = |enable: u8, high: u8| {
+            (control & enable != 0).then_some(if control & high != 0 {
+                Resolution::Bits16
+            } else {
+                Resolution::Bits8
+            })
+        };
+        Mission {
+            sample_rate,
+            temperature: resolution(0x01, 0x04),
+            humidity: resolution(0x02, 0x08),
+            rollover: control & 0x10 != 0,
+            start_delay: u32::from_le_bytes([regs[0x10], regs[0x11], regs[0x12], 0]),
+        }
+    }
+}
+
+/// State of the logger, read from its registers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Status {
+    /// The real-time clock.
+    pub clock: DateTime,
+    /// Whether the oscillator of the real-time clock runs.
+    pub oscillator: bool,
+    /// Settings of the current or the last mission.
+    pub mission: Mission,
+    /// Whether a mission is in progress, including its start delay.
+    pub mission_in_progress: bool,
+    /// Whether the mission memory was cleared, which is required to start a mission.
+    pub memory_cleared: bool,
+    /// Time of the first sample of the mission, if it was taken.
+    pub mission_start: Option<DateTime>,
+    /// Number of samples taken in the mission. With rollover, the datalog memory only holds
+    /// the latest samples.
+    pub mission_samples: u32,
+    /// Number of samples taken over the life of the device.
+    pub device_samples: u32,
+    /// Model of the logger.
+    pub model: Model,
+    /// Whether the passwords are enabled.
+    pub passwords_enabled: bool,
+}
+
+impl Status {
+    /// Parse the registers from the real-time clock to the password control register.
+    pub(crate) fn parse(regs: &[u8; STATUS_SIZE]) -> Self {
+        let counter = |i: usize| u32::from_le_bytes([regs[i], regs[i + 1], regs[i + 2], 0]);
+        let mission_samples = counter(0x20);
+        Status {
+            clock: DateTime::from_registers(&regs[0x00..0x06]),
+            oscillator: regs[0x12] & RTC_EOSC != 0,
+            mission: Mission::from_registers(&regs[0x06..0x20]),
+            mission_in_progress: regs[0x15] & STATUS_MIP != 0,
+            memory_cleared: regs[0x15] & STATUS_MEMCLR != 0,
+            mission_start: (mission_samples != 0)
+                .then(|| DateTime::from_registers(&regs[0x19..0x1f])),
+            mission_samples,
+            device_samples: counter(0x23),
+            model: Model::from_code(regs[0x26]),
+            passwords_enabled: regs[0x27] == PASSWORDS_ENABLED,
+        }
+    }
+}
+
+mod test {
+    #[test]
+    fn test_conversions() {
+        use super::*;
+
+        let time = DateTime {
+            year: 2026,
+            month: 10,
+            day: 17,
+            hour: 21,
+            minute: 5,
+            second: 59,
+        };
+        let regs = time.into_registers();
+        assert_eq!(regs, [0x59, 0x05, 0x21, 0x17, 0x10, 0x26]);
+        assert_eq!(DateTime::from_registers(&regs), time);
+        // 9 PM in the 12-hour format
+        assert_eq!(
+            DateTime::from_registers(&[0x59, 0x05, 0x69, 0x17, 0x10, 0x26]),
+            time
+        );
+        // 1 °C and -29.3125 °C on a DS1922L
+        let model = Model::from_code(0x40);
+        assert_eq!(model.temperature(0x54, 0x00).unwrap().millidegrees(), 1_000);
+        assert_eq!(model.temperature(0x17, 0x60).unwrap().raw(), -15_008);
+        assert_eq!(
+            Model::Ds1922t.temperature(0x02, 0x00).unwrap().celsius(),
+            0.0
+        );
+        assert_eq!(Model::Unknown(0).temperature(0, 0), None);
+    }
+
+    #[test]
+    fn test_start_mission() {
+        use super::*;
+        use embedded_hal_mock::eh1::delay::NoopDelay;
+        use embedded_onewire::testing::{Mock, Transaction};
+        extern crate std;
+
+        let password: Password = core::array::from_fn(|i| i as u8 + 1);
+        let mut regs = [0; MISSION_SIZE];
+        regs[0] = 0x0a;
+        regs[0x0c] = 0x01;
+        regs[0x0d] = 0x05;
+        let mut start = std::vec![START_MISSION_CMD];
+        start.extend_from_slice(&password);
+        start.push(0xff);
+        let bus = Mock::new(&[
+            Transaction::reset(true),
+            Transaction::skip_rom(),
+            Transaction::write(&[0x69, 0x06, 0x02]),
+            Transaction::write(&password),
+            Transaction::read(&[0; MISSION_SIZE]),
+            Transaction::read(&[0xcf, 0x3d]),
+            Transaction::reset(true),
+            Transaction::skip_rom(),
+            Transaction::write(&[0x0f, 0x06, 0x02]),
+            Transaction::write(&regs),
+            Transaction::read(&[0x7a, 0x75]),
+            Transaction::reset(true),
+            Transaction::skip_rom(),
+            Transaction::write(&[0xaa]),
+            Transaction::read(&[0x06, 0x02, 0x1f]),
+            Transaction::read(&regs),
+            Transaction::read(&[0x65, 0xab]),
+            Transaction::reset(true),
+            Transaction::skip_rom(),
+            Transaction::write(&[0x99, 0x06, 0x02, 0x1f]),
+            Transaction::write(&password),
+            Transaction::read(&[0xaa]),
+            Transaction::reset(true),
+            Transaction::skip_rom(),
+            Transaction::write(&start),
+        ]);
+        let mut logger = Ds1922::new(bus, None);
+        let mission = Mission {
+            sample_rate: SampleRate::Minutes(10),
+            temperature: Some(Resolution::Bits16),
+            humidity: None,
+            rollover: false,
+            start_delay: 0,
+        };
+        logger
+            .start_mission(&mission, &password, &mut NoopDelay::new())
+            .unwrap();
+        assert_eq!(Mission::from_registers(&regs), mission);
+        assert!(matches!(
+            logger.start_mission(
+                &Mission {
+                    sample_rate: SampleRate::Seconds(0),
+                    ..mission
+                },
+                &password,
+                &mut NoopDelay::new()
+            ),
+            Err(OneWireError::InvalidValue(_))
+        ));
+        logger.release().done();
+    }
+}
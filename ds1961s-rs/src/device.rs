@@ -0,0 +1,285 @@
+use crate::{
+    AuthenticatedPage, COMPUTE_NEXT_SECRET_CMD, COPY_SCRATCHPAD_CMD, DONE, LOAD_FIRST_SECRET_CMD,
+    MEMORY_END, PAGE_SIZE, PAGES, PROG_TIME_US, READ_AUTH_PAGE_CMD, READ_ROM_CMD, SCRATCHPAD_SIZE,
+    SECRET_ADDRESS, SHA_TIME_US,
+    mac::{self, MAC_SIZE, SECRET_SIZE},
+};
+use embedded_hal::delay::DelayNs;
+use embedded_onewire::{
+    OneWire, OneWireCrc, OneWireCrc16, OneWireError, OneWireResult, OneWireStrongPullup,
+};
+use onewire_eeprom::scratchpad::{self, Scratchpad};
+
+/// A DS1961S SHA-1 iButton on a 1-Wire bus.
+///
+/// The driver owns the bus master, which can be a `&mut` reference to one or a handle to a
+/// shared one. Every operation addresses the device first.
+///
+/// The authentication codes include the ROM of the device. Without a ROM, it is read with
+/// the Read ROM command the first time it is needed, and the device is addressed by it
+/// from then on.
+#[derive(Debug)]
+pub struct Ds1961s<O> {
+    pub(crate) bus: O,
+    pub(crate) rom: Option<u64>,
+}
+
+impl<O> Ds1961s<O> {
+    /// Creates a driver for the device with ROM `rom` on `bus`.
+    ///
+    /// Pass [`None`] to skip ROM addressing on a bus with a single device.
+    pub fn new(bus: O, rom: Option<u64>) -> Self {
+        Ds1961s { bus, rom }
+    }
+
+    /// ROM of the device, if it is addressed by ROM.
+    pub fn rom(&self) -> Option<u64> {
+        self.rom
+    }
+
+    /// Release the bus master.
+    pub fn release(self) -> O {
+        self.bus
+    }
+}
+
+impl<O: OneWire> Ds1961s<O> {
+    /// ROM of the device, read from the only device on the bus if it is not known.
+    pub(crate) fn id(&mut self) -> OneWireResult<u64, O::BusError> {
+        if let Some(rom) = self.rom {
+            return Ok(rom);
+        }
+        self.bus.reset()?;
+        self.bus.write_byte(READ_ROM_CMD)?;
+        let mut rom = [0; 8];
+        self.bus.read_bytes(&mut rom)?;
+        if !OneWireCrc::validate(&rom) {
+            return Err(OneWireError::InvalidCrc);
+        }
+        let rom = u64::from_le_bytes(rom);
+        self.rom = Some(rom);
+        Ok(rom)
+    }
+
+    /// Read the memory from `address` into `buf`.
+    ///
+    /// The data memory and the registers can be read, up to address `0x8f`. The secret
+    /// reads back as `0xff`.
+    ///
+    /// # Errors
+    /// Returns [`OneWireError::InvalidValue`] if the read goes past the end of the memory.
+    pub fn read(&mut self, address: u16, buf: &mut [u8]) -> OneWireResult<(), O::BusError> {
+        if address as usize + buf.len() > MEMORY_END {
+            return Err(OneWireError::InvalidValue("address"));
+        }
+        scratchpad::read_memory(&mut self.bus, self.rom, address, buf)
+    }
+
+    /// Read `page` along with the authentication code of the device for `challenge`.
+    ///
+    /// The challenge should be random, so that an earlier answer can not be replayed.
+    ///
+    /// # Errors
+    /// Returns [`OneWireError::InvalidValue`] if `page` does not exist, and
+    /// [`OneWireError::InvalidCrc`] if a transfer is corrupted.
+    pub fn read_authenticated_page<D: DelayNs>(
+        &mut self,
+        page: u8,
+        challenge: &[u8; 3],
+        delay: &mut D,
+    ) -> OneWireResult<AuthenticatedPage, O::BusError> {
+        if page >= PAGES {
+            return Err(OneWireError::InvalidValue("page"));
+        }
+        let address = page as u16 * PAGE_SIZE as u16;
+        scratchpad::write_scratchpad(&mut self.bus, self.rom, address, &challenge_data(challenge))?;
+        let [lo, hi] = address.to_le_bytes();
+        let cmd = [READ_AUTH_PAGE_CMD, lo, hi];
+        self.bus.address(self.rom)?;
+        self.bus.write_bytes(&cmd)?;
+        // The page is followed by a 0xff byte and the CRC-16
+        let mut data = [0; PAGE_SIZE + 3];
+        self.bus.read_bytes(&mut data)?;
+        let mut crc = OneWireCrc16::default();
+        crc.update_slice(&cmd);
+        crc.update_slice(&data);
+        if !crc.is_valid() {
+            return Err(OneWireError::InvalidCrc);
+        }
+        delay.delay_us(SHA_TIME_US);
+        let mut buf = [0; MAC_SIZE + 2];
+        self.bus.read_bytes(&mut buf)?;
+        parse_page(&data, &buf)
+    }
+
+    /// Read `page` and check that the device knows `secret`.
+    ///
+    /// # Errors
+    /// Returns [`OneWireError::VerificationFailed`] if the authentication code of the device
+    /// does not match, and the errors of [`Ds1961s::read_authenticated_page`].
+    pub fn authenticate_page<D: DelayNs>(
+        &mut self,
+        page: u8,
+        challenge: &[u8; 3],
+        secret: &[u8; SECRET_SIZE],
+        delay: &mut D,
+    ) -> OneWireResult<[u8; PAGE_SIZE], O::BusError> {
+        let rom = self.id()?;
+        let read = self.read_authenticated_page(page, challenge, delay)?;
+        check_page(&read, secret, page, rom, challenge)
+    }
+}
+
+impl<O: OneWireStrongPullup> Ds1961s<O> {
+    /// Write the scratchpad and read it back to verify it.
+    fn fill_scratchpad(
+        &mut self,
+        address: u16,
+        data: &[u8; SCRATCHPAD_SIZE],
+    ) -> OneWireResult<Scratchpad<SCRATCHPAD_SIZE>, O::BusError> {
+        scratchpad::write_scratchpad(&mut self.bus, self.rom, address, data)?;
+        let scratchpad = scratchpad::read_scratchpad(&mut self.bus, self.rom)?;
+        verify_scratchpad(&scratchpad, address, data)?;
+        Ok(scratchpad)
+    }
+
+    /// Wait for the device to finish an operation started with the strong pullup.
+    fn finish<D: DelayNs>(&mut self, delay: &mut D, us: u32) -> OneWireResult<(), O::BusError> {
+        delay.delay_us(us);
+        if self.bus.read_byte()? != DONE {
+            return Err(OneWireError::VerificationFailed);
+        }
+        Ok(())
+    }
+
+    /// Load `secret` into the device, without knowing the current one.
+    ///
+    /// # Errors
+    /// Returns [`OneWireError::InvalidCrc`] if a transfer of the scratchpad is corrupted, and
+    /// [`OneWireError::VerificationFailed`] if the scratchpad read back does not match or the
+    /// device does not confirm the load, e.g. because the secret is write-protected.
+    pub fn load_first_secret<D: DelayNs>(
+        &mut self,
+        secret: &[u8; SECRET_SIZE],
+        delay: &mut D,
+    ) -> OneWireResult<(), O::BusError> {
+        let scratchpad = self.fill_scratchpad(SECRET_ADDRESS, secret)?;
+        let [lo, hi] = SECRET_ADDRESS.to_le_bytes();
+        self.bus.address(self.rom)?;
+        self.bus.write_bytes(&[LOAD_FIRST_SECRET_CMD, lo, hi])?;
+        self.bus.write_byte_powered(scratchpad.es)?;
+        self.finish(delay, PROG_TIME_US)
+    }
+
+    /// Replace the secret by one computed from the current secret, `page` and `partial`.
+    ///
+    /// The bus master computes the new secret with [`mac::next_secret`], from the contents
+    /// of the page and `partial`.
+    ///
+    /// # Errors
+    /// Returns [`OneWireError::InvalidValue`] if `page` does not exist, and the errors of
+    /// [`Ds1961s::load_first_secret`].
+    pub fn compute_next_secret<D: DelayNs>(
+        &mut self,
+        page: u8,
+        partial: &[u8; SCRATCHPAD_SIZE],
+        delay: &mut D,
+    ) -> OneWireResult<(), O::BusError> {
+        if page >= PAGES {
+            return Err(OneWireError::InvalidValue("page"));
+        }
+        let address = page as u16 * PAGE_SIZE as u16;
+        self.fill_scratchpad(address, partial)?;
+        let [lo, hi] = address.to_le_bytes();
+        self.bus.address(self.rom)?;
+        self.bus.write_bytes(&[COMPUTE_NEXT_SECRET_CMD, lo])?;
+        self.bus.write_byte_powered(hi)?;
+        self.finish(delay, SHA_TIME_US + PROG_TIME_US)
+    }
+
+    /// Program the block at `address` with `data`, authorized with `secret`.
+    ///
+    /// The data memory and the registers are written in blocks of 8 bytes. The device only
+    /// copies the scratchpad to the memory if the bus master proves it knows the secret.
+    ///
+    /// # Errors
+    /// Returns [`OneWireError::InvalidValue`] if `address` is not the start of a block of the
+    /// data memory or the registers, and the errors of [`Ds1961s::load_first_secret`].
+    pub fn write_authenticated<D: DelayNs>(
+        &mut self,
+        address: u16,
+        data: &[u8; SCRATCHPAD_SIZE],
+        secret: &[u8; SECRET_SIZE],
+        delay: &mut D,
+    ) -> OneWireResult<(), O::BusError> {
+        if !(address as usize).is_multiple_of(SCRATCHPAD_SIZE)
+            || address as usize >= MEMORY_END
+            || address == SECRET_ADDRESS
+        {
+            return Err(OneWireError::InvalidValue("address"));
+        }
+        let rom = self.id()?;
+        let scratchpad = self.fill_scratchpad(address, data)?;
+        let mut page = [0; 28];
+        let start = address & !(PAGE_SIZE as u16 - 1);
+        scratchpad::read_memory(&mut self.bus, self.rom, start, &mut page)?;
+        let mac = mac::copy_scratchpad_mac(secret, &page, data, address, rom);
+        let [lo, hi] = address.to_le_bytes();
+        self.bus.address(self.rom)?;
+        self.bus.write_bytes(&[COPY_SCRATCHPAD_CMD, lo, hi])?;
+        self.bus.write_byte_powered(scratchpad.es)?;
+        delay.delay_us(SHA_TIME_US);
+        self.bus.write_bytes(&mac[..MAC_SIZE - 1])?;
+        self.bus.write_byte_powered(mac[MAC_SIZE - 1])?;
+        self.finish(delay, PROG_TIME_US)
+    }
+}
+
+/// Scratchpad holding `challenge` in bytes 4 to 6.
+pub(crate) fn challenge_data(challenge: &[u8; 3]) -> [u8; SCRATCHPAD_SIZE] {
+    let mut data = [0; SCRATCHPAD_SIZE];
+    data[4..7].copy_from_slice(challenge);
+    data
+}
+
+/// Split the page read by Read Authenticated Page, and check the CRC-16 of the
+/// authentication code.
+pub(crate) fn parse_page<E>(
+    data: &[u8; PAGE_SIZE + 3],
+    buf: &[u8; MAC_SIZE + 2],
+) -> OneWireResult<AuthenticatedPage, E> {
+    if !OneWireCrc16::validate(buf) {
+        return Err(OneWireError::InvalidCrc);
+    }
+    let mut page = AuthenticatedPage {
+        data: [0; PAGE_SIZE],
+        mac: [0; MAC_SIZE],
+    };
+    page.data.copy_from_slice(&data[..PAGE_SIZE]);
+    page.mac.copy_from_slice(&buf[..MAC_SIZE]);
+    Ok(page)
+}
+
+pub(crate) fn check_page<E>(
+    read: &AuthenticatedPage,
+    secret: &[u8; SECRET_SIZE],
+    page: u8,
+    rom: u64,
+    challenge: &[u8; 3],
+) -> OneWireResult<[u8; PAGE_SIZE], E> {
+    if mac::read_page_mac(secret, &read.data, page, rom, challenge) != read.mac {
+        return Err(OneWireError::VerificationFailed);
+    }
+    Ok(read.data)
+}
+
+pub(crate) fn verify_scratchpad<E>(
+    scratchpad: &Scratchpad<SCRATCHPAD_SIZE>,
+    address: u16,
+    data: &[u8; SCRATCHPAD_SIZE],
+) -> OneWireResult<(), E> {
+    if scratchpad.address != address || scratchpad.data != *data {
+        return Err(OneWireError::VerificationFailed);
+    }
+    Ok(())
+}
@@ -0,0 +1,193 @@
+use crate::{
+    AuthenticatedPage, COMPUTE_NEXT_SECRET_CMD, COPY_SCRATCHPAD_CMD, DONE, Ds1961s,
+    LOAD_FIRST_SECRET_CMD, MEMORY_END, PAGE_SIZE, PAGES, PROG_TIME_US, READ_AUTH_PAGE_CMD,
+    READ_ROM_CMD, SCRATCHPAD_SIZE, SECRET_ADDRESS, SHA_TIME_US,
+    device::{challenge_data, check_page, parse_page, verify_scratchpad},
+    mac::{self, MAC_SIZE, SECRET_SIZE},
+};
+use embedded_hal_async::delay::DelayNs;
+use embedded_onewire::{
+    OneWireAsync, OneWireCrc, OneWireCrc16, OneWireError, OneWireResult, OneWireStrongPullupAsync,
+};
+use onewire_eeprom::scratchpad::{self, Scratchpad};
+
+impl<O: OneWireAsync> Ds1961s<O> {
+    async fn id_async(&mut self) -> OneWireResult<u64, O::BusError> {
+        if let Some(rom) = self.rom {
+            return Ok(rom);
+        }
+        OneWireAsync::reset(&mut self.bus).await?;
+        OneWireAsync::write_byte(&mut self.bus, READ_ROM_CMD).await?;
+        let mut rom = [0; 8];
+        OneWireAsync::read_bytes(&mut self.bus, &mut rom).await?;
+        if !OneWireCrc::validate(&rom) {
+            return Err(OneWireError::InvalidCrc);
+        }
+        let rom = u64::from_le_bytes(rom);
+        self.rom = Some(rom);
+        Ok(rom)
+    }
+
+    /// Read the memory from `address` into `buf`.
+    ///
+    /// See [`Ds1961s::read`].
+    pub async fn read_async(
+        &mut self,
+        address: u16,
+        buf: &mut [u8],
+    ) -> OneWireResult<(), O::BusError> {
+        if address as usize + buf.len() > MEMORY_END {
+            return Err(OneWireError::InvalidValue("address"));
+        }
+        scratchpad::read_memory_async(&mut self.bus, self.rom, address, buf).await
+    }
+
+    /// Read `page` along with the authentication code of the device for `challenge`.
+    ///
+    /// See [`Ds1961s::read_authenticated_page`].
+    pub async fn read_authenticated_page_async<D: DelayNs>(
+        &mut self,
+        page: u8,
+        challenge: &[u8; 3],
+        delay: &mut D,
+    ) -> OneWireResult<AuthenticatedPage, O::BusError> {
+        if page >= PAGES {
+            return Err(OneWireError::InvalidValue("page"));
+        }
+        let address = page as u16 * PAGE_SIZE as u16;
+        scratchpad::write_scratchpad_async(
+            &mut self.bus,
+            self.rom,
+            address,
+            &challenge_data(challenge),
+        )
+        .await?;
+        let [lo, hi] = address.to_le_bytes();
+        let cmd = [READ_AUTH_PAGE_CMD, lo, hi];
+        OneWireAsync::address(&mut self.bus, self.rom).await?;
+        OneWireAsync::write_bytes(&mut self.bus, &cmd).await?;
+        let mut data = [0; PAGE_SIZE + 3];
+        OneWireAsync::read_bytes(&mut self.bus, &mut data).await?;
+        let mut crc = OneWireCrc16::default();
+        crc.update_slice(&cmd);
+        crc.update_slice(&data);
+        if !crc.is_valid() {
+            return Err(OneWireError::InvalidCrc);
+        }
+        delay.delay_us(SHA_TIME_US).await;
+        let mut buf = [0; MAC_SIZE + 2];
+        OneWireAsync::read_bytes(&mut self.bus, &mut buf).await?;
+        parse_page(&data, &buf)
+    }
+
+    /// Read `page` and check that the device knows `secret`.
+    ///
+    /// See [`Ds1961s::authenticate_page`].
+    pub async fn authenticate_page_async<D: DelayNs>(
+        &mut self,
+        page: u8,
+        challenge: &[u8; 3],
+        secret: &[u8; SECRET_SIZE],
+        delay: &mut D,
+    ) -> OneWireResult<[u8; PAGE_SIZE], O::BusError> {
+        let rom = self.id_async().await?;
+        let read = self
+            .read_authenticated_page_async(page, challenge, delay)
+            .await?;
+        check_page(&read, secret, page, rom, challenge)
+    }
+}
+
+impl<O: OneWireStrongPullupAsync> Ds1961s<O> {
+    async fn fill_scratchpad_async(
+        &mut self,
+        address: u16,
+        data: &[u8; SCRATCHPAD_SIZE],
+    ) -> OneWireResult<Scratchpad<SCRATCHPAD_SIZE>, O::BusError> {
+        scratchpad::write_scratchpad_async(&mut self.bus, self.rom, address, data).await?;
+        let scratchpad = scratchpad::read_scratchpad_async(&mut self.bus, self.rom).await?;
+        verify_scratchpad(&scratchpad, address, data)?;
+        Ok(scratchpad)
+    }
+
+    async fn finish_async<D: DelayNs>(
+        &mut self,
+        delay: &mut D,
+        us: u32,
+    ) -> OneWireResult<(), O::BusError> {
+        delay.delay_us(us).await;
+        if OneWireAsync::read_byte(&mut self.bus).await? != DONE {
+            return Err(OneWireError::VerificationFailed);
+        }
+        Ok(())
+    }
+
+    /// Load `secret` into the device, without knowing the current one.
+    ///
+    /// See [`Ds1961s::load_first_secret`].
+    pub async fn load_first_secret_async<D: DelayNs>(
+        &mut self,
+        secret: &[u8; SECRET_SIZE],
+        delay: &mut D,
+    ) -> OneWireResult<(), O::BusError> {
+        let scratchpad = self.fill_scratchpad_async(SECRET_ADDRESS, secret).await?;
+        let [lo, hi] = SECRET_ADDRESS.to_le_bytes();
+        OneWireAsync::address(&mut self.bus, self.rom).await?;
+        OneWireAsync::write_bytes(&mut self.bus, &[LOAD_FIRST_SECRET_CMD, lo, hi]).await?;
+        OneWireStrongPullupAsync::write_byte_powered(&mut self.bus, scratchpad.es).await?;
+        self.finish_async(delay, PROG_TIME_US).await
+    }
+
+    /// Replace the secret by one computed from the current secret, `page` and `partial`.
+    ///
+    /// See [`Ds1961s::compute_next_secret`].
+    pub async fn compute_next_secret_async<D: DelayNs>(
+        &mut self,
+        page: u8,
+        partial: &[u8; SCRATCHPAD_SIZE],
+        delay: &mut D,
+    ) -> OneWireResult<(), O::BusError> {
+        if page >= PAGES {
+            return Err(OneWireError::InvalidValue("page"));
+        }
+        let address = page as u16 * PAGE_SIZE as u16;
+        self.fill_scratchpad_async(address, partial).await?;
+        let [lo, hi] = address.to_le_bytes();
+        OneWireAsync::address(&mut self.bus, self.rom).await?;
+        OneWireAsync::write_bytes(&mut self.bus, &[COMPUTE_NEXT_SECRET_CMD, lo]).await?;
+        OneWireStrongPullupAsync::write_byte_powered(&mut self.bus, hi).await?;
+        self.finish_async(delay, SHA_TIME_US + PROG_TIME_US).await
+    }
+
+    /// Program the block at `address` with `data`, authorized with `secret`.
+    ///
+    /// See [`Ds1961s::write_authenticated`].
+    pub async fn write_authenticated_async<D: DelayNs>(
+        &mut self,
+        address: u16,
+        data: &[u8; SCRATCHPAD_SIZE],
+        secret: &[u8; SECRET_SIZE],
+        delay: &mut D,
+    ) -> OneWireResult<(), O::BusError> {
+        if !(address as usize).is_multiple_of(SCRATCHPAD_SIZE)
+            || address as usize >= MEMORY_END
+            || address == SECRET_ADDRESS
+        {
+            return Err(OneWireError::InvalidValue("address"));
+        }
+        let rom = self.id_async().await?;
+        let scratchpad = self.fill_scratchpad_async(address, data).await?;
+        let mut page = [0; 28];
+        let start = address & !(PAGE_SIZE as u16 - 1);
+        scratchpad::read_memory_async(&mut self.bus, self.rom, start, &mut page).await?;
+        let mac = mac::copy_scratchpad_mac(secret, &page, data, address, rom);
+        let [lo, hi] = address.to_le_bytes();
+        OneWireAsync::address(&mut self.bus, self.rom).await?;
+        OneWireAsync::write_bytes(&mut self.bus, &[COPY_SCRATCHPAD_CMD, lo, hi]).await?;
+        OneWireStrongPullupAsync::write_byte_powered(&mut self.bus, scratchpad.es).await?;
+        delay.delay_us(SHA_TIME_US).await;
+        OneWireAsync::write_bytes(&mut self.bus, &mac[..MAC_SIZE - 1]).await?;
+        OneWireStrongPullupAsync::write_byte_powered(&mut self.bus, mac[MAC_SIZE - 1]).await?;
+        self.finish_async(delay, PROG_TIME_US).await
+    }
+}
@@ -0,0 +1,140 @@
+#![no_std]
+#![deny(missing_docs)]
+#![cfg_attr(docsrs, feature(doc_cfg))]
+#![doc = include_str!("../README.md")]
+
+mod device;
+mod device_async;
+pub mod mac;
+
+pub use device::Ds1961s;
+pub use embedded_onewire::{OneWireError, OneWireResult};
+
+use mac::MAC_SIZE;
+
+/// Family code of the DS1961S, the low byte of its ROM.
+pub const FAMILY_CODE: u8 = 0x33;
+/// Size of a page of the data memory.
+pub const PAGE_SIZE: usize = 32;
+/// Number of pages of the data memory.
+pub const PAGES: u8 = 4;
+/// Size of the scratchpad, and of the blocks the memory is written in.
+pub const SCRATCHPAD_SIZE: usize = 8;
+/// Address of the secret, which reads back as `0xff`.
+pub const SECRET_ADDRESS: u16 = 0x80;
+/// Address of the write protection of the secret. Writing `0xaa` or `0x55` to it
+/// permanently protects the secret.
+pub const SECRET_PROTECTION_ADDRESS: u16 = 0x88;
+/// Address of the write protection of the data memory. Writing `0xaa` or `0x55` to it
+/// permanently protects the data memory.
+pub const MEMORY_PROTECTION_ADDRESS: u16 = 0x89;
+
+/// End of the memory, including the secret and the registers.
+pub(crate) const MEMORY_END: usize = 0x90;
+
+pub(crate) const LOAD_FIRST_SECRET_CMD: u8 = 0x5a;
+pub(crate) const COMPUTE_NEXT_SECRET_CMD: u8 = 0x33;
+pub(crate) const COPY_SCRATCHPAD_CMD: u8 = 0x55;
+pub(crate) const READ_AUTH_PAGE_CMD: u8 = 0xa5;
+pub(crate) const READ_ROM_CMD: u8 = 0x33;
+
+/// Pattern of alternating ones and zeros sent by the device after a successful operation.
+pub(crate) const DONE: u8 = 0xaa;
+
+/// Time for the device to compute a message authentication code.
+pub(crate) const SHA_TIME_US: u32 = 2_000;
+/// Time for the device to program the memory or the secret.
+pub(crate) const PROG_TIME_US: u32 = 10_000;
+
+/// A page read with the Read Authenticated Page command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AuthenticatedPage {
+    /// The page.
+    pub data: [u8; PAGE_SIZE],
+    /// The message authentication code computed by the device, see [`mac::read_page_mac`].
+    pub mac: [u8; MAC_SIZE],
+}
+
+mod test {
+    #[test]
+    fn test_authenticate_page() {
+        use super::*;
+        use embedded_hal_mock::eh1::delay::NoopDelay;
+        use embedded_onewire::testing::{Mock, Transaction};
+        extern crate std;
+
+        const ROM: u64 = u64::from_le_bytes([0x33, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0xd3]);
+        const MAC: [u8; MAC_SIZE] = [
+            0x0b, 0xcf, 0xb8, 0x53, 0x25, 0x7c, 0xea, 0xa4, 0xb3, 0x0c, 0xad, 0x8a, 0xeb, 0x29,
+            0x5a, 0xea, 0x27, 0xc6, 0x86, 0x10,
+        ];
+        let secret: [u8; 8] = core::array::from_fn(|i| 0x10 + i as u8);
+        let data: [u8; PAGE_SIZE] = core::array::from_fn(|i| 0x40 + i as u8);
+        let challenge = [0xa1, 0xb2, 0xc3];
+        let mut page = data.to_vec();
+        page.extend_from_slice(&[0xff, 0x6c, 0x6e]);
+        let mut mac = MAC.to_vec();
+        mac.extend_from_slice(&[0x00, 0x05]);
+        let read = [
+            Transaction::reset(true),
+            Transaction::match_rom(ROM),
+            Transaction::write(&[0x0f, 0x20, 0x00, 0, 0, 0, 0, 0xa1, 0xb2, 0xc3, 0]),
+            Transaction::read(&[0x1d, 0xaa]),
+            Transaction::reset(true),
+            Transaction::match_rom(ROM),
+            Transaction::write(&[READ_AUTH_PAGE_CMD, 0x20, 0x00]),
+            Transaction::read(&page),
+            Transaction::read(&mac),
+        ];
+        let bus = Mock::new(&[read.clone(), read].concat());
+        let mut token = Ds1961s::new(bus, Some(ROM));
+        assert_eq!(mac::read_page_mac(&secret, &data, 1, ROM, &challenge), MAC);
+        assert_eq!(
+            token
+                .authenticate_page(1, &challenge, &secret, &mut NoopDelay::new())
+                .unwrap(),
+            data
+        );
+        // A device that does not know the secret
+        assert!(matches!(
+            token.authenticate_page(1, &challenge, &[0; 8], &mut NoopDelay::new()),
+            Err(OneWireError::VerificationFailed)
+        ));
+        token.release().done();
+    }
+
+    #[test]
+    fn test_load_first_secret() {
+        use super::*;
+        use embedded_hal_mock::eh1::delay::NoopDelay;
+        use embedded_onewire::testing::{Mock, Transaction};
+        extern crate std;
+
+        let secret: [u8; 8] = core::array::from_fn(|i| 0x10 + i as u8);
+        let mut write = [0x0f, 0x80, 0x00].to_vec();
+        write.extend_from_slice(&secret);
+        let mut scratchpad = [0x80, 0x00, 0x07].to_vec();
+        scratchpad.extend_from_slice(&secret);
+        scratchpad.extend_from_slice(&[0x37, 0x32]);
+        let bus = Mock::new(&[
+            Transaction::reset(true),
+            Transaction::skip_rom(),
+            Transaction::write(&write),
+            Transaction::read(&[0x14, 0xe5]),
+            Transaction::reset(true),
+            Transaction::skip_rom(),
+            Transaction::write(&[0xaa]),
+            Transaction::read(&scratchpad),
+            Transaction::reset(true),
+            Transaction::skip_rom(),
+            Transaction::write(&[LOAD_FIRST_SECRET_CMD, 0x80, 0x00]),
+            Transaction::write_powered(0x07),
+            Transaction::read(&[DONE]),
+        ]);
+        let mut token = Ds1961s::new(bus, None);
+        token
+            .load_first_secret(&secret, &mut NoopDelay::new())
+            .unwrap();
+        token.release().done();
+    }
+}
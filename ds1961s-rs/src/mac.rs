@@ -0,0 +1,105 @@
+//! The SHA-1 message authentication codes of the DS1961S.
+//!
+//! The device hashes a single 64-byte block made of its secret, memory data, its ROM and the
+//! operation arguments, padded as a 55-byte SHA-1 message. The bus master computes the same
+//! codes with the secret to authenticate the device, or to authorize a write.
+//!
+//! The 20-byte codes are in the order sent on the bus: the words E, D, C, B and A of the
+//! SHA-1 state, least significant byte first.
+
+/// Size of the secret.
+pub const SECRET_SIZE: usize = 8;
+/// Size of a message authentication code.
+pub const MAC_SIZE: usize = 20;
+
+/// Message authentication code sent by the device for the Read Authenticated Page command.
+///
+/// # Arguments
+/// * `secret` - The secret of the device.
+/// * `data` - The page, as read by the command.
+/// * `page` - The number of the page.
+/// * `rom` - The ROM of the device.
+/// * `challenge` - The challenge written to bytes 4 to 6 of the scratchpad.
+pub fn read_page_mac(
+    secret: &[u8; SECRET_SIZE],
+    data: &[u8; 32],
+    page: u8,
+    rom: u64,
+    challenge: &[u8; 3],
+) -> [u8; MAC_SIZE] {
+    let mut block = [0; 64];
+    block[4..36].copy_from_slice(data);
+    block[36..40].fill(0xff);
+    block[40] = 0x40 | (page & 0x07);
+    block[52..55].copy_from_slice(challenge);
+    compute(block, secret, rom)
+}
+
+/// Message authentication code expected by the device for the Copy Scratchpad command.
+///
+/// # Arguments
+/// * `secret` - The secret of the device.
+/// * `page` - The first 28 bytes of the page targeted by the scratchpad, before the copy.
+/// * `scratchpad` - The data in the scratchpad.
+/// * `address` - The target address of the scratchpad.
+/// * `rom` - The ROM of the device.
+pub fn copy_scratchpad_mac(
+    secret: &[u8; SECRET_SIZE],
+    page: &[u8; 28],
+    scratchpad: &[u8; 8],
+    address: u16,
+    rom: u64,
+) -> [u8; MAC_SIZE] {
+    let mut block = [0; 64];
+    block[4..32].copy_from_slice(page);
+    block[32..40].copy_from_slice(scratchpad);
+    block[40] = (address >> 5) as u8 & 0x07;
+    block[52..55].fill(0xff);
+    compute(block, secret, rom)
+}
+
+/// Secret computed by the device for the Compute Next Secret command.
+///
+/// # Arguments
+/// * `secret` - The current secret of the device.
+/// * `page` - The page addressed by the command.
+/// * `scratchpad` - The partial secret in the scratchpad.
+pub fn next_secret(
+    secret: &[u8; SECRET_SIZE],
+    page: &[u8; 32],
+    scratchpad: &[u8; 8],
+) -> [u8; SECRET_SIZE] {
+    let mut block = [0; 64];
+    block[0..4].copy_from_slice(&secret[..4]);
+    block[4..36].copy_from_slice(page);
+    block[36..44].copy_from_slice(scratchpad);
+    block[44..48].copy_from_slice(&secret[4..]);
+    block[48..55].fill(0xff);
+    let mac = hash(block);
+    let mut next = [0; SECRET_SIZE];
+    next.copy_from_slice(&mac[..SECRET_SIZE]);
+    next
+}
+
+/// Fill in the secret and the ROM at the places common to the authentication codes, and
+/// hash the block.
+fn compute(mut block: [u8; 64], secret: &[u8; SECRET_SIZE], rom: u64) -> [u8; MAC_SIZE] {
+    block[0..4].copy_from_slice(&secret[..4]);
+    block[41..48].copy_from_slice(&rom.to_le_bytes()[..7]);
+    block[48..52].copy_from_slice(&secret[4..]);
+    hash(block)
+}
+
+/// Pad the 55-byte message in `block`, and hash it.
+fn hash(mut block: [u8; 64]) -> [u8; MAC_SIZE] {
+    block[55] = 0x80;
+    // Length of the message in bits
+    block[62..64].copy_from_slice(&(55u16 * 8).to_be_bytes());
+    let mut state = [0x67452301, 0xefcdab89, 0x98badcfe, 0x10325476, 0xc3d2e1f0];
+    sha1::compress(&mut state, &[block.into()]);
+    let mut mac = [0; MAC_SIZE];
+    for (chunk, word) in mac.chunks_exact_mut(4).zip(state.iter().rev()) {
+        chunk.copy_from_slice(&word.to_le_bytes());
+    }
+    mac
+}
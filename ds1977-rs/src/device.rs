@@ -0,0 +1,261 @@
+use crate::{
+    COPY_DONE, COPY_SCRATCHPAD_PASSWORD_CMD, DATA_END, MEMORY_END, PAGE_SIZE,
+    PASSWORD_CONTROL_ADDRESS, PASSWORD_SIZE, PASSWORD_TIME_US, PASSWORDS_ADDRESS,
+    PASSWORDS_ENABLED, PROG_TIME_US, Password, READ_MEMORY_PASSWORD_CMD,
+};
+use embedded_hal::delay::DelayNs;
+use embedded_onewire::{OneWireCrc16, OneWireError, OneWireResult, OneWireStrongPullup};
+use onewire_eeprom::scratchpad::{self, Scratchpad};
+
+/// A DS1977 password-protected 32KB EEPROM iButton on a 1-Wire bus.
+///
+/// The driver owns the bus master, which can be a `&mut` reference to one or a handle to a
+/// shared one. Every operation addresses the device first.
+///
+/// Every access to the memory carries a password, which the device checks with the bus
+/// powered, so reads also require a bus master implementing `OneWireStrongPullup`.
+#[derive(Debug)]
+pub struct Ds1977<O> {
+    pub(crate) bus: O,
+    pub(crate) rom: Option<u64>,
+}
+
+impl<O> Ds1977<O> {
+    /// Creates a driver for the device with ROM `rom` on `bus`.
+    ///
+    /// Pass [`None`] to skip ROM addressing on a bus with a single device.
+    pub fn new(bus: O, rom: Option<u64>) -> Self {
+        Ds1977 { bus, rom }
+    }
+
+    /// ROM of the device, if it is addressed by ROM.
+    pub fn rom(&self) -> Option<u64> {
+        self.rom
+    }
+
+    /// Release the bus master.
+    pub fn release(self) -> O {
+        self.bus
+    }
+}
+
+impl<O: OneWireStrongPullup> Ds1977<O> {
+    /// Send `password`, and power the bus while the device checks it.
+    fn send_password<D: DelayNs>(
+        &mut self,
+        password: &Password,
+        delay: &mut D,
+        us: u32,
+    ) -> OneWireResult<(), O::BusError> {
+        self.bus.write_bytes(&password[..PASSWORD_SIZE - 1])?;
+        self.bus.write_byte_powered(password[PASSWORD_SIZE - 1])?;
+        delay.delay_us(us);
+        Ok(())
+    }
+
+    /// Read the memory from `address` into `buf`, with the read access or the full access
+    /// password.
+    ///
+    /// The data memory and the register page can be read, up to address `0x7fff`. The device
+    /// sends the CRC-16 of every page it reads, so the read always continues to the end of
+    /// the last page touched by `buf`.
+    ///
+    /// # Errors
+    /// Returns [`OneWireError::InvalidValue`] if the read goes past the end of the memory,
+    /// and [`OneWireError::InvalidCrc`] if the CRC-16 of a page does not match. A device that
+    /// rejects the password stops responding, which also fails the CRC-16.
+    pub fn read<D: DelayNs>(
+        &mut self,
+        address: u16,
+        buf: &mut [u8],
+        password: &Password,
+        delay: &mut D,
+    ) -> OneWireResult<(), O::BusError> {
+        if address as usize + buf.len() > MEMORY_END {
+            return Err(OneWireError::InvalidValue("address"));
+        }
+        let [lo, hi] = address.to_le_bytes();
+        let cmd = [READ_MEMORY_PASSWORD_CMD, lo, hi];
+        self.bus.address(self.rom)?;
+        self.bus.write_bytes(&cmd)?;
+        self.send_password(password, delay, PASSWORD_TIME_US)?;
+        // The CRC of the first page includes the command and the address
+        let mut crc = OneWireCrc16::default();
+        crc.update_slice(&cmd);
+        let mut offset = address as usize % PAGE_SIZE;
+        let mut buf = buf;
+        while !buf.is_empty() {
+            let mut page = [0; PAGE_SIZE];
+            let page = &mut page[offset..];
+            self.bus.read_bytes(page)?;
+            let mut check = [0; 2];
+            self.bus.read_bytes(&mut check)?;
+            crc.update_slice(page);
+            crc.update_slice(&check);
+            if !crc.is_valid() {
+                return Err(OneWireError::InvalidCrc);
+            }
+            let len = page.len().min(buf.len());
+            buf[..len].copy_from_slice(&page[..len]);
+            buf = &mut buf[len..];
+            crc = OneWireCrc16::default();
+            offset = 0;
+        }
+        Ok(())
+    }
+
+    /// Whether the passwords are enabled, read with the read access or the full access
+    /// password.
+    ///
+    /// # Errors
+    /// See [`Ds1977::read`].
+    pub fn password_protection<D: DelayNs>(
+        &mut self,
+        password: &Password,
+        delay: &mut D,
+    ) -> OneWireResult<bool, O::BusError> {
+        let mut control = [0];
+        self.read(PASSWORD_CONTROL_ADDRESS, &mut control, password, delay)?;
+        Ok(control[0] == PASSWORDS_ENABLED)
+    }
+
+    /// Program the page at `address` with `page`, with the full access password.
+    ///
+    /// # Errors
+    /// Returns [`OneWireError::InvalidValue`] if `address` is not the start of a page of the
+    /// data memory, [`OneWireError::InvalidCrc`] if a transfer of the scratchpad is
+    /// corrupted, and [`OneWireError::VerificationFailed`] if the scratchpad read back does
+    /// not match, or the device does not confirm the copy, e.g. because it rejected the
+    /// password.
+    pub fn write_page<D: DelayNs>(
+        &mut self,
+        address: u16,
+        page: &[u8; PAGE_SIZE],
+        password: &Password,
+        delay: &mut D,
+    ) -> OneWireResult<(), O::BusError> {
+        if !(address as usize).is_multiple_of(PAGE_SIZE) || address as usize >= DATA_END {
+            return Err(OneWireError::InvalidValue("address"));
+        }
+        self.program_page(address, page, password, delay)
+    }
+
+    /// Write `data` to the data memory at `address`, with the full access password.
+    ///
+    /// Every page touched by `data` is programmed in full: pages that are only partially
+    /// covered are read first, so that the rest of their bytes are written back unchanged.
+    ///
+    /// # Errors
+    /// Returns [`OneWireError::InvalidValue`] if the write goes past the end of the data
+    /// memory, and the errors of [`Ds1977::write_page`]. The pages before the failed one are
+    /// already programmed.
+    pub fn write<D: DelayNs>(
+        &mut self,
+        address: u16,
+        data: &[u8],
+        password: &Password,
+        delay: &mut D,
+    ) -> OneWireResult<(), O::BusError> {
+        if address as usize + data.len() > DATA_END {
+            return Err(OneWireError::InvalidValue("address"));
+        }
+        self.program(address, data, password, delay)
+    }
+
+    /// Set the read access and the full access passwords, with the current full access
+    /// password.
+    ///
+    /// The new passwords take effect once the passwords are enabled with
+    /// [`Ds1977::set_password_protection`]. The passwords can not be read back, and a
+    /// forgotten full access password makes the memory read-only for good.
+    ///
+    /// # Errors
+    /// See [`Ds1977::write_page`].
+    pub fn set_passwords<D: DelayNs>(
+        &mut self,
+        read: &Password,
+        full: &Password,
+        password: &Password,
+        delay: &mut D,
+    ) -> OneWireResult<(), O::BusError> {
+        let mut passwords = [0; 2 * PASSWORD_SIZE];
+        passwords[..PASSWORD_SIZE].copy_from_slice(read);
+        passwords[PASSWORD_SIZE..].copy_from_slice(full);
+        self.program(PASSWORDS_ADDRESS, &passwords, password, delay)
+    }
+
+    /// Enable or disable the passwords, with the full access password.
+    ///
+    /// # Errors
+    /// See [`Ds1977::write_page`].
+    pub fn set_password_protection<D: DelayNs>(
+        &mut self,
+        enable: bool,
+        password: &Password,
+        delay: &mut D,
+    ) -> OneWireResult<(), O::BusError> {
+        let control = if enable { PASSWORDS_ENABLED } else { 0x00 };
+        self.program(PASSWORD_CONTROL_ADDRESS, &[control], password, delay)
+    }
+
+    /// Write `data` at `address`, which may fall in the register page.
+    fn program<D: DelayNs>(
+        &mut self,
+        address: u16,
+        data: &[u8],
+        password: &Password,
+        delay: &mut D,
+    ) -> OneWireResult<(), O::BusError> {
+        let mut address = address as usize;
+        let mut data = data;
+        while !data.is_empty() {
+            let offset = address % PAGE_SIZE;
+            let start = (address - offset) as u16;
+            let len = (PAGE_SIZE - offset).min(data.len());
+            let mut page = [0; PAGE_SIZE];
+            if len != PAGE_SIZE {
+                self.read(start, &mut page, password, delay)?;
+            }
+            page[offset..offset + len].copy_from_slice(&data[..len]);
+            self.program_page(start, &page, password, delay)?;
+            address += len;
+            data = &data[len..];
+        }
+        Ok(())
+    }
+
+    /// Fill the scratchpad with `page`, and copy it to the page at `address`.
+    fn program_page<D: DelayNs>(
+        &mut self,
+        address: u16,
+        page: &[u8; PAGE_SIZE],
+        password: &Password,
+        delay: &mut D,
+    ) -> OneWireResult<(), O::BusError> {
+        scratchpad::write_scratchpad(&mut self.bus, self.rom, address, page)?;
+        let scratchpad = scratchpad::read_scratchpad(&mut self.bus, self.rom)?;
+        verify_scratchpad(&scratchpad, address, page)?;
+        let [lo, hi] = address.to_le_bytes();
+        self.bus.address(self.rom)?;
+        self.bus
+            .write_bytes(&[COPY_SCRATCHPAD_PASSWORD_CMD, lo, hi, scratchpad.es])?;
+        self.send_password(password, delay, PROG_TIME_US)?;
+        if self.bus.read_byte()? != COPY_DONE {
+            return Err(OneWireError::VerificationFailed);
+        }
+        Ok(())
+    }
+}
+
+pub(crate) fn verify_scratchpad<E>(
+    scratchpad: &Scratchpad<PAGE_SIZE>,
+    address: u16,
+    data: &[u8; PAGE_SIZE],
+) -> OneWireResult<(), E> {
+    let ending = (PAGE_SIZE - 1) as u8;
+    if scratchpad.address != address || scratchpad.es & ending != ending || scratchpad.data != *data
+    {
+        return Err(OneWireError::VerificationFailed);
+    }
+    Ok(())
+}
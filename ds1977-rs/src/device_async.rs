@@ -0,0 +1,199 @@
+use crate::{
+    COPY_DONE, COPY_SCRATCHPAD_PASSWORD_CMD, DATA_END, Ds1977, MEMORY_END, PAGE_SIZE,
+    PASSWORD_CONTROL_ADDRESS, PASSWORD_SIZE, PASSWORD_TIME_US, PASSWORDS_ADDRESS,
+    PASSWORDS_ENABLED, PROG_TIME_US, Password, READ_MEMORY_PASSWORD_CMD, device::verify_scratchpad,
+};
+use embedded_hal_async::delay::DelayNs;
+use embedded_onewire::{
+    OneWireAsync, OneWireCrc16, OneWireError, OneWireResult, OneWireStrongPullupAsync,
+};
+use onewire_eeprom::scratchpad;
+
+impl<O: OneWireStrongPullupAsync> Ds1977<O> {
+    async fn send_password_async<D: DelayNs>(
+        &mut self,
+        password: &Password,
+        delay: &mut D,
+        us: u32,
+    ) -> OneWireResult<(), O::BusError> {
+        OneWireAsync::write_bytes(&mut self.bus, &password[..PASSWORD_SIZE - 1]).await?;
+        OneWireStrongPullupAsync::write_byte_powered(&mut self.bus, password[PASSWORD_SIZE - 1])
+            .await?;
+        delay.delay_us(us).await;
+        Ok(())
+    }
+
+    /// Read the memory from `address` into `buf`, with the read access or the full access
+    /// password.
+    ///
+    /// See [`Ds1977::read`].
+    pub async fn read_async<D: DelayNs>(
+        &mut self,
+        address: u16,
+        buf: &mut [u8],
+        password: &Password,
+        delay: &mut D,
+    ) -> OneWireResult<(), O::BusError> {
+        if address as usize + buf.len() > MEMORY_END {
+            return Err(OneWireError::InvalidValue("address"));
+        }
+        let [lo, hi] = address.to_le_bytes();
+        let cmd = [READ_MEMORY_PASSWORD_CMD, lo, hi];
+        OneWireAsync::address(&mut self.bus, self.rom).await?;
+        OneWireAsync::write_bytes(&mut self.bus, &cmd).await?;
+        self.send_password_async(password, delay, PASSWORD_TIME_US)
+            .await?;
+        let mut crc = OneWireCrc16::default();
+        crc.update_slice(&cmd);
+        let mut offset = address as usize % PAGE_SIZE;
+        let mut buf = buf;
+        while !buf.is_empty() {
+            let mut page = [0; PAGE_SIZE];
+            let page = &mut page[offset..];
+            OneWireAsync::read_bytes(&mut self.bus, page).await?;
+            let mut check = [0; 2];
+            OneWireAsync::read_bytes(&mut self.bus, &mut check).await?;
+            crc.update_slice(page);
+            crc.update_slice(&check);
+            if !crc.is_valid() {
+                return Err(OneWireError::InvalidCrc);
+            }
+            let len = page.len().min(buf.len());
+            buf[..len].copy_from_slice(&page[..len]);
+            buf = &mut buf[len..];
+            crc = OneWireCrc16::default();
+            offset = 0;
+        }
+        Ok(())
+    }
+
+    /// Whether the passwords are enabled.
+    ///
+    /// See [`Ds1977::password_protection`].
+    pub async fn password_protection_async<D: DelayNs>(
+        &mut self,
+        password: &Password,
+        delay: &mut D,
+    ) -> OneWireResult<bool, O::BusError> {
+        let mut control = [0];
+        self.read_async(PASSWORD_CONTROL_ADDRESS, &mut control, password, delay)
+            .await?;
+        Ok(control[0] == PASSWORDS_ENABLED)
+    }
+
+    /// Program the page at `address` with `page`, with the full access password.
+    ///
+    /// See [`Ds1977::write_page`].
+    pub async fn write_page_async<D: DelayNs>(
+        &mut self,
+        address: u16,
+        page: &[u8; PAGE_SIZE],
+        password: &Password,
+        delay: &mut D,
+    ) -> OneWireResult<(), O::BusError> {
+        if !(address as usize).is_multiple_of(PAGE_SIZE) || address as usize >= DATA_END {
+            return Err(OneWireError::InvalidValue("address"));
+        }
+        self.program_page_async(address, page, password, delay)
+            .await
+    }
+
+    /// Write `data` to the data memory at `address`, with the full access password.
+    ///
+    /// See [`Ds1977::write`].
+    pub async fn write_async<D: DelayNs>(
+        &mut self,
+        address: u16,
+        data: &[u8],
+        password: &Password,
+        delay: &mut D,
+    ) -> OneWireResult<(), O::BusError> {
+        if address as usize + data.len() > DATA_END {
+            return Err(OneWireError::InvalidValue("address"));
+        }
+        self.program_async(address, data, password, delay).await
+    }
+
+    /// Set the read access and the full access passwords, with the current full access
+    /// password.
+    ///
+    /// See [`Ds1977::set_passwords`].
+    pub async fn set_passwords_async<D: DelayNs>(
+        &mut self,
+        read: &Password,
+        full: &Password,
+        password: &Password,
+        delay: &mut D,
+    ) -> OneWireResult<(), O::BusError> {
+        let mut passwords = [0; 2 * PASSWORD_SIZE];
+        passwords[..PASSWORD_SIZE].copy_from_slice(read);
+        passwords[PASSWORD_SIZE..].copy_from_slice(full);
+        self.program_async(PASSWORDS_ADDRESS, &passwords, password, delay)
+            .await
+    }
+
+    /// Enable or disable the passwords, with the full access password.
+    ///
+    /// See [`Ds1977::set_password_protection`].
+    pub async fn set_password_protection_async<D: DelayNs>(
+        &mut self,
+        enable: bool,
+        password: &Password,
+        delay: &mut D,
+    ) -> OneWireResult<(), O::BusError> {
+        let control = if enable { PASSWORDS_ENABLED } else { 0x00 };
+        self.program_async(PASSWORD_CONTROL_ADDRESS, &[control], password, delay)
+            .await
+    }
+
+    async fn program_async<D: DelayNs>(
+        &mut self,
+        address: u16,
+        data: &[u8],
+        password: &Password,
+        delay: &mut D,
+    ) -> OneWireResult<(), O::BusError> {
+        let mut address = address as usize;
+        let mut data = data;
+        while !data.is_empty() {
+            let offset = address % PAGE_SIZE;
+            let start = (address - offset) as u16;
+            let len = (PAGE_SIZE - offset).min(data.len());
+            let mut page = [0; PAGE_SIZE];
+            if len != PAGE_SIZE {
+                self.read_async(start, &mut page, password, delay).await?;
+            }
+            page[offset..offset + len].copy_from_slice(&data[..len]);
+            self.program_page_async(start, &page, password, delay)
+                .await?;
+            address += len;
+            data = &data[len..];
+        }
+        Ok(())
+    }
+
+    async fn program_page_async<D: DelayNs>(
+        &mut self,
+        address: u16,
+        page: &[u8; PAGE_SIZE],
+        password: &Password,
+        delay: &mut D,
+    ) -> OneWireResult<(), O::BusError> {
+        scratchpad::write_scratchpad_async(&mut self.bus, self.rom, address, page).await?;
+        let scratchpad = scratchpad::read_scratchpad_async(&mut self.bus, self.rom).await?;
+        verify_scratchpad(&scratchpad, address, page)?;
+        let [lo, hi] = address.to_le_bytes();
+        OneWireAsync::address(&mut self.bus, self.rom).await?;
+        OneWireAsync::write_bytes(
+            &mut self.bus,
+            &[COPY_SCRATCHPAD_PASSWORD_CMD, lo, hi, scratchpad.es],
+        )
+        .await?;
+        self.send_password_async(password, delay, PROG_TIME_US)
+            .await?;
+        if OneWireAsync::read_byte(&mut self.bus).await? != COPY_DONE {
+            return Err(OneWireError::VerificationFailed);
+        }
+        Ok(())
+    }
+}
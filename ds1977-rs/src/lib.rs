@@ -0,0 +1,110 @@
+#![no_std]
+#![deny(missing_docs)]
+#![cfg_attr(docsrs, feature(doc_cfg))]
+#![doc = include_str!("../README.md")]
+
+mod device;
+mod device_async;
+
+pub use device::Ds1977;
+pub use embedded_onewire::{OneWireError, OneWireResult};
+
+/// Family code of the DS1977, the low byte of its ROM.
+pub const FAMILY_CODE: u8 = 0x37;
+/// Size of a page of the memory, and of the scratchpad.
+pub const PAGE_SIZE: usize = 64;
+/// Number of pages of the data memory. The register page follows them.
+pub const PAGES: u16 = 511;
+/// Size of a password.
+pub const PASSWORD_SIZE: usize = 8;
+/// Address of the register page, holding the passwords and the password control register.
+pub const REGISTER_PAGE_ADDRESS: u16 = 0x7fc0;
+
+/// Address of the read access password, followed by the full access password.
+pub(crate) const PASSWORDS_ADDRESS: u16 = REGISTER_PAGE_ADDRESS;
+/// Address of the password control register.
+pub(crate) const PASSWORD_CONTROL_ADDRESS: u16 = 0x7fd0;
+/// End of the data memory.
+pub(crate) const DATA_END: usize = REGISTER_PAGE_ADDRESS as usize;
+/// End of the memory, including the register page.
+pub(crate) const MEMORY_END: usize = 0x8000;
+
+pub(crate) const READ_MEMORY_PASSWORD_CMD: u8 = 0x69;
+pub(crate) const COPY_SCRATCHPAD_PASSWORD_CMD: u8 = 0x99;
+
+/// Value of the password control register that enables the passwords.
+pub(crate) const PASSWORDS_ENABLED: u8 = 0xaa;
+
+/// Pattern of alternating ones and zeros sent by the device after a successful copy.
+pub(crate) const COPY_DONE: u8 = 0xaa;
+
+/// Time for the device to check a password, with the bus powered.
+pub(crate) const PASSWORD_TIME_US: u32 = 5_000;
+/// Time for the device to program a page.
+pub(crate) const PROG_TIME_US: u32 = 10_000;
+
+/// A password of the device.
+///
+/// The read access password only grants reads, and the full access password grants reads
+/// and writes. While the passwords are disabled, any password is accepted.
+pub type Password = [u8; PASSWORD_SIZE];
+
+mod test {
+    #[test]
+    fn test_write_read() {
+        use super::*;
+        use embedded_hal_mock::eh1::delay::NoopDelay;
+        use embedded_onewire::testing::{Mock, Transaction};
+        extern crate std;
+
+        let password: Password = core::array::from_fn(|i| i as u8 + 1);
+        let page: [u8; PAGE_SIZE] = core::array::from_fn(|i| i as u8);
+        let bus = Mock::new(&[
+            Transaction::reset(true),
+            Transaction::skip_rom(),
+            Transaction::write(&[0x0f, 0x40, 0x00]),
+            Transaction::write(&page),
+            Transaction::read(&[0xa4, 0x18]),
+            Transaction::reset(true),
+            Transaction::skip_rom(),
+            Transaction::write(&[0xaa]),
+            Transaction::read(&[0x40, 0x00, 0x3f]),
+            Transaction::read(&page),
+            Transaction::read(&[0x63, 0x37]),
+            Transaction::reset(true),
+            Transaction::skip_rom(),
+            Transaction::write(&[0x99, 0x40, 0x00, 0x3f]),
+            Transaction::write(&password[..7]),
+            Transaction::write_powered(8),
+            Transaction::read(&[0xaa]),
+            // The read continues to the end of the page, and its CRC-16
+            Transaction::reset(true),
+            Transaction::skip_rom(),
+            Transaction::write(&[0x69, 0x50, 0x00]),
+            Transaction::write(&password[..7]),
+            Transaction::write_powered(8),
+            Transaction::read(&page[0x10..]),
+            Transaction::read(&[0x7e, 0xa0]),
+        ]);
+        let mut ibutton = Ds1977::new(bus, None);
+        ibutton
+            .write_page(0x0040, &page, &password, &mut NoopDelay::new())
+            .unwrap();
+        let mut buf = [0; 8];
+        ibutton
+            .read(0x0050, &mut buf, &password, &mut NoopDelay::new())
+            .unwrap();
+        assert_eq!(buf, page[0x10..0x18]);
+        // The register page is only written through the password functions
+        assert!(matches!(
+            ibutton.write_page(
+                REGISTER_PAGE_ADDRESS,
+                &page,
+                &password,
+                &mut NoopDelay::new()
+            ),
+            Err(OneWireError::InvalidValue(_))
+        ));
+        ibutton.release().done();
+    }
+}
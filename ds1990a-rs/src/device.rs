@@ -0,0 +1,95 @@
+use crate::{Event, FAMILY_CODE, Mode, READ_ROM_CMD, config, sample};
+use embedded_onewire::{
+    OneWire, OneWireCrc, OneWireError, OneWireResult, OneWireSearch, OneWireSearchKind,
+    OneWireStatus,
+};
+use ibutton_session::Debouncer;
+
+/// A reader for DS1990A iButtons touching a probe on a 1-Wire bus.
+///
+/// The reader owns the bus master, which can be a `&mut` reference to one or a handle to a
+/// shared one. The contact of an iButton with a probe is intermittent: [`Ds1990a::poll`]
+/// samples the probe, and reports an arrival or a departure once it was sampled several times
+/// in a row. The samples are debounced by the [`Debouncer`] of `ibutton-session`.
+#[derive(Debug)]
+pub struct Ds1990a<O> {
+    pub(crate) bus: O,
+    pub(crate) mode: Mode,
+    pub(crate) debouncer: Debouncer,
+}
+
+impl<O> Ds1990a<O> {
+    /// Creates a reader for the probe on `bus`.
+    ///
+    /// An arrival or a departure is reported after `samples` identical samples.
+    pub fn new(bus: O, mode: Mode, samples: u8) -> Self {
+        Ds1990a {
+            bus,
+            mode,
+            debouncer: Debouncer::new(config(samples)),
+        }
+    }
+
+    /// ROM of the iButton on the probe, once its arrival was reported.
+    pub fn current(&self) -> Option<u64> {
+        self.debouncer.current()
+    }
+
+    /// Release the bus master.
+    pub fn release(self) -> O {
+        self.bus
+    }
+}
+
+impl<O: OneWire> Ds1990a<O> {
+    /// Whether a device answers a reset with a presence pulse.
+    pub fn present(&mut self) -> OneWireResult<bool, O::BusError> {
+        match self.bus.reset() {
+            Ok(status) => Ok(status.presence()),
+            Err(OneWireError::NoDevicePresent) => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Read the ROM of the only device on the bus, if it is a DS1990A.
+    ///
+    /// # Errors
+    /// Returns [`OneWireError::InvalidCrc`] if the ROM is garbled, e.g. by the contact
+    /// bouncing.
+    pub fn read_rom(&mut self) -> OneWireResult<Option<u64>, O::BusError> {
+        if !self.present()? {
+            return Ok(None);
+        }
+        self.bus.write_byte(READ_ROM_CMD)?;
+        let mut rom = [0; 8];
+        self.bus.read_bytes(&mut rom)?;
+        if !OneWireCrc::validate(&rom) {
+            return Err(OneWireError::InvalidCrc);
+        }
+        Ok((rom[0] == FAMILY_CODE).then_some(u64::from_le_bytes(rom)))
+    }
+
+    /// Search the bus for the first DS1990A.
+    ///
+    /// # Errors
+    /// See [`Ds1990a::read_rom`].
+    pub fn search(&mut self) -> OneWireResult<Option<u64>, O::BusError> {
+        let mut search =
+            OneWireSearch::with_family(&mut self.bus, OneWireSearchKind::Normal, FAMILY_CODE);
+        match search.next() {
+            Err(OneWireError::NoDevicePresent) => Ok(None),
+            res => res,
+        }
+    }
+
+    /// Sample the probe, and return the arrival or departure it confirms.
+    ///
+    /// Samples garbled by the contact bouncing are discarded and restart the debouncing.
+    pub fn poll(&mut self) -> OneWireResult<Option<Event>, O::BusError> {
+        let rom = match self.mode {
+            Mode::SingleDrop => self.read_rom(),
+            Mode::MultiDrop => self.search(),
+        };
+        Ok(self.debouncer.sample(sample(rom)?))
+    }
+}
@@ -0,0 +1,57 @@
+use crate::{Ds1990a, Event, FAMILY_CODE, Mode, READ_ROM_CMD, sample};
+use embedded_onewire::{
+    OneWireAsync, OneWireCrc, OneWireError, OneWireResult, OneWireSearchAsync, OneWireSearchKind,
+    OneWireStatus,
+};
+
+impl<O: OneWireAsync> Ds1990a<O> {
+    /// Whether a device answers a reset with a presence pulse.
+    ///
+    /// See [`Ds1990a::present`].
+    pub async fn present_async(&mut self) -> OneWireResult<bool, O::BusError> {
+        match OneWireAsync::reset(&mut self.bus).await {
+            Ok(status) => Ok(status.presence()),
+            Err(OneWireError::NoDevicePresent) => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Read the ROM of the only device on the bus, if it is a DS1990A.
+    ///
+    /// See [`Ds1990a::read_rom`].
+    pub async fn read_rom_async(&mut self) -> OneWireResult<Option<u64>, O::BusError> {
+        if !self.present_async().await? {
+            return Ok(None);
+        }
+        OneWireAsync::write_byte(&mut self.bus, READ_ROM_CMD).await?;
+        let mut rom = [0; 8];
+        OneWireAsync::read_bytes(&mut self.bus, &mut rom).await?;
+        if !OneWireCrc::validate(&rom) {
+            return Err(OneWireError::InvalidCrc);
+        }
+        Ok((rom[0] == FAMILY_CODE).then_some(u64::from_le_bytes(rom)))
+    }
+
+    /// Search the bus for the first DS1990A.
+    ///
+    /// See [`Ds1990a::search`].
+    pub async fn search_async(&mut self) -> OneWireResult<Option<u64>, O::BusError> {
+        let mut search =
+            OneWireSearchAsync::with_family(&mut self.bus, OneWireSearchKind::Normal, FAMILY_CODE);
+        match search.next().await {
+            Err(OneWireError::NoDevicePresent) => Ok(None),
+            res => res,
+        }
+    }
+
+    /// Sample the probe, and return the arrival or departure it confirms.
+    ///
+    /// See [`Ds1990a::poll`].
+    pub async fn poll_async(&mut self) -> OneWireResult<Option<Event>, O::BusError> {
+        let rom = match self.mode {
+            Mode::SingleDrop => self.read_rom_async().await,
+            Mode::MultiDrop => self.search_async().await,
+        };
+        Ok(self.debouncer.sample(sample(rom)?))
+    }
+}
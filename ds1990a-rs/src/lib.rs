@@ -0,0 +1,90 @@
+#![no_std]
+#![deny(missing_docs)]
+#![cfg_attr(docsrs, feature(doc_cfg))]
+#![doc = include_str!("../README.md")]
+
+mod device;
+mod device_async;
+
+pub use device::Ds1990a;
+pub use embedded_onewire::{OneWireError, OneWireResult};
+pub use ibutton_session::Event;
+use ibutton_session::{Sample, SessionConfig};
+
+/// Family code of the DS1990A, the low byte of its ROM.
+pub const FAMILY_CODE: u8 = 0x01;
+
+pub(crate) const READ_ROM_CMD: u8 = 0x33;
+
+/// How the reader finds the iButton touching the probe.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    /// The probe is the only device on the bus, and the ROM is read with the Read ROM command.
+    SingleDrop,
+    /// The probe shares the bus with other devices, and the first DS1990A found by a search
+    /// is read.
+    MultiDrop,
+}
+
+/// Debouncing parameters of a reader that accepts a ROM, or the absence of one, after
+/// `samples` identical samples.
+pub(crate) fn config(samples: u8) -> SessionConfig {
+    let samples = samples.max(1);
+    SessionConfig {
+        // The ROM is read on every poll
+        presence_samples: 1,
+        confirm_reads: samples,
+        departure_samples: samples,
+        family: Some(FAMILY_CODE),
+    }
+}
+
+/// The sample of the probe from the ROM read by a poll.
+///
+/// ROMs garbled by the contact bouncing are discarded.
+pub(crate) fn sample<E>(rom: OneWireResult<Option<u64>, E>) -> OneWireResult<Sample, E> {
+    match rom {
+        Ok(Some(rom)) => Ok(Sample::Rom(rom)),
+        Ok(None) => Ok(Sample::Absent),
+        Err(OneWireError::InvalidCrc | OneWireError::ShortCircuit) => Ok(Sample::Garbled),
+        Err(e) => Err(e),
+    }
+}
+
+mod test {
+    #[test]
+    fn test_poll() {
+        use super::*;
+        use embedded_onewire::testing::{Mock, Transaction};
+        extern crate std;
+
+        const ROM: [u8; 8] = [0x01, 0x5a, 0x3c, 0x12, 0x00, 0x00, 0x00, 0x46];
+        let bus = Mock::new(&[
+            Transaction::reset(true),
+            Transaction::write(&[READ_ROM_CMD]),
+            Transaction::read(&ROM),
+            // The contact bounces
+            Transaction::reset(true),
+            Transaction::write(&[READ_ROM_CMD]),
+            Transaction::read(&[0x01, 0x5a, 0x3c, 0x12, 0x00, 0x00, 0x00, 0x47]),
+            Transaction::reset(true),
+            Transaction::write(&[READ_ROM_CMD]),
+            Transaction::read(&ROM),
+            Transaction::reset(true),
+            Transaction::write(&[READ_ROM_CMD]),
+            Transaction::read(&ROM),
+            Transaction::reset(false),
+            Transaction::reset(false),
+        ]);
+        let rom = u64::from_le_bytes(ROM);
+        let mut reader = Ds1990a::new(bus, Mode::SingleDrop, 2);
+        assert_eq!(reader.poll().unwrap(), None);
+        assert_eq!(reader.poll().unwrap(), None);
+        assert_eq!(reader.poll().unwrap(), None);
+        assert_eq!(reader.poll().unwrap(), Some(Event::Arrived(rom)));
+        assert_eq!(reader.current(), Some(rom));
+        assert_eq!(reader.poll().unwrap(), None);
+        assert_eq!(reader.poll().unwrap(), Some(Event::Departed(rom)));
+        reader.release().done();
+    }
+}
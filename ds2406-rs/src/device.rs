@@ -0,0 +1,126 @@
+use crate::{
+    ACTIVITY_LATCH_RESET, CHANNEL_ACCESS_CMD, CHANNEL_CONTROL, CHANNEL_CONTROL_2, CONTROL_ADDR,
+    Channel, ChannelInfo, Control, READ_STATUS_CMD, SearchChannels, SearchSource, WRITE_STATUS_CMD,
+};
+use embedded_onewire::{OneWire, OneWireCrc16, OneWireError, OneWireResult};
+
+/// A DS2406 or DS2407 dual addressable switch on a 1-Wire bus.
+///
+/// The driver owns the bus master, which can be a `&mut` reference to one or a handle to a
+/// shared one. Every operation addresses the device first.
+#[derive(Debug)]
+pub struct Ds2406<O> {
+    pub(crate) bus: O,
+    pub(crate) rom: Option<u64>,
+}
+
+impl<O> Ds2406<O> {
+    /// Creates a driver for the device with ROM `rom` on `bus`.
+    ///
+    /// Pass [`None`] to skip ROM addressing on a bus with a single device.
+    pub fn new(bus: O, rom: Option<u64>) -> Self {
+        Ds2406 { bus, rom }
+    }
+
+    /// ROM of the device, if it is addressed by ROM.
+    pub fn rom(&self) -> Option<u64> {
+        self.rom
+    }
+
+    /// Release the bus master.
+    pub fn release(self) -> O {
+        self.bus
+    }
+}
+
+impl<O: OneWire> Ds2406<O> {
+    fn command(&mut self, cmd: &[u8]) -> OneWireResult<(), O::BusError> {
+        self.bus.address(self.rom)?;
+        self.bus.write_bytes(cmd)
+    }
+
+    fn channel_access(&mut self, control: u8) -> OneWireResult<ChannelInfo, O::BusError> {
+        self.command(&[CHANNEL_ACCESS_CMD, control, CHANNEL_CONTROL_2])?;
+        Ok(ChannelInfo::from_bits(self.bus.read_byte()?))
+    }
+
+    /// Read the flip-flops, the pin levels and the activity latches.
+    pub fn read_channel_info(&mut self) -> OneWireResult<ChannelInfo, O::BusError> {
+        self.channel_access(CHANNEL_CONTROL)
+    }
+
+    /// Reset the activity latches of both channels, and return the channel info read as
+    /// they are reset.
+    pub fn reset_activity_latches(&mut self) -> OneWireResult<ChannelInfo, O::BusError> {
+        self.channel_access(CHANNEL_CONTROL | ACTIVITY_LATCH_RESET)
+    }
+
+    /// Read status byte 7, holding the flip-flops and the search configuration.
+    ///
+    /// # Errors
+    /// Returns [`OneWireError::InvalidCrc`] if the CRC-16 of the status byte does not match.
+    pub fn read_control(&mut self) -> OneWireResult<Control, O::BusError> {
+        let [lo, hi] = CONTROL_ADDR.to_le_bytes();
+        let cmd = [READ_STATUS_CMD, lo, hi];
+        self.command(&cmd)?;
+        // The CRC-16 follows the last status byte
+        let mut buf = [0; 3];
+        self.bus.read_bytes(&mut buf)?;
+        check_crc(&cmd, &buf)?;
+        Ok(Control::from_bits(buf[0]))
+    }
+
+    /// Write status byte 7.
+    ///
+    /// # Errors
+    /// Returns [`OneWireError::InvalidCrc`] if the CRC-16 sent back by the device does not
+    /// match.
+    pub fn write_control(&mut self, control: Control) -> OneWireResult<(), O::BusError> {
+        let [lo, hi] = CONTROL_ADDR.to_le_bytes();
+        let cmd = [WRITE_STATUS_CMD, lo, hi, control.into_bits()];
+        self.command(&cmd)?;
+        let mut crc = [0; 2];
+        self.bus.read_bytes(&mut crc)?;
+        check_crc(&cmd, &crc)
+    }
+
+    /// Set the flip-flop of `channel`, leaving the rest of status byte 7 unchanged.
+    ///
+    /// The output transistor pulls the pin low if `high` is false.
+    pub fn set_channel(&mut self, channel: Channel, high: bool) -> OneWireResult<(), O::BusError> {
+        let control = self.read_control()?.with_flip_flop(channel, high);
+        self.write_control(control)
+    }
+
+    /// Configure the conditional search.
+    ///
+    /// The device answers a search with [`OneWireSearchKind::Alarmed`] if the state of any
+    /// of `channels`, selected by `source`, equals `polarity`. The configuration is lost at
+    /// power-up.
+    ///
+    /// [`OneWireSearchKind::Alarmed`]: embedded_onewire::OneWireSearchKind::Alarmed
+    pub fn configure_search(
+        &mut self,
+        channels: SearchChannels,
+        source: SearchSource,
+        polarity: bool,
+    ) -> OneWireResult<(), O::BusError> {
+        let control = self
+            .read_control()?
+            .with_search_channels(channels)
+            .with_search_source(source)
+            .with_search_polarity(polarity);
+        self.write_control(control)
+    }
+}
+
+/// Check the inverted CRC-16 at the end of `data`, computed over `cmd` and `data`.
+pub(crate) fn check_crc<E>(cmd: &[u8], data: &[u8]) -> OneWireResult<(), E> {
+    let mut crc = OneWireCrc16::default();
+    crc.update_slice(cmd);
+    crc.update_slice(data);
+    if !crc.is_valid() {
+        return Err(OneWireError::InvalidCrc);
+    }
+    Ok(())
+}
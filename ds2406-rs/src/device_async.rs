@@ -0,0 +1,102 @@
+use crate::{
+    ACTIVITY_LATCH_RESET, CHANNEL_ACCESS_CMD, CHANNEL_CONTROL, CHANNEL_CONTROL_2, CONTROL_ADDR,
+    Channel, ChannelInfo, Control, Ds2406, READ_STATUS_CMD, SearchChannels, SearchSource,
+    WRITE_STATUS_CMD, device::check_crc,
+};
+use embedded_onewire::{OneWireAsync, OneWireResult};
+
+impl<O: OneWireAsync> Ds2406<O> {
+    async fn command_async(&mut self, cmd: &[u8]) -> OneWireResult<(), O::BusError> {
+        OneWireAsync::address(&mut self.bus, self.rom).await?;
+        OneWireAsync::write_bytes(&mut self.bus, cmd).await
+    }
+
+    async fn channel_access_async(
+        &mut self,
+        control: u8,
+    ) -> OneWireResult<ChannelInfo, O::BusError> {
+        self.command_async(&[CHANNEL_ACCESS_CMD, control, CHANNEL_CONTROL_2])
+            .await?;
+        Ok(ChannelInfo::from_bits(
+            OneWireAsync::read_byte(&mut self.bus).await?,
+        ))
+    }
+
+    /// Read the flip-flops, the pin levels and the activity latches.
+    ///
+    /// See [`Ds2406::read_channel_info`].
+    pub async fn read_channel_info_async(&mut self) -> OneWireResult<ChannelInfo, O::BusError> {
+        self.channel_access_async(CHANNEL_CONTROL).await
+    }
+
+    /// Reset the activity latches of both channels.
+    ///
+    /// See [`Ds2406::reset_activity_latches`].
+    pub async fn reset_activity_latches_async(
+        &mut self,
+    ) -> OneWireResult<ChannelInfo, O::BusError> {
+        self.channel_access_async(CHANNEL_CONTROL | ACTIVITY_LATCH_RESET)
+            .await
+    }
+
+    /// Read status byte 7, holding the flip-flops and the search configuration.
+    ///
+    /// See [`Ds2406::read_control`].
+    pub async fn read_control_async(&mut self) -> OneWireResult<Control, O::BusError> {
+        let [lo, hi] = CONTROL_ADDR.to_le_bytes();
+        let cmd = [READ_STATUS_CMD, lo, hi];
+        self.command_async(&cmd).await?;
+        let mut buf = [0; 3];
+        OneWireAsync::read_bytes(&mut self.bus, &mut buf).await?;
+        check_crc(&cmd, &buf)?;
+        Ok(Control::from_bits(buf[0]))
+    }
+
+    /// Write status byte 7.
+    ///
+    /// See [`Ds2406::write_control`].
+    pub async fn write_control_async(
+        &mut self,
+        control: Control,
+    ) -> OneWireResult<(), O::BusError> {
+        let [lo, hi] = CONTROL_ADDR.to_le_bytes();
+        let cmd = [WRITE_STATUS_CMD, lo, hi, control.into_bits()];
+        self.command_async(&cmd).await?;
+        let mut crc = [0; 2];
+        OneWireAsync::read_bytes(&mut self.bus, &mut crc).await?;
+        check_crc(&cmd, &crc)
+    }
+
+    /// Set the flip-flop of `channel`, leaving the rest of status byte 7 unchanged.
+    ///
+    /// See [`Ds2406::set_channel`].
+    pub async fn set_channel_async(
+        &mut self,
+        channel: Channel,
+        high: bool,
+    ) -> OneWireResult<(), O::BusError> {
+        let control = self
+            .read_control_async()
+            .await?
+            .with_flip_flop(channel, high);
+        self.write_control_async(control).await
+    }
+
+    /// Configure the conditional search.
+    ///
+    /// See [`Ds2406::configure_search`].
+    pub async fn configure_search_async(
+        &mut self,
+        channels: SearchChannels,
+        source: SearchSource,
+        polarity: bool,
+    ) -> OneWireResult<(), O::BusError> {
+        let control = self
+            .read_control_async()
+            .await?
+            .with_search_channels(channels)
+            .with_search_source(source)
+            .with_search_polarity(polarity);
+        self.write_control_async(control).await
+    }
+}
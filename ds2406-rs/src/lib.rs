@@ -0,0 +1,255 @@
+#![no_std]
+#![deny(missing_docs)]
+#![cfg_attr(docsrs, feature(doc_cfg))]
+#![doc = include_str!("../README.md")]
+
+mod device;
+mod device_async;
+
+pub use device::Ds2406;
+pub use embedded_onewire::{OneWireError, OneWireResult};
+
+use bitfield_struct::bitfield;
+
+/// Family code of the DS2406 and DS2407, the low byte of their ROM.
+pub const FAMILY_CODE: u8 = 0x12;
+
+pub(crate) const READ_STATUS_CMD: u8 = 0xaa;
+pub(crate) const WRITE_STATUS_CMD: u8 = 0x55;
+pub(crate) const CHANNEL_ACCESS_CMD: u8 = 0xf5;
+
+/// Address of the status byte holding the output flip-flops and the search configuration,
+/// the only one of the status memory in SRAM.
+pub(crate) const CONTROL_ADDR: u16 = 0x07;
+
+/// Channel control byte 1 of a Channel Access that selects PIO-A and reads the channel info
+/// without CRC. The activity latches are reset if [`ACTIVITY_LATCH_RESET`] is added.
+pub(crate) const CHANNEL_CONTROL: u8 = 0x04;
+/// ALR bit of the channel control byte 1.
+pub(crate) const ACTIVITY_LATCH_RESET: u8 = 0x80;
+/// Channel control byte 2, reserved.
+pub(crate) const CHANNEL_CONTROL_2: u8 = 0xff;
+
+/// A channel of the DS2406.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Channel {
+    /// PIO-A
+    A,
+    /// PIO-B, absent from the TO-92 package.
+    B,
+}
+
+/// Channels taking part in the conditional search.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SearchChannels {
+    /// No channel: the device never answers a conditional search.
+    None,
+    /// PIO-A
+    A,
+    /// PIO-B
+    B,
+    /// PIO-A and PIO-B, the condition is true if either matches.
+    Both,
+}
+
+impl SearchChannels {
+    const fn into_bits(self) -> u8 {
+        self as u8
+    }
+
+    const fn from_bits(bits: u8) -> Self {
+        match bits & 0b11 {
+            0 => SearchChannels::None,
+            1 => SearchChannels::A,
+            2 => SearchChannels::B,
+            _ => SearchChannels::Both,
+        }
+    }
+}
+
+/// State of the channels matched by the conditional search.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SearchSource {
+    /// The activity latches, matched if set: the polarity is ignored.
+    ActivityLatch,
+    /// The output flip-flops.
+    FlipFlop,
+    /// The logic level of the pins.
+    PioLevel,
+}
+
+impl SearchSource {
+    const fn into_bits(self) -> u8 {
+        match self {
+            SearchSource::ActivityLatch => 0b01,
+            SearchSource::FlipFlop => 0b10,
+            SearchSource::PioLevel => 0b11,
+        }
+    }
+
+    const fn from_bits(bits: u8) -> Self {
+        match bits & 0b11 {
+            0b01 => SearchSource::ActivityLatch,
+            0b10 => SearchSource::FlipFlop,
+            _ => SearchSource::PioLevel,
+        }
+    }
+}
+
+#[bitfield(u8)]
+#[derive(PartialEq, Eq)]
+/// # Status byte 7
+///
+/// The status byte in SRAM, holding the output flip-flops and the configuration of the
+/// conditional search. It is reset at power-up.
+pub struct Control {
+    /// CSS1, CSS0: Channels taking part in the conditional search.
+    #[bits(2)]
+    pub search_channels: SearchChannels,
+    /// CSS3, CSS2: State of the channels matched by the conditional search.
+    #[bits(2)]
+    pub search_source: SearchSource,
+    /// CSS4: Level that makes the search condition true.
+    pub search_polarity: bool,
+    /// PIO-A channel flip-flop. The output transistor of PIO-A pulls the pin low if false.
+    pub flip_flop_a: bool,
+    /// PIO-B channel flip-flop. The output transistor of PIO-B pulls the pin low if false.
+    pub flip_flop_b: bool,
+    /// Supply indication: The device is powered through its V<sub>CC</sub> pin.
+    #[bits(access = RO)]
+    pub vcc_powered: bool,
+}
+
+#[bitfield(u8)]
+#[derive(PartialEq, Eq)]
+/// # Channel info byte
+///
+/// Sent by the device at the start of a Channel Access.
+pub struct ChannelInfo {
+    /// PIO-A channel flip-flop.
+    pub flip_flop_a: bool,
+    /// PIO-B channel flip-flop.
+    pub flip_flop_b: bool,
+    /// Logic level of the PIO-A pin.
+    pub level_a: bool,
+    /// Logic level of the PIO-B pin.
+    pub level_b: bool,
+    /// PIO-A activity latch: the pin changed state since the latches were last reset.
+    pub activity_a: bool,
+    /// PIO-B activity latch.
+    pub activity_b: bool,
+    /// The device has both channels.
+    pub two_channels: bool,
+    /// Supply indication: The device is powered through its V<sub>CC</sub> pin.
+    pub vcc_powered: bool,
+}
+
+impl ChannelInfo {
+    /// Logic level of the pin of `channel`.
+    pub fn level(&self, channel: Channel) -> bool {
+        match channel {
+            Channel::A => self.level_a(),
+            Channel::B => self.level_b(),
+        }
+    }
+
+    /// Whether the pin of `channel` changed state since the activity latches were last
+    /// reset.
+    pub fn activity(&self, channel: Channel) -> bool {
+        match channel {
+            Channel::A => self.activity_a(),
+            Channel::B => self.activity_b(),
+        }
+    }
+}
+
+impl Control {
+    /// The control with the flip-flop of `channel` set to `high`.
+    pub fn with_flip_flop(self, channel: Channel, high: bool) -> Self {
+        match channel {
+            Channel::A => self.with_flip_flop_a(high),
+            Channel::B => self.with_flip_flop_b(high),
+        }
+    }
+}
+
+mod test {
+    #[test]
+    fn test_channel_info() {
+        use super::*;
+        use embedded_onewire::testing::{Mock, Transaction};
+        extern crate std;
+
+        let rom = 0x5a00_0000_1234_5612;
+        let bus = Mock::new(&[
+            Transaction::reset(true),
+            Transaction::match_rom(rom),
+            Transaction::write(&[CHANNEL_ACCESS_CMD, CHANNEL_CONTROL, CHANNEL_CONTROL_2]),
+            Transaction::read(&[0x56]),
+            Transaction::reset(true),
+            Transaction::match_rom(rom),
+            Transaction::write(&[CHANNEL_ACCESS_CMD, 0x84, CHANNEL_CONTROL_2]),
+            Transaction::read(&[0x46]),
+        ]);
+        let mut switch = Ds2406::new(bus, Some(rom));
+        let info = switch.read_channel_info().unwrap();
+        assert!(info.level(Channel::A));
+        assert!(!info.level(Channel::B));
+        assert!(info.activity(Channel::A));
+        assert!(!info.activity(Channel::B));
+        assert!(info.two_channels());
+        assert!(!info.vcc_powered());
+        let info = switch.reset_activity_latches().unwrap();
+        assert!(!info.activity(Channel::A));
+        switch.release().done();
+    }
+
+    #[test]
+    fn test_control() {
+        use super::*;
+        use embedded_onewire::testing::{Mock, Transaction};
+        extern crate std;
+
+        let read = [
+            Transaction::reset(true),
+            Transaction::skip_rom(),
+            Transaction::write(&[READ_STATUS_CMD, 0x07, 0x00]),
+            Transaction::read(&[0xe0, 0x6e, 0x6e]),
+        ];
+        let bus = Mock::new(
+            &[
+                &read[..],
+                &[
+                    Transaction::reset(true),
+                    Transaction::skip_rom(),
+                    Transaction::write(&[WRITE_STATUS_CMD, 0x07, 0x00, 0xc0]),
+                    Transaction::read(&[0x5f, 0xa2]),
+                ],
+                &read[..],
+                &[
+                    Transaction::reset(true),
+                    Transaction::skip_rom(),
+                    Transaction::write(&[WRITE_STATUS_CMD, 0x07, 0x00, 0xfd]),
+                    Transaction::read(&[0x9e, 0x73]),
+                ],
+                &[
+                    Transaction::reset(true),
+                    Transaction::skip_rom(),
+                    Transaction::write(&[READ_STATUS_CMD, 0x07, 0x00]),
+                    Transaction::read(&[0x60, 0x6e, 0x6e]),
+                ],
+            ]
+            .concat(),
+        );
+        let mut switch = Ds2406::new(bus, None);
+        switch.set_channel(Channel::A, false).unwrap();
+        switch
+            .configure_search(SearchChannels::A, SearchSource::PioLevel, true)
+            .unwrap();
+        assert!(matches!(
+            switch.read_control(),
+            Err(OneWireError::InvalidCrc)
+        ));
+        switch.release().done();
+    }
+}
@@ -0,0 +1,170 @@
+use crate::{
+    CHANNEL_ACCESS_WRITE_CMD, CHANNELS, CONFIRMATION, CONTROL_ADDR, Control, PIO_LOGIC_STATE_ADDR,
+    READ_PIO_REGISTERS_CMD, REGISTERS_LEN, RESET_ACTIVITY_LATCHES_CMD, Registers, SEARCH_MASK_ADDR,
+    WRITE_SEARCH_REGISTER_CMD,
+};
+use embedded_onewire::{OneWire, OneWireCrc16, OneWireError, OneWireResult};
+
+/// A DS2408 8-channel addressable switch on a 1-Wire bus.
+///
+/// The driver owns the bus master, which can be a `&mut` reference to one or a handle to a
+/// shared one. Every operation addresses the device first. The driver keeps the last state
+/// of the output latch it read or wrote, so that single channels can be changed without
+/// reading the latch back every time.
+#[derive(Debug)]
+pub struct Ds2408<O> {
+    pub(crate) bus: O,
+    pub(crate) rom: Option<u64>,
+    pub(crate) latch: Option<u8>,
+}
+
+impl<O> Ds2408<O> {
+    /// Creates a driver for the device with ROM `rom` on `bus`.
+    ///
+    /// Pass [`None`] to skip ROM addressing on a bus with a single device.
+    pub fn new(bus: O, rom: Option<u64>) -> Self {
+        Ds2408 {
+            bus,
+            rom,
+            latch: None,
+        }
+    }
+
+    /// ROM of the device, if it is addressed by ROM.
+    pub fn rom(&self) -> Option<u64> {
+        self.rom
+    }
+
+    /// Release the bus master.
+    pub fn release(self) -> O {
+        self.bus
+    }
+
+    /// Forget the last state of the output latch, e.g. after the device lost power.
+    pub fn invalidate(&mut self) {
+        self.latch = None;
+    }
+}
+
+impl<O: OneWire> Ds2408<O> {
+    fn command(&mut self, cmd: &[u8]) -> OneWireResult<(), O::BusError> {
+        self.bus.address(self.rom)?;
+        self.bus.write_bytes(cmd)
+    }
+
+    /// Read the PIO registers.
+    ///
+    /// # Errors
+    /// Returns [`OneWireError::InvalidCrc`] if the CRC of the registers does not match.
+    pub fn read_registers(&mut self) -> OneWireResult<Registers, O::BusError> {
+        let [lo, hi] = PIO_LOGIC_STATE_ADDR.to_le_bytes();
+        let cmd = [READ_PIO_REGISTERS_CMD, lo, hi];
+        self.command(&cmd)?;
+        let mut buf = [0; REGISTERS_LEN + 2];
+        self.bus.read_bytes(&mut buf)?;
+        let mut crc = OneWireCrc16::default();
+        crc.update_slice(&cmd);
+        crc.update_slice(&buf);
+        if !crc.is_valid() {
+            return Err(OneWireError::InvalidCrc);
+        }
+        let mut bytes = [0; REGISTERS_LEN];
+        bytes.copy_from_slice(&buf[..REGISTERS_LEN]);
+        let registers = Registers::parse(&bytes);
+        self.latch = Some(registers.output_latch);
+        Ok(registers)
+    }
+
+    /// The state of the output latch, read from the device if not known.
+    pub fn output_latch(&mut self) -> OneWireResult<u8, O::BusError> {
+        match self.latch {
+            Some(latch) => Ok(latch),
+            None => Ok(self.read_registers()?.output_latch),
+        }
+    }
+
+    /// Write the output latch, and return the logic state of the pins sampled right after.
+    ///
+    /// # Errors
+    /// Returns [`OneWireError::VerificationFailed`] if the device does not confirm the
+    /// write.
+    pub fn write_output_latch(&mut self, latch: u8) -> OneWireResult<u8, O::BusError> {
+        self.command(&[CHANNEL_ACCESS_WRITE_CMD, latch, !latch])?;
+        let mut buf = [0; 2];
+        self.bus.read_bytes(&mut buf)?;
+        if buf[0] != CONFIRMATION {
+            self.latch = None;
+            return Err(OneWireError::VerificationFailed);
+        }
+        self.latch = Some(latch);
+        Ok(buf[1])
+    }
+
+    /// Set the output latch of `channel`, leaving the other channels unchanged.
+    ///
+    /// # Errors
+    /// Returns [`OneWireError::InvalidValue`] if `channel` does not exist.
+    pub fn set_channel(&mut self, channel: u8, high: bool) -> OneWireResult<(), O::BusError> {
+        if channel >= CHANNELS {
+            return Err(OneWireError::InvalidValue("channel"));
+        }
+        let latch = self.output_latch()?;
+        let latch = if high {
+            latch | (1 << channel)
+        } else {
+            latch & !(1 << channel)
+        };
+        self.write_output_latch(latch)?;
+        Ok(())
+    }
+
+    /// Read the logic state of the pins.
+    pub fn read_logic_state(&mut self) -> OneWireResult<u8, O::BusError> {
+        Ok(self.read_registers()?.logic_state)
+    }
+
+    /// Reset the activity latches of all channels.
+    ///
+    /// # Errors
+    /// Returns [`OneWireError::VerificationFailed`] if the device does not confirm the
+    /// reset.
+    pub fn reset_activity_latches(&mut self) -> OneWireResult<(), O::BusError> {
+        self.command(&[RESET_ACTIVITY_LATCHES_CMD])?;
+        if self.bus.read_byte()? != CONFIRMATION {
+            return Err(OneWireError::VerificationFailed);
+        }
+        Ok(())
+    }
+
+    /// Write the control/status register.
+    pub fn set_control(&mut self, control: Control) -> OneWireResult<(), O::BusError> {
+        let [lo, hi] = CONTROL_ADDR.to_le_bytes();
+        self.command(&[WRITE_SEARCH_REGISTER_CMD, lo, hi, control.into_bits()])
+    }
+
+    /// Configure the conditional search.
+    ///
+    /// The device answers a search with [`OneWireSearchKind::Alarmed`] if the channels in
+    /// `mask` match `polarity`, all of them or any of them depending on
+    /// [`Control::search_and`]. The pins or the activity latches are matched depending on
+    /// [`Control::activity_latch_select`]. Clear [`Control::power_on_reset`] for the
+    /// condition to take effect.
+    ///
+    /// [`OneWireSearchKind::Alarmed`]: embedded_onewire::OneWireSearchKind::Alarmed
+    pub fn configure_search(
+        &mut self,
+        mask: u8,
+        polarity: u8,
+        control: Control,
+    ) -> OneWireResult<(), O::BusError> {
+        let [lo, hi] = SEARCH_MASK_ADDR.to_le_bytes();
+        self.command(&[
+            WRITE_SEARCH_REGISTER_CMD,
+            lo,
+            hi,
+            mask,
+            polarity,
+            control.into_bits(),
+        ])
+    }
+}
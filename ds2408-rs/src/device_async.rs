@@ -0,0 +1,124 @@
+use crate::{
+    CHANNEL_ACCESS_WRITE_CMD, CHANNELS, CONFIRMATION, CONTROL_ADDR, Control, Ds2408,
+    PIO_LOGIC_STATE_ADDR, READ_PIO_REGISTERS_CMD, REGISTERS_LEN, RESET_ACTIVITY_LATCHES_CMD,
+    Registers, SEARCH_MASK_ADDR, WRITE_SEARCH_REGISTER_CMD,
+};
+use embedded_onewire::{OneWireAsync, OneWireCrc16, OneWireError, OneWireResult};
+
+impl<O: OneWireAsync> Ds2408<O> {
+    async fn command_async(&mut self, cmd: &[u8]) -> OneWireResult<(), O::BusError> {
+        OneWireAsync::address(&mut self.bus, self.rom).await?;
+        OneWireAsync::write_bytes(&mut self.bus, cmd).await
+    }
+
+    /// Read the PIO registers.
+    ///
+    /// See [`Ds2408::read_registers`].
+    pub async fn read_registers_async(&mut self) -> OneWireResult<Registers, O::BusError> {
+        let [lo, hi] = PIO_LOGIC_STATE_ADDR.to_le_bytes();
+        let cmd = [READ_PIO_REGISTERS_CMD, lo, hi];
+        self.command_async(&cmd).await?;
+        let mut buf = [0; REGISTERS_LEN + 2];
+        OneWireAsync::read_bytes(&mut self.bus, &mut buf).await?;
+        let mut crc = OneWireCrc16::default();
+        crc.update_slice(&cmd);
+        crc.update_slice(&buf);
+        if !crc.is_valid() {
+            return Err(OneWireError::InvalidCrc);
+        }
+        let mut bytes = [0; REGISTERS_LEN];
+        bytes.copy_from_slice(&buf[..REGISTERS_LEN]);
+        let registers = Registers::parse(&bytes);
+        self.latch = Some(registers.output_latch);
+        Ok(registers)
+    }
+
+    /// The state of the output latch, read from the device if not known.
+    pub async fn output_latch_async(&mut self) -> OneWireResult<u8, O::BusError> {
+        match self.latch {
+            Some(latch) => Ok(latch),
+            None => Ok(self.read_registers_async().await?.output_latch),
+        }
+    }
+
+    /// Write the output latch, and return the logic state of the pins sampled right after.
+    ///
+    /// See [`Ds2408::write_output_latch`].
+    pub async fn write_output_latch_async(&mut self, latch: u8) -> OneWireResult<u8, O::BusError> {
+        self.command_async(&[CHANNEL_ACCESS_WRITE_CMD, latch, !latch])
+            .await?;
+        let mut buf = [0; 2];
+        OneWireAsync::read_bytes(&mut self.bus, &mut buf).await?;
+        if buf[0] != CONFIRMATION {
+            self.latch = None;
+            return Err(OneWireError::VerificationFailed);
+        }
+        self.latch = Some(latch);
+        Ok(buf[1])
+    }
+
+    /// Set the output latch of `channel`, leaving the other channels unchanged.
+    ///
+    /// See [`Ds2408::set_channel`].
+    pub async fn set_channel_async(
+        &mut self,
+        channel: u8,
+        high: bool,
+    ) -> OneWireResult<(), O::BusError> {
+        if channel >= CHANNELS {
+            return Err(OneWireError::InvalidValue("channel"));
+        }
+        let latch = self.output_latch_async().await?;
+        let latch = if high {
+            latch | (1 << channel)
+        } else {
+            latch & !(1 << channel)
+        };
+        self.write_output_latch_async(latch).await?;
+        Ok(())
+    }
+
+    /// Read the logic state of the pins.
+    pub async fn read_logic_state_async(&mut self) -> OneWireResult<u8, O::BusError> {
+        Ok(self.read_registers_async().await?.logic_state)
+    }
+
+    /// Reset the activity latches of all channels.
+    ///
+    /// See [`Ds2408::reset_activity_latches`].
+    pub async fn reset_activity_latches_async(&mut self) -> OneWireResult<(), O::BusError> {
+        self.command_async(&[RESET_ACTIVITY_LATCHES_CMD]).await?;
+        if OneWireAsync::read_byte(&mut self.bus).await? != CONFIRMATION {
+            return Err(OneWireError::VerificationFailed);
+        }
+        Ok(())
+    }
+
+    /// Write the control/status register.
+    pub async fn set_control_async(&mut self, control: Control) -> OneWireResult<(), O::BusError> {
+        let [lo, hi] = CONTROL_ADDR.to_le_bytes();
+        self.command_async(&[WRITE_SEARCH_REGISTER_CMD, lo, hi, control.into_bits()])
+            .await
+    }
+
+    /// Configure the conditional search.
+    ///
+    /// See [`Ds2408::configure_search`].
+    pub async fn configure_search_async(
+        &mut self,
+        mask: u8,
+        polarity: u8,
+        control: Control,
+    ) -> OneWireResult<(), O::BusError> {
+        let [lo, hi] = SEARCH_MASK_ADDR.to_le_bytes();
+        self.command_async(&[
+            WRITE_SEARCH_REGISTER_CMD,
+            lo,
+            hi,
+            mask,
+            polarity,
+            control.into_bits(),
+        ])
+        .await
+    }
+}
@@ -0,0 +1,171 @@
+#![no_std]
+#![deny(missing_docs)]
+#![cfg_attr(docsrs, feature(doc_cfg))]
+#![doc = include_str!("../README.md")]
+
+mod device;
+mod device_async;
+mod pin;
+
+pub use device::Ds2408;
+pub use embedded_onewire::{OneWireError, OneWireResult};
+pub use pin::{Ds2408Pin, PinError};
+
+use bitfield_struct::bitfield;
+
+/// Family code of the DS2408, the low byte of its ROM.
+pub const FAMILY_CODE: u8 = 0x29;
+
+/// Number of channels.
+pub const CHANNELS: u8 = 8;
+
+pub(crate) const READ_PIO_REGISTERS_CMD: u8 = 0xf0;
+pub(crate) const CHANNEL_ACCESS_WRITE_CMD: u8 = 0x5a;
+pub(crate) const WRITE_SEARCH_REGISTER_CMD: u8 = 0xcc;
+pub(crate) const RESET_ACTIVITY_LATCHES_CMD: u8 = 0xc3;
+
+/// Address of the PIO Logic State register, the first register.
+pub(crate) const PIO_LOGIC_STATE_ADDR: u16 = 0x88;
+/// Address of the Conditional Search Channel Selection Mask register.
+pub(crate) const SEARCH_MASK_ADDR: u16 = 0x8b;
+/// Address of the Control/Status register.
+pub(crate) const CONTROL_ADDR: u16 = 0x8d;
+/// Number of bytes read from [`PIO_LOGIC_STATE_ADDR`] to the end of the register page.
+pub(crate) const REGISTERS_LEN: usize = 8;
+
+/// Byte sent by the device to confirm a Channel Access Write or a Reset Activity Latches.
+pub(crate) const CONFIRMATION: u8 = 0xaa;
+
+#[bitfield(u8)]
+#[derive(PartialEq, Eq)]
+/// # Control/status register
+pub struct Control {
+    /// PLS: Selects the activity latches instead of the pins as the source of the
+    /// conditional search.
+    pub activity_latch_select: bool,
+    /// CT: Makes the conditional search condition true when all selected channels match
+    /// their polarity, instead of any of them.
+    pub search_and: bool,
+    /// ROS: Configures the RSTZ pin as a strobe output of Channel Access operations,
+    /// instead of a reset input.
+    pub rstz_strobe: bool,
+    /// PORL: Set at power-on, and cleared by writing 0. The device answers every
+    /// conditional search while it is set.
+    pub power_on_reset: bool,
+    #[bits(3)]
+    __: u8,
+    /// VCCP: The device is powered through its V<sub>CC</sub> pin.
+    #[bits(access = RO)]
+    pub vcc_powered: bool,
+}
+
+/// The PIO registers of the DS2408.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Registers {
+    /// Logic state of the pins, one bit per channel.
+    pub logic_state: u8,
+    /// State of the output latches. A channel with 0 pulls its pin low.
+    pub output_latch: u8,
+    /// Channels whose pins changed state since the activity latches were last reset.
+    pub activity_latch: u8,
+    /// Channels taking part in the conditional search.
+    pub search_mask: u8,
+    /// State of each channel selected by the mask that makes the search condition true.
+    pub search_polarity: u8,
+    /// The control/status register.
+    pub control: Control,
+}
+
+impl Registers {
+    pub(crate) fn parse(bytes: &[u8; REGISTERS_LEN]) -> Self {
+        Registers {
+            logic_state: bytes[0],
+            output_latch: bytes[1],
+            activity_latch: bytes[2],
+            search_mask: bytes[3],
+            search_polarity: bytes[4],
+            control: Control::from_bits(bytes[5]),
+        }
+    }
+}
+
+mod test {
+    #[test]
+    fn test_read_registers() {
+        use super::*;
+        use embedded_onewire::testing::{Mock, Transaction};
+        extern crate std;
+
+        let rom = 0x5a06050403020129;
+        let bus = Mock::new(&[
+            Transaction::reset(true),
+            Transaction::match_rom(rom),
+            Transaction::write(&[READ_PIO_REGISTERS_CMD, 0x88, 0x00]),
+            Transaction::read(&[0x0f, 0x0f, 0x30, 0x01, 0x00, 0x82, 0xff, 0xff, 0x5c, 0x16]),
+        ]);
+        let mut switch = Ds2408::new(bus, Some(rom));
+        let registers = switch.read_registers().unwrap();
+        assert_eq!(registers.logic_state, 0x0f);
+        assert_eq!(registers.activity_latch, 0x30);
+        assert!(registers.control.search_and());
+        assert!(registers.control.vcc_powered());
+        assert!(!registers.control.power_on_reset());
+        switch.release().done();
+    }
+
+    #[test]
+    fn test_channel_access_write() {
+        use super::*;
+        use embedded_onewire::testing::{Mock, Transaction};
+        extern crate std;
+
+        let bus = Mock::new(&[
+            Transaction::reset(true),
+            Transaction::skip_rom(),
+            Transaction::write(&[CHANNEL_ACCESS_WRITE_CMD, 0xf0, 0x0f]),
+            Transaction::read(&[CONFIRMATION, 0xf0]),
+            Transaction::reset(true),
+            Transaction::skip_rom(),
+            Transaction::write(&[CHANNEL_ACCESS_WRITE_CMD, 0x12, 0xed]),
+            Transaction::read(&[0x00, 0xff]),
+        ]);
+        let mut switch = Ds2408::new(bus, None);
+        assert_eq!(switch.write_output_latch(0xf0).unwrap(), 0xf0);
+        assert!(matches!(
+            switch.write_output_latch(0x12),
+            Err(OneWireError::VerificationFailed)
+        ));
+        switch.release().done();
+    }
+
+    #[test]
+    fn test_pins() {
+        use super::*;
+        use core::cell::RefCell;
+        use embedded_hal::digital::{InputPin, OutputPin, StatefulOutputPin};
+        use embedded_onewire::testing::{Mock, Transaction};
+        extern crate std;
+
+        let bus = Mock::new(&[
+            // The output latch is read once
+            Transaction::reset(true),
+            Transaction::skip_rom(),
+            Transaction::write(&[READ_PIO_REGISTERS_CMD, 0x88, 0x00]),
+            Transaction::read(&[0xff, 0xff, 0x00, 0x00, 0x00, 0x80, 0xff, 0xff, 0x3a, 0xad]),
+            Transaction::reset(true),
+            Transaction::skip_rom(),
+            Transaction::write(&[CHANNEL_ACCESS_WRITE_CMD, 0xfd, 0x02]),
+            Transaction::read(&[CONFIRMATION, 0xfd]),
+            Transaction::reset(true),
+            Transaction::skip_rom(),
+            Transaction::write(&[READ_PIO_REGISTERS_CMD, 0x88, 0x00]),
+            Transaction::read(&[0xfd, 0xfd, 0x02, 0x00, 0x00, 0x80, 0xff, 0xff, 0x99, 0x56]),
+        ]);
+        let switch = RefCell::new(Ds2408::new(bus, None));
+        let [_, mut led, mut button, ..] = Ds2408::pins(&switch);
+        led.set_low().unwrap();
+        assert!(led.is_set_low().unwrap());
+        assert!(button.is_high().unwrap());
+        switch.into_inner().release().done();
+    }
+}
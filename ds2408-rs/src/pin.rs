@@ -0,0 +1,98 @@
+use crate::{CHANNELS, Ds2408};
+use core::{cell::RefCell, fmt::Debug};
+use embedded_hal::digital::{self, ErrorKind, ErrorType, InputPin, OutputPin, StatefulOutputPin};
+use embedded_onewire::{OneWire, OneWireError};
+
+/// Error of a [`Ds2408Pin`] operation.
+#[derive(Debug)]
+pub struct PinError<E>(pub OneWireError<E>);
+
+impl<E> From<OneWireError<E>> for PinError<E> {
+    fn from(err: OneWireError<E>) -> Self {
+        PinError(err)
+    }
+}
+
+impl<E: Debug> digital::Error for PinError<E> {
+    fn kind(&self) -> ErrorKind {
+        ErrorKind::Other
+    }
+}
+
+/// A channel of a [`Ds2408`], as an `embedded-hal` digital pin.
+///
+/// The pin is an open-drain output: [`OutputPin::set_high`] turns the transistor off, which
+/// lets the pin be read as an input. Every operation borrows the shared driver for its own
+/// duration, see [`Ds2408::pins`].
+pub struct Ds2408Pin<'a, O> {
+    dev: &'a RefCell<Ds2408<O>>,
+    channel: u8,
+}
+
+impl<O> Ds2408<O> {
+    /// The eight channels of a shared driver as `embedded-hal` pins.
+    ///
+    /// Operations on different pins must not be nested, e.g. from an interrupt handler, or
+    /// the borrow of the driver panics.
+    pub fn pins(dev: &RefCell<Self>) -> [Ds2408Pin<'_, O>; CHANNELS as usize] {
+        core::array::from_fn(|channel| Ds2408Pin {
+            dev,
+            channel: channel as u8,
+        })
+    }
+}
+
+impl<O> Ds2408Pin<'_, O> {
+    /// The channel of the pin.
+    pub fn channel(&self) -> u8 {
+        self.channel
+    }
+}
+
+impl<O: OneWire> ErrorType for Ds2408Pin<'_, O>
+where
+    O::BusError: Debug,
+{
+    type Error = PinError<O::BusError>;
+}
+
+impl<O: OneWire> OutputPin for Ds2408Pin<'_, O>
+where
+    O::BusError: Debug,
+{
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        Ok(self.dev.borrow_mut().set_channel(self.channel, false)?)
+    }
+
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        Ok(self.dev.borrow_mut().set_channel(self.channel, true)?)
+    }
+}
+
+impl<O: OneWire> StatefulOutputPin for Ds2408Pin<'_, O>
+where
+    O::BusError: Debug,
+{
+    fn is_set_high(&mut self) -> Result<bool, Self::Error> {
+        let latch = self.dev.borrow_mut().output_latch()?;
+        Ok(latch & (1 << self.channel) != 0)
+    }
+
+    fn is_set_low(&mut self) -> Result<bool, Self::Error> {
+        Ok(!self.is_set_high()?)
+    }
+}
+
+impl<O: OneWire> InputPin for Ds2408Pin<'_, O>
+where
+    O::BusError: Debug,
+{
+    fn is_high(&mut self) -> Result<bool, Self::Error> {
+        let state = self.dev.borrow_mut().read_logic_state()?;
+        Ok(state & (1 << self.channel) != 0)
+    }
+
+    fn is_low(&mut self) -> Result<bool, Self::Error> {
+        Ok(!self.is_high()?)
+    }
+}
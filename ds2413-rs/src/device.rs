@@ -0,0 +1,97 @@
+use crate::{CONFIRMATION, Channel, PIO_ACCESS_READ_CMD, PIO_ACCESS_WRITE_CMD, Status};
+use embedded_onewire::{OneWire, OneWireError, OneWireResult};
+
+/// A DS2413 dual-channel addressable switch on a 1-Wire bus.
+///
+/// The driver owns the bus master, which can be a `&mut` reference to one or a handle to a
+/// shared one. Every operation addresses the device first. The driver keeps the last state
+/// of the output latches it read or wrote, so that a single channel can be changed without
+/// reading the latches back every time.
+#[derive(Debug)]
+pub struct Ds2413<O> {
+    pub(crate) bus: O,
+    pub(crate) rom: Option<u64>,
+    pub(crate) latches: Option<u8>,
+}
+
+impl<O> Ds2413<O> {
+    /// Creates a driver for the device with ROM `rom` on `bus`.
+    ///
+    /// Pass [`None`] to skip ROM addressing on a bus with a single device.
+    pub fn new(bus: O, rom: Option<u64>) -> Self {
+        Ds2413 {
+            bus,
+            rom,
+            latches: None,
+        }
+    }
+
+    /// ROM of the device, if it is addressed by ROM.
+    pub fn rom(&self) -> Option<u64> {
+        self.rom
+    }
+
+    /// Release the bus master.
+    pub fn release(self) -> O {
+        self.bus
+    }
+
+    /// Forget the last state of the output latches, e.g. after the device lost power.
+    pub fn invalidate(&mut self) {
+        self.latches = None;
+    }
+}
+
+impl<O: OneWire> Ds2413<O> {
+    /// Read the state of the pins and of the output latches.
+    ///
+    /// # Errors
+    /// Returns [`OneWireError::InvalidCrc`] if the check nibble of the status does not
+    /// match.
+    pub fn read_status(&mut self) -> OneWireResult<Status, O::BusError> {
+        self.bus.address(self.rom)?;
+        self.bus.write_byte(PIO_ACCESS_READ_CMD)?;
+        let status = Status::parse(self.bus.read_byte()?)?;
+        self.latches = Some(status.latches());
+        Ok(status)
+    }
+
+    /// Write the output latches, bit 0 for PIOA and bit 1 for PIOB, and return the status
+    /// sampled right after.
+    ///
+    /// # Errors
+    /// Returns [`OneWireError::VerificationFailed`] if the device does not confirm the
+    /// write.
+    pub fn write_latches(&mut self, latches: u8) -> OneWireResult<Status, O::BusError> {
+        let byte = 0xfc | latches;
+        self.bus.address(self.rom)?;
+        self.bus.write_bytes(&[PIO_ACCESS_WRITE_CMD, byte, !byte])?;
+        let mut buf = [0; 2];
+        self.bus.read_bytes(&mut buf)?;
+        if buf[0] != CONFIRMATION {
+            self.latches = None;
+            return Err(OneWireError::VerificationFailed);
+        }
+        self.latches = Some(latches & 0x03);
+        Status::parse(buf[1])
+    }
+
+    /// Set the output latch of `channel`, leaving the other channel unchanged, and return
+    /// the status sampled right after.
+    pub fn set_channel(
+        &mut self,
+        channel: Channel,
+        high: bool,
+    ) -> OneWireResult<Status, O::BusError> {
+        let latches = match self.latches {
+            Some(latches) => latches,
+            None => self.read_status()?.latches(),
+        };
+        let latches = if high {
+            latches | channel.latch_bit()
+        } else {
+            latches & !channel.latch_bit()
+        };
+        self.write_latches(latches)
+    }
+}
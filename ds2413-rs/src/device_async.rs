@@ -0,0 +1,51 @@
+use crate::{CONFIRMATION, Channel, Ds2413, PIO_ACCESS_READ_CMD, PIO_ACCESS_WRITE_CMD, Status};
+use embedded_onewire::{OneWireAsync, OneWireError, OneWireResult};
+
+impl<O: OneWireAsync> Ds2413<O> {
+    /// Read the state of the pins and of the output latches.
+    ///
+    /// See [`Ds2413::read_status`].
+    pub async fn read_status_async(&mut self) -> OneWireResult<Status, O::BusError> {
+        OneWireAsync::address(&mut self.bus, self.rom).await?;
+        OneWireAsync::write_byte(&mut self.bus, PIO_ACCESS_READ_CMD).await?;
+        let status = Status::parse(OneWireAsync::read_byte(&mut self.bus).await?)?;
+        self.latches = Some(status.latches());
+        Ok(status)
+    }
+
+    /// Write the output latches, and return the status sampled right after.
+    ///
+    /// See [`Ds2413::write_latches`].
+    pub async fn write_latches_async(&mut self, latches: u8) -> OneWireResult<Status, O::BusError> {
+        let byte = 0xfc | latches;
+        OneWireAsync::address(&mut self.bus, self.rom).await?;
+        OneWireAsync::write_bytes(&mut self.bus, &[PIO_ACCESS_WRITE_CMD, byte, !byte]).await?;
+        let mut buf = [0; 2];
+        OneWireAsync::read_bytes(&mut self.bus, &mut buf).await?;
+        if buf[0] != CONFIRMATION {
+            self.latches = None;
+            return Err(OneWireError::VerificationFailed);
+        }
+        self.latches = Some(latches & 0x03);
+        Status::parse(buf[1])
+    }
+
+    /// Set the output latch of `channel`, leaving the other channel unchanged, and return
+    /// the status sampled right after.
+    pub async fn set_channel_async(
+        &mut self,
+        channel: Channel,
+        high: bool,
+    ) -> OneWireResult<Status, O::BusError> {
+        let latches = match self.latches {
+            Some(latches) => latches,
+            None => self.read_status_async().await?.latches(),
+        };
+        let latches = if high {
+            latches | channel.latch_bit()
+        } else {
+            latches & !channel.latch_bit()
+        };
+        self.write_latches_async(latches).await
+    }
+}
@@ -0,0 +1,160 @@
+#![no_std]
+#![deny(missing_docs)]
+#![cfg_attr(docsrs, feature(doc_cfg))]
+#![doc = include_str!("../README.md")]
+
+mod device;
+mod device_async;
+mod pin;
+
+pub use device::Ds2413;
+pub use embedded_onewire::{OneWireError, OneWireResult};
+pub use pin::{Ds2413Pin, PinError};
+
+/// Family code of the DS2413, the low byte of its ROM.
+pub const FAMILY_CODE: u8 = 0x3a;
+
+pub(crate) const PIO_ACCESS_READ_CMD: u8 = 0xf5;
+pub(crate) const PIO_ACCESS_WRITE_CMD: u8 = 0x5a;
+
+/// Byte sent by the device to confirm a PIO Access Write.
+pub(crate) const CONFIRMATION: u8 = 0xaa;
+
+/// A channel of the DS2413.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Channel {
+    /// PIOA
+    A,
+    /// PIOB
+    B,
+}
+
+impl Channel {
+    /// Bit of the channel in the output latch.
+    pub(crate) fn latch_bit(&self) -> u8 {
+        match self {
+            Channel::A => 0x01,
+            Channel::B => 0x02,
+        }
+    }
+}
+
+/// The PIO status of the DS2413.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Status(u8);
+
+impl Status {
+    /// Parse the status byte, whose upper nibble is the complement of the lower one.
+    ///
+    /// # Errors
+    /// Returns [`OneWireError::InvalidCrc`] if the upper nibble does not match.
+    pub(crate) fn parse<E>(byte: u8) -> OneWireResult<Self, E> {
+        if byte >> 4 != !byte & 0x0f {
+            return Err(OneWireError::InvalidCrc);
+        }
+        Ok(Status(byte & 0x0f))
+    }
+
+    /// Logic state of the pin of `channel`.
+    pub fn pin(&self, channel: Channel) -> bool {
+        match channel {
+            Channel::A => self.0 & 0x01 != 0,
+            Channel::B => self.0 & 0x04 != 0,
+        }
+    }
+
+    /// State of the output latch of `channel`. The pin is pulled low if false.
+    pub fn latch(&self, channel: Channel) -> bool {
+        match channel {
+            Channel::A => self.0 & 0x02 != 0,
+            Channel::B => self.0 & 0x08 != 0,
+        }
+    }
+
+    /// The output latches, in the format written to the device.
+    pub(crate) fn latches(&self) -> u8 {
+        ((self.0 >> 1) & 0x01) | ((self.0 >> 2) & 0x02)
+    }
+}
+
+mod test {
+    #[test]
+    fn test_status() {
+        use super::*;
+        let status = Status::parse::<()>(0x3c).unwrap();
+        assert!(!status.pin(Channel::A));
+        assert!(!status.latch(Channel::A));
+        assert!(status.pin(Channel::B));
+        assert!(status.latch(Channel::B));
+        assert_eq!(status.latches(), 0x02);
+        assert!(matches!(
+            Status::parse::<()>(0x77),
+            Err(OneWireError::InvalidCrc)
+        ));
+    }
+
+    #[test]
+    fn test_pio_access_write() {
+        use super::*;
+        use embedded_onewire::testing::{Mock, Transaction};
+        extern crate std;
+
+        let bus = Mock::new(&[
+            // The output latch is read once
+            Transaction::reset(true),
+            Transaction::skip_rom(),
+            Transaction::write(&[PIO_ACCESS_READ_CMD]),
+            Transaction::read(&[0x0f]),
+            Transaction::reset(true),
+            Transaction::skip_rom(),
+            Transaction::write(&[PIO_ACCESS_WRITE_CMD, 0xfe, 0x01]),
+            Transaction::read(&[CONFIRMATION, 0x3c]),
+            Transaction::reset(true),
+            Transaction::skip_rom(),
+            Transaction::write(&[PIO_ACCESS_WRITE_CMD, 0xfc, 0x03]),
+            Transaction::read(&[0xff, 0xff]),
+        ]);
+        let mut switch = Ds2413::new(bus, None);
+        let status = switch.set_channel(Channel::A, false).unwrap();
+        assert!(!status.pin(Channel::A));
+        assert!(matches!(
+            switch.set_channel(Channel::B, false),
+            Err(OneWireError::VerificationFailed)
+        ));
+        switch.release().done();
+    }
+
+    #[test]
+    fn test_pins() {
+        use super::*;
+        use core::cell::RefCell;
+        use embedded_hal::digital::{InputPin, OutputPin};
+        use embedded_onewire::testing::{Mock, Transaction};
+        extern crate std;
+
+        let bus = Mock::new(&[
+            Transaction::reset(true),
+            Transaction::skip_rom(),
+            Transaction::write(&[PIO_ACCESS_READ_CMD]),
+            Transaction::read(&[0x0f]),
+            Transaction::reset(true),
+            Transaction::skip_rom(),
+            Transaction::write(&[PIO_ACCESS_WRITE_CMD, 0xfd, 0x02]),
+            Transaction::read(&[CONFIRMATION, 0xc3]),
+            Transaction::reset(true),
+            Transaction::skip_rom(),
+            Transaction::write(&[PIO_ACCESS_READ_CMD]),
+            Transaction::read(&[0xc3]),
+            Transaction::reset(true),
+            Transaction::skip_rom(),
+            Transaction::write(&[PIO_ACCESS_READ_CMD]),
+            Transaction::read(&[0xc3]),
+        ]);
+        let switch = RefCell::new(Ds2413::new(bus, None));
+        let [mut sense, mut relay] = Ds2413::pins(&switch);
+        relay.set_low().unwrap();
+        assert!(sense.is_high().unwrap());
+        assert!(relay.is_low().unwrap());
+        switch.into_inner().release().done();
+    }
+}
@@ -0,0 +1,109 @@
+use crate::{Channel, Ds2413};
+use core::{cell::RefCell, fmt::Debug};
+use embedded_hal::digital::{self, ErrorKind, ErrorType, InputPin, OutputPin, StatefulOutputPin};
+use embedded_onewire::{OneWire, OneWireError};
+
+/// Error of a [`Ds2413Pin`] operation.
+#[derive(Debug)]
+pub struct PinError<E>(pub OneWireError<E>);
+
+impl<E> From<OneWireError<E>> for PinError<E> {
+    fn from(err: OneWireError<E>) -> Self {
+        PinError(err)
+    }
+}
+
+impl<E: Debug> digital::Error for PinError<E> {
+    fn kind(&self) -> ErrorKind {
+        ErrorKind::Other
+    }
+}
+
+/// A channel of a [`Ds2413`], as an `embedded-hal` digital pin.
+///
+/// The pin is an open-drain output: [`OutputPin::set_high`] turns the transistor off, which
+/// lets the pin be read as an input. Every operation borrows the shared driver for its own
+/// duration, see [`Ds2413::pins`].
+pub struct Ds2413Pin<'a, O> {
+    dev: &'a RefCell<Ds2413<O>>,
+    channel: Channel,
+}
+
+impl<O> Ds2413<O> {
+    /// The two channels of a shared driver as `embedded-hal` pins, PIOA first.
+    ///
+    /// Operations on the two pins must not be nested, e.g. from an interrupt handler, or
+    /// the borrow of the driver panics.
+    pub fn pins(dev: &RefCell<Self>) -> [Ds2413Pin<'_, O>; 2] {
+        [
+            Ds2413Pin {
+                dev,
+                channel: Channel::A,
+            },
+            Ds2413Pin {
+                dev,
+                channel: Channel::B,
+            },
+        ]
+    }
+}
+
+impl<O> Ds2413Pin<'_, O> {
+    /// The channel of the pin.
+    pub fn channel(&self) -> Channel {
+        self.channel
+    }
+}
+
+impl<O: OneWire> ErrorType for Ds2413Pin<'_, O>
+where
+    O::BusError: Debug,
+{
+    type Error = PinError<O::BusError>;
+}
+
+impl<O: OneWire> OutputPin for Ds2413Pin<'_, O>
+where
+    O::BusError: Debug,
+{
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        self.dev.borrow_mut().set_channel(self.channel, false)?;
+        Ok(())
+    }
+
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        self.dev.borrow_mut().set_channel(self.channel, true)?;
+        Ok(())
+    }
+}
+
+impl<O: OneWire> StatefulOutputPin for Ds2413Pin<'_, O>
+where
+    O::BusError: Debug,
+{
+    fn is_set_high(&mut self) -> Result<bool, Self::Error> {
+        let mut dev = self.dev.borrow_mut();
+        let latches = match dev.latches {
+            Some(latches) => latches,
+            None => dev.read_status()?.latches(),
+        };
+        Ok(latches & self.channel.latch_bit() != 0)
+    }
+
+    fn is_set_low(&mut self) -> Result<bool, Self::Error> {
+        Ok(!self.is_set_high()?)
+    }
+}
+
+impl<O: OneWire> InputPin for Ds2413Pin<'_, O>
+where
+    O::BusError: Debug,
+{
+    fn is_high(&mut self) -> Result<bool, Self::Error> {
+        Ok(self.dev.borrow_mut().read_status()?.pin(self.channel))
+    }
+
+    fn is_low(&mut self) -> Result<bool, Self::Error> {
+        Ok(!self.is_high()?)
+    }
+}
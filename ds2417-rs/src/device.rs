@@ -0,0 +1,102 @@
+use crate::{Clock, Control, Interval, READ_CLOCK_CMD, WRITE_CLOCK_CMD};
+use embedded_onewire::{OneWire, OneWireError, OneWireResult};
+
+/// A DS2417 or DS1904 real-time clock on a 1-Wire bus.
+///
+/// The driver owns the bus master, which can be a `&mut` reference to one or a handle to a
+/// shared one. Every operation addresses the device first.
+///
+/// # Interrupts
+/// With [`Control::interrupt_enable`] set, the DS2417 holds the bus low for a long time at the
+/// end of every interval. Bus masters such as the DS2484 can not tell this pulse from a short
+/// circuit, and report [`OneWireError::ShortCircuit`] on the next reset. Use
+/// [`Ds2417::acknowledge_interrupt`] to poll for it.
+#[derive(Debug)]
+pub struct Ds2417<O> {
+    pub(crate) bus: O,
+    pub(crate) rom: Option<u64>,
+}
+
+impl<O> Ds2417<O> {
+    /// Creates a driver for the device with ROM `rom` on `bus`.
+    ///
+    /// Pass [`None`] to skip ROM addressing on a bus with a single device.
+    pub fn new(bus: O, rom: Option<u64>) -> Self {
+        Ds2417 { bus, rom }
+    }
+
+    /// ROM of the device, if it is addressed by ROM.
+    pub fn rom(&self) -> Option<u64> {
+        self.rom
+    }
+
+    /// Release the bus master.
+    pub fn release(self) -> O {
+        self.bus
+    }
+}
+
+impl<O: OneWire> Ds2417<O> {
+    fn command(&mut self, cmd: &[u8]) -> OneWireResult<(), O::BusError> {
+        self.bus.address(self.rom)?;
+        self.bus.write_bytes(cmd)
+    }
+
+    /// Read the control byte and the seconds counter.
+    pub fn read_clock(&mut self) -> OneWireResult<Clock, O::BusError> {
+        self.command(&[READ_CLOCK_CMD])?;
+        let mut buf = [0; 5];
+        self.bus.read_bytes(&mut buf)?;
+        Ok(Clock {
+            control: Control::from_bits(buf[0]),
+            seconds: u32::from_le_bytes([buf[1], buf[2], buf[3], buf[4]]),
+        })
+    }
+
+    /// Write the control byte and the seconds counter.
+    pub fn write_clock(
+        &mut self,
+        control: Control,
+        seconds: u32,
+    ) -> OneWireResult<(), O::BusError> {
+        let s = seconds.to_le_bytes();
+        self.command(&[WRITE_CLOCK_CMD, control.into_bits(), s[0], s[1], s[2], s[3]])
+    }
+
+    /// Set the seconds counter and start the oscillator, keeping the interrupt configuration.
+    pub fn set_seconds(&mut self, seconds: u32) -> OneWireResult<(), O::BusError> {
+        let control = self.read_clock()?.control.with_oscillator_running(true);
+        self.write_clock(control, seconds)
+    }
+
+    /// Signal an interrupt at the end of every `interval`, or never with [`None`].
+    ///
+    /// The control byte can only be written along with the seconds counter, which is read
+    /// back and rewritten, and may lose a fraction of a second. The DS1904 ignores the
+    /// interrupt configuration.
+    pub fn set_interrupt(&mut self, interval: Option<Interval>) -> OneWireResult<(), O::BusError> {
+        let clock = self.read_clock()?;
+        let control = match interval {
+            Some(interval) => clock
+                .control
+                .with_interval(interval)
+                .with_interrupt_enable(true),
+            None => clock.control.with_interrupt_enable(false),
+        };
+        self.write_clock(control, clock.seconds)
+    }
+
+    /// Reset the bus, and return whether a device held it low to signal an interrupt.
+    ///
+    /// The reset ends the interrupt pulse.
+    ///
+    /// # Note
+    /// A short circuit of the bus is reported as an interrupt too.
+    pub fn acknowledge_interrupt(&mut self) -> OneWireResult<bool, O::BusError> {
+        match self.bus.reset() {
+            Ok(_) => Ok(false),
+            Err(OneWireError::ShortCircuit) => Ok(true),
+            Err(e) => Err(e),
+        }
+    }
+}
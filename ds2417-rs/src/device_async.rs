@@ -0,0 +1,76 @@
+use crate::{Clock, Control, Ds2417, Interval, READ_CLOCK_CMD, WRITE_CLOCK_CMD};
+use embedded_onewire::{OneWireAsync, OneWireError, OneWireResult};
+
+impl<O: OneWireAsync> Ds2417<O> {
+    async fn command_async(&mut self, cmd: &[u8]) -> OneWireResult<(), O::BusError> {
+        OneWireAsync::address(&mut self.bus, self.rom).await?;
+        OneWireAsync::write_bytes(&mut self.bus, cmd).await
+    }
+
+    /// Read the control byte and the seconds counter.
+    ///
+    /// See [`Ds2417::read_clock`].
+    pub async fn read_clock_async(&mut self) -> OneWireResult<Clock, O::BusError> {
+        self.command_async(&[READ_CLOCK_CMD]).await?;
+        let mut buf = [0; 5];
+        OneWireAsync::read_bytes(&mut self.bus, &mut buf).await?;
+        Ok(Clock {
+            control: Control::from_bits(buf[0]),
+            seconds: u32::from_le_bytes([buf[1], buf[2], buf[3], buf[4]]),
+        })
+    }
+
+    /// Write the control byte and the seconds counter.
+    ///
+    /// See [`Ds2417::write_clock`].
+    pub async fn write_clock_async(
+        &mut self,
+        control: Control,
+        seconds: u32,
+    ) -> OneWireResult<(), O::BusError> {
+        let s = seconds.to_le_bytes();
+        self.command_async(&[WRITE_CLOCK_CMD, control.into_bits(), s[0], s[1], s[2], s[3]])
+            .await
+    }
+
+    /// Set the seconds counter and start the oscillator, keeping the interrupt configuration.
+    ///
+    /// See [`Ds2417::set_seconds`].
+    pub async fn set_seconds_async(&mut self, seconds: u32) -> OneWireResult<(), O::BusError> {
+        let control = self
+            .read_clock_async()
+            .await?
+            .control
+            .with_oscillator_running(true);
+        self.write_clock_async(control, seconds).await
+    }
+
+    /// Signal an interrupt at the end of every `interval`, or never with [`None`].
+    ///
+    /// See [`Ds2417::set_interrupt`].
+    pub async fn set_interrupt_async(
+        &mut self,
+        interval: Option<Interval>,
+    ) -> OneWireResult<(), O::BusError> {
+        let clock = self.read_clock_async().await?;
+        let control = match interval {
+            Some(interval) => clock
+                .control
+                .with_interval(interval)
+                .with_interrupt_enable(true),
+            None => clock.control.with_interrupt_enable(false),
+        };
+        self.write_clock_async(control, clock.seconds).await
+    }
+
+    /// Reset the bus, and return whether a device held it low to signal an interrupt.
+    ///
+    /// See [`Ds2417::acknowledge_interrupt`].
+    pub async fn acknowledge_interrupt_async(&mut self) -> OneWireResult<bool, O::BusError> {
+        match OneWireAsync::reset(&mut self.bus).await {
+            Ok(_) => Ok(false),
+            Err(OneWireError::ShortCircuit) => Ok(true),
+            Err(e) => Err(e),
+        }
+    }
+}
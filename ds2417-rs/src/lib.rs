@@ -0,0 +1,162 @@
+#![no_std]
+#![deny(missing_docs)]
+#![cfg_attr(docsrs, feature(doc_cfg))]
+#![doc = include_str!("../README.md")]
+
+mod device;
+mod device_async;
+
+pub use device::Ds2417;
+pub use embedded_onewire::{OneWireError, OneWireResult};
+
+use bitfield_struct::bitfield;
+
+/// Family code of the DS2417, the low byte of its ROM.
+pub const DS2417_FAMILY_CODE: u8 = 0x27;
+/// Family code of the DS1904, the low byte of its ROM.
+pub const DS1904_FAMILY_CODE: u8 = 0x24;
+
+pub(crate) const READ_CLOCK_CMD: u8 = 0x66;
+pub(crate) const WRITE_CLOCK_CMD: u8 = 0x99;
+
+/// Interval between two interrupts of the DS2417.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Interval {
+    /// 1 s
+    S1,
+    /// 4 s
+    S4,
+    /// 32 s
+    S32,
+    /// 64 s
+    S64,
+    /// 2048 s
+    S2048,
+    /// 4096 s
+    S4096,
+    /// 65536 s
+    S65536,
+    /// 131072 s
+    S131072,
+}
+
+impl Interval {
+    /// The interval in seconds.
+    pub fn seconds(&self) -> u32 {
+        match self {
+            Interval::S1 => 1,
+            Interval::S4 => 4,
+            Interval::S32 => 32,
+            Interval::S64 => 64,
+            Interval::S2048 => 2048,
+            Interval::S4096 => 4096,
+            Interval::S65536 => 65536,
+            Interval::S131072 => 131072,
+        }
+    }
+
+    const fn into_bits(self) -> u8 {
+        self as u8
+    }
+
+    const fn from_bits(bits: u8) -> Self {
+        match bits & 0x07 {
+            0 => Interval::S1,
+            1 => Interval::S4,
+            2 => Interval::S32,
+            3 => Interval::S64,
+            4 => Interval::S2048,
+            5 => Interval::S4096,
+            6 => Interval::S65536,
+            _ => Interval::S131072,
+        }
+    }
+}
+
+#[bitfield(u8)]
+#[derive(PartialEq, Eq)]
+/// # Device control byte
+///
+/// The DS1904 only has the oscillator control, and ignores the interrupt configuration.
+pub struct Control {
+    #[bits(2)]
+    __: u8,
+    /// OSC: The oscillator runs, and the counter counts seconds. Both bits are set or
+    /// cleared together.
+    #[bits(2)]
+    oscillator: u8,
+    /// IS: Interval between two interrupts.
+    #[bits(3)]
+    pub interval: Interval,
+    /// IE: The DS2417 signals an interrupt on the bus at the end of every interval.
+    pub interrupt_enable: bool,
+}
+
+impl Control {
+    /// Whether the oscillator runs.
+    pub fn oscillator_running(&self) -> bool {
+        self.oscillator() == 0b11
+    }
+
+    /// Start or stop the oscillator.
+    pub fn with_oscillator_running(self, running: bool) -> Self {
+        self.with_oscillator(if running { 0b11 } else { 0b00 })
+    }
+}
+
+/// The state of the clock.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Clock {
+    /// The device control byte.
+    pub control: Control,
+    /// The seconds counter, usually the number of seconds since the Unix epoch.
+    pub seconds: u32,
+}
+
+mod test {
+    #[test]
+    fn test_control() {
+        use super::*;
+        let control = Control::from_bits(0xcc);
+        assert!(control.oscillator_running());
+        assert!(control.interrupt_enable());
+        assert_eq!(control.interval(), Interval::S2048);
+        assert_eq!(control.interval().seconds(), 2048);
+        let control = Control::new()
+            .with_oscillator_running(true)
+            .with_interval(Interval::S64);
+        assert_eq!(control.into_bits(), 0x3c);
+    }
+
+    #[test]
+    fn test_clock() {
+        use super::*;
+        use embedded_onewire::testing::{Mock, Transaction};
+        extern crate std;
+
+        let bus = Mock::new(&[
+            Transaction::reset(true),
+            Transaction::skip_rom(),
+            Transaction::write(&[READ_CLOCK_CMD]),
+            Transaction::read(&[0x0c, 0x80, 0x51, 0x01, 0x00]),
+            Transaction::reset(true),
+            Transaction::skip_rom(),
+            Transaction::write(&[WRITE_CLOCK_CMD, 0x8c, 0x00, 0x00, 0x00, 0x68]),
+            // The interrupt pulse of the device is seen as a short circuit
+            Transaction::short_circuit(),
+            Transaction::reset(true),
+        ]);
+        let mut rtc = Ds2417::new(bus, None);
+        let clock = rtc.read_clock().unwrap();
+        assert!(clock.control.oscillator_running());
+        assert_eq!(clock.seconds, 86400);
+        let control = clock
+            .control
+            .with_interval(Interval::S1)
+            .with_interrupt_enable(true);
+        rtc.write_clock(control, 0x6800_0000).unwrap();
+        assert!(rtc.acknowledge_interrupt().unwrap());
+        assert!(!rtc.acknowledge_interrupt().unwrap());
+        rtc.release().done();
+    }
+}
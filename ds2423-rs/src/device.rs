@@ -0,0 +1,75 @@
+use crate::{Counter, PAGE_SIZE, PAGES, Page, READ_MEMORY_COUNTER_CMD, TRAILER_LEN, parse_trailer};
+use embedded_onewire::{OneWire, OneWireError, OneWireResult};
+
+/// A DS2423 dual counter on a 1-Wire bus.
+///
+/// The driver owns the bus master, which can be a `&mut` reference to one or a handle to a
+/// shared one. Every operation addresses the device first.
+#[derive(Debug)]
+pub struct Ds2423<O> {
+    pub(crate) bus: O,
+    pub(crate) rom: Option<u64>,
+}
+
+impl<O> Ds2423<O> {
+    /// Creates a driver for the device with ROM `rom` on `bus`.
+    ///
+    /// Pass [`None`] to skip ROM addressing on a bus with a single device.
+    pub fn new(bus: O, rom: Option<u64>) -> Self {
+        Ds2423 { bus, rom }
+    }
+
+    /// ROM of the device, if it is addressed by ROM.
+    pub fn rom(&self) -> Option<u64> {
+        self.rom
+    }
+
+    /// Release the bus master.
+    pub fn release(self) -> O {
+        self.bus
+    }
+}
+
+impl<O: OneWire> Ds2423<O> {
+    /// Read from `address` to the end of its page with the Read Memory + Counter command,
+    /// into the start of `data`, and return the counter of the page.
+    fn read_with_counter(
+        &mut self,
+        address: u16,
+        data: &mut [u8; PAGE_SIZE],
+    ) -> OneWireResult<u32, O::BusError> {
+        let [lo, hi] = address.to_le_bytes();
+        self.bus.address(self.rom)?;
+        self.bus.write_bytes(&[READ_MEMORY_COUNTER_CMD, lo, hi])?;
+        let data = &mut data[..PAGE_SIZE - address as usize % PAGE_SIZE];
+        self.bus.read_bytes(data)?;
+        let mut trailer = [0; TRAILER_LEN];
+        self.bus.read_bytes(&mut trailer)?;
+        parse_trailer(address, data, &trailer)
+    }
+
+    /// Read `page` with its counter.
+    ///
+    /// # Errors
+    /// Returns [`OneWireError::InvalidValue`] if `page` does not exist, and
+    /// [`OneWireError::InvalidCrc`] if the CRC-16 of the page does not match.
+    pub fn read_page(&mut self, page: u8) -> OneWireResult<Page, O::BusError> {
+        if page >= PAGES {
+            return Err(OneWireError::InvalidValue("page"));
+        }
+        let mut data = [0; PAGE_SIZE];
+        let counter = self.read_with_counter(page as u16 * PAGE_SIZE as u16, &mut data)?;
+        Ok(Page { data, counter })
+    }
+
+    /// Read `counter`.
+    ///
+    /// Only the last byte of the page of the counter is read with it.
+    ///
+    /// # Errors
+    /// Returns [`OneWireError::InvalidCrc`] if the CRC-16 does not match.
+    pub fn read_counter(&mut self, counter: Counter) -> OneWireResult<u32, O::BusError> {
+        let address = (counter.page() as u16 + 1) * PAGE_SIZE as u16 - 1;
+        self.read_with_counter(address, &mut [0; PAGE_SIZE])
+    }
+}
@@ -0,0 +1,47 @@
+use crate::{
+    Counter, Ds2423, PAGE_SIZE, PAGES, Page, READ_MEMORY_COUNTER_CMD, TRAILER_LEN, parse_trailer,
+};
+use embedded_onewire::{OneWireAsync, OneWireError, OneWireResult};
+
+impl<O: OneWireAsync> Ds2423<O> {
+    async fn read_with_counter_async(
+        &mut self,
+        address: u16,
+        data: &mut [u8; PAGE_SIZE],
+    ) -> OneWireResult<u32, O::BusError> {
+        let [lo, hi] = address.to_le_bytes();
+        OneWireAsync::address(&mut self.bus, self.rom).await?;
+        OneWireAsync::write_bytes(&mut self.bus, &[READ_MEMORY_COUNTER_CMD, lo, hi]).await?;
+        let data = &mut data[..PAGE_SIZE - address as usize % PAGE_SIZE];
+        OneWireAsync::read_bytes(&mut self.bus, data).await?;
+        let mut trailer = [0; TRAILER_LEN];
+        OneWireAsync::read_bytes(&mut self.bus, &mut trailer).await?;
+        parse_trailer(address, data, &trailer)
+    }
+
+    /// Read `page` with its counter.
+    ///
+    /// See [`Ds2423::read_page`].
+    pub async fn read_page_async(&mut self, page: u8) -> OneWireResult<Page, O::BusError> {
+        if page >= PAGES {
+            return Err(OneWireError::InvalidValue("page"));
+        }
+        let mut data = [0; PAGE_SIZE];
+        let counter = self
+            .read_with_counter_async(page as u16 * PAGE_SIZE as u16, &mut data)
+            .await?;
+        Ok(Page { data, counter })
+    }
+
+    /// Read `counter`.
+    ///
+    /// See [`Ds2423::read_counter`].
+    pub async fn read_counter_async(
+        &mut self,
+        counter: Counter,
+    ) -> OneWireResult<u32, O::BusError> {
+        let address = (counter.page() as u16 + 1) * PAGE_SIZE as u16 - 1;
+        self.read_with_counter_async(address, &mut [0; PAGE_SIZE])
+            .await
+    }
+}
@@ -0,0 +1,133 @@
+#![no_std]
+#![deny(missing_docs)]
+#![cfg_attr(docsrs, feature(doc_cfg))]
+#![doc = include_str!("../README.md")]
+
+mod device;
+mod device_async;
+
+pub use device::Ds2423;
+use embedded_onewire::OneWireCrc16;
+pub use embedded_onewire::{OneWireError, OneWireResult};
+
+/// Family code of the DS2423, the low byte of its ROM.
+pub const FAMILY_CODE: u8 = 0x1d;
+
+/// Size of a page of the memory.
+pub const PAGE_SIZE: usize = 32;
+
+/// Number of pages of the memory.
+pub const PAGES: u8 = 16;
+
+pub(crate) const READ_MEMORY_COUNTER_CMD: u8 = 0xa5;
+
+/// Number of bytes sent after the data of a page: the counter, four zero bytes and the
+/// CRC-16.
+pub(crate) const TRAILER_LEN: usize = 10;
+
+/// An externally triggered counter of the DS2423.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Counter {
+    /// Counter A, triggered by the A input, and read with page 14.
+    A,
+    /// Counter B, triggered by the B input, and read with page 15.
+    B,
+}
+
+impl Counter {
+    /// The page the counter is read with.
+    pub fn page(&self) -> u8 {
+        match self {
+            Counter::A => 14,
+            Counter::B => 15,
+        }
+    }
+}
+
+/// A page of the memory, with the counter read with it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Page {
+    /// Data of the page.
+    pub data: [u8; PAGE_SIZE],
+    /// The counter of the page: the write cycle counters of pages 12 and 13, or the
+    /// external counters of pages 14 and 15.
+    pub counter: u32,
+}
+
+/// Check the CRC-16 of a Read Memory + Counter from `address`, and return the counter.
+///
+/// # Errors
+/// Returns [`OneWireError::InvalidCrc`] if the CRC does not match.
+pub(crate) fn parse_trailer<E>(
+    address: u16,
+    data: &[u8],
+    trailer: &[u8; TRAILER_LEN],
+) -> OneWireResult<u32, E> {
+    let [lo, hi] = address.to_le_bytes();
+    let mut crc = OneWireCrc16::default();
+    crc.update_slice(&[READ_MEMORY_COUNTER_CMD, lo, hi]);
+    crc.update_slice(data);
+    crc.update_slice(trailer);
+    if !crc.is_valid() {
+        return Err(OneWireError::InvalidCrc);
+    }
+    let [b0, b1, b2, b3, ..] = *trailer;
+    Ok(u32::from_le_bytes([b0, b1, b2, b3]))
+}
+
+mod test {
+    #[test]
+    fn test_read_counter() {
+        use super::*;
+        use embedded_onewire::testing::{Mock, Transaction};
+        extern crate std;
+
+        let bus = Mock::new(&[
+            // The counter is read with the last byte of its page
+            Transaction::reset(true),
+            Transaction::skip_rom(),
+            Transaction::write(&[READ_MEMORY_COUNTER_CMD, 0xdf, 0x01]),
+            Transaction::read(&[0x00]),
+            Transaction::read(&[0x39, 0x30, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00]),
+            Transaction::read(&[0x4a, 0x5d]),
+            Transaction::reset(true),
+            Transaction::skip_rom(),
+            Transaction::write(&[READ_MEMORY_COUNTER_CMD, 0xdf, 0x01]),
+            Transaction::read(&[0x00]),
+            Transaction::read(&[0x39, 0x30, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00]),
+            Transaction::read(&[0x4a, 0x5c]),
+        ]);
+        let mut counter = Ds2423::new(bus, None);
+        assert_eq!(counter.read_counter(Counter::A).unwrap(), 12345);
+        assert!(matches!(
+            counter.read_counter(Counter::A),
+            Err(OneWireError::InvalidCrc)
+        ));
+        counter.release().done();
+    }
+
+    #[test]
+    fn test_read_page() {
+        use super::*;
+        use embedded_onewire::testing::{Mock, Transaction};
+        extern crate std;
+
+        let bus = Mock::new(&[
+            Transaction::reset(true),
+            Transaction::skip_rom(),
+            Transaction::write(&[READ_MEMORY_COUNTER_CMD, 0xc0, 0x01]),
+            Transaction::read(&[0xff; PAGE_SIZE]),
+            Transaction::read(&[0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00]),
+            Transaction::read(&[0x37, 0xd6]),
+        ]);
+        let mut counter = Ds2423::new(bus, None);
+        let page = counter.read_page(14).unwrap();
+        assert_eq!(page.data, [0xff; PAGE_SIZE]);
+        assert_eq!(page.counter, 1);
+        assert!(matches!(
+            counter.read_page(PAGES),
+            Err(OneWireError::InvalidValue(_))
+        ));
+        counter.release().done();
+    }
+}
@@ -0,0 +1,179 @@
+use crate::{
+    COPY_PROTECTION_ADDR, DATA_SIZE, EPROM_MODE, PAGE_SIZE, PAGES, PROG_TIME_US, PROTECTION_ADDR,
+    Protection, ROW_SIZE, WRITE_PROTECT, layout,
+};
+use embedded_hal::delay::DelayNs;
+use embedded_onewire::{OneWire, OneWireError, OneWireResult, OneWireStrongPullup};
+use onewire_eeprom::{Layout, OneWireEeprom};
+
+/// A DS2431 1024-bit EEPROM on a 1-Wire bus.
+///
+/// The driver owns the bus master, which can be a `&mut` reference to one or a handle to a
+/// shared one. Every operation addresses the device first.
+#[derive(Debug)]
+pub struct Ds2431<O> {
+    pub(crate) bus: O,
+    pub(crate) rom: Option<u64>,
+    pub(crate) layout: Layout<ROW_SIZE>,
+}
+
+impl<O> Ds2431<O> {
+    /// Creates a driver for the device with ROM `rom` on `bus`.
+    ///
+    /// Pass [`None`] to skip ROM addressing on a bus with a single device.
+    pub fn new(bus: O, rom: Option<u64>) -> Self {
+        Self::with_prog_time(bus, rom, PROG_TIME_US)
+    }
+
+    /// Creates a driver for a device compatible with the DS2431 whose copy of the
+    /// scratchpad takes `prog_time_us` microseconds, e.g. the DS28E07.
+    pub fn with_prog_time(bus: O, rom: Option<u64>, prog_time_us: u32) -> Self {
+        Ds2431 {
+            bus,
+            rom,
+            layout: layout(prog_time_us),
+        }
+    }
+
+    /// ROM of the device, if it is addressed by ROM.
+    pub fn rom(&self) -> Option<u64> {
+        self.rom
+    }
+
+    /// Release the bus master.
+    pub fn release(self) -> O {
+        self.bus
+    }
+}
+
+impl<O: OneWire> Ds2431<O> {
+    /// Read the memory from `address` into `buf`.
+    ///
+    /// The data memory, the protection registers and the factory bytes can be read, up to
+    /// address `0x8f`.
+    ///
+    /// # Errors
+    /// Returns [`OneWireError::InvalidValue`](crate::OneWireError::InvalidValue) if the read
+    /// goes past the end of the memory.
+    pub fn read(&mut self, address: u16, buf: &mut [u8]) -> OneWireResult<(), O::BusError> {
+        self.layout.read(&mut self.bus, self.rom, address, buf)
+    }
+
+    /// Read the write protection of `page`.
+    ///
+    /// # Errors
+    /// Returns [`OneWireError::InvalidValue`] if `page` is not a page of the data memory.
+    pub fn protection(&mut self, page: u8) -> OneWireResult<Protection, O::BusError> {
+        if page >= PAGES {
+            return Err(OneWireError::InvalidValue("page"));
+        }
+        let mut byte = [0];
+        self.read(PROTECTION_ADDR + page as u16, &mut byte)?;
+        Ok(Protection::from_byte(byte[0]))
+    }
+
+    /// Whether the protection registers, and the write-protected pages, are protected from
+    /// being the target of a copy of the scratchpad.
+    pub fn copy_protected(&mut self) -> OneWireResult<bool, O::BusError> {
+        let mut byte = [0];
+        self.read(COPY_PROTECTION_ADDR, &mut byte)?;
+        Ok(matches!(byte[0], WRITE_PROTECT | EPROM_MODE))
+    }
+}
+
+impl<O: OneWireStrongPullup> Ds2431<O> {
+    /// Program the row at `address` with `row`.
+    ///
+    /// The bus is held at the strong pullup while the row is copied from the scratchpad to
+    /// the memory.
+    ///
+    /// # Errors
+    /// Returns [`OneWireError::InvalidValue`](crate::OneWireError::InvalidValue) if `address`
+    /// is not the start of a writable row, and the errors of
+    /// [`scratchpad::write_block`](crate::scratchpad::write_block), e.g. because the row is
+    /// write-protected.
+    pub fn write_row<D: DelayNs>(
+        &mut self,
+        address: u16,
+        row: &[u8; ROW_SIZE],
+        delay: &mut D,
+    ) -> OneWireResult<(), O::BusError> {
+        self.layout
+            .write_block(&mut self.bus, self.rom, address, row, delay)
+    }
+
+    /// Write `data` to the memory at `address`.
+    ///
+    /// Every row touched by `data` is programmed in full: rows that are only partially
+    /// covered are read first, so that the rest of their bytes are written back unchanged.
+    /// The data memory and the protection registers can be written, up to address `0x87`.
+    ///
+    /// # Errors
+    /// Returns [`OneWireError::InvalidValue`](crate::OneWireError::InvalidValue) if the write
+    /// goes past the end of the writable memory, and the errors of [`Ds2431::write_row`]. The rows before the failed one are
+    /// already programmed.
+    pub fn write<D: DelayNs>(
+        &mut self,
+        address: u16,
+        data: &[u8],
+        delay: &mut D,
+    ) -> OneWireResult<(), O::BusError> {
+        self.layout
+            .write(&mut self.bus, self.rom, address, data, delay)
+    }
+
+    /// Set the write protection of `page`. This cannot be undone.
+    ///
+    /// # Errors
+    /// Returns [`OneWireError::InvalidValue`] if `page` is not a page of the data memory,
+    /// and the errors of [`Ds2431::write_row`], e.g. if the protection of the page is
+    /// already set.
+    pub fn set_protection<D: DelayNs>(
+        &mut self,
+        page: u8,
+        protection: Protection,
+        delay: &mut D,
+    ) -> OneWireResult<(), O::BusError> {
+        if page >= PAGES {
+            return Err(OneWireError::InvalidValue("page"));
+        }
+        self.write(
+            PROTECTION_ADDR + page as u16,
+            &[protection.into_byte()],
+            delay,
+        )
+    }
+
+    /// Protect the protection registers, and the write-protected pages, from being the
+    /// target of a copy of the scratchpad. This cannot be undone.
+    ///
+    /// # Errors
+    /// See [`Ds2431::write_row`].
+    pub fn set_copy_protection<D: DelayNs>(
+        &mut self,
+        delay: &mut D,
+    ) -> OneWireResult<(), O::BusError> {
+        self.write(COPY_PROTECTION_ADDR, &[WRITE_PROTECT], delay)
+    }
+}
+
+impl<O: OneWireStrongPullup> OneWireEeprom for Ds2431<O> {
+    type BusError = O::BusError;
+
+    const CAPACITY: usize = DATA_SIZE;
+    const PAGE_SIZE: usize = PAGE_SIZE;
+    const SCRATCHPAD_SIZE: usize = ROW_SIZE;
+
+    fn read(&mut self, address: u16, buf: &mut [u8]) -> OneWireResult<(), Self::BusError> {
+        Ds2431::read(self, address, buf)
+    }
+
+    fn write<D: DelayNs>(
+        &mut self,
+        address: u16,
+        data: &[u8],
+        delay: &mut D,
+    ) -> OneWireResult<(), Self::BusError> {
+        Ds2431::write(self, address, data, delay)
+    }
+}
@@ -0,0 +1,127 @@
+use crate::{
+    COPY_PROTECTION_ADDR, DATA_SIZE, Ds2431, EPROM_MODE, PAGE_SIZE, PAGES, PROTECTION_ADDR,
+    Protection, ROW_SIZE, WRITE_PROTECT,
+};
+use embedded_hal_async::delay::DelayNs;
+use embedded_onewire::{OneWireAsync, OneWireError, OneWireResult, OneWireStrongPullupAsync};
+use onewire_eeprom::OneWireEepromAsync;
+
+impl<O: OneWireAsync> Ds2431<O> {
+    /// Read the memory from `address` into `buf`.
+    ///
+    /// See [`Ds2431::read`].
+    pub async fn read_async(
+        &mut self,
+        address: u16,
+        buf: &mut [u8],
+    ) -> OneWireResult<(), O::BusError> {
+        self.layout
+            .read_async(&mut self.bus, self.rom, address, buf)
+            .await
+    }
+
+    /// Read the write protection of `page`.
+    ///
+    /// See [`Ds2431::protection`].
+    pub async fn protection_async(&mut self, page: u8) -> OneWireResult<Protection, O::BusError> {
+        if page >= PAGES {
+            return Err(OneWireError::InvalidValue("page"));
+        }
+        let mut byte = [0];
+        self.read_async(PROTECTION_ADDR + page as u16, &mut byte)
+            .await?;
+        Ok(Protection::from_byte(byte[0]))
+    }
+
+    /// Whether the protection registers, and the write-protected pages, are copy-protected.
+    ///
+    /// See [`Ds2431::copy_protected`].
+    pub async fn copy_protected_async(&mut self) -> OneWireResult<bool, O::BusError> {
+        let mut byte = [0];
+        self.read_async(COPY_PROTECTION_ADDR, &mut byte).await?;
+        Ok(matches!(byte[0], WRITE_PROTECT | EPROM_MODE))
+    }
+}
+
+impl<O: OneWireStrongPullupAsync> Ds2431<O> {
+    /// Program the row at `address` with `row`.
+    ///
+    /// See [`Ds2431::write_row`].
+    pub async fn write_row_async<D: DelayNs>(
+        &mut self,
+        address: u16,
+        row: &[u8; ROW_SIZE],
+        delay: &mut D,
+    ) -> OneWireResult<(), O::BusError> {
+        self.layout
+            .write_block_async(&mut self.bus, self.rom, address, row, delay)
+            .await
+    }
+
+    /// Write `data` to the memory at `address`.
+    ///
+    /// See [`Ds2431::write`].
+    pub async fn write_async<D: DelayNs>(
+        &mut self,
+        address: u16,
+        data: &[u8],
+        delay: &mut D,
+    ) -> OneWireResult<(), O::BusError> {
+        self.layout
+            .write_async(&mut self.bus, self.rom, address, data, delay)
+            .await
+    }
+
+    /// Set the write protection of `page`. This cannot be undone.
+    ///
+    /// See [`Ds2431::set_protection`].
+    pub async fn set_protection_async<D: DelayNs>(
+        &mut self,
+        page: u8,
+        protection: Protection,
+        delay: &mut D,
+    ) -> OneWireResult<(), O::BusError> {
+        if page >= PAGES {
+            return Err(OneWireError::InvalidValue("page"));
+        }
+        self.write_async(
+            PROTECTION_ADDR + page as u16,
+            &[protection.into_byte()],
+            delay,
+        )
+        .await
+    }
+
+    /// Protect the protection registers, and the write-protected pages, from copies of the
+    /// scratchpad. This cannot be undone.
+    ///
+    /// See [`Ds2431::set_copy_protection`].
+    pub async fn set_copy_protection_async<D: DelayNs>(
+        &mut self,
+        delay: &mut D,
+    ) -> OneWireResult<(), O::BusError> {
+        self.write_async(COPY_PROTECTION_ADDR, &[WRITE_PROTECT], delay)
+            .await
+    }
+}
+
+impl<O: OneWireStrongPullupAsync> OneWireEepromAsync for Ds2431<O> {
+    type BusError = O::BusError;
+
+    const CAPACITY: usize = DATA_SIZE;
+    const PAGE_SIZE: usize = PAGE_SIZE;
+    const SCRATCHPAD_SIZE: usize = ROW_SIZE;
+
+    async fn read(&mut self, address: u16, buf: &mut [u8]) -> OneWireResult<(), Self::BusError> {
+        self.read_async(address, buf).await
+    }
+
+    async fn write<D: DelayNs>(
+        &mut self,
+        address: u16,
+        data: &[u8],
+        delay: &mut D,
+    ) -> OneWireResult<(), Self::BusError> {
+        self.write_async(address, data, delay).await
+    }
+}
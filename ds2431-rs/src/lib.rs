@@ -0,0 +1,222 @@
+#![no_std]
+#![deny(missing_docs)]
+#![cfg_attr(docsrs, feature(doc_cfg))]
+#![doc = include_str!("../README.md")]
+
+mod device;
+mod device_async;
+
+pub use device::Ds2431;
+pub use embedded_onewire::{OneWireError, OneWireResult};
+/// The scratchpad flow, now provided by the `onewire-eeprom` crate.
+pub use onewire_eeprom::scratchpad;
+
+use onewire_eeprom::Layout;
+
+/// Family code of the DS2431, the low byte of its ROM.
+pub const FAMILY_CODE: u8 = 0x2d;
+
+/// Size of a row of the memory, and of the scratchpad.
+pub const ROW_SIZE: usize = 8;
+/// Size of a page of the memory, the unit of write protection.
+pub const PAGE_SIZE: usize = 32;
+/// Size of the data memory.
+pub const DATA_SIZE: usize = 0x80;
+/// Number of pages of the data memory.
+pub const PAGES: u8 = 4;
+
+/// Address of the page protection registers, one per page.
+pub(crate) const PROTECTION_ADDR: u16 = 0x80;
+/// Address of the copy protection register.
+pub(crate) const COPY_PROTECTION_ADDR: u16 = 0x84;
+/// End of the writable memory: the data memory and the protection registers.
+pub(crate) const WRITABLE_END: usize = 0x88;
+/// End of the readable memory, including the factory bytes.
+pub(crate) const MEMORY_END: usize = 0x90;
+
+/// Protection register value that write-protects a page.
+pub(crate) const WRITE_PROTECT: u8 = 0x55;
+/// Protection register value that puts a page in EPROM mode.
+pub(crate) const EPROM_MODE: u8 = 0xaa;
+
+/// Programming time of a row, in microseconds.
+pub(crate) const PROG_TIME_US: u32 = 10_000;
+
+/// Layout of the memory for the scratchpad engine.
+pub(crate) const fn layout(prog_time_us: u32) -> Layout<ROW_SIZE> {
+    Layout::new(MEMORY_END, WRITABLE_END, prog_time_us)
+}
+
+/// Write protection of a page of the data memory.
+///
+/// Protection is irreversible: once a protection register holds a protecting value, the
+/// register itself can no longer be changed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Protection {
+    /// The page can be written freely.
+    Open,
+    /// The page is read-only.
+    WriteProtected,
+    /// EPROM emulation: bits of the page can only be changed from 1 to 0.
+    Eprom,
+}
+
+impl Protection {
+    pub(crate) fn into_byte(self) -> u8 {
+        match self {
+            Protection::Open => 0x00,
+            Protection::WriteProtected => WRITE_PROTECT,
+            Protection::Eprom => EPROM_MODE,
+        }
+    }
+
+    pub(crate) fn from_byte(byte: u8) -> Self {
+        match byte {
+            WRITE_PROTECT => Protection::WriteProtected,
+            EPROM_MODE => Protection::Eprom,
+            _ => Protection::Open,
+        }
+    }
+}
+
+mod test {
+    #[test]
+    fn test_write_partial_row() {
+        use super::*;
+        use embedded_hal_mock::eh1::delay::NoopDelay;
+        use embedded_onewire::testing::{Mock, Transaction};
+        extern crate std;
+
+        let row = [0x11, 0x22, 0x33, 0xaa, 0x55, 0x66, 0x77, 0x88];
+        let bus = Mock::new(&[
+            // The rest of the row is read first
+            Transaction::reset(true),
+            Transaction::skip_rom(),
+            Transaction::write(&[0xf0, 0x00, 0x00]),
+            Transaction::read(&[0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77, 0x88]),
+            Transaction::reset(true),
+            Transaction::skip_rom(),
+            Transaction::write(&[0x0f, 0x00, 0x00]),
+            Transaction::write(&row),
+            Transaction::read(&[0xc6, 0xb7]),
+            Transaction::reset(true),
+            Transaction::skip_rom(),
+            Transaction::write(&[0xaa]),
+            Transaction::read(&[0x00, 0x00, 0x07]),
+            Transaction::read(&row),
+            Transaction::read(&[0x4b, 0x4a]),
+            Transaction::reset(true),
+            Transaction::skip_rom(),
+            Transaction::write(&[0x55, 0x00, 0x00]),
+            Transaction::write_powered(0x07),
+            Transaction::read(&[0xaa]),
+        ]);
+        let mut eeprom = Ds2431::new(bus, None);
+        eeprom.write(0x03, &[0xaa], &mut NoopDelay::new()).unwrap();
+        assert!(matches!(
+            eeprom.write(0x80, &[0; 9], &mut NoopDelay::new()),
+            Err(OneWireError::InvalidValue(_))
+        ));
+        assert!(matches!(
+            eeprom.read(0x8f, &mut [0; 2]),
+            Err(OneWireError::InvalidValue(_))
+        ));
+        eeprom.release().done();
+    }
+
+    #[test]
+    fn test_write_row_verification() {
+        use super::*;
+        use embedded_hal_mock::eh1::delay::NoopDelay;
+        use embedded_onewire::testing::{Mock, Transaction};
+        extern crate std;
+
+        let row = [0x11, 0x22, 0x33, 0xaa, 0x55, 0x66, 0x77, 0x88];
+        let bus = Mock::new(&[
+            Transaction::reset(true),
+            Transaction::skip_rom(),
+            Transaction::write(&[0x0f, 0x00, 0x00]),
+            Transaction::write(&row),
+            Transaction::read(&[0xc6, 0xb7]),
+            // The partial flag is set
+            Transaction::reset(true),
+            Transaction::skip_rom(),
+            Transaction::write(&[0xaa]),
+            Transaction::read(&[0x00, 0x00, 0x27]),
+            Transaction::read(&row),
+            Transaction::read(&[0xd2, 0x8b]),
+            // The write CRC is corrupted
+            Transaction::reset(true),
+            Transaction::skip_rom(),
+            Transaction::write(&[0x0f, 0x00, 0x00]),
+            Transaction::write(&row),
+            Transaction::read(&[0xc6, 0xb6]),
+        ]);
+        let mut eeprom = Ds2431::new(bus, None);
+        assert!(matches!(
+            eeprom.write_row(0x00, &row, &mut NoopDelay::new()),
+            Err(OneWireError::VerificationFailed)
+        ));
+        assert!(matches!(
+            eeprom.write_row(0x00, &row, &mut NoopDelay::new()),
+            Err(OneWireError::InvalidCrc)
+        ));
+        assert!(matches!(
+            eeprom.write_row(0x04, &row, &mut NoopDelay::new()),
+            Err(OneWireError::InvalidValue(_))
+        ));
+        eeprom.release().done();
+    }
+
+    #[test]
+    fn test_protection() {
+        use super::*;
+        use embedded_hal_mock::eh1::delay::NoopDelay;
+        use embedded_onewire::testing::{Mock, Transaction};
+        extern crate std;
+
+        let row = [0x00, 0x55, 0x00, 0x00, 0x00, 0x55, 0x12, 0x34];
+        let bus = Mock::new(&[
+            // The rest of the register row is read first
+            Transaction::reset(true),
+            Transaction::skip_rom(),
+            Transaction::write(&[0xf0, 0x80, 0x00]),
+            Transaction::read(&[0x00, 0x00, 0x00, 0x00, 0x00, 0x55, 0x12, 0x34]),
+            Transaction::reset(true),
+            Transaction::skip_rom(),
+            Transaction::write(&[0x0f, 0x80, 0x00]),
+            Transaction::write(&row),
+            Transaction::read(&[0xd0, 0x61]),
+            Transaction::reset(true),
+            Transaction::skip_rom(),
+            Transaction::write(&[0xaa]),
+            Transaction::read(&[0x80, 0x00, 0x07]),
+            Transaction::read(&row),
+            Transaction::read(&[0xf3, 0xb6]),
+            Transaction::reset(true),
+            Transaction::skip_rom(),
+            Transaction::write(&[0x55, 0x80, 0x00]),
+            Transaction::write_powered(0x07),
+            Transaction::read(&[0xaa]),
+            Transaction::reset(true),
+            Transaction::skip_rom(),
+            Transaction::write(&[0xf0, 0x81, 0x00]),
+            Transaction::read(&[0x55]),
+            Transaction::reset(true),
+            Transaction::skip_rom(),
+            Transaction::write(&[0xf0, 0x84, 0x00]),
+            Transaction::read(&[0xaa]),
+        ]);
+        let mut eeprom = Ds2431::new(bus, None);
+        eeprom
+            .set_protection(1, Protection::WriteProtected, &mut NoopDelay::new())
+            .unwrap();
+        assert_eq!(eeprom.protection(1).unwrap(), Protection::WriteProtected);
+        assert!(eeprom.copy_protected().unwrap());
+        assert!(matches!(
+            eeprom.protection(PAGES),
+            Err(OneWireError::InvalidValue(_))
+        ));
+        eeprom.release().done();
+    }
+}
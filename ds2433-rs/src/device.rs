@@ -0,0 +1,118 @@
+use crate::{LAYOUT, MEMORY_END, PAGE_SIZE};
+use embedded_hal::delay::DelayNs;
+use embedded_onewire::{OneWire, OneWireError, OneWireResult, OneWireStrongPullup};
+use onewire_eeprom::OneWireEeprom;
+
+/// A DS2433 4Kb EEPROM on a 1-Wire bus.
+///
+/// The driver owns the bus master, which can be a `&mut` reference to one or a handle to a
+/// shared one. Every operation addresses the device first.
+#[derive(Debug)]
+pub struct Ds2433<O> {
+    pub(crate) bus: O,
+    pub(crate) rom: Option<u64>,
+}
+
+impl<O> Ds2433<O> {
+    /// Creates a driver for the device with ROM `rom` on `bus`.
+    ///
+    /// Pass [`None`] to skip ROM addressing on a bus with a single device.
+    pub fn new(bus: O, rom: Option<u64>) -> Self {
+        Ds2433 { bus, rom }
+    }
+
+    /// ROM of the device, if it is addressed by ROM.
+    pub fn rom(&self) -> Option<u64> {
+        self.rom
+    }
+
+    /// Release the bus master.
+    pub fn release(self) -> O {
+        self.bus
+    }
+}
+
+impl<O: OneWire> Ds2433<O> {
+    /// Read the memory from `address` into `buf`.
+    ///
+    /// The Read Memory command of the DS2433 sends no CRC, so the transfer is not checked.
+    ///
+    /// # Errors
+    /// Returns [`OneWireError::InvalidValue`] if the read goes past the end of the memory.
+    pub fn read(&mut self, address: u16, buf: &mut [u8]) -> OneWireResult<(), O::BusError> {
+        LAYOUT.read(&mut self.bus, self.rom, address, buf)
+    }
+
+    /// Read the page at `address`.
+    ///
+    /// # Errors
+    /// Returns [`OneWireError::InvalidValue`] if `address` is not the start of a page.
+    pub fn read_page(&mut self, address: u16) -> OneWireResult<[u8; PAGE_SIZE], O::BusError> {
+        if !(address as usize).is_multiple_of(PAGE_SIZE) {
+            return Err(OneWireError::InvalidValue("address"));
+        }
+        let mut page = [0; PAGE_SIZE];
+        self.read(address, &mut page)?;
+        Ok(page)
+    }
+}
+
+impl<O: OneWireStrongPullup> Ds2433<O> {
+    /// Program the page at `address` with `page`.
+    ///
+    /// The bus is held at the strong pullup while the page is copied from the scratchpad to
+    /// the memory.
+    ///
+    /// # Errors
+    /// Returns [`OneWireError::InvalidValue`] if `address` is not the start of a page,
+    /// [`OneWireError::InvalidCrc`] if a transfer of the scratchpad is corrupted, and
+    /// [`OneWireError::VerificationFailed`] if the scratchpad read back does not match,
+    /// or the device does not confirm the copy.
+    pub fn write_page<D: DelayNs>(
+        &mut self,
+        address: u16,
+        page: &[u8; PAGE_SIZE],
+        delay: &mut D,
+    ) -> OneWireResult<(), O::BusError> {
+        LAYOUT.write_block(&mut self.bus, self.rom, address, page, delay)
+    }
+
+    /// Write `data` to the memory at `address`.
+    ///
+    /// Every page touched by `data` is programmed in full: pages that are only partially
+    /// covered are read first, so that the rest of their bytes are written back unchanged.
+    ///
+    /// # Errors
+    /// Returns [`OneWireError::InvalidValue`] if the write goes past the end of the memory,
+    /// and the errors of [`Ds2433::write_page`]. The pages before the failed one are
+    /// already programmed.
+    pub fn write<D: DelayNs>(
+        &mut self,
+        address: u16,
+        data: &[u8],
+        delay: &mut D,
+    ) -> OneWireResult<(), O::BusError> {
+        LAYOUT.write(&mut self.bus, self.rom, address, data, delay)
+    }
+}
+
+impl<O: OneWireStrongPullup> OneWireEeprom for Ds2433<O> {
+    type BusError = O::BusError;
+
+    const CAPACITY: usize = MEMORY_END;
+    const PAGE_SIZE: usize = PAGE_SIZE;
+    const SCRATCHPAD_SIZE: usize = PAGE_SIZE;
+
+    fn read(&mut self, address: u16, buf: &mut [u8]) -> OneWireResult<(), Self::BusError> {
+        Ds2433::read(self, address, buf)
+    }
+
+    fn write<D: DelayNs>(
+        &mut self,
+        address: u16,
+        data: &[u8],
+        delay: &mut D,
+    ) -> OneWireResult<(), Self::BusError> {
+        Ds2433::write(self, address, data, delay)
+    }
+}
@@ -0,0 +1,85 @@
+use crate::{Ds2433, LAYOUT, MEMORY_END, PAGE_SIZE};
+use embedded_hal_async::delay::DelayNs;
+use embedded_onewire::{OneWireAsync, OneWireError, OneWireResult, OneWireStrongPullupAsync};
+use onewire_eeprom::OneWireEepromAsync;
+
+impl<O: OneWireAsync> Ds2433<O> {
+    /// Read the memory from `address` into `buf`.
+    ///
+    /// See [`Ds2433::read`].
+    pub async fn read_async(
+        &mut self,
+        address: u16,
+        buf: &mut [u8],
+    ) -> OneWireResult<(), O::BusError> {
+        LAYOUT
+            .read_async(&mut self.bus, self.rom, address, buf)
+            .await
+    }
+
+    /// Read the page at `address`.
+    ///
+    /// See [`Ds2433::read_page`].
+    pub async fn read_page_async(
+        &mut self,
+        address: u16,
+    ) -> OneWireResult<[u8; PAGE_SIZE], O::BusError> {
+        if !(address as usize).is_multiple_of(PAGE_SIZE) {
+            return Err(OneWireError::InvalidValue("address"));
+        }
+        let mut page = [0; PAGE_SIZE];
+        self.read_async(address, &mut page).await?;
+        Ok(page)
+    }
+}
+
+impl<O: OneWireStrongPullupAsync> Ds2433<O> {
+    /// Program the page at `address` with `page`.
+    ///
+    /// See [`Ds2433::write_page`].
+    pub async fn write_page_async<D: DelayNs>(
+        &mut self,
+        address: u16,
+        page: &[u8; PAGE_SIZE],
+        delay: &mut D,
+    ) -> OneWireResult<(), O::BusError> {
+        LAYOUT
+            .write_block_async(&mut self.bus, self.rom, address, page, delay)
+            .await
+    }
+
+    /// Write `data` to the memory at `address`.
+    ///
+    /// See [`Ds2433::write`].
+    pub async fn write_async<D: DelayNs>(
+        &mut self,
+        address: u16,
+        data: &[u8],
+        delay: &mut D,
+    ) -> OneWireResult<(), O::BusError> {
+        LAYOUT
+            .write_async(&mut self.bus, self.rom, address, data, delay)
+            .await
+    }
+}
+
+impl<O: OneWireStrongPullupAsync> OneWireEepromAsync for Ds2433<O> {
+    type BusError = O::BusError;
+
+    const CAPACITY: usize = MEMORY_END;
+    const PAGE_SIZE: usize = PAGE_SIZE;
+    const SCRATCHPAD_SIZE: usize = PAGE_SIZE;
+
+    async fn read(&mut self, address: u16, buf: &mut [u8]) -> OneWireResult<(), Self::BusError> {
+        self.read_async(address, buf).await
+    }
+
+    async fn write<D: DelayNs>(
+        &mut self,
+        address: u16,
+        data: &[u8],
+        delay: &mut D,
+    ) -> OneWireResult<(), Self::BusError> {
+        self.write_async(address, data, delay).await
+    }
+}
@@ -0,0 +1,79 @@
+#![no_std]
+#![deny(missing_docs)]
+#![cfg_attr(docsrs, feature(doc_cfg))]
+#![doc = include_str!("../README.md")]
+
+mod device;
+mod device_async;
+
+pub use device::Ds2433;
+pub use embedded_onewire::{OneWireError, OneWireResult};
+
+use onewire_eeprom::Layout;
+
+/// Family code of the DS2433, the low byte of its ROM.
+pub const FAMILY_CODE: u8 = 0x23;
+
+/// Size of a page of the memory, and of the scratchpad.
+pub const PAGE_SIZE: usize = 32;
+
+/// Number of pages of the memory.
+pub const PAGES: usize = 16;
+
+/// End of the memory.
+pub(crate) const MEMORY_END: usize = PAGES * PAGE_SIZE;
+
+/// Programming time of a page, in microseconds.
+pub(crate) const PROG_TIME_US: u32 = 5_000;
+
+/// Layout of the memory for the scratchpad engine.
+pub(crate) const LAYOUT: Layout<PAGE_SIZE> = Layout::new(MEMORY_END, MEMORY_END, PROG_TIME_US);
+
+mod test {
+    #[test]
+    fn test_write_end_of_memory() {
+        use super::*;
+        use embedded_hal_mock::eh1::delay::NoopDelay;
+        use embedded_onewire::testing::{Mock, Transaction};
+        extern crate std;
+
+        let mut page = [0xff; PAGE_SIZE];
+        page[30..].copy_from_slice(&[0x12, 0x34]);
+        let bus = Mock::new(&[
+            Transaction::reset(true),
+            Transaction::skip_rom(),
+            Transaction::write(&[0xf0, 0xe0, 0x01]),
+            Transaction::read(&[0xff; PAGE_SIZE]),
+            Transaction::reset(true),
+            Transaction::skip_rom(),
+            Transaction::write(&[0x0f, 0xe0, 0x01]),
+            Transaction::write(&page),
+            Transaction::read(&[0x14, 0x3d]),
+            Transaction::reset(true),
+            Transaction::skip_rom(),
+            Transaction::write(&[0xaa]),
+            Transaction::read(&[0xe0, 0x01, 0x1f]),
+            Transaction::read(&page),
+            Transaction::read(&[0xa5, 0x60]),
+            Transaction::reset(true),
+            Transaction::skip_rom(),
+            Transaction::write(&[0x55, 0xe0, 0x01]),
+            Transaction::write_powered(0x1f),
+            Transaction::read(&[0xaa]),
+            Transaction::reset(true),
+            Transaction::skip_rom(),
+            Transaction::write(&[0xf0, 0xe0, 0x01]),
+            Transaction::read(&page),
+        ]);
+        let mut eeprom = Ds2433::new(bus, None);
+        eeprom
+            .write(0x01fe, &[0x12, 0x34], &mut NoopDelay::new())
+            .unwrap();
+        assert!(matches!(
+            eeprom.write(0x01ff, &[0x12, 0x34], &mut NoopDelay::new()),
+            Err(OneWireError::InvalidValue(_))
+        ));
+        assert_eq!(eeprom.read_page(0x01e0).unwrap(), page);
+        eeprom.release().done();
+    }
+}
@@ -0,0 +1,177 @@
+use crate::{
+    CONVERT_T_CMD, CONVERT_T_US, CONVERT_V_CMD, CONVERT_V_US, COPY_SCRATCHPAD_CMD,
+    COPY_SCRATCHPAD_US, Config, Measurements, PAGE_SIZE, PAGES, READ_SCRATCHPAD_CMD,
+    RECALL_MEMORY_CMD, Temperature, USER_PAGES, VoltageInput, WRITE_SCRATCHPAD_CMD,
+};
+use embedded_hal::delay::DelayNs;
+use embedded_onewire::{OneWire, OneWireCrc, OneWireError, OneWireResult};
+
+/// A DS2438 smart battery monitor on a 1-Wire bus.
+///
+/// The driver owns the bus master, which can be a `&mut` reference to one or a handle to a
+/// shared one. Every operation addresses the device first.
+#[derive(Debug)]
+pub struct Ds2438<O> {
+    pub(crate) bus: O,
+    pub(crate) rom: Option<u64>,
+}
+
+impl<O> Ds2438<O> {
+    /// Creates a driver for the device with ROM `rom` on `bus`.
+    ///
+    /// Pass [`None`] to skip ROM addressing on a bus with a single device.
+    pub fn new(bus: O, rom: Option<u64>) -> Self {
+        Ds2438 { bus, rom }
+    }
+
+    /// ROM of the device, if it is addressed by ROM.
+    pub fn rom(&self) -> Option<u64> {
+        self.rom
+    }
+
+    /// Release the bus master.
+    pub fn release(self) -> O {
+        self.bus
+    }
+}
+
+impl<O: OneWire> Ds2438<O> {
+    fn command(&mut self, cmd: &[u8]) -> OneWireResult<(), O::BusError> {
+        self.bus.address(self.rom)?;
+        self.bus.write_bytes(cmd)
+    }
+
+    fn read_scratchpad(&mut self, page: u8) -> OneWireResult<[u8; PAGE_SIZE], O::BusError> {
+        self.command(&[READ_SCRATCHPAD_CMD, page])?;
+        let mut buf = [0; PAGE_SIZE + 1];
+        self.bus.read_bytes(&mut buf)?;
+        if !OneWireCrc::validate(&buf) {
+            return Err(OneWireError::InvalidCrc);
+        }
+        let mut data = [0; PAGE_SIZE];
+        data.copy_from_slice(&buf[..PAGE_SIZE]);
+        Ok(data)
+    }
+
+    /// Read a page of the memory.
+    ///
+    /// # Errors
+    /// Returns [`OneWireError::InvalidValue`] if `page` does not exist, and
+    /// [`OneWireError::InvalidCrc`] if the CRC of the page does not match.
+    pub fn read_page(&mut self, page: u8) -> OneWireResult<[u8; PAGE_SIZE], O::BusError> {
+        if page >= PAGES {
+            return Err(OneWireError::InvalidValue("page"));
+        }
+        self.command(&[RECALL_MEMORY_CMD, page])?;
+        self.read_scratchpad(page)
+    }
+
+    /// Write a page of the user memory, see [`USER_PAGES`], and wait for the write to
+    /// complete.
+    ///
+    /// The data is written to the scratchpad and read back before being copied to the page.
+    ///
+    /// # Errors
+    /// Returns [`OneWireError::InvalidValue`] if `page` is not a user page, and
+    /// [`OneWireError::VerificationFailed`] if the scratchpad read back does not match.
+    pub fn write_page<D: DelayNs>(
+        &mut self,
+        page: u8,
+        data: &[u8; PAGE_SIZE],
+        delay: &mut D,
+    ) -> OneWireResult<(), O::BusError> {
+        if !USER_PAGES.contains(&page) {
+            return Err(OneWireError::InvalidValue("page"));
+        }
+        self.command(&[WRITE_SCRATCHPAD_CMD, page])?;
+        self.bus.write_bytes(data)?;
+        if self.read_scratchpad(page)? != *data {
+            return Err(OneWireError::VerificationFailed);
+        }
+        self.command(&[COPY_SCRATCHPAD_CMD, page])?;
+        delay.delay_us(COPY_SCRATCHPAD_US);
+        Ok(())
+    }
+
+    /// Read the measurements in page 0.
+    pub fn read_measurements(&mut self) -> OneWireResult<Measurements, O::BusError> {
+        Ok(Measurements::parse(&self.read_page(0)?))
+    }
+
+    /// Write the status/configuration register, and wait for the write to complete.
+    pub fn set_config<D: DelayNs>(
+        &mut self,
+        config: Config,
+        delay: &mut D,
+    ) -> OneWireResult<(), O::BusError> {
+        self.command(&[WRITE_SCRATCHPAD_CMD, 0, config.into_bits()])?;
+        self.command(&[COPY_SCRATCHPAD_CMD, 0])?;
+        delay.delay_us(COPY_SCRATCHPAD_US);
+        Ok(())
+    }
+
+    /// Start a temperature conversion.
+    pub fn start_temperature_conversion(&mut self) -> OneWireResult<(), O::BusError> {
+        self.command(&[CONVERT_T_CMD])
+    }
+
+    /// Select the input of the voltage A/D converter if needed, and start a voltage
+    /// conversion.
+    pub fn start_voltage_conversion<D: DelayNs>(
+        &mut self,
+        input: VoltageInput,
+        delay: &mut D,
+    ) -> OneWireResult<(), O::BusError> {
+        let config = self.read_measurements()?.config;
+        let vdd = input == VoltageInput::Vdd;
+        if config.voltage_vdd() != vdd {
+            self.set_config(config.with_voltage_vdd(vdd), delay)?;
+        }
+        self.command(&[CONVERT_V_CMD])
+    }
+
+    /// Start a temperature conversion, wait for it to complete and read the result.
+    pub fn measure_temperature<D: DelayNs>(
+        &mut self,
+        delay: &mut D,
+    ) -> OneWireResult<Temperature, O::BusError> {
+        self.start_temperature_conversion()?;
+        delay.delay_us(CONVERT_T_US);
+        Ok(self.read_measurements()?.temperature)
+    }
+
+    /// Start a voltage conversion on `input`, wait for it to complete and read the result,
+    /// in millivolts.
+    pub fn measure_voltage<D: DelayNs>(
+        &mut self,
+        input: VoltageInput,
+        delay: &mut D,
+    ) -> OneWireResult<u16, O::BusError> {
+        self.start_voltage_conversion(input, delay)?;
+        delay.delay_us(CONVERT_V_US);
+        Ok(self.read_measurements()?.voltage_mv)
+    }
+
+    /// Read the elapsed time meter, in seconds.
+    pub fn read_elapsed_time(&mut self) -> OneWireResult<u32, O::BusError> {
+        let page = self.read_page(1)?;
+        Ok(u32::from_le_bytes([page[0], page[1], page[2], page[3]]))
+    }
+
+    /// Read the integrated current accumulator, in units of 1 / (2048 R<sub>SENS</sub>) Ah.
+    pub fn read_integrated_current(&mut self) -> OneWireResult<u8, O::BusError> {
+        Ok(self.read_page(1)?[4])
+    }
+
+    /// Read the charging and discharging current accumulators, in units of
+    /// 1 / (64 R<sub>SENS</sub>) Ah.
+    ///
+    /// Only valid when enabled with [`Config::current_accumulator`].
+    pub fn read_charge_accumulators(&mut self) -> OneWireResult<(u16, u16), O::BusError> {
+        let page = self.read_page(7)?;
+        Ok((
+            u16::from_le_bytes([page[4], page[5]]),
+            u16::from_le_bytes([page[6], page[7]]),
+        ))
+    }
+}
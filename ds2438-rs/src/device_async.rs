@@ -0,0 +1,152 @@
+use crate::{
+    CONVERT_T_CMD, CONVERT_T_US, CONVERT_V_CMD, CONVERT_V_US, COPY_SCRATCHPAD_CMD,
+    COPY_SCRATCHPAD_US, Config, Ds2438, Measurements, PAGE_SIZE, PAGES, READ_SCRATCHPAD_CMD,
+    RECALL_MEMORY_CMD, Temperature, USER_PAGES, VoltageInput, WRITE_SCRATCHPAD_CMD,
+};
+use embedded_hal_async::delay::DelayNs as DelayNsAsync;
+use embedded_onewire::{OneWireAsync, OneWireCrc, OneWireError, OneWireResult};
+
+impl<O: OneWireAsync> Ds2438<O> {
+    async fn command_async(&mut self, cmd: &[u8]) -> OneWireResult<(), O::BusError> {
+        OneWireAsync::address(&mut self.bus, self.rom).await?;
+        OneWireAsync::write_bytes(&mut self.bus, cmd).await
+    }
+
+    async fn read_scratchpad_async(
+        &mut self,
+        page: u8,
+    ) -> OneWireResult<[u8; PAGE_SIZE], O::BusError> {
+        self.command_async(&[READ_SCRATCHPAD_CMD, page]).await?;
+        let mut buf = [0; PAGE_SIZE + 1];
+        OneWireAsync::read_bytes(&mut self.bus, &mut buf).await?;
+        if !OneWireCrc::validate(&buf) {
+            return Err(OneWireError::InvalidCrc);
+        }
+        let mut data = [0; PAGE_SIZE];
+        data.copy_from_slice(&buf[..PAGE_SIZE]);
+        Ok(data)
+    }
+
+    /// Read a page of the memory.
+    ///
+    /// See [`Ds2438::read_page`].
+    pub async fn read_page_async(
+        &mut self,
+        page: u8,
+    ) -> OneWireResult<[u8; PAGE_SIZE], O::BusError> {
+        if page >= PAGES {
+            return Err(OneWireError::InvalidValue("page"));
+        }
+        self.command_async(&[RECALL_MEMORY_CMD, page]).await?;
+        self.read_scratchpad_async(page).await
+    }
+
+    /// Write a page of the user memory, and wait for the write to complete.
+    ///
+    /// See [`Ds2438::write_page`].
+    pub async fn write_page_async<D: DelayNsAsync>(
+        &mut self,
+        page: u8,
+        data: &[u8; PAGE_SIZE],
+        delay: &mut D,
+    ) -> OneWireResult<(), O::BusError> {
+        if !USER_PAGES.contains(&page) {
+            return Err(OneWireError::InvalidValue("page"));
+        }
+        self.command_async(&[WRITE_SCRATCHPAD_CMD, page]).await?;
+        OneWireAsync::write_bytes(&mut self.bus, data).await?;
+        if self.read_scratchpad_async(page).await? != *data {
+            return Err(OneWireError::VerificationFailed);
+        }
+        self.command_async(&[COPY_SCRATCHPAD_CMD, page]).await?;
+        delay.delay_us(COPY_SCRATCHPAD_US).await;
+        Ok(())
+    }
+
+    /// Read the measurements in page 0.
+    pub async fn read_measurements_async(&mut self) -> OneWireResult<Measurements, O::BusError> {
+        Ok(Measurements::parse(&self.read_page_async(0).await?))
+    }
+
+    /// Write the status/configuration register, and wait for the write to complete.
+    pub async fn set_config_async<D: DelayNsAsync>(
+        &mut self,
+        config: Config,
+        delay: &mut D,
+    ) -> OneWireResult<(), O::BusError> {
+        self.command_async(&[WRITE_SCRATCHPAD_CMD, 0, config.into_bits()])
+            .await?;
+        self.command_async(&[COPY_SCRATCHPAD_CMD, 0]).await?;
+        delay.delay_us(COPY_SCRATCHPAD_US).await;
+        Ok(())
+    }
+
+    /// Start a temperature conversion.
+    pub async fn start_temperature_conversion_async(&mut self) -> OneWireResult<(), O::BusError> {
+        self.command_async(&[CONVERT_T_CMD]).await
+    }
+
+    /// Select the input of the voltage A/D converter if needed, and start a voltage
+    /// conversion.
+    pub async fn start_voltage_conversion_async<D: DelayNsAsync>(
+        &mut self,
+        input: VoltageInput,
+        delay: &mut D,
+    ) -> OneWireResult<(), O::BusError> {
+        let config = self.read_measurements_async().await?.config;
+        let vdd = input == VoltageInput::Vdd;
+        if config.voltage_vdd() != vdd {
+            self.set_config_async(config.with_voltage_vdd(vdd), delay)
+                .await?;
+        }
+        self.command_async(&[CONVERT_V_CMD]).await
+    }
+
+    /// Start a temperature conversion, wait for it to complete and read the result.
+    pub async fn measure_temperature_async<D: DelayNsAsync>(
+        &mut self,
+        delay: &mut D,
+    ) -> OneWireResult<Temperature, O::BusError> {
+        self.start_temperature_conversion_async().await?;
+        delay.delay_us(CONVERT_T_US).await;
+        Ok(self.read_measurements_async().await?.temperature)
+    }
+
+    /// Start a voltage conversion on `input`, wait for it to complete and read the result,
+    /// in millivolts.
+    pub async fn measure_voltage_async<D: DelayNsAsync>(
+        &mut self,
+        input: VoltageInput,
+        delay: &mut D,
+    ) -> OneWireResult<u16, O::BusError> {
+        self.start_voltage_conversion_async(input, delay).await?;
+        delay.delay_us(CONVERT_V_US).await;
+        Ok(self.read_measurements_async().await?.voltage_mv)
+    }
+
+    /// Read the elapsed time meter, in seconds.
+    pub async fn read_elapsed_time_async(&mut self) -> OneWireResult<u32, O::BusError> {
+        let page = self.read_page_async(1).await?;
+        Ok(u32::from_le_bytes([page[0], page[1], page[2], page[3]]))
+    }
+
+    /// Read the integrated current accumulator.
+    ///
+    /// See [`Ds2438::read_integrated_current`].
+    pub async fn read_integrated_current_async(&mut self) -> OneWireResult<u8, O::BusError> {
+        Ok(self.read_page_async(1).await?[4])
+    }
+
+    /// Read the charging and discharging current accumulators.
+    ///
+    /// See [`Ds2438::read_charge_accumulators`].
+    pub async fn read_charge_accumulators_async(
+        &mut self,
+    ) -> OneWireResult<(u16, u16), O::BusError> {
+        let page = self.read_page_async(7).await?;
+        Ok((
+            u16::from_le_bytes([page[4], page[5]]),
+            u16::from_le_bytes([page[6], page[7]]),
+        ))
+    }
+}
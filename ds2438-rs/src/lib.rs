@@ -0,0 +1,231 @@
+#![no_std]
+#![deny(missing_docs)]
+#![cfg_attr(docsrs, feature(doc_cfg))]
+#![doc = include_str!("../README.md")]
+
+mod device;
+mod device_async;
+
+pub use device::Ds2438;
+pub use embedded_onewire::{OneWireError, OneWireResult};
+
+use bitfield_struct::bitfield;
+
+/// Family code of the DS2438, the low byte of its ROM.
+pub const FAMILY_CODE: u8 = 0x26;
+
+/// Number of memory pages.
+pub const PAGES: u8 = 8;
+/// Size of a memory page, in bytes.
+pub const PAGE_SIZE: usize = 8;
+/// Pages free for user data. Page 7 holds the charge accumulators instead when they are
+/// enabled with [`Config::current_accumulator`].
+pub const USER_PAGES: core::ops::RangeInclusive<u8> = 3..=7;
+
+pub(crate) const WRITE_SCRATCHPAD_CMD: u8 = 0x4e;
+pub(crate) const READ_SCRATCHPAD_CMD: u8 = 0xbe;
+pub(crate) const COPY_SCRATCHPAD_CMD: u8 = 0x48;
+pub(crate) const RECALL_MEMORY_CMD: u8 = 0xb8;
+pub(crate) const CONVERT_T_CMD: u8 = 0x44;
+pub(crate) const CONVERT_V_CMD: u8 = 0xb4;
+
+/// Maximum temperature conversion time, in microseconds.
+pub(crate) const CONVERT_T_US: u32 = 10_000;
+/// Maximum voltage conversion time, in microseconds.
+pub(crate) const CONVERT_V_US: u32 = 10_000;
+/// Maximum time for the scratchpad to be copied to the memory, in microseconds.
+pub(crate) const COPY_SCRATCHPAD_US: u32 = 10_000;
+
+#[bitfield(u8)]
+#[derive(PartialEq, Eq)]
+/// # Status/configuration register
+///
+/// The first byte of page 0. The configuration bits are stored in EEPROM, and read
+/// `0x0f` on a new device.
+pub struct Config {
+    /// IAD: Enables the current A/D converter, which measures the current through the sense
+    /// resistor 36.41 times per second.
+    pub current_ad: bool,
+    /// CA: Enables the charge accumulators in page 7, which integrate the charging and
+    /// discharging currents separately.
+    pub current_accumulator: bool,
+    /// EE: Enables shadowing the charge accumulators to EEPROM.
+    pub shadow_accumulator: bool,
+    /// AD: Selects the input of the voltage A/D converter, V<sub>DD</sub> if set, and
+    /// V<sub>AD</sub> otherwise.
+    pub voltage_vdd: bool,
+    /// TB: A temperature conversion is in progress.
+    #[bits(access = RO)]
+    pub temperature_busy: bool,
+    /// NVB: A copy of the scratchpad to EEPROM is in progress.
+    #[bits(access = RO)]
+    pub eeprom_busy: bool,
+    /// ADB: A voltage conversion is in progress.
+    #[bits(access = RO)]
+    pub adc_busy: bool,
+    #[bits(1)]
+    __: u8,
+}
+
+/// Input of the voltage A/D converter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VoltageInput {
+    /// The supply voltage, up to 10 V.
+    Vdd,
+    /// The general purpose input, 1.5 V to 2 V above the supply voltage at most.
+    Vad,
+}
+
+/// A temperature reading, in units of 1/256 °C with a resolution of 0.03125 °C.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Temperature(i16);
+
+impl Temperature {
+    /// Temperature from the raw register value.
+    pub fn from_raw(raw: i16) -> Self {
+        Temperature(raw)
+    }
+
+    /// The raw register value, in units of 1/256 °C.
+    pub fn raw(&self) -> i16 {
+        self.0
+    }
+
+    /// The temperature in thousandths of a degree Celsius, truncated.
+    pub fn millidegrees(&self) -> i32 {
+        (self.0 >> 3) as i32 * 3125 / 100
+    }
+
+    /// The temperature in degrees Celsius.
+    pub fn celsius(&self) -> f32 {
+        self.0 as f32 / 256.0
+    }
+}
+
+/// Measurements in page 0 of the DS2438.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Measurements {
+    /// The status/configuration register.
+    pub config: Config,
+    /// Result of the last temperature conversion.
+    pub temperature: Temperature,
+    /// Result of the last voltage conversion, in millivolts.
+    pub voltage_mv: u16,
+    /// Last current measurement, in units of 1 / (4096 R<sub>SENS</sub>) A.
+    pub current: i16,
+    /// Threshold below which current measurements are not accumulated, in the upper bits.
+    pub threshold: u8,
+}
+
+impl Measurements {
+    pub(crate) fn parse(page: &[u8; PAGE_SIZE]) -> Self {
+        Measurements {
+            config: Config::from_bits(page[0]),
+            temperature: Temperature(i16::from_le_bytes([page[1] & 0xf8, page[2]])),
+            voltage_mv: (u16::from_le_bytes([page[3], page[4]]) & 0x3ff) * 10,
+            current: i16::from_le_bytes([page[5], page[6]]),
+            threshold: page[7],
+        }
+    }
+
+    /// The current through a sense resistor of `sense_milliohm`, in microamperes.
+    pub fn current_microamps(&self, sense_milliohm: u32) -> i32 {
+        (self.current as i64 * 1_000_000_000 / (4096 * sense_milliohm as i64)) as i32
+    }
+}
+
+mod test {
+    #[test]
+    fn test_measurements() {
+        use super::*;
+        let page = [0x07, 0x10, 0x19, 0xf4, 0x01, 0x10, 0x00, 0x40];
+        let m = Measurements::parse(&page);
+        assert!(m.config.current_ad());
+        assert!(!m.config.voltage_vdd());
+        assert_eq!(m.temperature.celsius(), 25.0625);
+        assert_eq!(m.temperature.millidegrees(), 25_062);
+        assert_eq!(m.voltage_mv, 5000);
+        assert_eq!(m.current, 16);
+        // 16 / (4096 * 0.05 Ohm) = 78.125 mA
+        assert_eq!(m.current_microamps(50), 78_125);
+        // Examples from the datasheet
+        assert_eq!(Temperature::from_raw(0x7d00).celsius(), 125.0);
+        assert_eq!(Temperature::from_raw(0xe700_u16 as i16).celsius(), -25.0);
+        assert_eq!(
+            Temperature::from_raw(0xff80_u16 as i16).millidegrees(),
+            -500
+        );
+    }
+
+    #[test]
+    fn test_measure_voltage() {
+        use super::*;
+        use embedded_hal_mock::eh1::delay::NoopDelay;
+        use embedded_onewire::testing::{Mock, Transaction};
+        extern crate std;
+
+        let bus = Mock::new(&[
+            // Read the configuration
+            Transaction::reset(true),
+            Transaction::skip_rom(),
+            Transaction::write(&[RECALL_MEMORY_CMD, 0]),
+            Transaction::reset(true),
+            Transaction::skip_rom(),
+            Transaction::write(&[READ_SCRATCHPAD_CMD, 0]),
+            Transaction::read(&[0x0f, 0x10, 0x19, 0xf4, 0x01, 0x10, 0x00, 0x40, 0xf6]),
+            // Select VAD
+            Transaction::reset(true),
+            Transaction::skip_rom(),
+            Transaction::write(&[WRITE_SCRATCHPAD_CMD, 0, 0x07]),
+            Transaction::reset(true),
+            Transaction::skip_rom(),
+            Transaction::write(&[COPY_SCRATCHPAD_CMD, 0]),
+            // Convert
+            Transaction::reset(true),
+            Transaction::skip_rom(),
+            Transaction::write(&[CONVERT_V_CMD]),
+            Transaction::reset(true),
+            Transaction::skip_rom(),
+            Transaction::write(&[RECALL_MEMORY_CMD, 0]),
+            Transaction::reset(true),
+            Transaction::skip_rom(),
+            Transaction::write(&[READ_SCRATCHPAD_CMD, 0]),
+            Transaction::read(&[0x07, 0x10, 0x19, 0xc8, 0x00, 0x10, 0x00, 0x40, 0xf6]),
+        ]);
+        let mut monitor = Ds2438::new(bus, None);
+        let vad = monitor
+            .measure_voltage(VoltageInput::Vad, &mut NoopDelay::new())
+            .unwrap();
+        assert_eq!(vad, 2000);
+        monitor.release().done();
+    }
+
+    #[test]
+    fn test_write_page() {
+        use super::*;
+        use embedded_hal_mock::eh1::delay::NoopDelay;
+        use embedded_onewire::testing::{Mock, Transaction};
+        extern crate std;
+
+        let data = [1, 2, 3, 4, 5, 6, 7, 8];
+        let bus = Mock::new(&[
+            Transaction::reset(true),
+            Transaction::skip_rom(),
+            Transaction::write(&[WRITE_SCRATCHPAD_CMD, 3, 1, 2, 3, 4, 5, 6, 7, 8]),
+            Transaction::reset(true),
+            Transaction::skip_rom(),
+            Transaction::write(&[READ_SCRATCHPAD_CMD, 3]),
+            Transaction::read(&[1, 2, 3, 4, 5, 6, 7, 8, 0x83]),
+            Transaction::reset(true),
+            Transaction::skip_rom(),
+            Transaction::write(&[COPY_SCRATCHPAD_CMD, 3]),
+        ]);
+        let mut monitor = Ds2438::new(bus, None);
+        monitor.write_page(3, &data, &mut NoopDelay::new()).unwrap();
+        assert!(matches!(
+            monitor.write_page(1, &data, &mut NoopDelay::new()),
+            Err(OneWireError::InvalidValue(_))
+        ));
+        monitor.release().done();
+    }
+}
@@ -0,0 +1,218 @@
+use crate::{
+    ALARM_ADDR, Alarms, CHANNELS, CONTROL_ADDR, CONVERSION_ADDR, CONVERT_CMD, ChannelControl,
+    PAGE_SIZE, READ_MEMORY_CMD, VCC_CONTROL_ADDR, VCC_POWERED, WRITE_MEMORY_CMD, check_crc,
+    conversion_time_us,
+};
+use embedded_hal::delay::DelayNs;
+use embedded_onewire::{OneWire, OneWireError, OneWireResult};
+
+/// A DS2450 quad A/D converter on a 1-Wire bus.
+///
+/// The driver owns the bus master, which can be a `&mut` reference to one or a handle to a
+/// shared one. Every operation addresses the device first.
+#[derive(Debug)]
+pub struct Ds2450<O> {
+    pub(crate) bus: O,
+    pub(crate) rom: Option<u64>,
+}
+
+impl<O> Ds2450<O> {
+    /// Creates a driver for the device with ROM `rom` on `bus`.
+    ///
+    /// Pass [`None`] to skip ROM addressing on a bus with a single device.
+    pub fn new(bus: O, rom: Option<u64>) -> Self {
+        Ds2450 { bus, rom }
+    }
+
+    /// ROM of the device, if it is addressed by ROM.
+    pub fn rom(&self) -> Option<u64> {
+        self.rom
+    }
+
+    /// Release the bus master.
+    pub fn release(self) -> O {
+        self.bus
+    }
+}
+
+impl<O: OneWire> Ds2450<O> {
+    fn command(&mut self, cmd: &[u8]) -> OneWireResult<(), O::BusError> {
+        self.bus.address(self.rom)?;
+        self.bus.write_bytes(cmd)
+    }
+
+    fn read_page(&mut self, address: u16) -> OneWireResult<[u8; PAGE_SIZE], O::BusError> {
+        let [lo, hi] = address.to_le_bytes();
+        let cmd = [READ_MEMORY_CMD, lo, hi];
+        self.command(&cmd)?;
+        let mut page = [0; PAGE_SIZE];
+        self.bus.read_bytes(&mut page)?;
+        let mut crc = [0; 2];
+        self.bus.read_bytes(&mut crc)?;
+        check_crc(&[&cmd, &page], &crc)?;
+        Ok(page)
+    }
+
+    /// Write `data` at `address`. Every byte is checked with the CRC-16 sent back by the
+    /// device, and read back.
+    fn write_memory(&mut self, address: u16, data: &[u8]) -> OneWireResult<(), O::BusError> {
+        let [lo, hi] = address.to_le_bytes();
+        self.command(&[WRITE_MEMORY_CMD, lo, hi])?;
+        for (i, &byte) in data.iter().enumerate() {
+            self.bus.write_byte(byte)?;
+            let mut buf = [0; 3];
+            self.bus.read_bytes(&mut buf)?;
+            let crc = [buf[0], buf[1]];
+            // The CRC of the first byte includes the command, and the following ones the
+            // incremented address
+            if i == 0 {
+                check_crc(&[&[WRITE_MEMORY_CMD, lo, hi, byte]], &crc)?;
+            } else {
+                let [lo, hi] = (address + i as u16).to_le_bytes();
+                check_crc(&[&[lo, hi, byte]], &crc)?;
+            }
+            if buf[2] != byte {
+                return Err(OneWireError::VerificationFailed);
+            }
+        }
+        Ok(())
+    }
+
+    /// Read the control/status of the four channels.
+    ///
+    /// # Errors
+    /// Returns [`OneWireError::InvalidCrc`] if the CRC of the page does not match.
+    pub fn read_controls(
+        &mut self,
+    ) -> OneWireResult<[ChannelControl; CHANNELS as usize], O::BusError> {
+        let page = self.read_page(CONTROL_ADDR)?;
+        Ok(core::array::from_fn(|channel| {
+            ChannelControl::from_bits(u16::from_le_bytes([
+                page[2 * channel],
+                page[2 * channel + 1],
+            ]))
+        }))
+    }
+
+    /// Write the control/status of `channel`: its resolution, input range, output and
+    /// alarm enables.
+    ///
+    /// The device answers a search with [`OneWireSearchKind::Alarmed`] if an alarm flag
+    /// enabled in the control of a channel is set by a conversion, see
+    /// [`Ds2450::set_alarms`]. Clear [`ChannelControl::power_on_reset`] for the condition
+    /// to take effect.
+    ///
+    /// # Errors
+    /// Returns [`OneWireError::InvalidValue`] if `channel` does not exist, and
+    /// [`OneWireError::InvalidCrc`] or [`OneWireError::VerificationFailed`] if the write
+    /// is corrupted.
+    ///
+    /// [`OneWireSearchKind::Alarmed`]: embedded_onewire::OneWireSearchKind::Alarmed
+    pub fn set_control(
+        &mut self,
+        channel: u8,
+        control: ChannelControl,
+    ) -> OneWireResult<(), O::BusError> {
+        if channel >= CHANNELS {
+            return Err(OneWireError::InvalidValue("channel"));
+        }
+        let address = CONTROL_ADDR + 2 * channel as u16;
+        self.write_memory(address, &control.into_bits().to_le_bytes())
+    }
+
+    /// Read the alarm thresholds of the four channels.
+    ///
+    /// # Errors
+    /// Returns [`OneWireError::InvalidCrc`] if the CRC of the page does not match.
+    pub fn read_alarms(&mut self) -> OneWireResult<[Alarms; CHANNELS as usize], O::BusError> {
+        let page = self.read_page(ALARM_ADDR)?;
+        Ok(core::array::from_fn(|channel| Alarms {
+            low: page[2 * channel],
+            high: page[2 * channel + 1],
+        }))
+    }
+
+    /// Write the alarm thresholds of `channel`.
+    ///
+    /// A conversion of the channel sets its alarm flags, which make the device answer the
+    /// conditional search if they are enabled, see [`Ds2450::set_control`].
+    ///
+    /// # Errors
+    /// See [`Ds2450::set_control`].
+    pub fn set_alarms(&mut self, channel: u8, alarms: Alarms) -> OneWireResult<(), O::BusError> {
+        if channel >= CHANNELS {
+            return Err(OneWireError::InvalidValue("channel"));
+        }
+        let address = ALARM_ADDR + 2 * channel as u16;
+        self.write_memory(address, &[alarms.low, alarms.high])
+    }
+
+    /// Tell the device whether it is powered through its V<sub>CC</sub> pin, which keeps
+    /// its analog circuits on between conversions.
+    ///
+    /// # Errors
+    /// Returns [`OneWireError::InvalidCrc`] or [`OneWireError::VerificationFailed`] if the
+    /// write is corrupted.
+    pub fn set_vcc_powered(&mut self, powered: bool) -> OneWireResult<(), O::BusError> {
+        let control = if powered { VCC_POWERED } else { 0 };
+        self.write_memory(VCC_CONTROL_ADDR, &[control])
+    }
+
+    /// Start the conversion of the channels in `mask`, bit 0 for channel A.
+    ///
+    /// A parasitically powered device needs the bus to be held at the strong pullup for
+    /// the conversion, which this driver does not support.
+    ///
+    /// # Errors
+    /// Returns [`OneWireError::InvalidValue`] if `mask` selects a channel that does not
+    /// exist, and [`OneWireError::InvalidCrc`] if the CRC of the command does not match,
+    /// in which case the conversion does not start.
+    pub fn start_conversion(&mut self, mask: u8) -> OneWireResult<(), O::BusError> {
+        if mask >> CHANNELS != 0 {
+            return Err(OneWireError::InvalidValue("mask"));
+        }
+        // The results of the channels are not preset before the conversion
+        let cmd = [CONVERT_CMD, mask, 0x00];
+        self.command(&cmd)?;
+        let mut crc = [0; 2];
+        self.bus.read_bytes(&mut crc)?;
+        check_crc(&[&cmd], &crc)
+    }
+
+    /// Whether the conversion is done.
+    ///
+    /// The device answers read time slots with 0 during the conversion, right after
+    /// [`Ds2450::start_conversion`] and without a reset in between.
+    pub fn conversion_done(&mut self) -> OneWireResult<bool, O::BusError> {
+        self.bus.read_bit()
+    }
+
+    /// Read the last conversion results of the four channels, left-justified to 16 bits.
+    ///
+    /// # Errors
+    /// Returns [`OneWireError::InvalidCrc`] if the CRC of the page does not match.
+    pub fn read_conversions(&mut self) -> OneWireResult<[u16; CHANNELS as usize], O::BusError> {
+        let page = self.read_page(CONVERSION_ADDR)?;
+        Ok(core::array::from_fn(|channel| {
+            u16::from_le_bytes([page[2 * channel], page[2 * channel + 1]])
+        }))
+    }
+
+    /// Convert the channels in `mask`, wait for the conversion, and return their voltage
+    /// in millivolts.
+    ///
+    /// The channels not in `mask` are [`None`].
+    pub fn measure<D: DelayNs>(
+        &mut self,
+        mask: u8,
+        delay: &mut D,
+    ) -> OneWireResult<[Option<u32>; CHANNELS as usize], O::BusError> {
+        let controls = self.read_controls()?;
+        self.start_conversion(mask)?;
+        delay.delay_us(conversion_time_us(&controls, mask));
+        let results = self.read_conversions()?;
+        Ok(core::array::from_fn(|channel| {
+            (mask & (1 << channel) != 0).then(|| controls[channel].millivolts(results[channel]))
+        }))
+    }
+}
@@ -0,0 +1,173 @@
+use crate::{
+    ALARM_ADDR, Alarms, CHANNELS, CONTROL_ADDR, CONVERSION_ADDR, CONVERT_CMD, ChannelControl,
+    Ds2450, PAGE_SIZE, READ_MEMORY_CMD, VCC_CONTROL_ADDR, VCC_POWERED, WRITE_MEMORY_CMD, check_crc,
+    conversion_time_us,
+};
+use embedded_hal_async::delay::DelayNs;
+use embedded_onewire::{OneWireAsync, OneWireError, OneWireResult};
+
+impl<O: OneWireAsync> Ds2450<O> {
+    async fn command_async(&mut self, cmd: &[u8]) -> OneWireResult<(), O::BusError> {
+        OneWireAsync::address(&mut self.bus, self.rom).await?;
+        OneWireAsync::write_bytes(&mut self.bus, cmd).await
+    }
+
+    async fn read_page_async(
+        &mut self,
+        address: u16,
+    ) -> OneWireResult<[u8; PAGE_SIZE], O::BusError> {
+        let [lo, hi] = address.to_le_bytes();
+        let cmd = [READ_MEMORY_CMD, lo, hi];
+        self.command_async(&cmd).await?;
+        let mut page = [0; PAGE_SIZE];
+        OneWireAsync::read_bytes(&mut self.bus, &mut page).await?;
+        let mut crc = [0; 2];
+        OneWireAsync::read_bytes(&mut self.bus, &mut crc).await?;
+        check_crc(&[&cmd, &page], &crc)?;
+        Ok(page)
+    }
+
+    async fn write_memory_async(
+        &mut self,
+        address: u16,
+        data: &[u8],
+    ) -> OneWireResult<(), O::BusError> {
+        let [lo, hi] = address.to_le_bytes();
+        self.command_async(&[WRITE_MEMORY_CMD, lo, hi]).await?;
+        for (i, &byte) in data.iter().enumerate() {
+            OneWireAsync::write_byte(&mut self.bus, byte).await?;
+            let mut buf = [0; 3];
+            OneWireAsync::read_bytes(&mut self.bus, &mut buf).await?;
+            let crc = [buf[0], buf[1]];
+            if i == 0 {
+                check_crc(&[&[WRITE_MEMORY_CMD, lo, hi, byte]], &crc)?;
+            } else {
+                let [lo, hi] = (address + i as u16).to_le_bytes();
+                check_crc(&[&[lo, hi, byte]], &crc)?;
+            }
+            if buf[2] != byte {
+                return Err(OneWireError::VerificationFailed);
+            }
+        }
+        Ok(())
+    }
+
+    /// Read the control/status of the four channels.
+    ///
+    /// See [`Ds2450::read_controls`].
+    pub async fn read_controls_async(
+        &mut self,
+    ) -> OneWireResult<[ChannelControl; CHANNELS as usize], O::BusError> {
+        let page = self.read_page_async(CONTROL_ADDR).await?;
+        Ok(core::array::from_fn(|channel| {
+            ChannelControl::from_bits(u16::from_le_bytes([
+                page[2 * channel],
+                page[2 * channel + 1],
+            ]))
+        }))
+    }
+
+    /// Write the control/status of `channel`.
+    ///
+    /// See [`Ds2450::set_control`].
+    pub async fn set_control_async(
+        &mut self,
+        channel: u8,
+        control: ChannelControl,
+    ) -> OneWireResult<(), O::BusError> {
+        if channel >= CHANNELS {
+            return Err(OneWireError::InvalidValue("channel"));
+        }
+        let address = CONTROL_ADDR + 2 * channel as u16;
+        self.write_memory_async(address, &control.into_bits().to_le_bytes())
+            .await
+    }
+
+    /// Read the alarm thresholds of the four channels.
+    ///
+    /// See [`Ds2450::read_alarms`].
+    pub async fn read_alarms_async(
+        &mut self,
+    ) -> OneWireResult<[Alarms; CHANNELS as usize], O::BusError> {
+        let page = self.read_page_async(ALARM_ADDR).await?;
+        Ok(core::array::from_fn(|channel| Alarms {
+            low: page[2 * channel],
+            high: page[2 * channel + 1],
+        }))
+    }
+
+    /// Write the alarm thresholds of `channel`.
+    ///
+    /// See [`Ds2450::set_alarms`].
+    pub async fn set_alarms_async(
+        &mut self,
+        channel: u8,
+        alarms: Alarms,
+    ) -> OneWireResult<(), O::BusError> {
+        if channel >= CHANNELS {
+            return Err(OneWireError::InvalidValue("channel"));
+        }
+        let address = ALARM_ADDR + 2 * channel as u16;
+        self.write_memory_async(address, &[alarms.low, alarms.high])
+            .await
+    }
+
+    /// Tell the device whether it is powered through its V<sub>CC</sub> pin.
+    ///
+    /// See [`Ds2450::set_vcc_powered`].
+    pub async fn set_vcc_powered_async(&mut self, powered: bool) -> OneWireResult<(), O::BusError> {
+        let control = if powered { VCC_POWERED } else { 0 };
+        self.write_memory_async(VCC_CONTROL_ADDR, &[control]).await
+    }
+
+    /// Start the conversion of the channels in `mask`.
+    ///
+    /// See [`Ds2450::start_conversion`].
+    pub async fn start_conversion_async(&mut self, mask: u8) -> OneWireResult<(), O::BusError> {
+        if mask >> CHANNELS != 0 {
+            return Err(OneWireError::InvalidValue("mask"));
+        }
+        let cmd = [CONVERT_CMD, mask, 0x00];
+        self.command_async(&cmd).await?;
+        let mut crc = [0; 2];
+        OneWireAsync::read_bytes(&mut self.bus, &mut crc).await?;
+        check_crc(&[&cmd], &crc)
+    }
+
+    /// Whether the conversion is done.
+    ///
+    /// See [`Ds2450::conversion_done`].
+    pub async fn conversion_done_async(&mut self) -> OneWireResult<bool, O::BusError> {
+        OneWireAsync::read_bit(&mut self.bus).await
+    }
+
+    /// Read the last conversion results of the four channels.
+    ///
+    /// See [`Ds2450::read_conversions`].
+    pub async fn read_conversions_async(
+        &mut self,
+    ) -> OneWireResult<[u16; CHANNELS as usize], O::BusError> {
+        let page = self.read_page_async(CONVERSION_ADDR).await?;
+        Ok(core::array::from_fn(|channel| {
+            u16::from_le_bytes([page[2 * channel], page[2 * channel + 1]])
+        }))
+    }
+
+    /// Convert the channels in `mask`, wait for the conversion, and return their voltage
+    /// in millivolts.
+    ///
+    /// See [`Ds2450::measure`].
+    pub async fn measure_async<D: DelayNs>(
+        &mut self,
+        mask: u8,
+        delay: &mut D,
+    ) -> OneWireResult<[Option<u32>; CHANNELS as usize], O::BusError> {
+        let controls = self.read_controls_async().await?;
+        self.start_conversion_async(mask).await?;
+        delay.delay_us(conversion_time_us(&controls, mask)).await;
+        let results = self.read_conversions_async().await?;
+        Ok(core::array::from_fn(|channel| {
+            (mask & (1 << channel) != 0).then(|| controls[channel].millivolts(results[channel]))
+        }))
+    }
+}
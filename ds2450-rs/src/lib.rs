@@ -0,0 +1,216 @@
+#![no_std]
+#![deny(missing_docs)]
+#![cfg_attr(docsrs, feature(doc_cfg))]
+#![doc = include_str!("../README.md")]
+
+mod device;
+mod device_async;
+
+pub use device::Ds2450;
+pub use embedded_onewire::{OneWireError, OneWireResult};
+
+use bitfield_struct::bitfield;
+use embedded_onewire::OneWireCrc16;
+
+/// Family code of the DS2450, the low byte of its ROM.
+pub const FAMILY_CODE: u8 = 0x20;
+
+/// Number of channels.
+pub const CHANNELS: u8 = 4;
+
+pub(crate) const READ_MEMORY_CMD: u8 = 0xaa;
+pub(crate) const WRITE_MEMORY_CMD: u8 = 0x55;
+pub(crate) const CONVERT_CMD: u8 = 0x3c;
+
+/// Size of a page of the memory.
+pub(crate) const PAGE_SIZE: usize = 8;
+/// Address of the conversion read-out page.
+pub(crate) const CONVERSION_ADDR: u16 = 0x00;
+/// Address of the control/status page.
+pub(crate) const CONTROL_ADDR: u16 = 0x08;
+/// Address of the alarm settings page.
+pub(crate) const ALARM_ADDR: u16 = 0x10;
+/// Address of the V<sub>CC</sub> control byte, in the calibration page.
+pub(crate) const VCC_CONTROL_ADDR: u16 = 0x1c;
+/// Value of the V<sub>CC</sub> control byte for a device powered through its V<sub>CC</sub>
+/// pin.
+pub(crate) const VCC_POWERED: u8 = 0x40;
+
+/// Conversion time per bit of resolution and per channel, in microseconds.
+pub(crate) const CONVERSION_TIME_PER_BIT_US: u32 = 80;
+/// Fixed conversion overhead, in microseconds.
+pub(crate) const CONVERSION_OFFSET_US: u32 = 160;
+
+#[bitfield(u16)]
+#[derive(PartialEq, Eq)]
+/// # Control/status of a channel
+///
+/// The two bytes of the channel in the control/status page, the first one in the low byte.
+pub struct ChannelControl {
+    /// RC: Resolution of the conversion in bits, 1 to 15, or 0 for 16 bits.
+    #[bits(4)]
+    pub resolution_bits: u8,
+    #[bits(2)]
+    __: u8,
+    /// OC: Output latch of the channel used as an output. The pin is pulled low if false.
+    pub output_control: bool,
+    /// OE: Use the channel as an open-drain output instead of an input.
+    pub output_enable: bool,
+    /// IR: Input range of 5.12 V instead of 2.56 V.
+    pub range_5v12: bool,
+    #[bits(1)]
+    __: u8,
+    /// AEL: The device answers the conditional search if the low alarm flag is set.
+    pub alarm_low_enable: bool,
+    /// AEH: The device answers the conditional search if the high alarm flag is set.
+    pub alarm_high_enable: bool,
+    /// AFL: The last conversion was below the low alarm threshold.
+    #[bits(access = RO)]
+    pub alarm_low: bool,
+    /// AFH: The last conversion was above the high alarm threshold.
+    #[bits(access = RO)]
+    pub alarm_high: bool,
+    #[bits(1)]
+    __: u8,
+    /// POR: Set at power-on, and cleared by writing 0. The device answers every
+    /// conditional search while it is set.
+    pub power_on_reset: bool,
+}
+
+impl ChannelControl {
+    /// Resolution of the conversion in bits.
+    pub fn resolution(&self) -> u32 {
+        match self.resolution_bits() {
+            0 => 16,
+            bits => bits as u32,
+        }
+    }
+
+    /// Full scale of the input range in millivolts.
+    pub fn full_scale_millivolts(&self) -> u32 {
+        if self.range_5v12() { 5120 } else { 2560 }
+    }
+
+    /// Convert a conversion result of the channel to millivolts.
+    ///
+    /// The results are left-justified to 16 bits whatever the resolution.
+    pub fn millivolts(&self, raw: u16) -> u32 {
+        (raw as u32 * self.full_scale_millivolts()) >> 16
+    }
+}
+
+/// Alarm thresholds of a channel.
+///
+/// The thresholds are compared to the most significant byte of the conversion result.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Alarms {
+    /// Low alarm threshold.
+    pub low: u8,
+    /// High alarm threshold.
+    pub high: u8,
+}
+
+/// Maximum duration of the conversion of the channels in `mask` with `controls`, in
+/// microseconds.
+pub(crate) fn conversion_time_us(controls: &[ChannelControl; CHANNELS as usize], mask: u8) -> u32 {
+    let bits: u32 = controls
+        .iter()
+        .enumerate()
+        .filter(|&(channel, _)| mask & (1 << channel) != 0)
+        .map(|(_, control)| control.resolution())
+        .sum();
+    bits * CONVERSION_TIME_PER_BIT_US + CONVERSION_OFFSET_US
+}
+
+/// Check the CRC-16 sent by the device after `bytes`.
+pub(crate) fn check_crc<E>(bytes: &[&[u8]], crc: &[u8; 2]) -> OneWireResult<(), E> {
+    let mut check = OneWireCrc16::default();
+    bytes.iter().for_each(|bytes| check.update_slice(bytes));
+    check.update_slice(crc);
+    if !check.is_valid() {
+        return Err(OneWireError::InvalidCrc);
+    }
+    Ok(())
+}
+
+mod test {
+    #[test]
+    fn test_channel_control() {
+        use super::*;
+        let control = ChannelControl::from_bits(0x8d08);
+        assert_eq!(control.resolution(), 8);
+        assert!(control.range_5v12());
+        assert!(control.alarm_high_enable());
+        assert!(!control.alarm_low());
+        assert!(control.power_on_reset());
+        assert_eq!(control.millivolts(0x8000), 2560);
+        assert_eq!(ChannelControl::new().resolution(), 16);
+        assert_eq!(ChannelControl::new().millivolts(0xffff), 2559);
+        let controls = [ChannelControl::new().with_resolution_bits(8); CHANNELS as usize];
+        assert_eq!(conversion_time_us(&controls, 0b0101), 16 * 80 + 160);
+    }
+
+    #[test]
+    fn test_measure() {
+        use super::*;
+        use embedded_hal_mock::eh1::delay::NoopDelay;
+        use embedded_onewire::testing::{Mock, Transaction};
+        extern crate std;
+
+        let bus = Mock::new(&[
+            Transaction::reset(true),
+            Transaction::skip_rom(),
+            Transaction::write(&[READ_MEMORY_CMD, 0x08, 0x00]),
+            Transaction::read(&[0x08, 0x01, 0x08, 0x00, 0x08, 0x00, 0x08, 0x00]),
+            Transaction::read(&[0x48, 0x41]),
+            Transaction::reset(true),
+            Transaction::skip_rom(),
+            Transaction::write(&[CONVERT_CMD, 0x01, 0x00]),
+            Transaction::read(&[0x3e, 0x63]),
+            Transaction::reset(true),
+            Transaction::skip_rom(),
+            Transaction::write(&[READ_MEMORY_CMD, 0x00, 0x00]),
+            Transaction::read(&[0x00, 0x80, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00]),
+            Transaction::read(&[0x5d, 0xed]),
+        ]);
+        let mut adc = Ds2450::new(bus, None);
+        let voltages = adc.measure(0x01, &mut NoopDelay::new()).unwrap();
+        assert_eq!(voltages, [Some(2560), None, None, None]);
+        adc.release().done();
+    }
+
+    #[test]
+    fn test_set_alarms() {
+        use super::*;
+        use embedded_onewire::testing::{Mock, Transaction};
+        extern crate std;
+
+        let bus = Mock::new(&[
+            Transaction::reset(true),
+            Transaction::skip_rom(),
+            Transaction::write(&[WRITE_MEMORY_CMD, 0x12, 0x00, 0x10]),
+            Transaction::read(&[0x4f, 0xfa, 0x10]),
+            Transaction::write(&[0xf0]),
+            Transaction::read(&[0x0e, 0x7e, 0xf0]),
+            Transaction::reset(true),
+            Transaction::skip_rom(),
+            Transaction::write(&[WRITE_MEMORY_CMD, 0x12, 0x00, 0x10]),
+            Transaction::read(&[0x4f, 0xfa, 0x11]),
+        ]);
+        let mut adc = Ds2450::new(bus, None);
+        let alarms = Alarms {
+            low: 0x10,
+            high: 0xf0,
+        };
+        adc.set_alarms(1, alarms).unwrap();
+        assert!(matches!(
+            adc.set_alarms(1, alarms),
+            Err(OneWireError::VerificationFailed)
+        ));
+        assert!(matches!(
+            adc.set_alarms(4, alarms),
+            Err(OneWireError::InvalidValue(_))
+        ));
+        adc.release().done();
+    }
+}
@@ -0,0 +1,292 @@
+use crate::{
+    CHUNK, COMMAND_MODE_CMD, CONFIGURATION, DATA_MODE_CMD, Ds2480bError, Ds2480bResult,
+    OVERDRIVE_BITS, RESET_CMD, ResetStatus, SEARCH_ACCELERATOR_OFF_CMD, SEARCH_ACCELERATOR_ON_CMD,
+    STOP_PULSE_CMD, Speed, bit_cmd, bit_response, escape, pulse_response,
+};
+use embedded_hal::delay::DelayNs;
+use embedded_io::{Read, Write};
+use embedded_onewire::{
+    OneWire, OneWireError, OneWireResult, OneWireStatus, OneWireStrongPullup,
+    consts::ONEWIRE_SKIP_ROM_CMD_OD,
+};
+
+/// Time for the DS2480B to calibrate on the timing byte, in microseconds.
+pub(crate) const CALIBRATION_US: u32 = 4_000;
+
+/// A DS2480B serial to 1-Wire line driver.
+///
+/// The DS2480B is driven over a serial port at 9600 baud, 8 data bits, no parity and one
+/// stop bit. It starts in command mode, where every byte is a command for the line driver,
+/// and switches to data mode to write and read whole bytes on the 1-Wire line. The driver
+/// keeps track of the mode, and switches only when needed.
+#[derive(Debug)]
+pub struct Ds2480b<S> {
+    pub(crate) serial: S,
+    pub(crate) speed: Speed,
+    pub(crate) overdrive: bool,
+    pub(crate) data_mode: bool,
+    pub(crate) pulse: bool,
+}
+
+impl<S> Ds2480b<S> {
+    /// Creates a driver for the DS2480B on `serial`, communicating at `speed` outside of
+    /// overdrive.
+    ///
+    /// The DS2480B must be initialized with [`Ds2480b::init`] after power-up.
+    pub fn new(serial: S, speed: Speed) -> Self {
+        Ds2480b {
+            serial,
+            speed,
+            overdrive: false,
+            data_mode: false,
+            pulse: false,
+        }
+    }
+
+    /// Speed of the 1-Wire communication outside of overdrive.
+    pub fn speed(&self) -> Speed {
+        self.speed
+    }
+
+    /// Release the serial port.
+    pub fn release(self) -> S {
+        self.serial
+    }
+
+    /// Speed bits of the commands.
+    pub(crate) fn speed_bits(&self) -> u8 {
+        if self.overdrive {
+            OVERDRIVE_BITS
+        } else {
+            self.speed.bits()
+        }
+    }
+}
+
+impl<S: Read + Write> Ds2480b<S> {
+    /// Initialize the DS2480B after power-up.
+    ///
+    /// Sends the timing byte the DS2480B calibrates its baud rate on, and configures the
+    /// timing of the flexible speed and a strong pullup that lasts until it is terminated.
+    ///
+    /// # Errors
+    /// Returns [`Ds2480bError::InvalidResponse`] if the DS2480B does not acknowledge the
+    /// configuration, e.g. because it was not reset by a break on the serial line.
+    pub fn init<D: DelayNs>(&mut self, delay: &mut D) -> Ds2480bResult<(), S::Error> {
+        self.send(&[RESET_CMD])?;
+        delay.delay_us(CALIBRATION_US);
+        self.data_mode = false;
+        self.pulse = false;
+        self.overdrive = false;
+        self.send(&CONFIGURATION)?;
+        let mut responses = [0; CONFIGURATION.len()];
+        self.receive(&mut responses)?;
+        for (&cmd, &response) in CONFIGURATION.iter().zip(responses.iter()) {
+            // The response is the command without its lowest bit
+            if response != cmd & !1 {
+                return Err(Ds2480bError::InvalidResponse(response));
+            }
+        }
+        Ok(())
+    }
+
+    fn send(&mut self, bytes: &[u8]) -> Ds2480bResult<(), S::Error> {
+        self.serial.write_all(bytes)?;
+        Ok(self.serial.flush()?)
+    }
+
+    fn receive(&mut self, buf: &mut [u8]) -> Ds2480bResult<(), S::Error> {
+        Ok(self.serial.read_exact(buf)?)
+    }
+
+    fn command_mode(&mut self) -> Ds2480bResult<(), S::Error> {
+        if self.data_mode {
+            self.send(&[COMMAND_MODE_CMD])?;
+            self.data_mode = false;
+        }
+        Ok(())
+    }
+
+    fn data_mode(&mut self) -> Ds2480bResult<(), S::Error> {
+        if !self.data_mode {
+            self.send(&[DATA_MODE_CMD])?;
+            self.data_mode = true;
+        }
+        Ok(())
+    }
+
+    /// Terminate the strong pullup started by [`OneWireStrongPullup::write_byte_powered`].
+    fn end_pulse(&mut self) -> Ds2480bResult<(), S::Error> {
+        if self.pulse {
+            self.command_mode()?;
+            self.send(&[STOP_PULSE_CMD])?;
+            let mut response = [0];
+            self.receive(&mut response)?;
+            self.pulse = false;
+            pulse_response(response[0])?;
+        }
+        Ok(())
+    }
+
+    /// Write up to [`CHUNK`] bytes of `data` on the 1-Wire line, reading the bytes seen on
+    /// the line into `buf`.
+    fn transfer(&mut self, data: &[u8], buf: &mut [u8]) -> Ds2480bResult<(), S::Error> {
+        self.end_pulse()?;
+        self.data_mode()?;
+        let mut tx = [0; 2 * CHUNK];
+        let len = escape(data, &mut tx);
+        self.send(&tx[..len])?;
+        self.receive(buf)
+    }
+
+    /// Write and read a single bit.
+    fn bit(&mut self, bit: bool) -> Ds2480bResult<bool, S::Error> {
+        self.end_pulse()?;
+        self.command_mode()?;
+        self.send(&[bit_cmd(self.speed_bits(), bit, false)])?;
+        let mut response = [0];
+        self.receive(&mut response)?;
+        bit_response(response[0])
+    }
+
+    /// Run a pass of the search accelerator along `path`, and return the discrepancies and
+    /// the ROM found.
+    ///
+    /// The Search ROM command must have been sent already.
+    pub(crate) fn accelerate(
+        &mut self,
+        path: &[u8; CHUNK],
+    ) -> Ds2480bResult<[u8; CHUNK], S::Error> {
+        self.end_pulse()?;
+        self.command_mode()?;
+        self.send(&[SEARCH_ACCELERATOR_ON_CMD | (self.speed_bits() << 2)])?;
+        let mut found = [0; CHUNK];
+        self.transfer(path, &mut found)?;
+        self.command_mode()?;
+        self.send(&[SEARCH_ACCELERATOR_OFF_CMD | (self.speed_bits() << 2)])?;
+        Ok(found)
+    }
+}
+
+impl<S: Read + Write> OneWire for Ds2480b<S> {
+    type Status = ResetStatus;
+
+    type BusError = Ds2480bError<S::Error>;
+
+    fn reset(&mut self) -> OneWireResult<Self::Status, Self::BusError> {
+        self.end_pulse()?;
+        self.command_mode()?;
+        self.send(&[RESET_CMD | (self.speed_bits() << 2)])?;
+        let mut response = [0];
+        self.receive(&mut response)?;
+        let status = ResetStatus::parse::<S::Error>(response[0])?;
+        if status.shortcircuit() {
+            Err(OneWireError::ShortCircuit)
+        } else if !status.presence() {
+            Err(OneWireError::NoDevicePresent)
+        } else {
+            Ok(status)
+        }
+    }
+
+    fn write_byte(&mut self, byte: u8) -> OneWireResult<(), Self::BusError> {
+        Ok(self.transfer(&[byte], &mut [0])?)
+    }
+
+    fn read_byte(&mut self) -> OneWireResult<u8, Self::BusError> {
+        let mut byte = [0];
+        self.transfer(&[0xff], &mut byte)?;
+        Ok(byte[0])
+    }
+
+    fn write_bytes(&mut self, bytes: &[u8]) -> OneWireResult<(), Self::BusError> {
+        for chunk in bytes.chunks(CHUNK) {
+            let mut echo = [0; CHUNK];
+            self.transfer(chunk, &mut echo[..chunk.len()])?;
+        }
+        Ok(())
+    }
+
+    fn read_bytes(&mut self, buf: &mut [u8]) -> OneWireResult<(), Self::BusError> {
+        for chunk in buf.chunks_mut(CHUNK) {
+            self.transfer(&[0xff; CHUNK][..chunk.len()], chunk)?;
+        }
+        Ok(())
+    }
+
+    fn write_bit(&mut self, bit: bool) -> OneWireResult<(), Self::BusError> {
+        self.bit(bit)?;
+        Ok(())
+    }
+
+    fn read_bit(&mut self) -> OneWireResult<bool, Self::BusError> {
+        Ok(self.bit(true)?)
+    }
+
+    #[cfg(feature = "triplet-read")]
+    fn read_triplet(
+        &mut self,
+        direction: bool,
+    ) -> OneWireResult<(bool, bool, bool), Self::BusError> {
+        self.end_pulse()?;
+        self.command_mode()?;
+        // Both read slots in one serial transfer
+        let read = bit_cmd(self.speed_bits(), true, false);
+        self.send(&[read, read])?;
+        let mut responses = [0; 2];
+        self.receive(&mut responses)?;
+        let id_bit = bit_response::<S::Error>(responses[0])?;
+        let complement_bit = bit_response::<S::Error>(responses[1])?;
+        if id_bit && complement_bit {
+            // No device responded
+            return Ok((true, true, true));
+        }
+        let taken = if id_bit != complement_bit {
+            id_bit
+        } else {
+            direction
+        };
+        self.bit(taken)?;
+        Ok((id_bit, complement_bit, taken))
+    }
+
+    fn get_overdrive_mode(&mut self) -> bool {
+        self.overdrive
+    }
+
+    fn set_overdrive_mode(&mut self, enable: bool) -> OneWireResult<(), Self::BusError> {
+        if enable == self.overdrive {
+            return Ok(()); // No change needed
+        }
+        if enable {
+            self.reset()?;
+            self.write_byte(ONEWIRE_SKIP_ROM_CMD_OD)?;
+            self.overdrive = true;
+        } else {
+            self.overdrive = false;
+            // A reset at standard speed returns the devices to standard speed
+            self.reset()?;
+        }
+        Ok(())
+    }
+}
+
+impl<S: Read + Write> OneWireStrongPullup for Ds2480b<S> {
+    fn write_byte_powered(&mut self, byte: u8) -> OneWireResult<(), Self::BusError> {
+        self.end_pulse()?;
+        self.command_mode()?;
+        // The byte is written bit by bit, to arm the strong pullup after the last bit
+        let mut cmds = [0; 8];
+        for (i, cmd) in cmds.iter_mut().enumerate() {
+            *cmd = bit_cmd(self.speed_bits(), byte & (1 << i) != 0, i == 7);
+        }
+        self.send(&cmds)?;
+        let mut responses = [0; 8];
+        self.receive(&mut responses)?;
+        self.pulse = true;
+        for response in responses {
+            bit_response::<S::Error>(response)?;
+        }
+        Ok(())
+    }
+}
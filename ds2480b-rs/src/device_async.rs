@@ -0,0 +1,229 @@
+use crate::{
+    CHUNK, COMMAND_MODE_CMD, CONFIGURATION, DATA_MODE_CMD, Ds2480b, Ds2480bError, Ds2480bResult,
+    RESET_CMD, ResetStatus, SEARCH_ACCELERATOR_OFF_CMD, SEARCH_ACCELERATOR_ON_CMD, STOP_PULSE_CMD,
+    bit_cmd, bit_response, device::CALIBRATION_US, escape, pulse_response,
+};
+use embedded_hal_async::delay::DelayNs as DelayNsAsync;
+use embedded_io_async::{Read, Write};
+use embedded_onewire::{
+    OneWireAsync, OneWireError, OneWireResult, OneWireStatus, OneWireStrongPullupAsync,
+    consts::ONEWIRE_SKIP_ROM_CMD_OD,
+};
+
+impl<S: Read + Write> Ds2480b<S> {
+    /// Initialize the DS2480B after power-up.
+    ///
+    /// See [`Ds2480b::init`].
+    pub async fn init_async<D: DelayNsAsync>(
+        &mut self,
+        delay: &mut D,
+    ) -> Ds2480bResult<(), S::Error> {
+        self.send_async(&[RESET_CMD]).await?;
+        delay.delay_us(CALIBRATION_US).await;
+        self.data_mode = false;
+        self.pulse = false;
+        self.overdrive = false;
+        self.send_async(&CONFIGURATION).await?;
+        let mut responses = [0; CONFIGURATION.len()];
+        self.receive_async(&mut responses).await?;
+        for (&cmd, &response) in CONFIGURATION.iter().zip(responses.iter()) {
+            if response != cmd & !1 {
+                return Err(Ds2480bError::InvalidResponse(response));
+            }
+        }
+        Ok(())
+    }
+
+    async fn send_async(&mut self, bytes: &[u8]) -> Ds2480bResult<(), S::Error> {
+        self.serial.write_all(bytes).await?;
+        Ok(self.serial.flush().await?)
+    }
+
+    async fn receive_async(&mut self, buf: &mut [u8]) -> Ds2480bResult<(), S::Error> {
+        Ok(self.serial.read_exact(buf).await?)
+    }
+
+    async fn command_mode_async(&mut self) -> Ds2480bResult<(), S::Error> {
+        if self.data_mode {
+            self.send_async(&[COMMAND_MODE_CMD]).await?;
+            self.data_mode = false;
+        }
+        Ok(())
+    }
+
+    async fn data_mode_async(&mut self) -> Ds2480bResult<(), S::Error> {
+        if !self.data_mode {
+            self.send_async(&[DATA_MODE_CMD]).await?;
+            self.data_mode = true;
+        }
+        Ok(())
+    }
+
+    async fn end_pulse_async(&mut self) -> Ds2480bResult<(), S::Error> {
+        if self.pulse {
+            self.command_mode_async().await?;
+            self.send_async(&[STOP_PULSE_CMD]).await?;
+            let mut response = [0];
+            self.receive_async(&mut response).await?;
+            self.pulse = false;
+            pulse_response(response[0])?;
+        }
+        Ok(())
+    }
+
+    async fn transfer_async(&mut self, data: &[u8], buf: &mut [u8]) -> Ds2480bResult<(), S::Error> {
+        self.end_pulse_async().await?;
+        self.data_mode_async().await?;
+        let mut tx = [0; 2 * CHUNK];
+        let len = escape(data, &mut tx);
+        self.send_async(&tx[..len]).await?;
+        self.receive_async(buf).await
+    }
+
+    async fn bit_async(&mut self, bit: bool) -> Ds2480bResult<bool, S::Error> {
+        self.end_pulse_async().await?;
+        self.command_mode_async().await?;
+        self.send_async(&[bit_cmd(self.speed_bits(), bit, false)])
+            .await?;
+        let mut response = [0];
+        self.receive_async(&mut response).await?;
+        bit_response(response[0])
+    }
+
+    pub(crate) async fn accelerate_async(
+        &mut self,
+        path: &[u8; CHUNK],
+    ) -> Ds2480bResult<[u8; CHUNK], S::Error> {
+        self.end_pulse_async().await?;
+        self.command_mode_async().await?;
+        self.send_async(&[SEARCH_ACCELERATOR_ON_CMD | (self.speed_bits() << 2)])
+            .await?;
+        let mut found = [0; CHUNK];
+        self.transfer_async(path, &mut found).await?;
+        self.command_mode_async().await?;
+        self.send_async(&[SEARCH_ACCELERATOR_OFF_CMD | (self.speed_bits() << 2)])
+            .await?;
+        Ok(found)
+    }
+}
+
+impl<S: Read + Write> OneWireAsync for Ds2480b<S> {
+    type Status = ResetStatus;
+
+    type BusError = Ds2480bError<S::Error>;
+
+    async fn reset(&mut self) -> OneWireResult<Self::Status, Self::BusError> {
+        self.end_pulse_async().await?;
+        self.command_mode_async().await?;
+        self.send_async(&[RESET_CMD | (self.speed_bits() << 2)])
+            .await?;
+        let mut response = [0];
+        self.receive_async(&mut response).await?;
+        let status = ResetStatus::parse::<S::Error>(response[0])?;
+        if status.shortcircuit() {
+            Err(OneWireError::ShortCircuit)
+        } else if !status.presence() {
+            Err(OneWireError::NoDevicePresent)
+        } else {
+            Ok(status)
+        }
+    }
+
+    async fn write_byte(&mut self, byte: u8) -> OneWireResult<(), Self::BusError> {
+        Ok(self.transfer_async(&[byte], &mut [0]).await?)
+    }
+
+    async fn read_byte(&mut self) -> OneWireResult<u8, Self::BusError> {
+        let mut byte = [0];
+        self.transfer_async(&[0xff], &mut byte).await?;
+        Ok(byte[0])
+    }
+
+    async fn write_bytes(&mut self, bytes: &[u8]) -> OneWireResult<(), Self::BusError> {
+        for chunk in bytes.chunks(CHUNK) {
+            let mut echo = [0; CHUNK];
+            self.transfer_async(chunk, &mut echo[..chunk.len()]).await?;
+        }
+        Ok(())
+    }
+
+    async fn read_bytes(&mut self, buf: &mut [u8]) -> OneWireResult<(), Self::BusError> {
+        for chunk in buf.chunks_mut(CHUNK) {
+            self.transfer_async(&[0xff; CHUNK][..chunk.len()], chunk)
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn write_bit(&mut self, bit: bool) -> OneWireResult<(), Self::BusError> {
+        self.bit_async(bit).await?;
+        Ok(())
+    }
+
+    async fn read_bit(&mut self) -> OneWireResult<bool, Self::BusError> {
+        Ok(self.bit_async(true).await?)
+    }
+
+    #[cfg(feature = "triplet-read")]
+    async fn read_triplet(
+        &mut self,
+        direction: bool,
+    ) -> OneWireResult<(bool, bool, bool), Self::BusError> {
+        self.end_pulse_async().await?;
+        self.command_mode_async().await?;
+        let read = bit_cmd(self.speed_bits(), true, false);
+        self.send_async(&[read, read]).await?;
+        let mut responses = [0; 2];
+        self.receive_async(&mut responses).await?;
+        let id_bit = bit_response::<S::Error>(responses[0])?;
+        let complement_bit = bit_response::<S::Error>(responses[1])?;
+        if id_bit && complement_bit {
+            return Ok((true, true, true));
+        }
+        let taken = if id_bit != complement_bit {
+            id_bit
+        } else {
+            direction
+        };
+        self.bit_async(taken).await?;
+        Ok((id_bit, complement_bit, taken))
+    }
+
+    fn get_overdrive_mode(&mut self) -> bool {
+        self.overdrive
+    }
+
+    async fn set_overdrive_mode(&mut self, enable: bool) -> OneWireResult<(), Self::BusError> {
+        if enable == self.overdrive {
+            return Ok(());
+        }
+        if enable {
+            OneWireAsync::reset(self).await?;
+            OneWireAsync::write_byte(self, ONEWIRE_SKIP_ROM_CMD_OD).await?;
+            self.overdrive = true;
+        } else {
+            self.overdrive = false;
+            OneWireAsync::reset(self).await?;
+        }
+        Ok(())
+    }
+}
+
+impl<S: Read + Write> OneWireStrongPullupAsync for Ds2480b<S> {
+    async fn write_byte_powered(&mut self, byte: u8) -> OneWireResult<(), Self::BusError> {
+        self.end_pulse_async().await?;
+        self.command_mode_async().await?;
+        let mut cmds = [0; 8];
+        for (i, cmd) in cmds.iter_mut().enumerate() {
+            *cmd = bit_cmd(self.speed_bits(), byte & (1 << i) != 0, i == 7);
+        }
+        self.send_async(&cmds).await?;
+        let mut responses = [0; 8];
+        self.receive_async(&mut responses).await?;
+        self.pulse = true;
+        for response in responses {
+            bit_response::<S::Error>(response)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,47 @@
+#[derive(Debug)]
+/// DS2480B Hardware Errors
+pub enum Ds2480bError<E> {
+    /// Serial port errors.
+    Serial(E),
+    /// The serial port reached its end before the response of the DS2480B.
+    UnexpectedEof,
+    /// The DS2480B sent a response that does not match the command, e.g. because it lost
+    /// track of its mode or its baud rate.
+    InvalidResponse(u8),
+}
+
+impl<E> From<E> for Ds2480bError<E> {
+    fn from(value: E) -> Self {
+        Self::Serial(value)
+    }
+}
+
+impl<E> From<embedded_io::ReadExactError<E>> for Ds2480bError<E> {
+    fn from(value: embedded_io::ReadExactError<E>) -> Self {
+        match value {
+            embedded_io::ReadExactError::UnexpectedEof => Self::UnexpectedEof,
+            embedded_io::ReadExactError::Other(e) => Self::Serial(e),
+        }
+    }
+}
+
+impl<E: core::fmt::Display> core::fmt::Display for Ds2480bError<E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Serial(e) => write!(f, "serial port error: {e}"),
+            Self::UnexpectedEof => write!(f, "serial port closed before the DS2480B responded"),
+            Self::InvalidResponse(byte) => {
+                write!(f, "invalid response {byte:#04x} from the DS2480B")
+            }
+        }
+    }
+}
+
+impl<E: core::error::Error + 'static> core::error::Error for Ds2480bError<E> {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            Self::Serial(e) => Some(e),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,278 @@
+#![no_std]
+#![deny(missing_docs)]
+#![cfg_attr(docsrs, feature(doc_cfg))]
+#![doc = include_str!("../README.md")]
+
+pub use embedded_onewire::{
+    OneWire, OneWireAsync, OneWireError, OneWireResult, OneWireSearchKind, OneWireStrongPullup,
+    OneWireStrongPullupAsync,
+};
+mod device;
+mod device_async;
+mod error;
+mod search;
+
+pub use device::Ds2480b;
+pub use error::Ds2480bError;
+pub use search::AcceleratedSearch;
+
+/// Results of DS2480B-specific function calls.
+pub type Ds2480bResult<T, E> = Result<T, Ds2480bError<E>>;
+
+/// Switches the DS2480B from command mode to data mode.
+pub(crate) const DATA_MODE_CMD: u8 = 0xe1;
+/// Switches the DS2480B from data mode to command mode. Sent twice in data mode to write
+/// the byte itself.
+pub(crate) const COMMAND_MODE_CMD: u8 = 0xe3;
+/// Reset command, ORed with the speed.
+pub(crate) const RESET_CMD: u8 = 0xc1;
+/// Single bit command, ORed with the speed, the bit and [`STRONG_PULLUP_BIT`].
+pub(crate) const SINGLE_BIT_CMD: u8 = 0x81;
+/// Arms the strong pullup at the end of a single bit command.
+pub(crate) const STRONG_PULLUP_BIT: u8 = 0x02;
+/// Terminates the strong pullup.
+pub(crate) const STOP_PULSE_CMD: u8 = 0xf1;
+/// Turns the search accelerator on, ORed with the speed.
+pub(crate) const SEARCH_ACCELERATOR_ON_CMD: u8 = 0xb1;
+/// Turns the search accelerator off, ORed with the speed.
+pub(crate) const SEARCH_ACCELERATOR_OFF_CMD: u8 = 0xa1;
+
+/// Configuration written by [`Ds2480b::init`]: a pulldown slew rate of 1.37 V/µs, a write-1
+/// low time of 10 µs, a data sample offset of 8 µs, and a strong pullup that lasts until it
+/// is terminated.
+pub(crate) const CONFIGURATION: [u8; 4] = [0x17, 0x45, 0x5b, 0x3f];
+
+/// Speed of the 1-Wire communication outside of overdrive.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Speed {
+    /// Standard speed, with the timing of the 1-Wire specification.
+    #[default]
+    Standard,
+    /// Flexible speed, with the slew rate and the timing set by [`Ds2480b::init`], for long
+    /// lines and large networks.
+    Flexible,
+}
+
+impl Speed {
+    pub(crate) fn bits(&self) -> u8 {
+        match self {
+            Speed::Standard => 0b00,
+            Speed::Flexible => 0b01,
+        }
+    }
+}
+
+/// Speed bits of the overdrive speed.
+pub(crate) const OVERDRIVE_BITS: u8 = 0b10;
+
+/// Outcome of a 1-Wire reset, from the response of the DS2480B.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ResetStatus(u8);
+
+impl ResetStatus {
+    /// Parse the response to a reset command.
+    pub(crate) fn parse<E>(response: u8) -> Ds2480bResult<Self, E> {
+        if response & 0xe0 != 0xc0 {
+            return Err(Ds2480bError::InvalidResponse(response));
+        }
+        Ok(ResetStatus(response & 0x03))
+    }
+
+    /// Whether the presence pulse came with an alarm, signaled by some devices.
+    pub fn alarm(&self) -> bool {
+        self.0 == 0b10
+    }
+}
+
+impl embedded_onewire::OneWireStatus for ResetStatus {
+    fn presence(&self) -> bool {
+        self.0 == 0b01 || self.0 == 0b10
+    }
+
+    fn shortcircuit(&self) -> bool {
+        self.0 == 0b00
+    }
+}
+
+/// Command byte of a single bit at `speed`.
+pub(crate) fn bit_cmd(speed: u8, bit: bool, strong_pullup: bool) -> u8 {
+    let mut cmd = SINGLE_BIT_CMD | (speed << 2);
+    if bit {
+        cmd |= 0x10;
+    }
+    if strong_pullup {
+        cmd |= STRONG_PULLUP_BIT;
+    }
+    cmd
+}
+
+/// Parse the response to a single bit command, the bit read.
+pub(crate) fn bit_response<E>(response: u8) -> Ds2480bResult<bool, E> {
+    if response & 0xe0 != 0x80 {
+        return Err(Ds2480bError::InvalidResponse(response));
+    }
+    Ok(response & 0x01 != 0)
+}
+
+/// Check the response to a command that terminates the strong pullup.
+pub(crate) fn pulse_response<E>(response: u8) -> Ds2480bResult<(), E> {
+    if response & 0xe0 != 0xe0 {
+        return Err(Ds2480bError::InvalidResponse(response));
+    }
+    Ok(())
+}
+
+/// Escape `data` to be sent in data mode into `buf`, returning the length used.
+///
+/// `buf` must be twice as long as `data`.
+pub(crate) fn escape(data: &[u8], buf: &mut [u8]) -> usize {
+    let mut len = 0;
+    for &byte in data {
+        buf[len] = byte;
+        len += 1;
+        if byte == COMMAND_MODE_CMD {
+            buf[len] = byte;
+            len += 1;
+        }
+    }
+    len
+}
+
+/// Number of bytes sent at once in data mode.
+pub(crate) const CHUNK: usize = 16;
+
+mod test {
+    extern crate std;
+
+    /// A serial port replaying `rx`, and recording the bytes written in `tx`.
+    #[allow(dead_code)]
+    struct Serial {
+        tx: std::vec::Vec<u8>,
+        rx: std::collections::VecDeque<u8>,
+    }
+
+    #[allow(dead_code)]
+    impl Serial {
+        fn new(rx: &[u8]) -> Self {
+            Serial {
+                tx: std::vec::Vec::new(),
+                rx: rx.iter().copied().collect(),
+            }
+        }
+
+        fn done(&self, tx: &[u8]) {
+            assert_eq!(self.tx, tx);
+            assert!(self.rx.is_empty(), "bytes not read: {:?}", self.rx);
+        }
+    }
+
+    impl embedded_io::ErrorType for Serial {
+        type Error = core::convert::Infallible;
+    }
+
+    impl embedded_io::Read for Serial {
+        fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+            let len = buf.len().min(self.rx.len());
+            for byte in buf[..len].iter_mut() {
+                *byte = self.rx.pop_front().unwrap();
+            }
+            Ok(len)
+        }
+    }
+
+    impl embedded_io::Write for Serial {
+        fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+            self.tx.extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> Result<(), Self::Error> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_init_and_transfer() {
+        use super::*;
+        use embedded_hal_mock::eh1::delay::NoopDelay;
+
+        let serial = Serial::new(&[
+            // Configuration
+            0x16, 0x44, 0x5a, 0x3e, //
+            // Reset, presence
+            0xcd, //
+            // Written bytes
+            0xcc, 0xe3, //
+            // Read byte
+            0x5a, //
+            // Read bit
+            0x93, //
+            // Reset, no presence
+            0xcf,
+        ]);
+        let mut bus = Ds2480b::new(serial, Speed::Standard);
+        bus.init(&mut NoopDelay::new()).unwrap();
+        assert!(!embedded_onewire::OneWireStatus::presence(&ResetStatus(
+            0b11
+        )));
+        bus.reset().unwrap();
+        // 0xe3 is doubled in data mode
+        bus.write_bytes(&[0xcc, 0xe3]).unwrap();
+        assert_eq!(bus.read_byte().unwrap(), 0x5a);
+        assert!(bus.read_bit().unwrap());
+        assert!(matches!(bus.reset(), Err(OneWireError::NoDevicePresent)));
+        bus.release().done(&[
+            0xc1, 0x17, 0x45, 0x5b, 0x3f, // Timing byte and configuration
+            0xc1, // Reset
+            0xe1, 0xcc, 0xe3, 0xe3, // Data mode
+            0xff, //
+            0xe3, 0x91, // Command mode
+            0xc1,
+        ]);
+    }
+
+    #[test]
+    fn test_strong_pullup() {
+        use super::*;
+
+        let serial = Serial::new(&[
+            0x80, 0x80, 0x93, 0x80, 0x80, 0x80, 0x93, 0x80, // Bits of 0x44
+            0xf0, // End of the pulse
+            0x12,
+        ]);
+        let mut bus = Ds2480b::new(serial, Speed::Flexible);
+        bus.write_byte_powered(0x44).unwrap();
+        assert_eq!(bus.read_byte().unwrap(), 0x12);
+        bus.release().done(&[
+            0x85, 0x85, 0x95, 0x85, 0x85, 0x85, 0x95,
+            0x87, // Strong pullup after the last bit
+            0xf1, // Stop the pulse
+            0xe1, 0xff,
+        ]);
+    }
+
+    #[test]
+    fn test_accelerated_search() {
+        use super::*;
+
+        let serial = Serial::new(&[
+            // First pass, discrepancy at bit 8 where the 0 branch is taken
+            0xcd, 0xf0, 0x80, 0x08, 0x01, 0x00, 0x02, 0x00, 0x08, 0x00, 0x0a, 0x00, 0x20, 0x00,
+            0x22, 0x00, 0x20, 0x0a, //
+            // Second pass, the 1 branch
+            0xcd, 0xf0, 0x80, 0x08, 0x03, 0x00, 0x02, 0x00, 0x08, 0x00, 0x0a, 0x00, 0x20, 0x00,
+            0x22, 0x00, 0x0a, 0x00,
+        ]);
+        let mut bus = Ds2480b::new(serial, Speed::Standard);
+        let mut search = AcceleratedSearch::new(&mut bus, OneWireSearchKind::Normal);
+        assert_eq!(search.next().unwrap(), Some(0x3405040302010028));
+        assert_eq!(search.next().unwrap(), Some(0x0305040302010128));
+        assert_eq!(search.next().unwrap(), None);
+        let mut tx = std::vec![0xc1, 0xe1, 0xf0, 0xe3, 0xb1, 0xe1];
+        tx.extend_from_slice(&[0; 16]);
+        tx.extend_from_slice(&[0xe3, 0xa1, 0xc1, 0xe1, 0xf0, 0xe3, 0xb1, 0xe1]);
+        tx.extend_from_slice(&[0x80, 0x08, 0x02, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+        tx.extend_from_slice(&[0xe3, 0xa1]);
+        bus.release().done(&tx);
+    }
+}
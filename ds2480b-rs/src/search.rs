@@ -0,0 +1,117 @@
+use crate::{CHUNK, Ds2480b, Ds2480bError};
+use embedded_onewire::{OneWire, OneWireAsync, OneWireCrc, OneWireError, OneWireSearchKind};
+
+/// A search for the devices on a 1-Wire bus with the search accelerator of the DS2480B.
+///
+/// The search accelerator runs a whole pass of the search algorithm, 64 triplets, in a
+/// single exchange of 16 bytes on the serial port, which is much faster than the triplets
+/// of [`OneWireSearch`](embedded_onewire::OneWireSearch) at 9600 baud. The triplets are
+/// sent one at a time, so they can not use it.
+#[derive(Debug)]
+pub struct AcceleratedSearch<'a, S> {
+    bus: &'a mut Ds2480b<S>,
+    cmd: u8,
+    last_device: bool,
+    last_discrepancy: u8,
+    rom: [u8; 8],
+}
+
+impl<'a, S> AcceleratedSearch<'a, S> {
+    /// Creates a search of `kind` on the 1-Wire bus of `bus`.
+    pub fn new(bus: &'a mut Ds2480b<S>, kind: OneWireSearchKind) -> Self {
+        Self {
+            bus,
+            cmd: kind as _,
+            last_device: false,
+            last_discrepancy: 0,
+            rom: [0; 8],
+        }
+    }
+
+    /// The path of the next pass: the ROM found in the previous pass up to the last
+    /// discrepancy, where the 1 branch is taken instead.
+    ///
+    /// Bit `n` of the path is bit `2 (n % 4) + 1` of byte `n / 4`.
+    fn path(&self) -> [u8; CHUNK] {
+        let mut path = [0; CHUNK];
+        for n in 0..64 {
+            let bit = match (n + 1).cmp(&(self.last_discrepancy as usize)) {
+                core::cmp::Ordering::Less => self.rom[n / 8] & (1 << (n % 8)) != 0,
+                core::cmp::Ordering::Equal => true,
+                core::cmp::Ordering::Greater => false,
+            };
+            if bit {
+                path[n / 4] |= 1 << (2 * (n % 4) + 1);
+            }
+        }
+        path
+    }
+
+    /// Record the ROM found in a pass, and return it if it is valid.
+    ///
+    /// Bit `2 (n % 4)` of byte `n / 4` flags a discrepancy at bit `n` of the ROM, and bit
+    /// `2 (n % 4) + 1` is the bit of the ROM.
+    fn record<E>(&mut self, found: &[u8; CHUNK]) -> Result<Option<u64>, OneWireError<E>> {
+        let mut last_zero = 0;
+        self.rom = [0; 8];
+        for n in 0..64 {
+            let byte = found[n / 4] >> (2 * (n % 4));
+            let discrepancy = byte & 0x01 != 0;
+            let bit = byte & 0x02 != 0;
+            if bit {
+                self.rom[n / 8] |= 1 << (n % 8);
+            } else if discrepancy {
+                last_zero = n as u8 + 1;
+            }
+        }
+        self.last_discrepancy = last_zero;
+        self.last_device = last_zero == 0;
+        if self.rom[0] == 0 {
+            return Ok(None);
+        }
+        if !OneWireCrc::validate(&self.rom) {
+            self.last_device = true;
+            return Err(OneWireError::InvalidCrc);
+        }
+        Ok(Some(u64::from_le_bytes(self.rom)))
+    }
+}
+
+impl<S: embedded_io::Read + embedded_io::Write> AcceleratedSearch<'_, S> {
+    /// Searches for the next device on the 1-Wire bus.
+    ///
+    /// Returns [`None`] once every device has been found.
+    ///
+    /// # Errors
+    /// Returns [`OneWireError::InvalidCrc`] if the ROM found is corrupted, which ends the
+    /// search.
+    #[allow(clippy::should_implement_trait)]
+    pub fn next(&mut self) -> Result<Option<u64>, OneWireError<Ds2480bError<S::Error>>> {
+        if self.last_device {
+            return Ok(None);
+        }
+        OneWire::reset(&mut *self.bus)?;
+        OneWire::write_byte(&mut *self.bus, self.cmd)?;
+        let path = self.path();
+        let found = self.bus.accelerate(&path)?;
+        self.record(&found)
+    }
+}
+
+impl<S: embedded_io_async::Read + embedded_io_async::Write> AcceleratedSearch<'_, S> {
+    /// Searches for the next device on the 1-Wire bus.
+    ///
+    /// See [`AcceleratedSearch::next`].
+    pub async fn next_async(
+        &mut self,
+    ) -> Result<Option<u64>, OneWireError<Ds2480bError<S::Error>>> {
+        if self.last_device {
+            return Ok(None);
+        }
+        OneWireAsync::reset(&mut *self.bus).await?;
+        OneWireAsync::write_byte(&mut *self.bus, self.cmd).await?;
+        let path = self.path();
+        let found = self.bus.accelerate_async(&path).await?;
+        self.record(&found)
+    }
+}
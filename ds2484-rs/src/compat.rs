@@ -0,0 +1,109 @@
+use embedded_hal::{
+    delay::DelayNs,
+    i2c::{ErrorKind, ErrorType, I2c, Operation, SevenBitAddress},
+};
+use embedded_hal_02::blocking::{
+    delay::DelayUs,
+    i2c::{Read, Write, WriteRead},
+};
+
+/// Adapter for `embedded-hal` 0.2 peripherals.
+///
+/// Wraps a blocking I2C bus implementing the `embedded-hal` 0.2 [`Write`], [`Read`] and
+/// [`WriteRead`] traits, or a timer implementing `DelayUs<u32>`, so that it can be used
+/// to build a [`Ds2484`](crate::Ds2484).
+///
+/// `embedded-hal` 0.2 has no generic I2C transactions. A write followed by a read is
+/// performed as a single write-read, and any other operation of a transaction is
+/// performed as a separate I2C transfer. The DS2484 driver only issues writes, reads
+/// and write-reads.
+#[derive(Debug)]
+pub struct Compat<T>(pub T);
+
+impl<T> Compat<T> {
+    /// Wraps an `embedded-hal` 0.2 peripheral.
+    pub fn new(inner: T) -> Self {
+        Compat(inner)
+    }
+
+    /// Returns the wrapped peripheral.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+/// Error of an `embedded-hal` 0.2 I2C bus wrapped in [`Compat`].
+#[derive(Debug)]
+pub struct CompatError<E>(pub E);
+
+impl<E: core::fmt::Debug> embedded_hal::i2c::Error for CompatError<E> {
+    fn kind(&self) -> ErrorKind {
+        ErrorKind::Other
+    }
+}
+
+impl<T: Write> ErrorType for Compat<T>
+where
+    T::Error: core::fmt::Debug,
+{
+    type Error = CompatError<T::Error>;
+}
+
+impl<T, E> I2c<SevenBitAddress> for Compat<T>
+where
+    T: Write<Error = E> + Read<Error = E> + WriteRead<Error = E>,
+    E: core::fmt::Debug,
+{
+    fn read(&mut self, address: u8, read: &mut [u8]) -> Result<(), Self::Error> {
+        Read::read(&mut self.0, address, read).map_err(CompatError)
+    }
+
+    fn write(&mut self, address: u8, write: &[u8]) -> Result<(), Self::Error> {
+        Write::write(&mut self.0, address, write).map_err(CompatError)
+    }
+
+    fn write_read(
+        &mut self,
+        address: u8,
+        write: &[u8],
+        read: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        WriteRead::write_read(&mut self.0, address, write, read).map_err(CompatError)
+    }
+
+    fn transaction(
+        &mut self,
+        address: u8,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        let mut operations = operations;
+        loop {
+            operations = match operations {
+                [Operation::Write(write), Operation::Read(read), rest @ ..] => {
+                    WriteRead::write_read(&mut self.0, address, write, read)
+                        .map_err(CompatError)?;
+                    rest
+                }
+                [Operation::Write(write), rest @ ..] => {
+                    Write::write(&mut self.0, address, write).map_err(CompatError)?;
+                    rest
+                }
+                [Operation::Read(read), rest @ ..] => {
+                    Read::read(&mut self.0, address, read).map_err(CompatError)?;
+                    rest
+                }
+                [] => return Ok(()),
+            };
+        }
+    }
+}
+
+impl<T: DelayUs<u32>> DelayNs for Compat<T> {
+    fn delay_ns(&mut self, ns: u32) {
+        self.0.delay_us(ns.div_ceil(1000));
+    }
+
+    fn delay_us(&mut self, us: u32) {
+        self.0.delay_us(us);
+    }
+}
@@ -1,10 +1,17 @@
 #[derive(Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 /// DS2484 Hardware Errors
 pub enum Ds2484Error<E> {
     /// I2C bus errors.
     I2c(E),
-    /// Busy wait retries exceeded.
+    /// Busy wait retries exceeded, or the timeout elapsed.
     RetriesExceeded,
+    /// A parameter is outside the range supported by the device.
+    InvalidValue(&'static str),
+    /// The value read back from the device does not match the value written.
+    VerificationFailed,
+    /// The device has reset unexpectedly, e.g. after a brown-out, and lost its configuration.
+    DeviceReset,
 }
 
 impl<E> From<E> for Ds2484Error<E> {
@@ -12,3 +19,24 @@ impl<E> From<E> for Ds2484Error<E> {
         Self::I2c(value)
     }
 }
+
+impl<E: core::fmt::Display> core::fmt::Display for Ds2484Error<E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::I2c(e) => write!(f, "I2C bus error: {e}"),
+            Self::RetriesExceeded => write!(f, "timed out waiting for the DS2484"),
+            Self::InvalidValue(name) => write!(f, "value of `{name}` is out of range"),
+            Self::VerificationFailed => write!(f, "value read back from the DS2484 does not match"),
+            Self::DeviceReset => write!(f, "DS2484 reset unexpectedly"),
+        }
+    }
+}
+
+impl<E: core::error::Error + 'static> core::error::Error for Ds2484Error<E> {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            Self::I2c(e) => Some(e),
+            _ => None,
+        }
+    }
+}
@@ -1,23 +1,44 @@
 #![no_std]
 #![deny(missing_docs)]
+#![cfg_attr(docsrs, feature(doc_cfg))]
 #![doc = include_str!("../README.md")]
 
-pub use embedded_onewire::{OneWire, OneWireAsync, OneWireError, OneWireResult};
+pub use embedded_onewire::{
+    OneWire, OneWireAsync, OneWireError, OneWireResult, OneWireStrongPullup,
+    OneWireStrongPullupAsync,
+};
+#[cfg(feature = "embedded-hal-02")]
+mod compat;
 mod error;
 mod onewire;
 mod onewire_async;
 mod registers;
 mod registers_async;
+mod shared;
+mod stats;
+#[cfg(feature = "testing")]
+#[cfg_attr(docsrs, doc(cfg(feature = "testing")))]
+pub mod testing;
 mod traits;
 mod traits_async;
+mod wait;
 
+#[cfg(feature = "embedded-hal-02")]
+#[cfg_attr(docsrs, doc(cfg(feature = "embedded-hal-02")))]
+pub use compat::{Compat, CompatError};
 pub use error::Ds2484Error;
 pub use registers::{
     DeviceConfiguration, DeviceStatus, Ds2484, Ds2484Builder, OneWireConfigurationBuilder,
-    OneWirePortConfiguration,
+    OneWirePortConfiguration, OneWirePortTiming, OneWirePresence, RegisterDump, ResetOutcome,
+    SelfTestResult,
 };
+pub use shared::{SharedDs2484, SharedDs2484Handle};
+#[cfg(feature = "stats")]
+#[cfg_attr(docsrs, doc(cfg(feature = "stats")))]
+pub use stats::BusStatistics;
 pub use traits::Interact;
 pub use traits_async::InteractAsync;
+pub use wait::{ExponentialBackoff, FixedInterval, WaitStrategy};
 
 /// Results of DS2484-specific function calls.
 pub type Ds2484Result<T, E> = Result<T, Ds2484Error<E>>;
@@ -51,4 +72,1346 @@ mod test {
         stat.write(&mut ds2484).unwrap();
         i2c.done();
     }
+
+    #[test]
+    fn test_overdrive_polling() {
+        use crate::registers::{DEVICE_RST_CMD, DEVICE_STATUS_PTR, READ_PTR_CMD};
+        extern crate std;
+        use super::*;
+        use embedded_hal_mock::eh1::delay::{CheckedDelay, Transaction as DelayTransaction};
+        use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+
+        let mut i2c = I2cMock::new(&[
+            I2cTransaction::write(0x18, std::vec![DEVICE_RST_CMD]),
+            I2cTransaction::write_read(
+                0x18,
+                std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR],
+                std::vec![0x10],
+            ),
+            I2cTransaction::write(0x18, std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR]),
+            I2cTransaction::read(0x18, std::vec![0x00]),
+            I2cTransaction::write(0x18, std::vec![0xd2, 0x78]), // overdrive configuration
+            I2cTransaction::read(0x18, std::vec![0x08]),
+            I2cTransaction::write(0x18, std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR]),
+            I2cTransaction::read(0x18, std::vec![0x01]), // 1-Wire busy
+            I2cTransaction::read(0x18, std::vec![0x00]),
+            I2cTransaction::write(0x18, std::vec![0xa5, 0xcc]), // write byte
+        ]);
+        // A busy 1-Wire line is polled at microsecond granularity in overdrive
+        let mut delay = CheckedDelay::new(&[DelayTransaction::delay_us(100)]);
+
+        let mut ds2484 = Ds2484Builder::default()
+            .with_config(DeviceConfiguration::new().with_onewire_speed(true))
+            .build(&mut i2c, &mut delay)
+            .unwrap();
+        assert!(ds2484.get_overdrive_mode());
+        ds2484.write_byte(0xcc).unwrap();
+        i2c.done();
+        delay.done();
+    }
+
+    #[test]
+    fn test_timeout() {
+        use crate::registers::{DEVICE_RST_CMD, DEVICE_STATUS_PTR, READ_PTR_CMD};
+        extern crate std;
+        use super::*;
+        use embedded_hal_mock::eh1::delay::{CheckedDelay, Transaction as DelayTransaction};
+        use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+
+        let mut i2c = I2cMock::new(&[
+            I2cTransaction::write(0x18, std::vec![DEVICE_RST_CMD]),
+            I2cTransaction::write_read(
+                0x18,
+                std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR],
+                std::vec![0x10],
+            ),
+            I2cTransaction::write(0x18, std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR]),
+            I2cTransaction::read(0x18, std::vec![0x00]),
+            I2cTransaction::write(0x18, std::vec![0xd2, 0xf0]),
+            I2cTransaction::read(0x18, std::vec![0x00]),
+            I2cTransaction::write(0x18, std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR]),
+            I2cTransaction::read(0x18, std::vec![0x01]), // 1-Wire busy
+            I2cTransaction::read(0x18, std::vec![0x01]),
+            I2cTransaction::read(0x18, std::vec![0x01]),
+        ]);
+        // The device is given up on after 2 ms, regardless of the retry count
+        let mut delay = CheckedDelay::new(&[
+            DelayTransaction::delay_us(1000),
+            DelayTransaction::delay_us(1000),
+        ]);
+
+        let mut ds2484 = Ds2484Builder::default()
+            .with_retries(255)
+            .with_timeout_ms(2)
+            .build(&mut i2c, &mut delay)
+            .unwrap();
+        assert!(matches!(
+            ds2484.write_byte(0xcc),
+            Err(OneWireError::Other(Ds2484Error::RetriesExceeded))
+        ));
+        i2c.done();
+        delay.done();
+    }
+
+    #[test]
+    fn test_reset_retries() {
+        use crate::registers::{DEVICE_RST_CMD, DEVICE_STATUS_PTR, READ_PTR_CMD};
+        extern crate std;
+        use super::*;
+        use embedded_hal_mock::eh1::delay::NoopDelay as DelayMock;
+        use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+
+        let mut i2c = I2cMock::new(&[
+            I2cTransaction::write(0x18, std::vec![DEVICE_RST_CMD]),
+            I2cTransaction::write_read(
+                0x18,
+                std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR],
+                std::vec![0x10],
+            ),
+            I2cTransaction::write(0x18, std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR]),
+            I2cTransaction::read(0x18, std::vec![0x00]),
+            I2cTransaction::write(0x18, std::vec![0xd2, 0xf0]),
+            I2cTransaction::read(0x18, std::vec![0x00]),
+            // The reset may poll twice
+            I2cTransaction::write(0x18, std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR]),
+            I2cTransaction::read(0x18, std::vec![0x00]),
+            I2cTransaction::write(0x18, std::vec![0xb4]),
+            I2cTransaction::write(0x18, std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR]),
+            I2cTransaction::read(0x18, std::vec![0x01]),
+            I2cTransaction::read(0x18, std::vec![0x01]),
+            I2cTransaction::read(0x18, std::vec![0x02]),
+            // The byte may not
+            I2cTransaction::write(0x18, std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR]),
+            I2cTransaction::read(0x18, std::vec![0x00]),
+            I2cTransaction::write(0x18, std::vec![0xa5, 0xcc]),
+            I2cTransaction::write(0x18, std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR]),
+            I2cTransaction::read(0x18, std::vec![0x01]),
+            I2cTransaction::read(0x18, std::vec![0x01]),
+        ]);
+
+        let mut ds2484 = Ds2484Builder::default()
+            .with_retries(0)
+            .with_reset_retries(2)
+            .build(&mut i2c, DelayMock::new())
+            .unwrap();
+        ds2484.reset().unwrap();
+        ds2484.write_byte(0xcc).unwrap();
+        assert!(matches!(
+            ds2484.write_byte(0x44),
+            Err(OneWireError::Other(Ds2484Error::RetriesExceeded))
+        ));
+        i2c.done();
+    }
+
+    #[test]
+    fn test_error_display() {
+        extern crate std;
+        use super::*;
+        use std::string::ToString;
+
+        let err: Ds2484Error<std::io::Error> = std::io::Error::other("nack").into();
+        assert_eq!(err.to_string(), "I2C bus error: nack");
+        assert!(core::error::Error::source(&err).is_some());
+        let err = Ds2484Error::<std::io::Error>::InvalidValue("reset_time");
+        assert_eq!(err.to_string(), "value of `reset_time` is out of range");
+    }
+
+    #[test]
+    fn test_pipelining() {
+        use crate::registers::{DEVICE_RST_CMD, DEVICE_STATUS_PTR, READ_PTR_CMD};
+        extern crate std;
+        use super::*;
+        use embedded_hal_mock::eh1::delay::{CheckedDelay, Transaction as DelayTransaction};
+        use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+
+        let mut i2c = I2cMock::new(&[
+            I2cTransaction::write(0x18, std::vec![DEVICE_RST_CMD]),
+            I2cTransaction::write_read(
+                0x18,
+                std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR],
+                std::vec![0x10],
+            ),
+            I2cTransaction::write(0x18, std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR]),
+            I2cTransaction::read(0x18, std::vec![0x00]),
+            I2cTransaction::write(0x18, std::vec![0xd2, 0xf0]),
+            I2cTransaction::read(0x18, std::vec![0x00]),
+            // The line was idle after the configuration write, no status poll is needed
+            I2cTransaction::write(0x18, std::vec![0xa5, 0xcc]),
+            I2cTransaction::read(0x18, std::vec![0x00]),
+            I2cTransaction::write(0x18, std::vec![0xa5, 0x44]),
+            I2cTransaction::read(0x18, std::vec![0x00]),
+        ]);
+        let byte_time = 8 * OneWirePortConfiguration::default().slot_time(false);
+        let mut delay = CheckedDelay::new(&[
+            DelayTransaction::delay_ns(byte_time),
+            DelayTransaction::delay_ns(byte_time),
+        ]);
+
+        let mut ds2484 = Ds2484Builder::default()
+            .with_pipelining(true)
+            .build(&mut i2c, &mut delay)
+            .unwrap();
+        ds2484.write_byte(0xcc).unwrap();
+        ds2484.write_byte(0x44).unwrap();
+        i2c.done();
+        delay.done();
+    }
+
+    #[test]
+    fn test_build_overdrive() {
+        use crate::registers::{DEVICE_RST_CMD, DEVICE_STATUS_PTR, READ_PTR_CMD};
+        extern crate std;
+        use super::*;
+        use embedded_hal_mock::eh1::delay::NoopDelay as DelayMock;
+        use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+
+        let mut i2c = I2cMock::new(&[
+            I2cTransaction::write(0x18, std::vec![DEVICE_RST_CMD]),
+            I2cTransaction::write_read(
+                0x18,
+                std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR],
+                std::vec![0x10],
+            ),
+            I2cTransaction::write(0x18, std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR]),
+            I2cTransaction::read(0x18, std::vec![0x00]),
+            I2cTransaction::write(0x18, std::vec![0xd2, 0xf0]),
+            I2cTransaction::read(0x18, std::vec![0x00]),
+            I2cTransaction::write(0x18, std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR]),
+            I2cTransaction::read(0x18, std::vec![0x00]),
+            I2cTransaction::write(0x18, std::vec![0xb4]),
+            I2cTransaction::write(0x18, std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR]),
+            I2cTransaction::read(0x18, std::vec![0x02]),
+            I2cTransaction::write(0x18, std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR]),
+            I2cTransaction::read(0x18, std::vec![0x00]),
+            I2cTransaction::write(0x18, std::vec![0xa5, 0x3c]), // Overdrive-Skip ROM
+            I2cTransaction::write(0x18, std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR]),
+            I2cTransaction::read(0x18, std::vec![0x00]),
+            I2cTransaction::write(0x18, std::vec![0xd2, 0x78]),
+            I2cTransaction::read(0x18, std::vec![0x08]),
+            I2cTransaction::write(0x18, std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR]),
+            I2cTransaction::read(0x18, std::vec![0x00]),
+            I2cTransaction::write(0x18, std::vec![0xb4]),
+            I2cTransaction::write(0x18, std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR]),
+            I2cTransaction::read(0x18, std::vec![0x02]),
+        ]);
+
+        let mut ds2484 = Ds2484Builder::default()
+            .with_overdrive(true)
+            .build(&mut i2c, DelayMock::new())
+            .unwrap();
+        assert!(ds2484.get_overdrive_mode());
+        i2c.done();
+    }
+
+    #[test]
+    fn test_build_preset() {
+        use crate::registers::{DEVICE_RST_CMD, DEVICE_STATUS_PTR, READ_PTR_CMD};
+        extern crate std;
+        use super::*;
+        use embedded_hal_mock::eh1::delay::NoopDelay as DelayMock;
+        use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+
+        let mut i2c = I2cMock::new(&[
+            I2cTransaction::write(0x18, std::vec![DEVICE_RST_CMD]),
+            I2cTransaction::write_read(
+                0x18,
+                std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR],
+                std::vec![0x10],
+            ),
+            I2cTransaction::write(0x18, std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR]),
+            I2cTransaction::read(0x18, std::vec![0x00]),
+            I2cTransaction::write(0x18, std::vec![0xd2, 0xe1]), // APU
+            I2cTransaction::read(0x18, std::vec![0x01]),
+            I2cTransaction::write(0x18, std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR]),
+            I2cTransaction::read(0x18, std::vec![0x00]),
+            I2cTransaction::write(
+                0x18,
+                std::vec![0xc3, 0x06, 0x16, 0x29, 0x36, 0x46, 0x56, 0x68, 0x80],
+            ),
+            I2cTransaction::write_read(
+                0x18,
+                std::vec![READ_PTR_CMD, 0xb4],
+                std::vec![0x06, 0x06, 0x09, 0x06, 0x06, 0x06, 0x08, 0x00],
+            ),
+        ]);
+
+        let ds2484 = Ds2484Builder::long_line()
+            .build(&mut i2c, DelayMock::new())
+            .unwrap();
+        assert_eq!(ds2484.port.presence_detect_time(), 74_000);
+        assert_eq!(ds2484.port.write_zero_recovery_time(), 10_250);
+        assert_eq!(ds2484.port.weak_pullup_resistor(), 500);
+        i2c.done();
+    }
+
+    #[cfg(feature = "testing")]
+    #[test]
+    fn test_testing_harness() {
+        use super::*;
+        use embedded_hal_mock::eh1::delay::NoopDelay as DelayMock;
+        use embedded_hal_mock::eh1::i2c::Mock as I2cMock;
+
+        let config = DeviceConfiguration::new().with_active_pullup(true);
+        let mut expectations = testing::build(config);
+        expectations.extend(testing::onewire_reset(true));
+        expectations.extend(testing::write_byte(0xcc));
+        expectations.extend(testing::read_byte(0xab));
+        let mut i2c = I2cMock::new(&expectations);
+
+        let mut ds2484 = Ds2484Builder::default()
+            .with_config(config)
+            .build(&mut i2c, DelayMock::new())
+            .unwrap();
+        ds2484.reset().unwrap();
+        ds2484.write_byte(0xcc).unwrap();
+        assert_eq!(ds2484.read_byte().unwrap(), 0xab);
+        i2c.done();
+    }
+
+    #[test]
+    fn test_bulk_read() {
+        use crate::registers::{DEVICE_RST_CMD, DEVICE_STATUS_PTR, READ_PTR_CMD};
+        extern crate std;
+        use super::*;
+        use embedded_hal_mock::eh1::delay::NoopDelay as DelayMock;
+        use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+
+        let mut i2c = I2cMock::new(&[
+            I2cTransaction::write(0x18, std::vec![DEVICE_RST_CMD]),
+            I2cTransaction::write_read(
+                0x18,
+                std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR],
+                std::vec![0x10],
+            ),
+            I2cTransaction::write(0x18, std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR]),
+            I2cTransaction::read(0x18, std::vec![0x00]),
+            I2cTransaction::write(0x18, std::vec![0xd2, 0xf0]),
+            I2cTransaction::read(0x18, std::vec![0x00]),
+            I2cTransaction::write(0x18, std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR]),
+            I2cTransaction::read(0x18, std::vec![0x00]),
+            // The read pointer stays at the status register after each read byte command
+            I2cTransaction::write(0x18, std::vec![0x96]),
+            I2cTransaction::read(0x18, std::vec![0x00]),
+            I2cTransaction::write_read(0x18, std::vec![READ_PTR_CMD, 0xe1], std::vec![0xab]),
+            I2cTransaction::write(0x18, std::vec![0x96]),
+            I2cTransaction::read(0x18, std::vec![0x00]),
+            I2cTransaction::write_read(0x18, std::vec![READ_PTR_CMD, 0xe1], std::vec![0xcd]),
+        ]);
+
+        let mut ds2484 = Ds2484Builder::default()
+            .build(&mut i2c, DelayMock::new())
+            .unwrap();
+        let mut buf = [0; 2];
+        ds2484.read_bytes(&mut buf).unwrap();
+        assert_eq!(buf, [0xab, 0xcd]);
+        i2c.done();
+    }
+
+    #[test]
+    fn test_auto_recovery() {
+        use crate::registers::{DEVICE_RST_CMD, DEVICE_STATUS_PTR, READ_PTR_CMD};
+        extern crate std;
+        use super::*;
+        use embedded_hal_mock::eh1::delay::NoopDelay as DelayMock;
+        use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+
+        let mut i2c = I2cMock::new(&[
+            I2cTransaction::write(0x18, std::vec![DEVICE_RST_CMD]),
+            I2cTransaction::write_read(
+                0x18,
+                std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR],
+                std::vec![0x10],
+            ),
+            I2cTransaction::write(0x18, std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR]),
+            I2cTransaction::read(0x18, std::vec![0x00]),
+            I2cTransaction::write(0x18, std::vec![0xd2, 0xe1]), // active pullup
+            I2cTransaction::read(0x18, std::vec![0x01]),
+            // The device has reset since the configuration was written
+            I2cTransaction::write(0x18, std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR]),
+            I2cTransaction::read(0x18, std::vec![0x10]),
+            // The configuration is restored
+            I2cTransaction::write(0x18, std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR]),
+            I2cTransaction::read(0x18, std::vec![0x10]),
+            I2cTransaction::write(0x18, std::vec![0xd2, 0xe1]),
+            I2cTransaction::read(0x18, std::vec![0x01]),
+            // The operation is retried
+            I2cTransaction::write(0x18, std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR]),
+            I2cTransaction::read(0x18, std::vec![0x00]),
+            I2cTransaction::write(0x18, std::vec![0xa5, 0xcc]),
+        ]);
+
+        let mut ds2484 = Ds2484Builder::default()
+            .with_config(DeviceConfiguration::new().with_active_pullup(true))
+            .with_auto_recovery(true)
+            .build(&mut i2c, DelayMock::new())
+            .unwrap();
+        ds2484.write_byte(0xcc).unwrap();
+        i2c.done();
+    }
+
+    #[test]
+    fn test_shared_bus() {
+        use crate::registers::{DEVICE_RST_CMD, DEVICE_STATUS_PTR, READ_PTR_CMD};
+        extern crate std;
+        use super::*;
+        use core::cell::RefCell;
+        use embedded_hal::i2c::I2c;
+        use embedded_hal_bus::i2c::RefCellDevice;
+        use embedded_hal_mock::eh1::delay::NoopDelay as DelayMock;
+        use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+
+        let mut i2c = I2cMock::new(&[
+            I2cTransaction::write(0x18, std::vec![DEVICE_RST_CMD]),
+            I2cTransaction::write_read(
+                0x18,
+                std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR],
+                std::vec![0x10],
+            ),
+            I2cTransaction::write(0x18, std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR]),
+            I2cTransaction::read(0x18, std::vec![0x00]),
+            I2cTransaction::write(0x18, std::vec![0xd2, 0xf0]),
+            I2cTransaction::read(0x18, std::vec![0x00]),
+            // Another device on the same bus
+            I2cTransaction::write(0x40, std::vec![0x01]),
+            I2cTransaction::write(0x18, std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR]),
+            I2cTransaction::read(0x18, std::vec![0x00]),
+            I2cTransaction::write(0x18, std::vec![0xa5, 0xcc]),
+        ]);
+
+        let bus = RefCell::new(i2c.clone());
+        let mut ds2484 = Ds2484Builder::default()
+            .build(RefCellDevice::new(&bus), DelayMock::new())
+            .unwrap();
+        let mut other = RefCellDevice::new(&bus);
+        other.write(0x40, &[0x01]).unwrap();
+        ds2484.write_byte(0xcc).unwrap();
+        i2c.done();
+    }
+
+    #[test]
+    fn test_shared_master() {
+        use crate::registers::{DEVICE_RST_CMD, DEVICE_STATUS_PTR, READ_PTR_CMD};
+        extern crate std;
+        use super::*;
+        use embedded_hal_mock::eh1::delay::NoopDelay as DelayMock;
+        use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+
+        // A driver that takes the bus master by value
+        fn send<O: OneWire>(mut bus: O, cmd: u8) -> OneWireResult<(), O::BusError> {
+            bus.write_byte(cmd)
+        }
+
+        let mut i2c = I2cMock::new(&[
+            I2cTransaction::write(0x18, std::vec![DEVICE_RST_CMD]),
+            I2cTransaction::write_read(
+                0x18,
+                std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR],
+                std::vec![0x10],
+            ),
+            I2cTransaction::write(0x18, std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR]),
+            I2cTransaction::read(0x18, std::vec![0x00]),
+            I2cTransaction::write(0x18, std::vec![0xd2, 0xf0]),
+            I2cTransaction::read(0x18, std::vec![0x00]),
+            I2cTransaction::write(0x18, std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR]),
+            I2cTransaction::read(0x18, std::vec![0x00]),
+            I2cTransaction::write(0x18, std::vec![0xa5, 0x33]),
+            I2cTransaction::write(0x18, std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR]),
+            I2cTransaction::read(0x18, std::vec![0x00]),
+            I2cTransaction::write(0x18, std::vec![0xa5, 0xcc]),
+            I2cTransaction::write(0x18, std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR]),
+            I2cTransaction::read(0x18, std::vec![0x00]),
+            I2cTransaction::write(0x18, std::vec![0xa5, 0x44]),
+        ]);
+
+        let mut ds2484 = Ds2484Builder::default()
+            .build(&mut i2c, DelayMock::new())
+            .unwrap();
+        send(&mut ds2484, 0x33).unwrap();
+
+        let shared = SharedDs2484::new(ds2484);
+        let first = shared.handle();
+        let second = shared.handle();
+        send(first, 0xcc).unwrap();
+        send(second, 0x44).unwrap();
+        shared.into_inner();
+        i2c.done();
+    }
+
+    #[test]
+    fn test_check_presence() {
+        use crate::registers::{DEVICE_RST_CMD, DEVICE_STATUS_PTR, READ_PTR_CMD};
+        extern crate std;
+        use super::*;
+        use embedded_hal_mock::eh1::delay::NoopDelay as DelayMock;
+        use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+
+        let mut i2c = I2cMock::new(&[
+            I2cTransaction::write(0x18, std::vec![DEVICE_RST_CMD]),
+            I2cTransaction::write_read(
+                0x18,
+                std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR],
+                std::vec![0x10],
+            ),
+            I2cTransaction::write(0x18, std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR]),
+            I2cTransaction::read(0x18, std::vec![0x00]),
+            I2cTransaction::write(0x18, std::vec![0xd2, 0xf0]),
+            I2cTransaction::read(0x18, std::vec![0x00]),
+            I2cTransaction::write(0x18, std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR]),
+            I2cTransaction::read(0x18, std::vec![0x00]),
+            I2cTransaction::write(0x18, std::vec![0xb4]), // 1-Wire reset
+            I2cTransaction::write(0x18, std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR]),
+            I2cTransaction::read(0x18, std::vec![0x00]), // no presence pulse
+            I2cTransaction::write(0x18, std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR]),
+            I2cTransaction::read(0x18, std::vec![0x00]),
+            I2cTransaction::write(0x18, std::vec![0xb4]),
+            I2cTransaction::write(0x18, std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR]),
+            I2cTransaction::read(0x18, std::vec![0x06]), // presence pulse and short
+        ]);
+
+        let mut ds2484 = Ds2484Builder::default()
+            .build(&mut i2c, DelayMock::new())
+            .unwrap();
+        assert_eq!(ds2484.check_presence().unwrap(), OneWirePresence::Empty);
+        assert_eq!(ds2484.check_presence().unwrap(), OneWirePresence::Short);
+        i2c.done();
+    }
+
+    #[test]
+    fn test_dump_registers() {
+        use crate::registers::{DEVICE_RST_CMD, DEVICE_STATUS_PTR, READ_PTR_CMD};
+        extern crate std;
+        use super::*;
+        use embedded_hal_mock::eh1::delay::NoopDelay as DelayMock;
+        use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+
+        let mut i2c = I2cMock::new(&[
+            I2cTransaction::write(0x18, std::vec![DEVICE_RST_CMD]),
+            I2cTransaction::write_read(
+                0x18,
+                std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR],
+                std::vec![0x10],
+            ),
+            I2cTransaction::write(0x18, std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR]),
+            I2cTransaction::read(0x18, std::vec![0x00]),
+            I2cTransaction::write(0x18, std::vec![0xd2, 0xf0]),
+            I2cTransaction::read(0x18, std::vec![0x00]),
+            I2cTransaction::write_read(
+                0x18,
+                std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR],
+                std::vec![0x0a], // presence pulse, line high
+            ),
+            I2cTransaction::write_read(0x18, std::vec![READ_PTR_CMD, 0xc3], std::vec![0x01]),
+            I2cTransaction::write_read(
+                0x18,
+                std::vec![READ_PTR_CMD, 0xb4],
+                std::vec![0x06, 0x06, 0x06, 0x06, 0x06, 0x06, 0x06, 0x06],
+            ),
+        ]);
+
+        let mut ds2484 = Ds2484Builder::default()
+            .build(&mut i2c, DelayMock::new())
+            .unwrap();
+        let dump = ds2484.dump_registers().unwrap();
+        assert!(dump.status.present_pulse_detect());
+        assert!(dump.status.logic_level());
+        assert!(dump.config.active_pullup());
+        assert_eq!(dump.port, OneWirePortTiming::default());
+        i2c.done();
+    }
+
+    #[test]
+    fn test_config_setters() {
+        use crate::registers::{DEVICE_RST_CMD, DEVICE_STATUS_PTR, READ_PTR_CMD};
+        extern crate std;
+        use super::*;
+        use embedded_hal_mock::eh1::delay::NoopDelay as DelayMock;
+        use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+
+        let mut i2c = I2cMock::new(&[
+            I2cTransaction::write(0x18, std::vec![DEVICE_RST_CMD]),
+            I2cTransaction::write_read(
+                0x18,
+                std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR],
+                std::vec![0x10],
+            ),
+            I2cTransaction::write(0x18, std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR]),
+            I2cTransaction::read(0x18, std::vec![0x00]),
+            I2cTransaction::write(0x18, std::vec![0xd2, 0x78]),
+            I2cTransaction::read(0x18, std::vec![0x08]),
+            // The overdrive speed is kept when enabling the active pullup
+            I2cTransaction::write_read(0x18, std::vec![READ_PTR_CMD, 0xc3], std::vec![0x08]),
+            I2cTransaction::write(0x18, std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR]),
+            I2cTransaction::read(0x18, std::vec![0x00]),
+            I2cTransaction::write(0x18, std::vec![0xd2, 0x69]),
+            I2cTransaction::read(0x18, std::vec![0x09]),
+        ]);
+
+        let mut ds2484 = Ds2484Builder::default()
+            .with_config(DeviceConfiguration::new().with_onewire_speed(true))
+            .build(&mut i2c, DelayMock::new())
+            .unwrap();
+        ds2484.set_active_pullup(true).unwrap();
+        i2c.done();
+    }
+
+    #[test]
+    fn test_self_test() {
+        use crate::registers::{DEVICE_RST_CMD, DEVICE_STATUS_PTR, READ_PTR_CMD};
+        extern crate std;
+        use super::*;
+        use embedded_hal_mock::eh1::delay::NoopDelay as DelayMock;
+        use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+
+        let mut i2c = I2cMock::new(&[
+            I2cTransaction::write(0x18, std::vec![DEVICE_RST_CMD]),
+            I2cTransaction::write_read(
+                0x18,
+                std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR],
+                std::vec![0x10],
+            ),
+            I2cTransaction::write(0x18, std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR]),
+            I2cTransaction::read(0x18, std::vec![0x00]),
+            I2cTransaction::write(0x18, std::vec![0xd2, 0xf0]),
+            I2cTransaction::read(0x18, std::vec![0x00]),
+            I2cTransaction::write(0x18, std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR]),
+            I2cTransaction::read(0x18, std::vec![0x00]),
+            I2cTransaction::write(0x18, std::vec![0xd2, 0xf0]),
+            I2cTransaction::read(0x18, std::vec![0x00]),
+            I2cTransaction::write(0x18, std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR]),
+            I2cTransaction::read(0x18, std::vec![0x00]),
+            I2cTransaction::write(0x18, std::vec![0xb4]),
+            I2cTransaction::write(0x18, std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR]),
+            I2cTransaction::read(0x18, std::vec![0x02]),
+            // The configuration read back does not match
+            I2cTransaction::write(0x18, std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR]),
+            I2cTransaction::read(0x18, std::vec![0x00]),
+            I2cTransaction::write(0x18, std::vec![0xd2, 0xf0]),
+            I2cTransaction::read(0x18, std::vec![0xff]),
+        ]);
+
+        let mut ds2484 = Ds2484Builder::default()
+            .build(&mut i2c, DelayMock::new())
+            .unwrap();
+        assert_eq!(ds2484.self_test(), SelfTestResult::Present);
+        assert_eq!(ds2484.self_test(), SelfTestResult::Unresponsive);
+        i2c.done();
+    }
+
+    #[test]
+    fn test_self_test_after_reset() {
+        use crate::registers::{DEVICE_RST_CMD, DEVICE_STATUS_PTR, READ_PTR_CMD};
+        extern crate std;
+        use super::*;
+        use embedded_hal_mock::eh1::delay::NoopDelay as DelayMock;
+        use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+
+        let mut i2c = I2cMock::new(&[
+            I2cTransaction::write(0x18, std::vec![DEVICE_RST_CMD]),
+            I2cTransaction::write_read(
+                0x18,
+                std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR],
+                std::vec![0x10],
+            ),
+            I2cTransaction::write(0x18, std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR]),
+            I2cTransaction::read(0x18, std::vec![0x00]),
+            I2cTransaction::write(0x18, std::vec![0xd2, 0xe1]), // active pullup
+            I2cTransaction::read(0x18, std::vec![0x01]),
+            // The device has reset since the configuration was written
+            I2cTransaction::write(0x18, std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR]),
+            I2cTransaction::read(0x18, std::vec![0x10]),
+            // The last written configuration is restored, not the one read back
+            I2cTransaction::write(0x18, std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR]),
+            I2cTransaction::read(0x18, std::vec![0x10]),
+            I2cTransaction::write(0x18, std::vec![0xd2, 0xe1]),
+            I2cTransaction::read(0x18, std::vec![0x01]),
+            I2cTransaction::write(0x18, std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR]),
+            I2cTransaction::read(0x18, std::vec![0x00]),
+            I2cTransaction::write(0x18, std::vec![0xb4]),
+            I2cTransaction::write(0x18, std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR]),
+            I2cTransaction::read(0x18, std::vec![0x02]),
+        ]);
+
+        let mut ds2484 = Ds2484Builder::default()
+            .with_config(DeviceConfiguration::new().with_active_pullup(true))
+            .build(&mut i2c, DelayMock::new())
+            .unwrap();
+        assert!(ds2484.write_byte(0xcc).is_err());
+        assert_eq!(ds2484.self_test(), SelfTestResult::Present);
+        i2c.done();
+    }
+
+    #[test]
+    fn test_i2c_retries() {
+        use crate::registers::{DEVICE_RST_CMD, DEVICE_STATUS_PTR, READ_PTR_CMD};
+        extern crate std;
+        use super::*;
+        use embedded_hal::i2c::{ErrorKind, NoAcknowledgeSource};
+        use embedded_hal_mock::eh1::delay::NoopDelay as DelayMock;
+        use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+
+        let nack = ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address);
+        let mut i2c = I2cMock::new(&[
+            I2cTransaction::write(0x18, std::vec![DEVICE_RST_CMD]).with_error(nack),
+            I2cTransaction::write(0x18, std::vec![DEVICE_RST_CMD]),
+            I2cTransaction::write_read(
+                0x18,
+                std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR],
+                std::vec![0x10],
+            ),
+            I2cTransaction::write(0x18, std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR]),
+            I2cTransaction::read(0x18, std::vec![0x00]).with_error(nack),
+            I2cTransaction::read(0x18, std::vec![0x00]),
+            I2cTransaction::write(0x18, std::vec![0xd2, 0xf0]),
+            I2cTransaction::read(0x18, std::vec![0x00]),
+            // The retry budget is spent on each transaction separately
+            I2cTransaction::write_read(0x18, std::vec![READ_PTR_CMD, 0xf0], std::vec![0x00])
+                .with_error(nack),
+            I2cTransaction::write_read(0x18, std::vec![READ_PTR_CMD, 0xf0], std::vec![0x00])
+                .with_error(nack),
+        ]);
+
+        let mut ds2484 = Ds2484Builder::default()
+            .with_i2c_retries(1)
+            .build(&mut i2c, DelayMock::new())
+            .unwrap();
+        assert!(matches!(ds2484.get_status(), Err(Ds2484Error::I2c(_))));
+        i2c.done();
+    }
+
+    #[test]
+    fn test_last_status() {
+        use crate::registers::{DEVICE_RST_CMD, DEVICE_STATUS_PTR, READ_PTR_CMD};
+        extern crate std;
+        use super::*;
+        use embedded_hal_mock::eh1::delay::NoopDelay as DelayMock;
+        use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+
+        let mut i2c = I2cMock::new(&[
+            I2cTransaction::write(0x18, std::vec![DEVICE_RST_CMD]),
+            I2cTransaction::write_read(
+                0x18,
+                std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR],
+                std::vec![0x10],
+            ),
+            I2cTransaction::write(0x18, std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR]),
+            I2cTransaction::read(0x18, std::vec![0x00]),
+            I2cTransaction::write(0x18, std::vec![0xd2, 0xf0]),
+            I2cTransaction::read(0x18, std::vec![0x00]),
+            I2cTransaction::write(0x18, std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR]),
+            I2cTransaction::read(0x18, std::vec![0x00]),
+            I2cTransaction::write(0x18, std::vec![0xb4]),
+            I2cTransaction::write(0x18, std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR]),
+            I2cTransaction::read(0x18, std::vec![0x06]), // PPD, SD
+        ]);
+
+        let mut ds2484 = Ds2484Builder::default()
+            .build(&mut i2c, DelayMock::new())
+            .unwrap();
+        assert!(!ds2484.last_status().onewire_busy());
+        assert!(matches!(ds2484.reset(), Err(OneWireError::ShortCircuit)));
+        let status = ds2484.last_status();
+        assert!(status.short_detect());
+        assert!(status.present_pulse_detect());
+        i2c.done();
+    }
+
+    #[test]
+    fn test_reset_detailed() {
+        use crate::registers::{DEVICE_RST_CMD, DEVICE_STATUS_PTR, READ_PTR_CMD};
+        extern crate std;
+        use super::*;
+        use embedded_hal_mock::eh1::delay::NoopDelay as DelayMock;
+        use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+
+        let mut i2c = I2cMock::new(&[
+            I2cTransaction::write(0x18, std::vec![DEVICE_RST_CMD]),
+            I2cTransaction::write_read(
+                0x18,
+                std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR],
+                std::vec![0x10],
+            ),
+            I2cTransaction::write(0x18, std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR]),
+            I2cTransaction::read(0x18, std::vec![0x00]),
+            I2cTransaction::write(0x18, std::vec![0xd2, 0xf0]),
+            I2cTransaction::read(0x18, std::vec![0x00]),
+            I2cTransaction::write(0x18, std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR]),
+            I2cTransaction::read(0x18, std::vec![0x00]),
+            I2cTransaction::write(0x18, std::vec![0xb4]),
+            I2cTransaction::write(0x18, std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR]),
+            I2cTransaction::read(0x18, std::vec![0x08]), // LL, no presence pulse
+        ]);
+
+        let mut ds2484 = Ds2484Builder::default()
+            .build(&mut i2c, DelayMock::new())
+            .unwrap();
+        assert_eq!(
+            ds2484.reset_detailed().unwrap(),
+            ResetOutcome {
+                presence: false,
+                short: false,
+                logic_level: true,
+                overdrive: false,
+                reset_time: 560_000,
+            }
+        );
+        i2c.done();
+    }
+
+    #[test]
+    fn test_raw_command() {
+        use crate::registers::{DEVICE_RST_CMD, DEVICE_STATUS_PTR, READ_PTR_CMD};
+        extern crate std;
+        use super::*;
+        use embedded_hal_mock::eh1::delay::NoopDelay as DelayMock;
+        use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+
+        let mut i2c = I2cMock::new(&[
+            I2cTransaction::write(0x18, std::vec![DEVICE_RST_CMD]),
+            I2cTransaction::write_read(
+                0x18,
+                std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR],
+                std::vec![0x10],
+            ),
+            I2cTransaction::write(0x18, std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR]),
+            I2cTransaction::read(0x18, std::vec![0x00]),
+            I2cTransaction::write(0x18, std::vec![0xd2, 0xf0]),
+            I2cTransaction::read(0x18, std::vec![0x00]),
+            // 1-Wire write byte issued without a modeled wrapper
+            I2cTransaction::write(0x18, std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR]),
+            I2cTransaction::read(0x18, std::vec![0x00]),
+            I2cTransaction::write(0x18, std::vec![0xa5, 0x33]),
+            I2cTransaction::write(0x18, std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR]),
+            I2cTransaction::read(0x18, std::vec![0x01]),
+            I2cTransaction::read(0x18, std::vec![0x00]),
+            // 1-Wire reset, no parameter byte
+            I2cTransaction::write(0x18, std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR]),
+            I2cTransaction::read(0x18, std::vec![0x00]),
+            I2cTransaction::write(0x18, std::vec![0xb4]),
+            I2cTransaction::write(0x18, std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR]),
+            I2cTransaction::read(0x18, std::vec![0x02]),
+        ]);
+
+        let mut ds2484 = Ds2484Builder::default()
+            .build(&mut i2c, DelayMock::new())
+            .unwrap();
+        let status = ds2484.raw_command(0xa5, Some(0x33)).unwrap();
+        assert!(!status.onewire_busy());
+        let status = ds2484.raw_command(0xb4, None).unwrap();
+        assert!(status.present_pulse_detect());
+        i2c.done();
+    }
+
+    #[test]
+    fn test_strong_pullup() {
+        use crate::registers::{DEVICE_RST_CMD, DEVICE_STATUS_PTR, READ_PTR_CMD};
+        extern crate std;
+        use super::*;
+        use embedded_hal_mock::eh1::delay::NoopDelay as DelayMock;
+        use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+
+        let mut i2c = I2cMock::new(&[
+            I2cTransaction::write(0x18, std::vec![DEVICE_RST_CMD]),
+            I2cTransaction::write_read(
+                0x18,
+                std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR],
+                std::vec![0x10],
+            ),
+            I2cTransaction::write(0x18, std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR]),
+            I2cTransaction::read(0x18, std::vec![0x00]),
+            I2cTransaction::write(0x18, std::vec![0xd2, 0xf0]),
+            I2cTransaction::read(0x18, std::vec![0x00]),
+            I2cTransaction::write_read(0x18, std::vec![READ_PTR_CMD, 0xc3], std::vec![0x00]),
+            I2cTransaction::write(0x18, std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR]),
+            I2cTransaction::read(0x18, std::vec![0x00]),
+            I2cTransaction::write(0x18, std::vec![0xd2, 0xb4]),
+            I2cTransaction::read(0x18, std::vec![0x04]),
+            I2cTransaction::write(0x18, std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR]),
+            I2cTransaction::read(0x18, std::vec![0x00]),
+            I2cTransaction::write(0x18, std::vec![0xa5, 0x44]),
+            // Enabling the active pullup keeps the strong pullup asserted
+            I2cTransaction::write_read(0x18, std::vec![READ_PTR_CMD, 0xc3], std::vec![0x04]),
+            I2cTransaction::write(0x18, std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR]),
+            I2cTransaction::read(0x18, std::vec![0x00]),
+            I2cTransaction::write(0x18, std::vec![0xd2, 0xa5]),
+            I2cTransaction::read(0x18, std::vec![0x05]),
+            I2cTransaction::write(0x18, std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR]),
+            I2cTransaction::read(0x18, std::vec![0x00]),
+            I2cTransaction::write(0x18, std::vec![0x96]),
+            I2cTransaction::read(0x18, std::vec![0x00]),
+            I2cTransaction::write_read(0x18, std::vec![READ_PTR_CMD, 0xe1], std::vec![0xff]),
+        ]);
+
+        let mut ds2484 = Ds2484Builder::default()
+            .build(&mut i2c, DelayMock::new())
+            .unwrap();
+        ds2484.set_strong_pullup(true).unwrap();
+        assert!(!ds2484.strong_pullup_active());
+        ds2484.write_byte(0x44).unwrap();
+        assert!(ds2484.strong_pullup_active());
+        ds2484.set_active_pullup(true).unwrap();
+        assert!(ds2484.strong_pullup_active());
+        ds2484.read_byte().unwrap();
+        assert!(!ds2484.strong_pullup_active());
+        i2c.done();
+    }
+
+    #[test]
+    fn test_powered_command() {
+        use crate::registers::{DEVICE_RST_CMD, DEVICE_STATUS_PTR, READ_PTR_CMD};
+        extern crate std;
+        use super::*;
+        use embedded_hal_mock::eh1::delay::NoopDelay as DelayMock;
+        use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+
+        let mut i2c = I2cMock::new(&[
+            I2cTransaction::write(0x18, std::vec![DEVICE_RST_CMD]),
+            I2cTransaction::write_read(
+                0x18,
+                std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR],
+                std::vec![0x10],
+            ),
+            I2cTransaction::write(0x18, std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR]),
+            I2cTransaction::read(0x18, std::vec![0x00]),
+            I2cTransaction::write(0x18, std::vec![0xd2, 0xf0]),
+            I2cTransaction::read(0x18, std::vec![0x00]),
+            // Enable the strong pullup
+            I2cTransaction::write_read(0x18, std::vec![READ_PTR_CMD, 0xc3], std::vec![0x00]),
+            I2cTransaction::write(0x18, std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR]),
+            I2cTransaction::read(0x18, std::vec![0x00]),
+            I2cTransaction::write(0x18, std::vec![0xd2, 0xb4]),
+            I2cTransaction::read(0x18, std::vec![0x04]),
+            // Convert T, and wait for the byte to be sent
+            I2cTransaction::write(0x18, std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR]),
+            I2cTransaction::read(0x18, std::vec![0x00]),
+            I2cTransaction::write(0x18, std::vec![0xa5, 0x44]),
+            I2cTransaction::write(0x18, std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR]),
+            I2cTransaction::read(0x18, std::vec![0x00]),
+            // End the strong pullup, and check the line
+            I2cTransaction::write_read(0x18, std::vec![READ_PTR_CMD, 0xc3], std::vec![0x04]),
+            I2cTransaction::write(0x18, std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR]),
+            I2cTransaction::read(0x18, std::vec![0x00]),
+            I2cTransaction::write(0x18, std::vec![0xd2, 0xf0]),
+            I2cTransaction::read(0x18, std::vec![0x00]),
+            I2cTransaction::write_read(
+                0x18,
+                std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR],
+                std::vec![0x08], // LL
+            ),
+        ]);
+
+        let mut ds2484 = Ds2484Builder::default()
+            .build(&mut i2c, DelayMock::new())
+            .unwrap();
+        ds2484.powered_command(0x44, 750_000_000).unwrap();
+        assert!(!ds2484.strong_pullup_active());
+        i2c.done();
+    }
+
+    #[test]
+    fn test_write_byte_powered() {
+        use crate::registers::{DEVICE_RST_CMD, DEVICE_STATUS_PTR, READ_PTR_CMD};
+        extern crate std;
+        use super::*;
+        use embedded_hal_mock::eh1::delay::NoopDelay as DelayMock;
+        use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+
+        let mut i2c = I2cMock::new(&[
+            I2cTransaction::write(0x18, std::vec![DEVICE_RST_CMD]),
+            I2cTransaction::write_read(
+                0x18,
+                std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR],
+                std::vec![0x10],
+            ),
+            I2cTransaction::write(0x18, std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR]),
+            I2cTransaction::read(0x18, std::vec![0x00]),
+            I2cTransaction::write(0x18, std::vec![0xd2, 0xf0]),
+            I2cTransaction::read(0x18, std::vec![0x00]),
+            // Enable the strong pullup
+            I2cTransaction::write_read(0x18, std::vec![READ_PTR_CMD, 0xc3], std::vec![0x00]),
+            I2cTransaction::write(0x18, std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR]),
+            I2cTransaction::read(0x18, std::vec![0x00]),
+            I2cTransaction::write(0x18, std::vec![0xd2, 0xb4]),
+            I2cTransaction::read(0x18, std::vec![0x04]),
+            // Convert T, the strong pullup is left on
+            I2cTransaction::write(0x18, std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR]),
+            I2cTransaction::read(0x18, std::vec![0x00]),
+            I2cTransaction::write(0x18, std::vec![0xa5, 0x44]),
+        ]);
+
+        let mut ds2484 = Ds2484Builder::default()
+            .build(&mut i2c, DelayMock::new())
+            .unwrap();
+        ds2484.write_byte_powered(0x44).unwrap();
+        assert!(ds2484.strong_pullup_active());
+        i2c.done();
+    }
+
+    #[test]
+    fn test_port_config_encoding() {
+        use super::*;
+
+        // The presence-detect time is decoded in ns, not in units of 100 ns
+        let cfg = OneWirePortConfiguration::default();
+        assert_eq!(cfg.presence_detect_time(), 68000);
+        assert_eq!(cfg.presence_detect_time_overdrive(), 8000);
+        // Values beyond the longest setting are clamped to it, not to the shortest one
+        let builder: OneWireConfigurationBuilder = OneWirePortConfiguration::default().into();
+        let cfg = builder.reset_pulse(800000, 80000).build();
+        assert_eq!(cfg.reset_time(), 740000);
+        assert_eq!(cfg.reset_time_overdrive(), 74000);
+        // The write-zero recovery time is set in ns, not in units of 10 ns
+        let builder: OneWireConfigurationBuilder = OneWirePortConfiguration::default().into();
+        let cfg = builder.write_zero_recovery_time(10250).build();
+        assert_eq!(cfg.write_zero_recovery_time(), 10250);
+        // Selecting the 1000 Ohm pullup is not a no-op
+        let builder: OneWireConfigurationBuilder = OneWirePortConfiguration::default().into();
+        let cfg = builder.weak_pullup_resistor(500).build();
+        assert_eq!(cfg.weak_pullup_resistor(), 500);
+        let builder: OneWireConfigurationBuilder = cfg.into();
+        let cfg = builder.weak_pullup_resistor(1000).build();
+        assert_eq!(cfg.weak_pullup_resistor(), 1000);
+    }
+
+    #[test]
+    fn test_port_timing() {
+        use super::*;
+
+        let timing = OneWirePortTiming::default();
+        assert_eq!(timing.presence_detect_time, 68000);
+        assert_eq!(timing.weak_pullup_resistor, 1000);
+        let cfg = timing.encode::<()>().unwrap();
+        assert_eq!(cfg, OneWirePortConfiguration::default());
+        // Values in between settings are rounded up
+        let timing = OneWirePortTiming {
+            reset_time: 601000,
+            ..Default::default()
+        };
+        assert_eq!(timing.encode::<()>().unwrap().reset_time(), 620000);
+        let timing = OneWirePortTiming {
+            write_zero_recovery_time: 30000,
+            ..Default::default()
+        };
+        assert!(matches!(
+            timing.encode::<()>(),
+            Err(Ds2484Error::InvalidValue("write_zero_recovery_time"))
+        ));
+    }
+
+    #[cfg(feature = "triplet-read")]
+    #[test]
+    fn test_triplet_direction() {
+        use crate::registers::{DEVICE_RST_CMD, DEVICE_STATUS_PTR, READ_PTR_CMD};
+        extern crate std;
+        use super::*;
+        use embedded_hal_mock::eh1::delay::NoopDelay as DelayMock;
+        use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+
+        let mut i2c = I2cMock::new(&[
+            I2cTransaction::write(0x18, std::vec![DEVICE_RST_CMD]),
+            I2cTransaction::write_read(
+                0x18,
+                std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR],
+                std::vec![0x10],
+            ),
+            I2cTransaction::write(0x18, std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR]),
+            I2cTransaction::read(0x18, std::vec![0x00]),
+            I2cTransaction::write(0x18, std::vec![0xd2, 0xf0]),
+            I2cTransaction::read(0x18, std::vec![0x00]),
+            // The line was found idle after the configuration write
+            I2cTransaction::write(0x18, std::vec![0x78, 0xff]),
+            I2cTransaction::read(0x18, std::vec![0x80]), // both bits 0, direction 1 taken
+            // The next triplet follows as soon as the line is idle
+            I2cTransaction::write(0x18, std::vec![0x78, 0x00]),
+            I2cTransaction::read(0x18, std::vec![0xa0]), // bits 1 and 0, direction 1 taken
+        ]);
+
+        let mut ds2484 = Ds2484Builder::default()
+            .build(&mut i2c, DelayMock::new())
+            .unwrap();
+        assert_eq!(ds2484.read_triplet(true).unwrap(), (false, false, true));
+        assert_eq!(ds2484.read_triplet(false).unwrap(), (true, false, true));
+        i2c.done();
+    }
+
+    #[cfg(feature = "stats")]
+    #[test]
+    fn test_stats() {
+        use crate::registers::{DEVICE_RST_CMD, DEVICE_STATUS_PTR, READ_PTR_CMD};
+        extern crate std;
+        use super::*;
+        use embedded_hal_mock::eh1::delay::NoopDelay as DelayMock;
+        use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+
+        let mut i2c = I2cMock::new(&[
+            I2cTransaction::write(0x18, std::vec![DEVICE_RST_CMD]),
+            I2cTransaction::write_read(
+                0x18,
+                std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR],
+                std::vec![0x10],
+            ),
+            I2cTransaction::write(0x18, std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR]),
+            I2cTransaction::read(0x18, std::vec![0x00]),
+            I2cTransaction::write(0x18, std::vec![0xd2, 0xf0]),
+            I2cTransaction::read(0x18, std::vec![0x00]),
+            I2cTransaction::write(0x18, std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR]),
+            I2cTransaction::read(0x18, std::vec![0x01]), // 1-Wire busy
+            I2cTransaction::read(0x18, std::vec![0x00]),
+            I2cTransaction::write(0x18, std::vec![0xa5, 0xcc]),
+        ]);
+
+        let mut ds2484 = Ds2484Builder::default()
+            .build(&mut i2c, DelayMock::new())
+            .unwrap();
+        ds2484.write_byte(0xcc).unwrap();
+        ds2484.record_crc_failure();
+        let stats = *ds2484.stats();
+        assert_eq!(stats.bytes_written, 1);
+        assert_eq!(stats.busy_polls, 1);
+        assert_eq!(stats.crc_failures, 1);
+        assert_eq!(stats.resets, 0);
+        ds2484.clear_stats();
+        assert_eq!(*ds2484.stats(), BusStatistics::default());
+        i2c.done();
+    }
+
+    #[cfg(feature = "embedded-hal-02")]
+    #[test]
+    fn test_compat() {
+        use crate::registers::{DEVICE_RST_CMD, DEVICE_STATUS_PTR, READ_PTR_CMD};
+        extern crate std;
+        use super::*;
+        use embedded_hal_mock::eh0::delay::NoopDelay as DelayMock;
+        use embedded_hal_mock::eh0::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+
+        let mut i2c = I2cMock::new(&[
+            I2cTransaction::write(0x18, std::vec![DEVICE_RST_CMD]),
+            I2cTransaction::write_read(
+                0x18,
+                std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR],
+                std::vec![0x10],
+            ),
+            I2cTransaction::write(0x18, std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR]),
+            I2cTransaction::read(0x18, std::vec![0x00]),
+            I2cTransaction::write(0x18, std::vec![0xd2, 0xf0]),
+            I2cTransaction::read(0x18, std::vec![0x00]),
+        ]);
+
+        let ds2484 = Ds2484Builder::default()
+            .build(Compat(i2c.clone()), Compat(DelayMock::new()))
+            .unwrap();
+        drop(ds2484);
+        i2c.done();
+    }
+
+    #[test]
+    fn test_release() {
+        use crate::registers::{DEVICE_RST_CMD, DEVICE_STATUS_PTR, READ_PTR_CMD};
+        extern crate std;
+        use super::*;
+        use embedded_hal_mock::eh1::delay::CheckedDelay;
+        use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+
+        let transactions = [
+            I2cTransaction::write(0x18, std::vec![DEVICE_RST_CMD]),
+            I2cTransaction::write_read(
+                0x18,
+                std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR],
+                std::vec![0x10],
+            ),
+            I2cTransaction::write(0x18, std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR]),
+            I2cTransaction::read(0x18, std::vec![0x00]),
+            I2cTransaction::write(0x18, std::vec![0xd2, 0xf0]),
+            I2cTransaction::read(0x18, std::vec![0x00]),
+            // The device is reset on release
+            I2cTransaction::write(0x18, std::vec![DEVICE_RST_CMD]),
+            I2cTransaction::write_read(
+                0x18,
+                std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR],
+                std::vec![0x10],
+            ),
+        ];
+        // The async driver reads the status right after the device reset, and the
+        // configuration in the same transaction as it is written
+        let transactions_async = [
+            I2cTransaction::write(0x18, std::vec![DEVICE_RST_CMD]),
+            I2cTransaction::read(0x18, std::vec![0x10]),
+            I2cTransaction::write(0x18, std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR]),
+            I2cTransaction::read(0x18, std::vec![0x00]),
+            I2cTransaction::write_read(0x18, std::vec![0xd2, 0xf0], std::vec![0x00]),
+            I2cTransaction::write(0x18, std::vec![DEVICE_RST_CMD]),
+            I2cTransaction::read(0x18, std::vec![0x10]),
+        ];
+
+        let ds2484 = Ds2484Builder::default()
+            .build(I2cMock::new(&transactions), CheckedDelay::new(&[]))
+            .unwrap();
+        let (mut i2c, mut delay) = ds2484.release();
+        i2c.done();
+        delay.done();
+
+        let ds2484 = block_on(Ds2484Builder::default().build_async(
+            Async(I2cMock::new(&transactions_async)),
+            Async(CheckedDelay::new(&[])),
+        ))
+        .unwrap();
+        let (Async(mut i2c), Async(mut delay)) = block_on(ds2484.release_async());
+        i2c.done();
+        delay.done();
+    }
+
+    #[cfg(test)]
+    fn block_on<F: core::future::Future>(fut: F) -> F::Output {
+        let mut fut = core::pin::pin!(fut);
+        use core::task::{Context, Poll, Waker};
+        let mut cx = Context::from_waker(Waker::noop());
+        loop {
+            if let Poll::Ready(out) = fut.as_mut().poll(&mut cx) {
+                return out;
+            }
+        }
+    }
+
+    /// The async interface of the sync mocks.
+    #[cfg(test)]
+    struct Async<T>(T);
+    #[cfg(test)]
+    impl<T: embedded_hal::i2c::ErrorType> embedded_hal::i2c::ErrorType for Async<T> {
+        type Error = T::Error;
+    }
+    #[cfg(test)]
+    impl<T: embedded_hal::i2c::I2c> embedded_hal_async::i2c::I2c for Async<T> {
+        async fn read(&mut self, address: u8, read: &mut [u8]) -> Result<(), Self::Error> {
+            self.0.read(address, read)
+        }
+
+        async fn write(&mut self, address: u8, write: &[u8]) -> Result<(), Self::Error> {
+            self.0.write(address, write)
+        }
+
+        async fn write_read(
+            &mut self,
+            address: u8,
+            write: &[u8],
+            read: &mut [u8],
+        ) -> Result<(), Self::Error> {
+            self.0.write_read(address, write, read)
+        }
+
+        async fn transaction(
+            &mut self,
+            address: u8,
+            operations: &mut [embedded_hal::i2c::Operation<'_>],
+        ) -> Result<(), Self::Error> {
+            self.0.transaction(address, operations)
+        }
+    }
+    #[cfg(test)]
+    impl<T: embedded_hal::delay::DelayNs> embedded_hal_async::delay::DelayNs for Async<T> {
+        async fn delay_ns(&mut self, ns: u32) {
+            self.0.delay_ns(ns)
+        }
+    }
+
+    #[test]
+    fn test_logic_level() {
+        use crate::registers::{DEVICE_RST_CMD, DEVICE_STATUS_PTR, READ_PTR_CMD};
+        extern crate std;
+        use super::*;
+        use embedded_hal_mock::eh1::delay::NoopDelay;
+        use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+
+        let transactions = [
+            I2cTransaction::write(0x18, std::vec![DEVICE_RST_CMD]),
+            I2cTransaction::write_read(
+                0x18,
+                std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR],
+                std::vec![0x10],
+            ),
+            I2cTransaction::write(0x18, std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR]),
+            I2cTransaction::read(0x18, std::vec![0x00]),
+            I2cTransaction::write(0x18, std::vec![0xd2, 0xf0]),
+            I2cTransaction::read(0x18, std::vec![0x00]),
+            // Line high
+            I2cTransaction::write_read(
+                0x18,
+                std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR],
+                std::vec![0x08],
+            ),
+            // Line low
+            I2cTransaction::write_read(
+                0x18,
+                std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR],
+                std::vec![0x00],
+            ),
+        ];
+        let transactions_async = [
+            I2cTransaction::write(0x18, std::vec![DEVICE_RST_CMD]),
+            I2cTransaction::read(0x18, std::vec![0x10]),
+            I2cTransaction::write(0x18, std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR]),
+            I2cTransaction::read(0x18, std::vec![0x00]),
+            I2cTransaction::write_read(0x18, std::vec![0xd2, 0xf0], std::vec![0x00]),
+            I2cTransaction::write_read(
+                0x18,
+                std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR],
+                std::vec![0x08],
+            ),
+            I2cTransaction::write_read(
+                0x18,
+                std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR],
+                std::vec![0x00],
+            ),
+        ];
+
+        let mut i2c = I2cMock::new(&transactions);
+        let mut ds2484 = Ds2484Builder::default()
+            .build(&mut i2c, NoopDelay::new())
+            .unwrap();
+        assert!(ds2484.read_logic_level().unwrap());
+        assert!(!ds2484.read_logic_level().unwrap());
+        i2c.done();
+
+        let mut i2c = I2cMock::new(&transactions_async);
+        let mut ds2484 = block_on(
+            Ds2484Builder::default().build_async(Async(&mut i2c), Async(NoopDelay::new())),
+        )
+        .unwrap();
+        assert!(block_on(ds2484.read_logic_level_async()).unwrap());
+        assert!(!block_on(ds2484.read_logic_level_async()).unwrap());
+        i2c.done();
+    }
+
+    #[test]
+    fn test_no_recovery_async() {
+        use crate::registers::{DEVICE_RST_CMD, DEVICE_STATUS_PTR, READ_PTR_CMD};
+        extern crate std;
+        use super::*;
+        use embedded_hal_mock::eh1::delay::NoopDelay;
+        use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+
+        let mut i2c = I2cMock::new(&[
+            I2cTransaction::write(0x18, std::vec![DEVICE_RST_CMD]),
+            I2cTransaction::read(0x18, std::vec![0x10]),
+            I2cTransaction::write(0x18, std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR]),
+            I2cTransaction::read(0x18, std::vec![0x00]),
+            I2cTransaction::write_read(0x18, std::vec![0xd2, 0xf0], std::vec![0x00]),
+            // The device has reset since the configuration was written
+            I2cTransaction::write(0x18, std::vec![READ_PTR_CMD, DEVICE_STATUS_PTR]),
+            I2cTransaction::read(0x18, std::vec![0x10]),
+        ]);
+
+        let mut ds2484 = block_on(
+            Ds2484Builder::default().build_async(Async(&mut i2c), Async(NoopDelay::new())),
+        )
+        .unwrap();
+        assert!(matches!(
+            block_on(OneWireAsync::write_byte(&mut ds2484, 0xcc)),
+            Err(OneWireError::Other(Ds2484Error::DeviceReset))
+        ));
+        // Operations fail without bus traffic until the configuration is written again
+        assert!(matches!(
+            block_on(OneWireAsync::write_byte(&mut ds2484, 0xcc)),
+            Err(OneWireError::BusUninitialized)
+        ));
+        i2c.done();
+    }
 }
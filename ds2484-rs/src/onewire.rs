@@ -1,5 +1,5 @@
 use crate::{
-    DeviceConfiguration, Ds2484, Ds2484Error, Interact,
+    DeviceConfiguration, Ds2484, Ds2484Error, Interact, WaitStrategy,
     registers::{DeviceStatus, READ_PTR_CMD},
 };
 use embedded_hal::{
@@ -7,7 +7,8 @@ use embedded_hal::{
     i2c::{I2c, SevenBitAddress},
 };
 use embedded_onewire::{
-    OneWire, OneWireError, OneWireResult, OneWireStatus, consts::ONEWIRE_SKIP_ROM_CMD_OD,
+    OneWire, OneWireError, OneWireResult, OneWireStatus, OneWireStrongPullup,
+    consts::ONEWIRE_SKIP_ROM_CMD_OD,
 };
 
 pub(crate) const ONEWIRE_RESET_CMD: u8 = 0xb4;
@@ -18,98 +19,137 @@ pub(crate) const ONEWIRE_SINGLE_BIT: u8 = 0x87;
 #[cfg(feature = "triplet-read")]
 pub(crate) const ONEWIRE_TRIPLET: u8 = 0x78;
 
-impl<I2C: I2c<SevenBitAddress>, D: DelayNs> OneWire for Ds2484<I2C, D> {
+impl<I2C: I2c<SevenBitAddress>, D: DelayNs, W: WaitStrategy> OneWire for Ds2484<I2C, D, W> {
     type Status = DeviceStatus;
 
     type BusError = Ds2484Error<I2C::Error>;
 
     fn reset(&mut self) -> OneWireResult<Self::Status, Self::BusError> {
-        if self.reset {
-            return Err(OneWireError::BusUninitialized);
-        }
-        self.onewire_wait()?;
-        self.i2c
-            .write(self.addr, &[ONEWIRE_RESET_CMD])
-            .map_err(Ds2484Error::from)?;
-        self.onewire_wait().map(|v| {
-            if v.short_detect() {
-                Err(OneWireError::ShortCircuit)
-            } else if !v.presence() {
-                Err(OneWireError::NoDevicePresent)
-            } else {
-                Ok(v)
-            }
-        })?
+        self.recovering(|dev| {
+            dev.onewire_ready()?;
+            dev.i2c_write(&[ONEWIRE_RESET_CMD])?;
+            dev.command_issued(false);
+            dev.resetting = true;
+            dev.record(|s| s.resets = s.resets.wrapping_add(1));
+            dev.onewire_settle(dev.port.reset_cycle_time(dev.overdrive))
+                .map(|v| {
+                    if v.short_detect() {
+                        Err(OneWireError::ShortCircuit)
+                    } else if !v.presence() {
+                        Err(OneWireError::NoDevicePresent)
+                    } else {
+                        Ok(v)
+                    }
+                })?
+        })
     }
 
     fn write_byte(&mut self, byte: u8) -> OneWireResult<(), Self::BusError> {
-        if self.reset {
-            return Err(OneWireError::BusUninitialized);
-        }
-        self.onewire_wait()?;
-        self.i2c
-            .write(self.addr, &[ONEWIRE_WRITE_BYTE, byte])
-            .map_err(Ds2484Error::from)?;
-        Ok(())
+        self.recovering(|dev| {
+            dev.onewire_ready()?;
+            dev.i2c_write(&[ONEWIRE_WRITE_BYTE, byte])?;
+            dev.command_issued(true);
+            dev.record(|s| s.bytes_written = s.bytes_written.wrapping_add(1));
+            if dev.pipelined {
+                dev.onewire_settle(8 * dev.port.slot_time(dev.overdrive))?;
+            }
+            Ok(())
+        })
     }
 
     fn read_byte(&mut self) -> OneWireResult<u8, Self::BusError> {
-        if self.reset {
-            return Err(OneWireError::BusUninitialized);
-        }
-        self.onewire_wait()?;
-        self.i2c
-            .write(self.addr, &[ONEWIRE_READ_BYTE])
-            .map_err(Ds2484Error::from)?;
-        self.onewire_wait()?;
-        let mut val = [0; 1];
-        self.i2c
-            .write_read(self.addr, &[READ_PTR_CMD, ONEWIRE_READ_DATA_PTR], &mut val)
-            .map_err(Ds2484Error::from)?;
-        Ok(val[0])
+        self.recovering(|dev| {
+            dev.onewire_ready()?;
+            dev.i2c_write(&[ONEWIRE_READ_BYTE])?;
+            dev.command_issued(false);
+            dev.record(|s| s.bytes_read = s.bytes_read.wrapping_add(1));
+            if dev.pipelined {
+                dev.delay.delay_ns(8 * dev.port.slot_time(dev.overdrive));
+            }
+            // The read pointer is already at the status register
+            dev.onewire_poll()?;
+            let mut val = [0; 1];
+            dev.i2c_write_read(&[READ_PTR_CMD, ONEWIRE_READ_DATA_PTR], &mut val)?;
+            Ok(val[0])
+        })
+    }
+
+    fn write_bytes(&mut self, bytes: &[u8]) -> OneWireResult<(), Self::BusError> {
+        self.recovering(|dev| {
+            let byte_time = 8 * dev.port.slot_time(dev.overdrive);
+            dev.onewire_ready()?;
+            for &byte in bytes.iter() {
+                dev.i2c_write(&[ONEWIRE_WRITE_BYTE, byte])?;
+                dev.command_issued(true);
+                dev.record(|s| s.bytes_written = s.bytes_written.wrapping_add(1));
+                // Sleep through the byte so that the first status poll finds the line idle
+                dev.delay.delay_ns(byte_time);
+                dev.onewire_poll()?;
+            }
+            Ok(())
+        })
+    }
+
+    fn read_bytes(&mut self, buf: &mut [u8]) -> OneWireResult<(), Self::BusError> {
+        self.recovering(|dev| {
+            let byte_time = 8 * dev.port.slot_time(dev.overdrive);
+            dev.onewire_ready()?;
+            for byte in buf.iter_mut() {
+                dev.i2c_write(&[ONEWIRE_READ_BYTE])?;
+                dev.command_issued(false);
+                dev.record(|s| s.bytes_read = s.bytes_read.wrapping_add(1));
+                // Sleep through the byte so that the first status poll finds the line idle
+                dev.delay.delay_ns(byte_time);
+                dev.onewire_poll()?;
+                let mut val = [0; 1];
+                dev.i2c_write_read(&[READ_PTR_CMD, ONEWIRE_READ_DATA_PTR], &mut val)?;
+                *byte = val[0];
+            }
+            Ok(())
+        })
     }
 
     fn write_bit(&mut self, bit: bool) -> OneWireResult<(), Self::BusError> {
-        if self.reset {
-            return Err(OneWireError::BusUninitialized);
-        }
-        self.onewire_wait()?;
-        self.i2c
-            .write(
-                self.addr,
-                &[ONEWIRE_SINGLE_BIT, { if bit { 0x80 } else { 0x0 } }],
-            )
-            .map_err(Ds2484Error::from)?;
-        Ok(())
+        self.recovering(|dev| {
+            dev.onewire_ready()?;
+            dev.i2c_write(&[ONEWIRE_SINGLE_BIT, { if bit { 0x80 } else { 0x0 } }])?;
+            dev.command_issued(true);
+            if dev.pipelined {
+                dev.onewire_settle(dev.port.slot_time(dev.overdrive))?;
+            }
+            Ok(())
+        })
     }
 
     fn read_bit(&mut self) -> OneWireResult<bool, Self::BusError> {
-        if self.reset {
-            return Err(OneWireError::BusUninitialized);
-        }
-        self.write_bit(true)?;
-        Ok(self.onewire_wait()?.single_bit_result())
+        self.recovering(|dev| {
+            dev.write_bit(true)?;
+            Ok(dev.onewire_wait()?.single_bit_result())
+        })
     }
 
     #[cfg(feature = "triplet-read")]
-    fn read_triplet(&mut self) -> OneWireResult<(bool, bool, bool), Self::BusError> {
-        if self.reset {
-            return Err(OneWireError::BusUninitialized);
-        }
-        let direction = self.onewire_wait()?.branch_dir_taken();
-        self.i2c
-            .write(
-                self.addr,
-                &[ONEWIRE_TRIPLET, { if direction { 0xff } else { 0x0 } }],
-            )
-            .map_err(Ds2484Error::from)?;
-        Ok(self.onewire_wait().map(|v| {
-            (
-                v.single_bit_result(),
-                v.triplet_second_bit(),
-                v.branch_dir_taken(),
-            )
-        })?)
+    fn read_triplet(
+        &mut self,
+        direction: bool,
+    ) -> OneWireResult<(bool, bool, bool), Self::BusError> {
+        self.recovering(|dev| {
+            // Back-to-back triplets during a search skip the wait, the line was found idle
+            if !dev.idle {
+                dev.onewire_wait()?;
+            }
+            dev.i2c_write(&[ONEWIRE_TRIPLET, { if direction { 0xff } else { 0x0 } }])?;
+            dev.command_issued(false);
+            // Sleep through the triplet, the read pointer is already at the status register
+            dev.delay.delay_ns(3 * dev.port.slot_time(dev.overdrive));
+            Ok(dev.onewire_poll().map(|v| {
+                (
+                    v.single_bit_result(),
+                    v.triplet_second_bit(),
+                    v.branch_dir_taken(),
+                )
+            })?)
+        })
     }
 
     fn get_overdrive_mode(&mut self) -> bool {
@@ -140,3 +180,12 @@ impl<I2C: I2c<SevenBitAddress>, D: DelayNs> OneWire for Ds2484<I2C, D> {
         Ok(())
     }
 }
+
+impl<I2C: I2c<SevenBitAddress>, D: DelayNs, W: WaitStrategy> OneWireStrongPullup
+    for Ds2484<I2C, D, W>
+{
+    fn write_byte_powered(&mut self, byte: u8) -> OneWireResult<(), Self::BusError> {
+        self.set_strong_pullup(true)?;
+        self.write_byte(byte)
+    }
+}
@@ -1,7 +1,7 @@
 #[cfg(feature = "triplet-read")]
 use crate::onewire::ONEWIRE_TRIPLET;
 use crate::{
-    DeviceConfiguration, Ds2484, Ds2484Error, InteractAsync,
+    DeviceConfiguration, Ds2484, Ds2484Error, InteractAsync, WaitStrategy,
     onewire::{
         ONEWIRE_READ_BYTE, ONEWIRE_READ_DATA_PTR, ONEWIRE_RESET_CMD, ONEWIRE_SINGLE_BIT,
         ONEWIRE_WRITE_BYTE,
@@ -12,88 +12,163 @@ use embedded_hal_async::{
     delay::DelayNs as DelayNsAsync,
     i2c::{I2c as I2cAsync, SevenBitAddress as SevenBitAddressAsync},
 };
-use embedded_onewire::{consts::ONEWIRE_SKIP_ROM_CMD_OD, OneWireAsync, OneWireError, OneWireResult, OneWireStatus};
+use embedded_onewire::{
+    OneWireAsync, OneWireError, OneWireResult, OneWireStatus, OneWireStrongPullupAsync,
+    consts::ONEWIRE_SKIP_ROM_CMD_OD,
+};
 
-impl<I2C: I2cAsync<SevenBitAddressAsync>, D: DelayNsAsync> OneWireAsync for Ds2484<I2C, D> {
+impl<I2C: I2cAsync<SevenBitAddressAsync>, D: DelayNsAsync, W: WaitStrategy> OneWireAsync
+    for Ds2484<I2C, D, W>
+{
     type Status = DeviceStatus;
 
     type BusError = Ds2484Error<I2C::Error>;
 
     async fn reset(&mut self) -> OneWireResult<Self::Status, Self::BusError> {
-        self.onewire_wait_async().await?;
-        self.i2c
-            .write(self.addr, &[ONEWIRE_RESET_CMD])
-            .await
-            .map_err(Ds2484Error::from)?;
-        self.onewire_wait_async().await.map(|v| {
-            if v.short_detect() {
-                Err(OneWireError::ShortCircuit)
-            } else if !v.presence() {
-                Err(OneWireError::NoDevicePresent)
-            } else {
-                Ok(v)
-            }
-        })?
+        self.recovering_async(async |dev| {
+            dev.onewire_ready_async().await?;
+            dev.i2c_write_async(&[ONEWIRE_RESET_CMD]).await?;
+            dev.command_issued(false);
+            dev.resetting = true;
+            dev.record(|s| s.resets = s.resets.wrapping_add(1));
+            dev.onewire_settle_async(dev.port.reset_cycle_time(dev.overdrive))
+                .await
+                .map(|v| {
+                    if v.short_detect() {
+                        Err(OneWireError::ShortCircuit)
+                    } else if !v.presence() {
+                        Err(OneWireError::NoDevicePresent)
+                    } else {
+                        Ok(v)
+                    }
+                })?
+        })
+        .await
     }
 
     async fn write_byte(&mut self, byte: u8) -> OneWireResult<(), Self::BusError> {
-        self.onewire_wait_async().await?;
-        self.i2c
-            .write(self.addr, &[ONEWIRE_WRITE_BYTE, byte])
-            .await
-            .map_err(Ds2484Error::from)?;
-        Ok(())
+        self.recovering_async(async |dev| {
+            dev.onewire_ready_async().await?;
+            dev.i2c_write_async(&[ONEWIRE_WRITE_BYTE, byte]).await?;
+            dev.command_issued(true);
+            dev.record(|s| s.bytes_written = s.bytes_written.wrapping_add(1));
+            if dev.pipelined {
+                dev.onewire_settle_async(8 * dev.port.slot_time(dev.overdrive))
+                    .await?;
+            }
+            Ok(())
+        })
+        .await
     }
 
     async fn read_byte(&mut self) -> OneWireResult<u8, Self::BusError> {
-        self.onewire_wait_async().await?;
-        self.i2c
-            .write(self.addr, &[ONEWIRE_READ_BYTE])
-            .await
-            .map_err(Ds2484Error::from)?;
-        self.onewire_wait_async().await?;
-        let mut val = [0; 1];
-        self.i2c
-            .write_read(self.addr, &[READ_PTR_CMD, ONEWIRE_READ_DATA_PTR], &mut val)
-            .await
-            .map_err(Ds2484Error::from)?;
-        Ok(val[0])
+        self.recovering_async(async |dev| {
+            dev.onewire_ready_async().await?;
+            dev.i2c_write_async(&[ONEWIRE_READ_BYTE]).await?;
+            dev.command_issued(false);
+            dev.record(|s| s.bytes_read = s.bytes_read.wrapping_add(1));
+            if dev.pipelined {
+                dev.delay
+                    .delay_ns(8 * dev.port.slot_time(dev.overdrive))
+                    .await;
+            }
+            // The read pointer is already at the status register
+            dev.onewire_poll_async().await?;
+            let mut val = [0; 1];
+            dev.i2c_write_read_async(&[READ_PTR_CMD, ONEWIRE_READ_DATA_PTR], &mut val)
+                .await?;
+            Ok(val[0])
+        })
+        .await
+    }
+
+    async fn write_bytes(&mut self, bytes: &[u8]) -> OneWireResult<(), Self::BusError> {
+        self.recovering_async(async |dev| {
+            let byte_time = 8 * dev.port.slot_time(dev.overdrive);
+            dev.onewire_ready_async().await?;
+            for &byte in bytes.iter() {
+                dev.i2c_write_async(&[ONEWIRE_WRITE_BYTE, byte]).await?;
+                dev.command_issued(true);
+                dev.record(|s| s.bytes_written = s.bytes_written.wrapping_add(1));
+                // Sleep through the byte so that the first status poll finds the line idle
+                dev.delay.delay_ns(byte_time).await;
+                dev.onewire_poll_async().await?;
+            }
+            Ok(())
+        })
+        .await
+    }
+
+    async fn read_bytes(&mut self, buf: &mut [u8]) -> OneWireResult<(), Self::BusError> {
+        self.recovering_async(async |dev| {
+            let byte_time = 8 * dev.port.slot_time(dev.overdrive);
+            dev.onewire_ready_async().await?;
+            for byte in buf.iter_mut() {
+                dev.i2c_write_async(&[ONEWIRE_READ_BYTE]).await?;
+                dev.command_issued(false);
+                dev.record(|s| s.bytes_read = s.bytes_read.wrapping_add(1));
+                // Sleep through the byte so that the first status poll finds the line idle
+                dev.delay.delay_ns(byte_time).await;
+                dev.onewire_poll_async().await?;
+                let mut val = [0; 1];
+                dev.i2c_write_read_async(&[READ_PTR_CMD, ONEWIRE_READ_DATA_PTR], &mut val)
+                    .await?;
+                *byte = val[0];
+            }
+            Ok(())
+        })
+        .await
     }
 
     async fn write_bit(&mut self, bit: bool) -> OneWireResult<(), Self::BusError> {
-        self.onewire_wait_async().await?;
-        self.i2c
-            .write(
-                self.addr,
-                &[ONEWIRE_SINGLE_BIT, { if bit { 0x80 } else { 0x0 } }],
-            )
-            .await
-            .map_err(Ds2484Error::from)?;
-        Ok(())
+        self.recovering_async(async |dev| {
+            dev.onewire_ready_async().await?;
+            dev.i2c_write_async(&[ONEWIRE_SINGLE_BIT, { if bit { 0x80 } else { 0x0 } }])
+                .await?;
+            dev.command_issued(true);
+            if dev.pipelined {
+                dev.onewire_settle_async(dev.port.slot_time(dev.overdrive))
+                    .await?;
+            }
+            Ok(())
+        })
+        .await
     }
 
     async fn read_bit(&mut self) -> OneWireResult<bool, Self::BusError> {
-        self.write_bit(true).await?;
-        Ok(self.onewire_wait_async().await?.single_bit_result())
+        self.recovering_async(async |dev| {
+            dev.write_bit(true).await?;
+            Ok(dev.onewire_wait_async().await?.single_bit_result())
+        })
+        .await
     }
 
     #[cfg(feature = "triplet-read")]
-    async fn read_triplet(&mut self) -> OneWireResult<(bool, bool, bool), Self::BusError> {
-        let direction = self.onewire_wait_async().await?.branch_dir_taken();
-        self.i2c
-            .write(
-                self.addr,
-                &[ONEWIRE_TRIPLET, { if direction { 0xff } else { 0x0 } }],
-            )
-            .await
-            .map_err(Ds2484Error::from)?;
-        Ok(self.onewire_wait_async().await.map(|v| {
-            (
-                v.single_bit_result(),
-                v.triplet_second_bit(),
-                v.branch_dir_taken(),
-            )
-        })?)
+    async fn read_triplet(
+        &mut self,
+        direction: bool,
+    ) -> OneWireResult<(bool, bool, bool), Self::BusError> {
+        self.recovering_async(async |dev| {
+            // Back-to-back triplets during a search skip the wait, the line was found idle
+            if !dev.idle {
+                dev.onewire_wait_async().await?;
+            }
+            dev.i2c_write_async(&[ONEWIRE_TRIPLET, { if direction { 0xff } else { 0x0 } }])
+                .await?;
+            dev.command_issued(false);
+            // Sleep through the triplet, the read pointer is already at the status register
+            dev.delay
+                .delay_ns(3 * dev.port.slot_time(dev.overdrive))
+                .await;
+            Ok(dev.onewire_poll_async().await.map(|v| {
+                (
+                    v.single_bit_result(),
+                    v.triplet_second_bit(),
+                    v.branch_dir_taken(),
+                )
+            })?)
+        })
+        .await
     }
 
     fn get_overdrive_mode(&mut self) -> bool {
@@ -124,3 +199,12 @@ impl<I2C: I2cAsync<SevenBitAddressAsync>, D: DelayNsAsync> OneWireAsync for Ds24
         Ok(())
     }
 }
+
+impl<I2C: I2cAsync<SevenBitAddressAsync>, D: DelayNsAsync, W: WaitStrategy> OneWireStrongPullupAsync
+    for Ds2484<I2C, D, W>
+{
+    async fn write_byte_powered(&mut self, byte: u8) -> OneWireResult<(), Self::BusError> {
+        self.set_strong_pullup_async(true).await?;
+        OneWireAsync::write_byte(self, byte).await
+    }
+}
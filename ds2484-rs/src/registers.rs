@@ -1,5 +1,6 @@
 use crate::{
-    Ds2484Error, Ds2484Result, InteractAsync,
+    Ds2484Error, Ds2484Result, FixedInterval, InteractAsync, WaitStrategy,
+    stats::BusStatistics,
     traits::{Addressing, Interact},
 };
 use bitfield_struct::bitfield;
@@ -11,74 +12,318 @@ use embedded_hal_async::{
     delay::DelayNs as DelayNsAsync,
     i2c::{I2c as I2cAsync, SevenBitAddress as SevenBitAddressAsync},
 };
-use embedded_onewire::OneWireStatus;
+use embedded_onewire::{
+    OneWire, OneWireError, OneWireResult, OneWireStatus, consts::ONEWIRE_SKIP_ROM_CMD_OD,
+};
 
 pub(crate) const READ_PTR_CMD: u8 = 0xe1; // Set the read pointer
 pub(crate) const DEVICE_STATUS_PTR: u8 = 0xf0; // Device status register
 pub(crate) const DEVICE_RST_CMD: u8 = 0xf0; // Reset the device
 
+/// State of the strong pullup, which the device clears on its own.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum StrongPullup {
+    /// The SPU bit is clear.
+    Off,
+    /// The SPU bit is set, the strong pullup starts after the next Write Byte or Single Bit command.
+    Armed,
+    /// The strong pullup is asserted until the next 1-Wire command.
+    Active,
+}
+
 /// A DS2484 I2C to 1-Wire bridge device.
 ///
 /// Takes ownership of an I2C bus (implementing [`I2c`](embedded_hal::i2c::I2c) trait)
 /// and a timer object implementing the [`DelayNs`](embedded_hal::delay::DelayNs) trait.
-pub struct Ds2484<I, D> {
+/// The status register polls performed while the 1-Wire line is busy are paced by
+/// a [`WaitStrategy`].
+///
+/// The I2C bus can be shared with other devices by passing a `&mut` reference to the
+/// bus, or a device wrapper from [`embedded-hal-bus`](https://crates.io/crates/embedded-hal-bus).
+/// Each status poll while the 1-Wire line is busy is an I2C transaction of its own, so
+/// other devices can use the bus between polls. The driver does not rely on consecutive
+/// transactions being atomic: the DS2484 keeps its read pointer and 1-Wire state across
+/// transactions addressed to other devices, and only another driver addressing the
+/// DS2484 itself can disturb an ongoing operation.
+pub struct Ds2484<I, D, W = FixedInterval> {
     pub(crate) i2c: I,
     pub(crate) addr: u8,
     pub(crate) delay: D,
+    pub(crate) wait: W,
     pub(crate) retries: u8,
-    pub(crate) reset: bool, // Indicates if the device has been reset
+    pub(crate) i2c_retries: u8,
+    pub(crate) timeout_us: Option<u32>,
+    pub(crate) reset_retries: Option<u8>,
+    pub(crate) reset_timeout_us: Option<u32>,
+    pub(crate) resetting: bool, // The last 1-Wire command issued is a reset
+    pub(crate) reset: bool,     // Indicates if the device has been reset
     pub(crate) overdrive: bool,
+    pub(crate) port: OneWirePortConfiguration, // Last known 1-Wire port configuration
+    pub(crate) config: DeviceConfiguration,    // Last written device configuration
+    pub(crate) auto_recover: bool,
+    pub(crate) spu: StrongPullup,
+    pub(crate) pipelined: bool,
+    pub(crate) idle: bool, // The 1-Wire line was idle at the last status poll, and no command followed
+    pub(crate) status: DeviceStatus, // Last status read from the device
+    #[cfg(feature = "stats")]
+    pub(crate) stats: BusStatistics,
 }
 
 /// Builder for creating a [`Ds2484`] instance with custom configuration.
-pub struct Ds2484Builder {
+pub struct Ds2484Builder<W = FixedInterval> {
     pub(crate) retries: u8,
+    pub(crate) i2c_retries: u8,
+    pub(crate) timeout_us: Option<u32>,
+    pub(crate) reset_retries: Option<u8>,
+    pub(crate) reset_timeout_us: Option<u32>,
     pub(crate) config: DeviceConfiguration,
+    pub(crate) port: OneWirePortConfiguration,
+    pub(crate) wait: W,
+    pub(crate) auto_recover: bool,
+    pub(crate) pipelined: bool,
+    pub(crate) overdrive: bool,
 }
 
 impl Default for Ds2484Builder {
     fn default() -> Self {
         Ds2484Builder {
             retries: 100,
+            i2c_retries: 0,
+            timeout_us: None,
+            reset_retries: None,
+            reset_timeout_us: None,
             config: DeviceConfiguration::new(),
+            port: OneWirePortConfiguration::default(),
+            wait: FixedInterval::default(),
+            auto_recover: false,
+            pipelined: false,
+            overdrive: false,
         }
     }
 }
 
 impl Ds2484Builder {
+    /// Preset for long 1-Wire lines, e.g. a sensor network running over tens of meters
+    /// of cable at standard speed.
+    ///
+    /// Enables the active pullup, selects the 500 Ω weak pullup, delays the presence
+    /// sampling to 74 µs to tolerate the slow rising edges of a heavily loaded line, and
+    /// extends the recovery time to 10.25 µs so that the line and parasitically powered
+    /// devices recharge between time slots.
+    pub fn long_line() -> Self {
+        Ds2484Builder::default()
+            .with_config(DeviceConfiguration::new().with_active_pullup(true))
+            .with_port_config(
+                OneWireConfigurationBuilder::default()
+                    .presence_detect_time(74_000, 8_000)
+                    .write_zero_recovery_time(10_250)
+                    .weak_pullup_resistor(500)
+                    .build(),
+            )
+    }
+
+    /// Preset for short 1-Wire lines with few devices, e.g. on the same board.
+    ///
+    /// Enables the active pullup and shortens the reset low time to 480 µs and the
+    /// recovery time to 2.75 µs, which speeds up every time slot.
+    pub fn short_line() -> Self {
+        Ds2484Builder::default()
+            .with_config(DeviceConfiguration::new().with_active_pullup(true))
+            .with_port_config(
+                OneWireConfigurationBuilder::default()
+                    .reset_pulse(480_000, 56_000)
+                    .write_zero_recovery_time(2_750)
+                    .build(),
+            )
+    }
+
+    /// Preset for overdrive speed.
+    ///
+    /// Enables the active pullup, shortens the recovery time to 2.75 µs, which otherwise
+    /// dominates the overdrive time slots, and brings the bus up at overdrive speed (see
+    /// [`Ds2484Builder::with_overdrive`]).
+    pub fn overdrive_optimized() -> Self {
+        Ds2484Builder::default()
+            .with_config(DeviceConfiguration::new().with_active_pullup(true))
+            .with_port_config(
+                OneWireConfigurationBuilder::default()
+                    .write_zero_recovery_time(2_750)
+                    .build(),
+            )
+            .with_overdrive(true)
+    }
+}
+
+impl<W: WaitStrategy> Ds2484Builder<W> {
     /// Sets the retry count for the device.
     ///
     /// The retry count is used to determine how long
     /// the host waits before operations on the 1-Wire
-    /// or I2C bus time out.
+    /// or I2C bus time out. Ignored if a timeout is set
+    /// with [`Ds2484Builder::with_timeout_ms`].
     pub fn with_retries(mut self, retries: u8) -> Self {
         self.retries = retries;
         self
     }
 
+    /// Sets how many times a failed I2C transaction is retried.
+    ///
+    /// Transient I2C failures, e.g. a NACK caused by noise on a long bus, are retried
+    /// immediately up to this many times before the error is returned. This budget is
+    /// separate from the retry count used while the 1-Wire line is busy. Defaults to 0,
+    /// i.e. every I2C error is returned as is.
+    pub fn with_i2c_retries(mut self, retries: u8) -> Self {
+        self.i2c_retries = retries;
+        self
+    }
+
+    /// Sets the poll delay budget, in ms, before waits for the device time out.
+    ///
+    /// Applies to every wait for the device, including the device reset, 1-Wire
+    /// commands and configuration writes, and takes precedence over the retry count.
+    ///
+    /// This is not a wall-clock timeout: only the delays requested between status polls
+    /// are added up, i.e. the intervals returned by the [`WaitStrategy`], at least 1 µs
+    /// per poll. The time spent in I2C transactions comes on top of it, so a wait can
+    /// take much longer than the budget on a slow bus, or with short intervals such as
+    /// the first ones of an [`ExponentialBackoff`](crate::ExponentialBackoff).
+    pub fn with_timeout_ms(mut self, timeout_ms: u32) -> Self {
+        self.timeout_us = Some(timeout_ms.saturating_mul(1000));
+        self
+    }
+
+    /// Sets the retry count for 1-Wire resets.
+    ///
+    /// A 1-Wire reset lasts about 1 ms at standard speed, much longer than a byte, and
+    /// may need a larger budget than the other 1-Wire commands. Takes precedence over
+    /// [`Ds2484Builder::with_retries`] and [`Ds2484Builder::with_timeout_ms`] for resets,
+    /// and is ignored if a reset timeout is set with
+    /// [`Ds2484Builder::with_reset_timeout_ms`]. By default, resets use the same budget
+    /// as the other operations.
+    pub fn with_reset_retries(mut self, retries: u8) -> Self {
+        self.reset_retries = Some(retries);
+        self
+    }
+
+    /// Sets the poll delay budget, in ms, before a 1-Wire reset times out.
+    ///
+    /// Takes precedence over every other budget for resets, see
+    /// [`Ds2484Builder::with_reset_retries`]. Like [`Ds2484Builder::with_timeout_ms`],
+    /// this counts the delays between status polls, not the wall-clock time.
+    pub fn with_reset_timeout_ms(mut self, timeout_ms: u32) -> Self {
+        self.reset_timeout_us = Some(timeout_ms.saturating_mul(1000));
+        self
+    }
+
     /// Sets the device configuration.
     pub fn with_config(mut self, config: DeviceConfiguration) -> Self {
         self.config = config;
         self
     }
 
+    /// Sets the 1-Wire port parameters.
+    ///
+    /// The parameters are written after the device configuration if they differ from
+    /// the power-on defaults.
+    pub fn with_port_config(mut self, port: OneWirePortConfiguration) -> Self {
+        self.port = port;
+        self
+    }
+
+    /// Enables automatic recovery after an unexpected device reset.
+    ///
+    /// When enabled, a 1-Wire operation that finds the device reset, e.g. after a
+    /// brown-out, restores the last written device configuration and 1-Wire port
+    /// parameters, and retries the operation once. This also applies after
+    /// [`Ds2484::bus_reset`]. When disabled (the default), such operations fail until the
+    /// device configuration is written again.
+    ///
+    /// Recovery restores the DS2484, not the state of the 1-Wire slaves. If the device
+    /// reset in the middle of a transaction, the retried operation is sent out of context,
+    /// e.g. a byte written to a slave that was never addressed after the reset.
+    pub fn with_auto_recovery(mut self, enable: bool) -> Self {
+        self.auto_recover = enable;
+        self
+    }
+
+    /// Enables pipelined 1-Wire commands.
+    ///
+    /// When enabled, the host sleeps through every 1-Wire command for its expected
+    /// duration, computed from the 1-Wire port parameters and speed, and confirms with a
+    /// single status poll that the line is idle. The next command is then issued without
+    /// polling the status again, which roughly halves the I2C transactions per byte.
+    pub fn with_pipelining(mut self, enable: bool) -> Self {
+        self.pipelined = enable;
+        self
+    }
+
+    /// Brings the 1-Wire bus up at overdrive speed.
+    ///
+    /// After the device configuration is written, the devices on the 1-Wire bus are
+    /// switched to overdrive speed with an Overdrive-Skip ROM command, followed by the
+    /// DS2484. Building succeeds even if no device answers the 1-Wire resets.
+    pub fn with_overdrive(mut self, enable: bool) -> Self {
+        self.overdrive = enable;
+        self
+    }
+
+    /// Sets the strategy used to pace the status polls while the device is busy.
+    ///
+    /// Defaults to [`FixedInterval`].
+    pub fn with_wait_strategy<V: WaitStrategy>(self, wait: V) -> Ds2484Builder<V> {
+        Ds2484Builder {
+            retries: self.retries,
+            i2c_retries: self.i2c_retries,
+            timeout_us: self.timeout_us,
+            reset_retries: self.reset_retries,
+            reset_timeout_us: self.reset_timeout_us,
+            config: self.config,
+            port: self.port,
+            wait,
+            auto_recover: self.auto_recover,
+            pipelined: self.pipelined,
+            overdrive: self.overdrive,
+        }
+    }
+
     /// Builds a new `Ds2484` instance with the specified configuration.
     pub fn build<I: I2c<SevenBitAddress>, D: DelayNs>(
         mut self,
         i2c: I,
         delay: D,
-    ) -> Ds2484Result<Ds2484<I, D>, I::Error> {
+    ) -> Ds2484Result<Ds2484<I, D, W>, I::Error> {
         let mut dev = Ds2484 {
             i2c,
             addr: 0x18,
             delay,
+            wait: self.wait,
             retries: self.retries,
+            i2c_retries: self.i2c_retries,
+            timeout_us: self.timeout_us,
+            reset_retries: self.reset_retries,
+            reset_timeout_us: self.reset_timeout_us,
+            resetting: false,
             reset: false,
             overdrive: false,
+            port: OneWirePortConfiguration::default(),
+            config: DeviceConfiguration::new(),
+            auto_recover: self.auto_recover,
+            spu: StrongPullup::Off,
+            pipelined: self.pipelined,
+            idle: false,
+            status: DeviceStatus::new(),
+            #[cfg(feature = "stats")]
+            stats: BusStatistics::default(),
         };
         dev.bus_reset()?;
         self.config.write(&mut dev)?;
         dev.overdrive = self.config.onewire_speed();
+        if self.port != OneWirePortConfiguration::default() {
+            self.port.write(&mut dev)?;
+        }
+        if self.overdrive && !dev.overdrive {
+            dev.enter_overdrive()?;
+        }
         Ok(dev)
     }
 
@@ -87,81 +332,596 @@ impl Ds2484Builder {
         mut self,
         i2c: I,
         delay: D,
-    ) -> Ds2484Result<Ds2484<I, D>, I::Error> {
+    ) -> Ds2484Result<Ds2484<I, D, W>, I::Error> {
         let mut dev = Ds2484 {
             i2c,
             addr: 0x18,
             delay,
+            wait: self.wait,
             retries: self.retries,
+            i2c_retries: self.i2c_retries,
+            timeout_us: self.timeout_us,
+            reset_retries: self.reset_retries,
+            reset_timeout_us: self.reset_timeout_us,
+            resetting: false,
             reset: false,
             overdrive: false,
+            port: OneWirePortConfiguration::default(),
+            config: DeviceConfiguration::new(),
+            auto_recover: self.auto_recover,
+            spu: StrongPullup::Off,
+            pipelined: self.pipelined,
+            idle: false,
+            status: DeviceStatus::new(),
+            #[cfg(feature = "stats")]
+            stats: BusStatistics::default(),
         };
         dev.bus_reset_async().await?;
         self.config.async_write(&mut dev).await?;
         dev.overdrive = self.config.onewire_speed();
+        if self.port != OneWirePortConfiguration::default() {
+            self.port.async_write(&mut dev).await?;
+        }
+        if self.overdrive && !dev.overdrive {
+            dev.enter_overdrive_async().await?;
+        }
         Ok(dev)
     }
 }
 
-impl<I: I2c<SevenBitAddress>, D: DelayNs> Ds2484<I, D> {
+impl<I, D, W> Ds2484<I, D, W> {
+    /// Get the operation counters.
+    #[cfg(feature = "stats")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "stats")))]
+    pub fn stats(&self) -> &BusStatistics {
+        &self.stats
+    }
+
+    /// Clear the operation counters.
+    #[cfg(feature = "stats")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "stats")))]
+    pub fn clear_stats(&mut self) {
+        self.stats = BusStatistics::default();
+    }
+
+    /// Count a CRC failure observed by the caller in data read from the 1-Wire bus.
+    #[cfg(feature = "stats")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "stats")))]
+    pub fn record_crc_failure(&mut self) {
+        self.stats.crc_failures = self.stats.crc_failures.wrapping_add(1);
+    }
+
+    /// Whether the strong pullup is asserted on the 1-Wire line.
+    ///
+    /// The strong pullup is asserted by the first 1-Wire Write Byte or Single Bit command
+    /// after it is enabled with [`Ds2484::set_strong_pullup`] or a configuration write,
+    /// and ends with the next 1-Wire command, a configuration write disabling it, or a
+    /// device reset, at which point the device clears the SPU bit on its own.
+    pub fn strong_pullup_active(&self) -> bool {
+        self.spu == StrongPullup::Active
+    }
+
+    /// The status most recently read from the device.
+    ///
+    /// Updated by every status read the driver performs, including the polls that wait
+    /// for a 1-Wire command to complete. Useful to inspect the presence, short and
+    /// triplet bits after an operation, e.g. a failed search step, without another I2C
+    /// transaction.
+    pub fn last_status(&self) -> DeviceStatus {
+        self.status
+    }
+
+    /// Update the line and strong pullup state after issuing a 1-Wire command.
+    ///
+    /// `powering` is true for the Write Byte and Single Bit commands, which start an
+    /// armed strong pullup. The other commands leave it armed.
+    pub(crate) fn command_issued(&mut self, powering: bool) {
+        self.idle = false;
+        self.resetting = false;
+        self.spu = match self.spu {
+            StrongPullup::Armed if powering => StrongPullup::Active,
+            StrongPullup::Armed => StrongPullup::Armed,
+            _ => StrongPullup::Off,
+        };
+    }
+
+    /// Whether a wait for the device has timed out, after `tries` busy polls and
+    /// `waited_us` microseconds of requested poll delays.
+    pub(crate) fn expired(&self, tries: u32, waited_us: u32) -> bool {
+        let (retries, timeout_us) = if !self.resetting {
+            (self.retries, self.timeout_us)
+        } else if let Some(timeout_us) = self.reset_timeout_us {
+            (self.retries, Some(timeout_us))
+        } else if let Some(retries) = self.reset_retries {
+            (retries, None)
+        } else {
+            (self.retries, self.timeout_us)
+        };
+        match timeout_us {
+            Some(timeout_us) => waited_us >= timeout_us,
+            None => tries > retries.into(),
+        }
+    }
+
+    /// Update the operation counters, if enabled.
+    #[inline(always)]
+    pub(crate) fn record(&mut self, _update: impl FnOnce(&mut BusStatistics)) {
+        #[cfg(feature = "stats")]
+        _update(&mut self.stats);
+    }
+}
+
+impl<I: I2c<SevenBitAddress>, D, W> Ds2484<I, D, W> {
+    /// Runs an I2C transaction, retrying it up to the I2C retry count if it fails.
+    fn i2c_retrying<T>(
+        &mut self,
+        mut op: impl FnMut(&mut I, u8) -> Result<T, I::Error>,
+    ) -> Ds2484Result<T, I::Error> {
+        let mut tries = 0;
+        loop {
+            match op(&mut self.i2c, self.addr) {
+                Err(_) if tries < self.i2c_retries => tries += 1,
+                res => return Ok(res?),
+            }
+        }
+    }
+
+    pub(crate) fn i2c_write(&mut self, bytes: &[u8]) -> Ds2484Result<(), I::Error> {
+        self.i2c_retrying(|i2c, addr| i2c.write(addr, bytes))
+    }
+
+    pub(crate) fn i2c_read(&mut self, buf: &mut [u8]) -> Ds2484Result<(), I::Error> {
+        self.i2c_retrying(|i2c, addr| i2c.read(addr, buf))
+    }
+
+    pub(crate) fn i2c_write_read(
+        &mut self,
+        bytes: &[u8],
+        buf: &mut [u8],
+    ) -> Ds2484Result<(), I::Error> {
+        self.i2c_retrying(|i2c, addr| i2c.write_read(addr, bytes, buf))
+    }
+}
+
+impl<I: I2c<SevenBitAddress>, D: DelayNs, W: WaitStrategy> Ds2484<I, D, W> {
     /// Get the status of the device.
     pub fn get_status(&mut self) -> Ds2484Result<DeviceStatus, I::Error> {
         let mut stat = DeviceStatus::default();
         stat.read(self)?;
         Ok(stat)
     }
+
+    /// Sample the logic level of the 1-Wire line.
+    ///
+    /// Does not initiate any 1-Wire communication. Useful to diagnose a line that is
+    /// held low, e.g. by a short or a missing pullup.
+    pub fn read_logic_level(&mut self) -> Ds2484Result<bool, I::Error> {
+        Ok(self.get_status()?.logic_level())
+    }
+
+    /// Check for devices on the 1-Wire bus.
+    ///
+    /// Issues a 1-Wire reset and reports whether a presence pulse or a short circuit
+    /// was detected.
+    pub fn check_presence(&mut self) -> OneWireResult<OneWirePresence, Ds2484Error<I::Error>> {
+        match self.reset() {
+            Ok(_) => Ok(OneWirePresence::Present),
+            Err(OneWireError::NoDevicePresent) => Ok(OneWirePresence::Empty),
+            Err(OneWireError::ShortCircuit) => Ok(OneWirePresence::Short),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Issue a 1-Wire reset, and report the presence detect cycle along with the port
+    /// state it ran with.
+    ///
+    /// Unlike [`OneWire::reset`], an empty or shorted bus is not an error. The speed and
+    /// reset low time help tell an empty bus from a reset that the devices did not
+    /// recognize, e.g. because the bus master runs at overdrive speed and the devices do not.
+    pub fn reset_detailed(&mut self) -> OneWireResult<ResetOutcome, Ds2484Error<I::Error>> {
+        match self.reset() {
+            Ok(_) | Err(OneWireError::NoDevicePresent) | Err(OneWireError::ShortCircuit) => {
+                Ok(ResetOutcome::new(self))
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Read the status, the configuration and the 1-Wire port parameters of the device.
+    pub fn dump_registers(&mut self) -> Ds2484Result<RegisterDump, I::Error> {
+        let status = self.get_status()?;
+        let mut config = DeviceConfiguration::new();
+        config.read(self)?;
+        let port = self.port_config()?;
+        Ok(RegisterDump {
+            status,
+            config,
+            port,
+        })
+    }
+
+    /// Check the device and the 1-Wire bus.
+    ///
+    /// Writes the last written configuration back to the device and verifies the value
+    /// read back, then issues a 1-Wire reset. After a device reset, the 1-Wire port
+    /// parameters are restored as well. Any error along the way classifies the device as
+    /// unresponsive.
+    pub fn self_test(&mut self) -> SelfTestResult {
+        let expected = self.config;
+        let res = if self.reset {
+            self.recover()
+        } else {
+            let mut config = expected;
+            config.write(self)
+        };
+        if res.is_err() || self.config != expected {
+            // Keep the configuration to restore, and treat the device as reset
+            self.config = expected;
+            self.reset = true;
+            return SelfTestResult::Unresponsive;
+        }
+        match self.check_presence() {
+            Ok(OneWirePresence::Present) => SelfTestResult::Present,
+            Ok(OneWirePresence::Empty) => SelfTestResult::Empty,
+            Ok(OneWirePresence::Short) => SelfTestResult::Short,
+            Err(_) => SelfTestResult::Unresponsive,
+        }
+    }
+
+    /// Enable or disable the active pullup.
+    ///
+    /// See [`DeviceConfiguration::active_pullup`].
+    pub fn set_active_pullup(&mut self, enable: bool) -> Ds2484Result<(), I::Error> {
+        self.update_config(|c| c.set_active_pullup(enable))
+    }
+
+    /// Enable or disable the strong pullup for the next 1-Wire Write Byte or Single Bit command.
+    ///
+    /// See [`DeviceConfiguration::strong_pullup`].
+    pub fn set_strong_pullup(&mut self, enable: bool) -> Ds2484Result<(), I::Error> {
+        self.update_config(|c| c.set_strong_pullup(enable))
+    }
+
+    /// Remove or restore power on the 1-Wire port.
+    ///
+    /// See [`DeviceConfiguration::power_down_1wire`].
+    pub fn set_power_down_1wire(&mut self, enable: bool) -> Ds2484Result<(), I::Error> {
+        self.update_config(|c| c.set_power_down_1wire(enable))
+    }
+
+    /// Write a byte to the 1-Wire bus and power the bus with the strong pullup for
+    /// `hold_ns` nanoseconds after it.
+    ///
+    /// This is the sequence needed by parasitically powered devices for e.g. a temperature
+    /// conversion or an EEPROM copy: the strong pullup is enabled, the byte is written, and
+    /// the pullup is held once the byte is sent. The strong pullup is then disabled, which
+    /// is verified by reading the configuration back, and the 1-Wire line is checked to be
+    /// high again.
+    ///
+    /// # Errors
+    /// Returns [`OneWireError::ShortCircuit`] if the 1-Wire line is still low after the
+    /// strong pullup ends.
+    pub fn powered_command(
+        &mut self,
+        byte: u8,
+        hold_ns: u32,
+    ) -> OneWireResult<(), Ds2484Error<I::Error>> {
+        self.set_strong_pullup(true)?;
+        self.write_byte(byte)?;
+        // The strong pullup starts once the byte is sent
+        if !self.idle {
+            self.onewire_wait()?;
+        }
+        self.delay.delay_ns(hold_ns);
+        self.set_strong_pullup(false)?;
+        if !self.read_logic_level()? {
+            return Err(OneWireError::ShortCircuit);
+        }
+        Ok(())
+    }
+
+    /// Read-modify-write the device configuration, and verify the value read back.
+    fn update_config(
+        &mut self,
+        update: impl FnOnce(&mut DeviceConfiguration),
+    ) -> Ds2484Result<(), I::Error> {
+        let mut config = DeviceConfiguration::new();
+        config.read(self)?;
+        update(&mut config);
+        let expected = config;
+        config.write(self)?;
+        if config != expected {
+            return Err(Ds2484Error::VerificationFailed);
+        }
+        Ok(())
+    }
+
+    /// Read the 1-Wire port parameters.
+    pub fn port_config(&mut self) -> Ds2484Result<OneWirePortTiming, I::Error> {
+        let mut cfg = OneWirePortConfiguration::default();
+        cfg.read(self)?;
+        Ok(cfg.into())
+    }
+
+    /// Set the 1-Wire port parameters.
+    ///
+    /// Each parameter is rounded up to the next setting supported by the device, and
+    /// the parameters are read back after writing to verify that the device accepted them.
+    ///
+    /// # Errors
+    /// Returns [`Ds2484Error::InvalidValue`] naming the first parameter that exceeds the
+    /// longest supported setting, and [`Ds2484Error::VerificationFailed`] if the parameters
+    /// read back do not match the ones written.
+    pub fn set_port_config(&mut self, timing: &OneWirePortTiming) -> Ds2484Result<(), I::Error> {
+        let cfg = timing.encode()?;
+        let mut readback = cfg;
+        readback.write(self)?;
+        if readback != cfg {
+            return Err(Ds2484Error::VerificationFailed);
+        }
+        Ok(())
+    }
 }
 
-impl<I2C: I2c<SevenBitAddress>, D: DelayNs> Ds2484<I2C, D> {
+impl<I2C: I2c<SevenBitAddress>, D: DelayNs, W: WaitStrategy> Ds2484<I2C, D, W> {
     /// Reset the device.
     ///
     /// Performs a global reset of device state machine logic. Terminates any ongoing 1-Wire
     /// communication.
     pub fn bus_reset(&mut self) -> Ds2484Result<DeviceStatus, I2C::Error> {
-        self.i2c.write(self.addr, &[DEVICE_RST_CMD])?;
+        self.i2c_write(&[DEVICE_RST_CMD])?;
         self.reset = true;
+        self.port = OneWirePortConfiguration::default();
+        self.spu = StrongPullup::Off;
+        self.idle = false;
+        self.resetting = false;
         let mut tries = 0;
+        let mut waited = 0;
         let mut status = DeviceStatus::default();
         loop {
             status.read(self)?;
-            if status.device_reset() || tries > self.retries {
+            if status.device_reset() || self.expired(tries, waited) {
                 break;
             }
+            let us = self.wait.interval_us(tries, self.overdrive);
+            if us > 0 {
+                self.delay.delay_us(us);
+            }
+            waited = waited.saturating_add(us.max(1));
             tries += 1;
-            self.delay.delay_ms(1);
+            self.record(|s| s.busy_polls = s.busy_polls.wrapping_add(1));
         }
-        if tries > self.retries {
+        if self.expired(tries, waited) {
+            self.record(|s| s.retries_exceeded = s.retries_exceeded.wrapping_add(1));
             Err(Ds2484Error::RetriesExceeded)
         } else {
             Ok(status)
         }
     }
 
+    /// Issue a command that is not modeled by the driver.
+    ///
+    /// Waits for the 1-Wire line to be idle, writes the command code followed by the
+    /// optional parameter byte, and returns the status once the line is idle again.
+    ///
+    /// This is a low-level escape hatch: the driver does not know the effect of the
+    /// command, so its view of the device configuration, 1-Wire speed and port parameters
+    /// may no longer match the device afterwards.
+    pub fn raw_command(
+        &mut self,
+        cmd: u8,
+        param: Option<u8>,
+    ) -> Ds2484Result<DeviceStatus, I2C::Error> {
+        self.onewire_wait()?;
+        match param {
+            Some(param) => self.i2c_write(&[cmd, param])?,
+            None => self.i2c_write(&[cmd])?,
+        }
+        self.command_issued(false);
+        self.onewire_wait()
+    }
+
+    /// Release the I2C bus and the timer.
+    ///
+    /// Resets the device before returning, which terminates any ongoing 1-Wire
+    /// communication, disables the strong pullup and returns the 1-Wire port to
+    /// standard speed. Errors encountered during the reset are ignored.
+    pub fn release(mut self) -> (I2C, D) {
+        let _ = self.bus_reset();
+        (self.i2c, self.delay)
+    }
+
     pub(crate) fn onewire_wait(&mut self) -> Ds2484Result<DeviceStatus, I2C::Error> {
+        self.i2c_write(&[READ_PTR_CMD, DEVICE_STATUS_PTR])?;
+        self.onewire_poll()
+    }
+
+    /// Polls the status register until the 1-Wire line is idle.
+    ///
+    /// The read pointer must already be positioned at the status register, which is the
+    /// case after any 1-Wire communication command.
+    pub(crate) fn onewire_poll(&mut self) -> Ds2484Result<DeviceStatus, I2C::Error> {
         let mut tries = 0;
+        let mut waited = 0;
         let mut status = DeviceStatus::default();
         let mut buf = [0; 1];
-        self.i2c
-            .write(self.addr, &[READ_PTR_CMD, DEVICE_STATUS_PTR])?;
         loop {
-            self.i2c.read(self.addr, &mut buf)?;
+            self.i2c_read(&mut buf)?;
             status.0 = buf[0];
-            if !status.onewire_busy() || tries > self.retries {
+            if !status.onewire_busy() || self.expired(tries, waited) {
                 break;
             }
-            tries += 1;
-            if !self.overdrive {
-                self.delay.delay_ms(1);
-            } else {
-                self.delay.delay_us(100);
+            let us = self.wait.interval_us(tries, self.overdrive);
+            if us > 0 {
+                self.delay.delay_us(us);
             }
+            waited = waited.saturating_add(us.max(1));
+            tries += 1;
+            self.record(|s| s.busy_polls = s.busy_polls.wrapping_add(1));
         }
-        if status.onewire_busy() && tries > self.retries {
+        self.status = status;
+        if status.device_reset() && !self.reset {
+            self.reset = true;
+            self.spu = StrongPullup::Off;
+            Err(Ds2484Error::DeviceReset)
+        } else if status.onewire_busy() && self.expired(tries, waited) {
+            self.record(|s| s.retries_exceeded = s.retries_exceeded.wrapping_add(1));
             Err(Ds2484Error::RetriesExceeded)
         } else {
+            self.idle = true;
             Ok(status)
         }
     }
+
+    /// Waits until the 1-Wire line is idle before issuing a command.
+    ///
+    /// In pipelined mode, the wait is skipped if the line was found idle after the last
+    /// command.
+    pub(crate) fn onewire_ready(&mut self) -> Ds2484Result<(), I2C::Error> {
+        if self.pipelined && self.idle {
+            return Ok(());
+        }
+        self.onewire_wait().map(|_| ())
+    }
+
+    /// Waits for a 1-Wire command, expected to last `duration_ns`, to complete.
+    ///
+    /// In pipelined mode, sleeps through the command before the first status poll.
+    pub(crate) fn onewire_settle(
+        &mut self,
+        duration_ns: u32,
+    ) -> Ds2484Result<DeviceStatus, I2C::Error> {
+        if self.pipelined {
+            self.delay.delay_ns(duration_ns);
+            self.onewire_poll()
+        } else {
+            self.onewire_wait()
+        }
+    }
+
+    /// Switches the devices on the 1-Wire bus, if any, and the DS2484 to overdrive speed.
+    ///
+    /// Only I2C and device errors are reported, the 1-Wire resets may find the bus empty.
+    pub(crate) fn enter_overdrive(&mut self) -> Ds2484Result<(), I2C::Error> {
+        bus_errors(self.reset())?;
+        bus_errors(self.write_byte(ONEWIRE_SKIP_ROM_CMD_OD))?;
+        let mut config = self.config.with_onewire_speed(true);
+        config.write(self)?;
+        self.overdrive = true;
+        bus_errors(self.reset())
+    }
+
+    /// Restores the last written device configuration and 1-Wire port parameters.
+    pub(crate) fn recover(&mut self) -> Ds2484Result<(), I2C::Error> {
+        let port = self.port;
+        let mut config = self.config;
+        config.write(self)?;
+        if port != OneWirePortConfiguration::default() {
+            let mut port = port;
+            port.write(self)?;
+        }
+        Ok(())
+    }
+
+    /// Runs a 1-Wire operation, recovering from a device reset if enabled.
+    ///
+    /// Fails with [`OneWireError::BusUninitialized`] if the device has been reset and
+    /// automatic recovery is disabled. Otherwise, the operation is retried once if it
+    /// finds that the device reset while it was running.
+    pub(crate) fn recovering<T>(
+        &mut self,
+        mut op: impl FnMut(&mut Self) -> OneWireResult<T, Ds2484Error<I2C::Error>>,
+    ) -> OneWireResult<T, Ds2484Error<I2C::Error>> {
+        if self.reset {
+            if !self.auto_recover {
+                return Err(OneWireError::BusUninitialized);
+            }
+            self.recover()?;
+        }
+        match op(self) {
+            Err(OneWireError::Other(Ds2484Error::DeviceReset)) if self.auto_recover => {
+                self.recover()?;
+                op(self)
+            }
+            res => res,
+        }
+    }
+}
+
+/// Snapshot of the DS2484 registers, see [`Ds2484::dump_registers`].
+#[derive(Debug, Clone, Copy)]
+pub struct RegisterDump {
+    /// Status register.
+    pub status: DeviceStatus,
+    /// Device configuration register.
+    pub config: DeviceConfiguration,
+    /// Decoded 1-Wire port parameters.
+    pub port: OneWirePortTiming,
+}
+
+/// Result of a 1-Wire presence check, see [`Ds2484::check_presence`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OneWirePresence {
+    /// At least one device answered with a presence pulse.
+    Present,
+    /// No device is present on the bus.
+    Empty,
+    /// The 1-Wire line is shorted.
+    Short,
+}
+
+/// Result of a 1-Wire reset, see [`Ds2484::reset_detailed`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ResetOutcome {
+    /// A presence pulse was detected.
+    pub presence: bool,
+    /// A short circuit was detected on the 1-Wire line.
+    pub short: bool,
+    /// Logic level of the 1-Wire line after the reset.
+    pub logic_level: bool,
+    /// The reset was issued at overdrive speed.
+    pub overdrive: bool,
+    /// Reset low time (tRSTL) of the reset, in ns.
+    pub reset_time: u32,
+}
+
+impl ResetOutcome {
+    /// Collects the outcome of the reset just issued by `dev`.
+    pub(crate) fn new<I, D, W>(dev: &Ds2484<I, D, W>) -> Self {
+        let status = dev.status;
+        ResetOutcome {
+            presence: status.presence(),
+            short: status.short_detect(),
+            logic_level: status.logic_level(),
+            overdrive: dev.overdrive,
+            reset_time: if dev.overdrive {
+                dev.port.reset_time_overdrive()
+            } else {
+                dev.port.reset_time()
+            },
+        }
+    }
+}
+
+/// Keeps the errors of a 1-Wire operation that come from the DS2484 or the I2C bus.
+pub(crate) fn bus_errors<T, E>(res: OneWireResult<T, Ds2484Error<E>>) -> Ds2484Result<(), E> {
+    match res {
+        Err(OneWireError::Other(e)) => Err(e),
+        _ => Ok(()),
+    }
+}
+
+/// Result of [`Ds2484::self_test`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SelfTestResult {
+    /// The device works, and at least one device is present on the 1-Wire bus.
+    Present,
+    /// The device works, and no device is present on the 1-Wire bus.
+    Empty,
+    /// The device works, and the 1-Wire line is shorted.
+    Short,
+    /// The device does not respond, or does not accept its configuration.
+    Unresponsive,
 }
 
 /// Status register for DS2484
@@ -176,7 +936,7 @@ impl<I2C: I2c<SevenBitAddress>, D: DelayNs> Ds2484<I2C, D> {
 /// minimal protocol overhead. Status information is updated
 /// during the execution of certain commands only. Bit details
 /// are given in the following descriptions.
-#[bitfield(u8)]
+#[bitfield(u8, defmt = cfg(feature = "defmt"))]
 pub struct DeviceStatus {
     /// The 1WB bit reports to the host processor whether the
     /// 1-Wire line is busy. During 1-Wire communication 1WB
@@ -272,26 +1032,26 @@ impl Addressing for DeviceStatus {
 }
 
 impl Interact for DeviceStatus {
-    fn read<I: I2c<SevenBitAddress>, D>(
+    fn read<I: I2c<SevenBitAddress>, D, W>(
         &mut self,
-        dev: &mut Ds2484<I, D>,
+        dev: &mut Ds2484<I, D, W>,
     ) -> Result<(), Ds2484Error<I::Error>> {
         let mut val = [0; 1];
-        dev.i2c
-            .write_read(dev.addr, &[READ_PTR_CMD, Self::READ_PTR], &mut val)?;
+        dev.i2c_write_read(&[READ_PTR_CMD, Self::READ_PTR], &mut val)?;
         self.0 = val[0];
+        dev.status = *self;
         Ok(())
     }
 
-    fn write<I: I2c<SevenBitAddress>, D>(
+    fn write<I: I2c<SevenBitAddress>, D, W>(
         &mut self,
-        _dev: &mut Ds2484<I, D>,
+        _dev: &mut Ds2484<I, D, W>,
     ) -> Result<(), Ds2484Error<I::Error>> {
         Ok(())
     }
 }
 
-#[bitfield(u8)]
+#[bitfield(u8, defmt = cfg(feature = "defmt"))]
 /// # Device configuration register
 ///
 /// The DS2484 supports four 1-Wire features that are
@@ -308,6 +1068,7 @@ impl Interact for DeviceStatus {
 /// After a device reset (power-up cycle or initiated by the
 /// Device Reset command), the Device Configuration register
 /// reads 00h.
+#[derive(PartialEq, Eq)]
 pub struct DeviceConfiguration {
     /// The APU bit controls whether an active pullup (low impedance
     /// transistor) or a passive pullup (R WPU resistor) is
@@ -380,28 +1141,34 @@ impl Addressing for DeviceConfiguration {
 }
 
 impl Interact for DeviceConfiguration {
-    fn read<I: I2c<SevenBitAddress>, D: DelayNs>(
+    fn read<I: I2c<SevenBitAddress>, D: DelayNs, W: WaitStrategy>(
         &mut self,
-        dev: &mut Ds2484<I, D>,
+        dev: &mut Ds2484<I, D, W>,
     ) -> Result<(), Ds2484Error<I::Error>> {
         let mut buf = [0; 1];
-        dev.i2c
-            .write_read(dev.addr, &[READ_PTR_CMD, Self::READ_PTR], &mut buf)?;
+        dev.i2c_write_read(&[READ_PTR_CMD, Self::READ_PTR], &mut buf)?;
         self.0 = buf[0];
         Ok(())
     }
 
-    fn write<I: I2c<SevenBitAddress>, D: DelayNs>(
+    fn write<I: I2c<SevenBitAddress>, D: DelayNs, W: WaitStrategy>(
         &mut self,
-        dev: &mut Ds2484<I, D>,
+        dev: &mut Ds2484<I, D, W>,
     ) -> Result<(), Ds2484Error<I::Error>> {
         dev.onewire_wait()?;
         let out = (self.0 & 0x0f) | ((!self.0 & 0x0f) << 4);
         let mut buf = [0; 1];
-        dev.i2c.write(dev.addr, &[Self::WRITE_ADDR, out])?;
-        dev.i2c.read(dev.addr, &mut buf)?;
+        dev.i2c_write(&[Self::WRITE_ADDR, out])?;
+        dev.i2c_read(&mut buf)?;
         dev.reset = false; // Reset the device state after writing configuration
         self.0 = buf[0];
+        dev.spu = match (self.strong_pullup(), dev.spu) {
+            (true, StrongPullup::Active) => StrongPullup::Active, // Kept by a read-modify-write
+            (true, _) => StrongPullup::Armed,
+            (false, _) => StrongPullup::Off,
+        };
+        // The strong pullup is one-shot, and is not restored after a device reset
+        dev.config = self.with_strong_pullup(false);
         Ok(())
     }
 }
@@ -412,7 +1179,8 @@ impl Interact for DeviceConfiguration {
 ///
 /// # Note: Upon a power-on reset or after a
 /// Device Reset command, the parameter default values apply.
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct OneWirePortConfiguration {
     t_rstl: u8,    // 0b0000
     t_rstl_od: u8, // 0b0001
@@ -430,91 +1198,127 @@ impl Addressing for OneWirePortConfiguration {
 }
 
 impl Interact for OneWirePortConfiguration {
-    fn read<I: I2c<SevenBitAddress>, D: DelayNs>(
+    fn read<I: I2c<SevenBitAddress>, D: DelayNs, W: WaitStrategy>(
         &mut self,
-        dev: &mut Ds2484<I, D>,
+        dev: &mut Ds2484<I, D, W>,
     ) -> Result<(), Ds2484Error<I::Error>> {
         let mut buf = [0; 8];
-        dev.i2c
-            .write_read(dev.addr, &[READ_PTR_CMD, Self::READ_PTR], &mut buf)?;
+        dev.i2c_write_read(&[READ_PTR_CMD, Self::READ_PTR], &mut buf)?;
         *self = Self::from_bytes(buf);
+        dev.port = *self;
         Ok(())
     }
 
-    fn write<I: I2c<SevenBitAddress>, D: DelayNs>(
+    fn write<I: I2c<SevenBitAddress>, D: DelayNs, W: WaitStrategy>(
         &mut self,
-        dev: &mut Ds2484<I, D>,
+        dev: &mut Ds2484<I, D, W>,
     ) -> Result<(), Ds2484Error<I::Error>> {
         dev.onewire_wait()?;
-        dev.i2c.write(dev.addr, &self.to_bytes())?;
+        dev.i2c_write(&self.to_bytes())?;
         self.read(dev)
     }
 }
 
+// Parameter values for each 4-bit code of the 1-Wire port parameters, in ns and Ohms
+// (Table 7 of the datasheet).
+const T_RSTL: [u32; 16] = [
+    440000, 460000, 480000, 500000, 520000, 540000, 560000, 580000, 600000, 620000, 640000, 660000,
+    680000, 700000, 720000, 740000,
+];
+const T_RSTL_OD: [u32; 16] = [
+    44000, 46000, 48000, 50000, 52000, 54000, 56000, 58000, 60000, 62000, 64000, 66000, 68000,
+    70000, 72000, 74000,
+];
+const T_MSP: [u32; 16] = [
+    58000, 58000, 60000, 62000, 64000, 66000, 68000, 70000, 72000, 74000, 76000, 76000, 76000,
+    76000, 76000, 76000,
+];
+const T_MSP_OD: [u32; 16] = [
+    5500, 5500, 6000, 6500, 7000, 7500, 8000, 8500, 9000, 9500, 10000, 10500, 11000, 11000, 11000,
+    11000,
+];
+const T_W0L: [u32; 16] = [
+    52000, 54000, 56000, 58000, 60000, 62000, 64000, 66000, 68000, 70000, 70000, 70000, 70000,
+    70000, 70000, 70000,
+];
+const T_W0L_OD: [u32; 16] = [
+    5000, 5500, 6000, 6500, 7000, 7500, 8000, 8500, 9000, 9500, 10000, 10000, 10000, 10000, 10000,
+    10000,
+];
+const T_REC0: [u32; 16] = [
+    2750, 2750, 2750, 2750, 2750, 2750, 5250, 7750, 10250, 12750, 15250, 17750, 20250, 22750,
+    25250, 25250,
+];
+const R_WPU: [u32; 16] = [
+    500, 500, 500, 500, 500, 500, 1000, 1000, 1000, 1000, 1000, 1000, 1000, 1000, 1000, 1000,
+];
+
+/// Code of the shortest parameter value that is at least `value`, if any.
+fn encode(table: &[u32; 16], value: u32) -> Option<u8> {
+    table.iter().position(|&v| v >= value).map(|idx| idx as u8)
+}
+
 impl OneWirePortConfiguration {
     /// Reset low time in ns (tRSTL).
     pub fn reset_time(&self) -> u32 {
-        (self.t_rstl & 0x0f) as u32 * 20000 + 440000
+        T_RSTL[(self.t_rstl & 0x0f) as usize]
     }
 
     /// Reset low time in OverDrive mode, in ns (tRSTL).
     pub fn reset_time_overdrive(&self) -> u32 {
-        (self.t_rstl_od & 0x0f) as u32 * 2000 + 44000
+        T_RSTL_OD[(self.t_rstl_od & 0x0f) as usize]
     }
 
     /// Presence-detect sampling time in ns (tMSP).
     pub fn presence_detect_time(&self) -> u32 {
-        (match (self.t_msp & 0x0f) as u32 {
-            0..=1 => 58,
-            13..=u32::MAX => 76,
-            val => 60 + (val - 2) * 2,
-        }) * 100
+        T_MSP[(self.t_msp & 0x0f) as usize]
     }
 
     /// Presence-detect sampling time in OverDrive mode, in ns (tMSP).
     pub fn presence_detect_time_overdrive(&self) -> u32 {
-        (match (self.t_msp_od & 0x0f) as u32 {
-            0..=1 => 55,
-            13..=u32::MAX => 110,
-            val => 60 + (val - 2) * 5,
-        }) * 100
+        T_MSP_OD[(self.t_msp_od & 0x0f) as usize]
     }
 
     /// Write zero low time in ns (tW0L).
     pub fn write_zero_low_time(&self) -> u32 {
-        match (self.t_w0l & 0x0f) as u32 {
-            10..=u32::MAX => 70000,
-            val => 52000 + val * 2000,
-        }
+        T_W0L[(self.t_w0l & 0x0f) as usize]
     }
 
     /// Write zero low time in OverDrive mode, in ns (tW0L).
     pub fn write_zero_low_time_overdrive(&self) -> u32 {
-        match (self.t_w0l_od & 0x0f) as u32 {
-            10..=u32::MAX => 10000,
-            val => 5000 + val * 500,
-        }
+        T_W0L_OD[(self.t_w0l_od & 0x0f) as usize]
     }
 
     /// Write zero recovery time in ns (tREC0).
     pub fn write_zero_recovery_time(&self) -> u32 {
-        match (self.t_rec0 & 0x0f) as u32 {
-            0..=4 => 2750,
-            15..=u32::MAX => 25250,
-            val => 2750 + (val - 5) * 2500,
-        }
+        T_REC0[(self.t_rec0 & 0x0f) as usize]
     }
 
     /// Weak pull-up resistor value in Ohms (R_WPU).
     pub fn weak_pullup_resistor(&self) -> u16 {
-        if self.r_wpu & 0x0f < 0b0110 {
-            500 // 500 Ohm
+        R_WPU[(self.r_wpu & 0x0f) as usize] as u16
+    }
+
+    /// Duration of a reset/presence-detect cycle in ns (tRSTL + tRSTH).
+    pub(crate) fn reset_cycle_time(&self, overdrive: bool) -> u32 {
+        // The presence-detect high time tRSTH is as long as tRSTL
+        if overdrive {
+            2 * self.reset_time_overdrive()
         } else {
-            1000 // 1000 Ohm
+            2 * self.reset_time()
         }
     }
 
-    pub(crate) fn to_bytes(&self) -> [u8; 9] {
+    /// Duration of a write-zero time slot in ns (tW0L + tREC0).
+    pub(crate) fn slot_time(&self, overdrive: bool) -> u32 {
+        if overdrive {
+            self.write_zero_low_time_overdrive() + self.write_zero_recovery_time()
+        } else {
+            self.write_zero_low_time() + self.write_zero_recovery_time()
+        }
+    }
+
+    pub(crate) fn to_bytes(self) -> [u8; 9] {
         [
             0xc3,
             self.t_rstl,
@@ -572,63 +1376,44 @@ impl Into<OneWireConfigurationBuilder> for OneWirePortConfiguration {
 
 impl OneWireConfigurationBuilder {
     /// Set the reset low time in nanoseconds (tRSTL).
+    ///
+    /// Values are rounded up to the next supported setting, and clamped to the longest one.
     pub fn reset_pulse(mut self, normal: u32, overdrive: u32) -> Self {
-        const NORMAL: [u32; 16] = [
-            440000, 460000, 480000, 500000, 520000, 540000, 560000, 580000, 600000, 620000, 640000,
-            660000, 680000, 700000, 720000, 740000,
-        ];
-        let index = NORMAL.iter().position(|&v| v >= normal).unwrap_or(0);
-        self.cfg.t_rstl = (self.cfg.t_rstl & 0xf0) | (index as u8);
-        const OVERDRIVE: [u32; 16] = [
-            44000, 46000, 48000, 50000, 52000, 54000, 56000, 58000, 60000, 62000, 64000, 66000,
-            68000, 70000, 72000, 74000,
-        ];
-        let index = OVERDRIVE.iter().position(|&v| v >= overdrive).unwrap_or(0);
-        self.cfg.t_rstl_od = (self.cfg.t_rstl_od & 0xf0) | (index as u8);
+        let index = encode(&T_RSTL, normal).unwrap_or(15);
+        self.cfg.t_rstl = (self.cfg.t_rstl & 0xf0) | index;
+        let index = encode(&T_RSTL_OD, overdrive).unwrap_or(15);
+        self.cfg.t_rstl_od = (self.cfg.t_rstl_od & 0xf0) | index;
         self
     }
 
     /// Set the presence-detect sampling time in nanoseconds (tMSP).
+    ///
+    /// Values are rounded up to the next supported setting, and clamped to the longest one.
     pub fn presence_detect_time(mut self, normal: u32, overdrive: u32) -> Self {
-        const NORMAL: [u32; 16] = [
-            58000, 58000, 60000, 62000, 64000, 66000, 68000, 70000, 72000, 74000, 76000, 76000,
-            76000, 76000, 76000, 76000,
-        ];
-        let index = NORMAL.iter().position(|&v| v >= normal).unwrap_or(0);
-        self.cfg.t_msp = (self.cfg.t_msp & 0xf0) | (index as u8);
-        const OVERDRIVE: [u32; 16] = [
-            5500, 5500, 6000, 6500, 7000, 7500, 8000, 8500, 9000, 9500, 10000, 10500, 11000, 11000,
-            11000, 11000,
-        ];
-        let index = OVERDRIVE.iter().position(|&v| v >= overdrive).unwrap_or(0);
-        self.cfg.t_msp_od = (self.cfg.t_msp_od & 0xf0) | (index as u8);
+        let index = encode(&T_MSP, normal).unwrap_or(15);
+        self.cfg.t_msp = (self.cfg.t_msp & 0xf0) | index;
+        let index = encode(&T_MSP_OD, overdrive).unwrap_or(15);
+        self.cfg.t_msp_od = (self.cfg.t_msp_od & 0xf0) | index;
         self
     }
 
     /// Set the write zero low time in nanoseconds (tW0L).
+    ///
+    /// Values are rounded up to the next supported setting, and clamped to the longest one.
     pub fn write_zero_low_time(mut self, normal: u32, overdrive: u32) -> Self {
-        const NORMAL: [u32; 16] = [
-            52000, 54000, 56000, 58000, 60000, 62000, 64000, 66000, 68000, 70000, 70000, 70000,
-            70000, 70000, 70000, 70000,
-        ];
-        let index = NORMAL.iter().position(|&v| v >= normal).unwrap_or(0);
-        self.cfg.t_w0l = (self.cfg.t_w0l & 0xf0) | (index as u8);
-        const OVERDRIVE: [u32; 16] = [
-            5000, 5500, 6000, 6500, 7000, 7500, 8000, 8500, 9000, 9500, 10000, 10000, 10000, 10000,
-            10000, 10000,
-        ];
-        let index = OVERDRIVE.iter().position(|&v| v >= overdrive).unwrap_or(0);
-        self.cfg.t_w0l_od = (self.cfg.t_w0l_od & 0xf0) | (index as u8);
+        let index = encode(&T_W0L, normal).unwrap_or(15);
+        self.cfg.t_w0l = (self.cfg.t_w0l & 0xf0) | index;
+        let index = encode(&T_W0L_OD, overdrive).unwrap_or(15);
+        self.cfg.t_w0l_od = (self.cfg.t_w0l_od & 0xf0) | index;
         self
     }
 
     /// Set the write zero recovery time in nanoseconds (tREC0).
+    ///
+    /// Values are rounded up to the next supported setting, and clamped to the longest one.
     pub fn write_zero_recovery_time(mut self, value: u16) -> Self {
-        const VALUES: [u16; 16] = [
-            275, 275, 275, 275, 275, 275, 525, 775, 1025, 1275, 1525, 1775, 2025, 2275, 2525, 2525,
-        ];
-        let index = VALUES.iter().position(|&v| v >= value).unwrap_or(0);
-        self.cfg.t_rec0 = (self.cfg.t_rec0 & 0xf0) | (index as u8);
+        let index = encode(&T_REC0, value as u32).unwrap_or(15);
+        self.cfg.t_rec0 = (self.cfg.t_rec0 & 0xf0) | index;
         self
     }
 
@@ -637,7 +1422,7 @@ impl OneWireConfigurationBuilder {
         if value < 1000 {
             self.cfg.r_wpu &= 0xf0; // 500 Ohm
         } else {
-            self.cfg.r_wpu &= 0xff; // 1000 Ohm
+            self.cfg.r_wpu = (self.cfg.r_wpu & 0xf0) | 0b0110; // 1000 Ohm
         }
         self
     }
@@ -647,3 +1432,102 @@ impl OneWireConfigurationBuilder {
         self.cfg
     }
 }
+
+/// 1-Wire port parameters in physical units.
+///
+/// Times are in nanoseconds and the pullup resistor is in Ohms. See
+/// [`OneWirePortConfiguration`] for the register representation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OneWirePortTiming {
+    /// Reset low time (tRSTL), in ns.
+    pub reset_time: u32,
+    /// Reset low time in OverDrive mode (tRSTL), in ns.
+    pub reset_time_overdrive: u32,
+    /// Presence-detect sampling time (tMSP), in ns.
+    pub presence_detect_time: u32,
+    /// Presence-detect sampling time in OverDrive mode (tMSP), in ns.
+    pub presence_detect_time_overdrive: u32,
+    /// Write zero low time (tW0L), in ns.
+    pub write_zero_low_time: u32,
+    /// Write zero low time in OverDrive mode (tW0L), in ns.
+    pub write_zero_low_time_overdrive: u32,
+    /// Write zero recovery time (tREC0), in ns.
+    pub write_zero_recovery_time: u32,
+    /// Weak pull-up resistor value (R_WPU), in Ohms.
+    pub weak_pullup_resistor: u16,
+}
+
+impl Default for OneWirePortTiming {
+    fn default() -> Self {
+        OneWirePortConfiguration::default().into()
+    }
+}
+
+impl From<OneWirePortConfiguration> for OneWirePortTiming {
+    fn from(cfg: OneWirePortConfiguration) -> Self {
+        OneWirePortTiming {
+            reset_time: cfg.reset_time(),
+            reset_time_overdrive: cfg.reset_time_overdrive(),
+            presence_detect_time: cfg.presence_detect_time(),
+            presence_detect_time_overdrive: cfg.presence_detect_time_overdrive(),
+            write_zero_low_time: cfg.write_zero_low_time(),
+            write_zero_low_time_overdrive: cfg.write_zero_low_time_overdrive(),
+            write_zero_recovery_time: cfg.write_zero_recovery_time(),
+            weak_pullup_resistor: cfg.weak_pullup_resistor(),
+        }
+    }
+}
+
+impl OneWirePortTiming {
+    /// Encodes the parameters into the register representation.
+    ///
+    /// Values are rounded up to the next supported setting. Values beyond the
+    /// longest supported setting are rejected.
+    pub(crate) fn encode<E>(&self) -> Result<OneWirePortConfiguration, Ds2484Error<E>> {
+        fn code<E>(
+            table: &[u32; 16],
+            value: u32,
+            name: &'static str,
+        ) -> Result<u8, Ds2484Error<E>> {
+            encode(table, value).ok_or(Ds2484Error::InvalidValue(name))
+        }
+        let cfg = OneWirePortConfiguration::default();
+        Ok(OneWirePortConfiguration {
+            t_rstl: (cfg.t_rstl & 0xf0) | code(&T_RSTL, self.reset_time, "reset_time")?,
+            t_rstl_od: (cfg.t_rstl_od & 0xf0)
+                | code(
+                    &T_RSTL_OD,
+                    self.reset_time_overdrive,
+                    "reset_time_overdrive",
+                )?,
+            t_msp: (cfg.t_msp & 0xf0)
+                | code(&T_MSP, self.presence_detect_time, "presence_detect_time")?,
+            t_msp_od: (cfg.t_msp_od & 0xf0)
+                | code(
+                    &T_MSP_OD,
+                    self.presence_detect_time_overdrive,
+                    "presence_detect_time_overdrive",
+                )?,
+            t_w0l: (cfg.t_w0l & 0xf0)
+                | code(&T_W0L, self.write_zero_low_time, "write_zero_low_time")?,
+            t_w0l_od: (cfg.t_w0l_od & 0xf0)
+                | code(
+                    &T_W0L_OD,
+                    self.write_zero_low_time_overdrive,
+                    "write_zero_low_time_overdrive",
+                )?,
+            t_rec0: (cfg.t_rec0 & 0xf0)
+                | code(
+                    &T_REC0,
+                    self.write_zero_recovery_time,
+                    "write_zero_recovery_time",
+                )?,
+            r_wpu: (cfg.r_wpu & 0xf0)
+                | code(
+                    &R_WPU,
+                    self.weak_pullup_resistor as u32,
+                    "weak_pullup_resistor",
+                )?,
+        })
+    }
+}
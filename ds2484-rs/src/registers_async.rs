@@ -1,148 +1,508 @@
 use crate::{
     DeviceConfiguration, DeviceStatus, Ds2484, Ds2484Error, Ds2484Result, OneWirePortConfiguration,
-    registers::{DEVICE_RST_CMD, DEVICE_STATUS_PTR, READ_PTR_CMD},
+    OneWirePortTiming, OneWirePresence, RegisterDump, ResetOutcome, SelfTestResult,
+    registers::{DEVICE_RST_CMD, DEVICE_STATUS_PTR, READ_PTR_CMD, StrongPullup, bus_errors},
     traits::Addressing,
     traits_async::InteractAsync,
+    wait::WaitStrategy,
 };
 use embedded_hal_async::{
     delay::DelayNs as DelayNsAsync,
     i2c::{I2c as I2cAsync, SevenBitAddress as SevenBitAddressAsync},
 };
+use embedded_onewire::{
+    OneWireAsync, OneWireError, OneWireResult, consts::ONEWIRE_SKIP_ROM_CMD_OD,
+};
+
+impl<I: I2cAsync<SevenBitAddressAsync>, D, W> Ds2484<I, D, W> {
+    /// Runs an I2C transaction, retrying it up to the I2C retry count if it fails.
+    async fn i2c_retrying_async<T>(
+        &mut self,
+        mut op: impl AsyncFnMut(&mut I, u8) -> Result<T, I::Error>,
+    ) -> Ds2484Result<T, I::Error> {
+        let mut tries = 0;
+        loop {
+            match op(&mut self.i2c, self.addr).await {
+                Err(_) if tries < self.i2c_retries => tries += 1,
+                res => return Ok(res?),
+            }
+        }
+    }
+
+    pub(crate) async fn i2c_write_async(&mut self, bytes: &[u8]) -> Ds2484Result<(), I::Error> {
+        self.i2c_retrying_async(async |i2c, addr| i2c.write(addr, bytes).await)
+            .await
+    }
+
+    pub(crate) async fn i2c_read_async(&mut self, buf: &mut [u8]) -> Ds2484Result<(), I::Error> {
+        self.i2c_retrying_async(async |i2c, addr| i2c.read(addr, buf).await)
+            .await
+    }
+
+    pub(crate) async fn i2c_write_read_async(
+        &mut self,
+        bytes: &[u8],
+        buf: &mut [u8],
+    ) -> Ds2484Result<(), I::Error> {
+        self.i2c_retrying_async(async |i2c, addr| i2c.write_read(addr, bytes, buf).await)
+            .await
+    }
+}
 
-impl<I: I2cAsync<SevenBitAddressAsync>, D: DelayNsAsync> Ds2484<I, D> {
+impl<I: I2cAsync<SevenBitAddressAsync>, D: DelayNsAsync, W: WaitStrategy> Ds2484<I, D, W> {
     /// Get the status of the device.
     pub async fn get_status_async(&mut self) -> Ds2484Result<DeviceStatus, I::Error> {
         let mut stat = DeviceStatus::default();
         stat.async_read(self).await?;
         Ok(stat)
     }
+
+    /// Sample the logic level of the 1-Wire line.
+    ///
+    /// Does not initiate any 1-Wire communication. Useful to diagnose a line that is
+    /// held low, e.g. by a short or a missing pullup.
+    pub async fn read_logic_level_async(&mut self) -> Ds2484Result<bool, I::Error> {
+        Ok(self.get_status_async().await?.logic_level())
+    }
+
+    /// Check for devices on the 1-Wire bus.
+    ///
+    /// Issues a 1-Wire reset and reports whether a presence pulse or a short circuit
+    /// was detected.
+    pub async fn check_presence_async(
+        &mut self,
+    ) -> OneWireResult<OneWirePresence, Ds2484Error<I::Error>> {
+        match OneWireAsync::reset(self).await {
+            Ok(_) => Ok(OneWirePresence::Present),
+            Err(OneWireError::NoDevicePresent) => Ok(OneWirePresence::Empty),
+            Err(OneWireError::ShortCircuit) => Ok(OneWirePresence::Short),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Issue a 1-Wire reset, and report the presence detect cycle along with the port
+    /// state it ran with.
+    ///
+    /// Unlike [`OneWireAsync::reset`], an empty or shorted bus is not an error. The speed
+    /// and reset low time help tell an empty bus from a reset that the devices did not
+    /// recognize, e.g. because the bus master runs at overdrive speed and the devices do not.
+    pub async fn reset_detailed_async(
+        &mut self,
+    ) -> OneWireResult<ResetOutcome, Ds2484Error<I::Error>> {
+        match OneWireAsync::reset(self).await {
+            Ok(_) | Err(OneWireError::NoDevicePresent) | Err(OneWireError::ShortCircuit) => {
+                Ok(ResetOutcome::new(self))
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Read the status, the configuration and the 1-Wire port parameters of the device.
+    pub async fn dump_registers_async(&mut self) -> Ds2484Result<RegisterDump, I::Error> {
+        let status = self.get_status_async().await?;
+        let mut config = DeviceConfiguration::new();
+        config.async_read(self).await?;
+        let port = self.port_config_async().await?;
+        Ok(RegisterDump {
+            status,
+            config,
+            port,
+        })
+    }
+
+    /// Check the device and the 1-Wire bus.
+    ///
+    /// Writes the last written configuration back to the device and verifies the value
+    /// read back, then issues a 1-Wire reset. After a device reset, the 1-Wire port
+    /// parameters are restored as well. Any error along the way classifies the device as
+    /// unresponsive.
+    pub async fn self_test_async(&mut self) -> SelfTestResult {
+        let expected = self.config;
+        let res = if self.reset {
+            self.recover_async().await
+        } else {
+            let mut config = expected;
+            config.async_write(self).await
+        };
+        if res.is_err() || self.config != expected {
+            // Keep the configuration to restore, and treat the device as reset
+            self.config = expected;
+            self.reset = true;
+            return SelfTestResult::Unresponsive;
+        }
+        match self.check_presence_async().await {
+            Ok(OneWirePresence::Present) => SelfTestResult::Present,
+            Ok(OneWirePresence::Empty) => SelfTestResult::Empty,
+            Ok(OneWirePresence::Short) => SelfTestResult::Short,
+            Err(_) => SelfTestResult::Unresponsive,
+        }
+    }
+
+    /// Enable or disable the active pullup.
+    ///
+    /// See [`DeviceConfiguration::active_pullup`].
+    pub async fn set_active_pullup_async(&mut self, enable: bool) -> Ds2484Result<(), I::Error> {
+        self.update_config_async(|c| c.set_active_pullup(enable))
+            .await
+    }
+
+    /// Enable or disable the strong pullup for the next 1-Wire Write Byte or Single Bit command.
+    ///
+    /// See [`DeviceConfiguration::strong_pullup`].
+    pub async fn set_strong_pullup_async(&mut self, enable: bool) -> Ds2484Result<(), I::Error> {
+        self.update_config_async(|c| c.set_strong_pullup(enable))
+            .await
+    }
+
+    /// Remove or restore power on the 1-Wire port.
+    ///
+    /// See [`DeviceConfiguration::power_down_1wire`].
+    pub async fn set_power_down_1wire_async(&mut self, enable: bool) -> Ds2484Result<(), I::Error> {
+        self.update_config_async(|c| c.set_power_down_1wire(enable))
+            .await
+    }
+
+    /// Write a byte to the 1-Wire bus and power the bus with the strong pullup for
+    /// `hold_ns` nanoseconds after it.
+    ///
+    /// This is the sequence needed by parasitically powered devices for e.g. a temperature
+    /// conversion or an EEPROM copy: the strong pullup is enabled, the byte is written, and
+    /// the pullup is held once the byte is sent. The strong pullup is then disabled, which
+    /// is verified by reading the configuration back, and the 1-Wire line is checked to be
+    /// high again.
+    ///
+    /// # Errors
+    /// Returns [`OneWireError::ShortCircuit`] if the 1-Wire line is still low after the
+    /// strong pullup ends.
+    pub async fn powered_command_async(
+        &mut self,
+        byte: u8,
+        hold_ns: u32,
+    ) -> OneWireResult<(), Ds2484Error<I::Error>> {
+        self.set_strong_pullup_async(true).await?;
+        OneWireAsync::write_byte(self, byte).await?;
+        // The strong pullup starts once the byte is sent
+        if !self.idle {
+            self.onewire_wait_async().await?;
+        }
+        self.delay.delay_ns(hold_ns).await;
+        self.set_strong_pullup_async(false).await?;
+        if !self.read_logic_level_async().await? {
+            return Err(OneWireError::ShortCircuit);
+        }
+        Ok(())
+    }
+
+    /// Read-modify-write the device configuration, and verify the value read back.
+    async fn update_config_async(
+        &mut self,
+        update: impl FnOnce(&mut DeviceConfiguration),
+    ) -> Ds2484Result<(), I::Error> {
+        let mut config = DeviceConfiguration::new();
+        config.async_read(self).await?;
+        update(&mut config);
+        let expected = config;
+        config.async_write(self).await?;
+        if config != expected {
+            return Err(Ds2484Error::VerificationFailed);
+        }
+        Ok(())
+    }
+
+    /// Read the 1-Wire port parameters.
+    pub async fn port_config_async(&mut self) -> Ds2484Result<OneWirePortTiming, I::Error> {
+        let mut cfg = OneWirePortConfiguration::default();
+        cfg.async_read(self).await?;
+        Ok(cfg.into())
+    }
+
+    /// Set the 1-Wire port parameters.
+    ///
+    /// Each parameter is rounded up to the next setting supported by the device, and
+    /// the parameters are read back after writing to verify that the device accepted them.
+    ///
+    /// # Errors
+    /// Returns [`Ds2484Error::InvalidValue`] naming the first parameter that exceeds the
+    /// longest supported setting, and [`Ds2484Error::VerificationFailed`] if the parameters
+    /// read back do not match the ones written.
+    pub async fn set_port_config_async(
+        &mut self,
+        timing: &OneWirePortTiming,
+    ) -> Ds2484Result<(), I::Error> {
+        let cfg = timing.encode()?;
+        let mut readback = cfg;
+        readback.async_write(self).await?;
+        if readback != cfg {
+            return Err(Ds2484Error::VerificationFailed);
+        }
+        Ok(())
+    }
 }
 
-impl<I2C: I2cAsync<SevenBitAddressAsync>, D: DelayNsAsync> Ds2484<I2C, D> {
+impl<I2C: I2cAsync<SevenBitAddressAsync>, D: DelayNsAsync, W: WaitStrategy> Ds2484<I2C, D, W> {
     /// Reset the device.
     ///
     /// Performs a global reset of device state machine logic. Terminates any ongoing 1-Wire
     /// communication.
     pub async fn bus_reset_async(&mut self) -> Ds2484Result<DeviceStatus, I2C::Error> {
-        self.i2c.write(self.addr, &[DEVICE_RST_CMD]).await?;
+        self.i2c_write_async(&[DEVICE_RST_CMD]).await?;
         self.reset = true;
+        self.port = OneWirePortConfiguration::default();
+        self.spu = StrongPullup::Off;
+        self.idle = false;
+        self.resetting = false;
         let mut tries = 0;
+        let mut waited = 0;
         let mut status = [0; 1];
         loop {
-            self.i2c.read(self.addr, &mut status).await?;
+            self.i2c_read_async(&mut status).await?;
             let status = DeviceStatus::from(status[0]);
-            if status.device_reset() || tries > self.retries {
+            if status.device_reset() || self.expired(tries, waited) {
                 break;
             }
+            let us = self.wait.interval_us(tries, self.overdrive);
+            if us > 0 {
+                self.delay.delay_us(us).await;
+            }
+            waited = waited.saturating_add(us.max(1));
             tries += 1;
-            self.delay.delay_ms(1).await;
+            self.record(|s| s.busy_polls = s.busy_polls.wrapping_add(1));
         }
         let status: DeviceStatus = status[0].into();
-        if tries > self.retries {
+        self.status = status;
+        if self.expired(tries, waited) {
+            self.record(|s| s.retries_exceeded = s.retries_exceeded.wrapping_add(1));
             Err(Ds2484Error::RetriesExceeded)
         } else {
             Ok(status)
         }
     }
 
+    /// Issue a command that is not modeled by the driver.
+    ///
+    /// Waits for the 1-Wire line to be idle, writes the command code followed by the
+    /// optional parameter byte, and returns the status once the line is idle again.
+    ///
+    /// This is a low-level escape hatch: the driver does not know the effect of the
+    /// command, so its view of the device configuration, 1-Wire speed and port parameters
+    /// may no longer match the device afterwards.
+    pub async fn raw_command_async(
+        &mut self,
+        cmd: u8,
+        param: Option<u8>,
+    ) -> Ds2484Result<DeviceStatus, I2C::Error> {
+        self.onewire_wait_async().await?;
+        match param {
+            Some(param) => self.i2c_write_async(&[cmd, param]).await?,
+            None => self.i2c_write_async(&[cmd]).await?,
+        }
+        self.command_issued(false);
+        self.onewire_wait_async().await
+    }
+
+    /// Release the I2C bus and the timer.
+    ///
+    /// Resets the device before returning, which terminates any ongoing 1-Wire
+    /// communication, disables the strong pullup and returns the 1-Wire port to
+    /// standard speed. Errors encountered during the reset are ignored.
+    pub async fn release_async(mut self) -> (I2C, D) {
+        let _ = self.bus_reset_async().await;
+        (self.i2c, self.delay)
+    }
+
     pub(crate) async fn onewire_wait_async(&mut self) -> Ds2484Result<DeviceStatus, I2C::Error> {
+        self.i2c_write_async(&[READ_PTR_CMD, DEVICE_STATUS_PTR])
+            .await?;
+        self.onewire_poll_async().await
+    }
+
+    /// Polls the status register until the 1-Wire line is idle.
+    ///
+    /// The read pointer must already be positioned at the status register, which is the
+    /// case after any 1-Wire communication command.
+    pub(crate) async fn onewire_poll_async(&mut self) -> Ds2484Result<DeviceStatus, I2C::Error> {
         let mut tries = 0;
+        let mut waited = 0;
         let mut status = [0; 1];
-        self.i2c
-            .write(self.addr, &[READ_PTR_CMD, DEVICE_STATUS_PTR])
-            .await?;
         loop {
-            self.i2c.read(self.addr, &mut status).await?;
+            self.i2c_read_async(&mut status).await?;
             let status = DeviceStatus::from(status[0]);
-            if !status.onewire_busy() || tries > self.retries {
+            if !status.onewire_busy() || self.expired(tries, waited) {
                 break;
             }
-            tries += 1;
-            if !self.overdrive {
-                self.delay.delay_ms(1).await;
-            } else {
-                self.delay.delay_us(100).await;
+            let us = self.wait.interval_us(tries, self.overdrive);
+            if us > 0 {
+                self.delay.delay_us(us).await;
             }
+            waited = waited.saturating_add(us.max(1));
+            tries += 1;
+            self.record(|s| s.busy_polls = s.busy_polls.wrapping_add(1));
         }
         let status: DeviceStatus = status[0].into();
-        if status.onewire_busy() && tries > self.retries {
+        self.status = status;
+        if status.device_reset() && !self.reset {
+            self.reset = true;
+            self.spu = StrongPullup::Off;
+            Err(Ds2484Error::DeviceReset)
+        } else if status.onewire_busy() && self.expired(tries, waited) {
+            self.record(|s| s.retries_exceeded = s.retries_exceeded.wrapping_add(1));
             Err(Ds2484Error::RetriesExceeded)
         } else {
+            self.idle = true;
             Ok(status)
         }
     }
+
+    /// Waits until the 1-Wire line is idle before issuing a command.
+    ///
+    /// In pipelined mode, the wait is skipped if the line was found idle after the last
+    /// command.
+    pub(crate) async fn onewire_ready_async(&mut self) -> Ds2484Result<(), I2C::Error> {
+        if self.pipelined && self.idle {
+            return Ok(());
+        }
+        self.onewire_wait_async().await.map(|_| ())
+    }
+
+    /// Waits for a 1-Wire command, expected to last `duration_ns`, to complete.
+    ///
+    /// In pipelined mode, sleeps through the command before the first status poll.
+    pub(crate) async fn onewire_settle_async(
+        &mut self,
+        duration_ns: u32,
+    ) -> Ds2484Result<DeviceStatus, I2C::Error> {
+        if self.pipelined {
+            self.delay.delay_ns(duration_ns).await;
+            self.onewire_poll_async().await
+        } else {
+            self.onewire_wait_async().await
+        }
+    }
+
+    /// Switches the devices on the 1-Wire bus, if any, and the DS2484 to overdrive speed.
+    ///
+    /// Only I2C and device errors are reported, the 1-Wire resets may find the bus empty.
+    pub(crate) async fn enter_overdrive_async(&mut self) -> Ds2484Result<(), I2C::Error> {
+        bus_errors(OneWireAsync::reset(self).await)?;
+        bus_errors(OneWireAsync::write_byte(self, ONEWIRE_SKIP_ROM_CMD_OD).await)?;
+        let mut config = self.config.with_onewire_speed(true);
+        config.async_write(self).await?;
+        self.overdrive = true;
+        bus_errors(OneWireAsync::reset(self).await)
+    }
+
+    /// Restores the last written device configuration and 1-Wire port parameters.
+    pub(crate) async fn recover_async(&mut self) -> Ds2484Result<(), I2C::Error> {
+        let port = self.port;
+        let mut config = self.config;
+        config.async_write(self).await?;
+        if port != OneWirePortConfiguration::default() {
+            let mut port = port;
+            port.async_write(self).await?;
+        }
+        Ok(())
+    }
+
+    /// Runs a 1-Wire operation, recovering from a device reset if enabled.
+    ///
+    /// Fails with [`OneWireError::BusUninitialized`] if the device has been reset and
+    /// automatic recovery is disabled. Otherwise, the operation is retried once if it
+    /// finds that the device reset while it was running.
+    pub(crate) async fn recovering_async<T>(
+        &mut self,
+        mut op: impl AsyncFnMut(&mut Self) -> OneWireResult<T, Ds2484Error<I2C::Error>>,
+    ) -> OneWireResult<T, Ds2484Error<I2C::Error>> {
+        if self.reset {
+            if !self.auto_recover {
+                return Err(OneWireError::BusUninitialized);
+            }
+            self.recover_async().await?;
+        }
+        match op(self).await {
+            Err(OneWireError::Other(Ds2484Error::DeviceReset)) if self.auto_recover => {
+                self.recover_async().await?;
+                op(self).await
+            }
+            res => res,
+        }
+    }
 }
 
 impl InteractAsync for DeviceStatus {
-    async fn async_read<I: I2cAsync<SevenBitAddressAsync>, D>(
+    async fn async_read<I: I2cAsync<SevenBitAddressAsync>, D, W>(
         &mut self,
-        dev: &mut Ds2484<I, D>,
+        dev: &mut Ds2484<I, D, W>,
     ) -> Result<(), Ds2484Error<I::Error>> {
         let mut val = [0; 1];
-        dev.i2c
-            .write_read(dev.addr, &[READ_PTR_CMD, Self::READ_PTR], &mut val)
+        dev.i2c_write_read_async(&[READ_PTR_CMD, Self::READ_PTR], &mut val)
             .await?;
         *self = DeviceStatus::from(val[0]);
+        dev.status = *self;
         Ok(())
     }
 
-    async fn async_write<I: I2cAsync<SevenBitAddressAsync>, D>(
+    async fn async_write<I: I2cAsync<SevenBitAddressAsync>, D, W>(
         &mut self,
-        _dev: &mut Ds2484<I, D>,
+        _dev: &mut Ds2484<I, D, W>,
     ) -> Result<(), Ds2484Error<I::Error>> {
         Ok(())
     }
 }
 
 impl InteractAsync for DeviceConfiguration {
-    async fn async_read<I: I2cAsync<SevenBitAddressAsync>, D: DelayNsAsync>(
+    async fn async_read<I: I2cAsync<SevenBitAddressAsync>, D: DelayNsAsync, W: WaitStrategy>(
         &mut self,
-        dev: &mut Ds2484<I, D>,
+        dev: &mut Ds2484<I, D, W>,
     ) -> Result<(), Ds2484Error<I::Error>> {
         let mut val = [0; 1];
-        dev.i2c
-            .write_read(dev.addr, &[READ_PTR_CMD, Self::READ_PTR], &mut val)
+        dev.i2c_write_read_async(&[READ_PTR_CMD, Self::READ_PTR], &mut val)
             .await?;
         *self = DeviceConfiguration::from(val[0]);
         Ok(())
     }
 
-    async fn async_write<I: I2cAsync<SevenBitAddressAsync>, D: DelayNsAsync>(
+    async fn async_write<I: I2cAsync<SevenBitAddressAsync>, D: DelayNsAsync, W: WaitStrategy>(
         &mut self,
-        dev: &mut Ds2484<I, D>,
+        dev: &mut Ds2484<I, D, W>,
     ) -> Result<(), Ds2484Error<I::Error>> {
         dev.onewire_wait_async().await?;
         let out = u8::from(*self);
         let out = (out & 0x0f) | ((!out & 0x0f) << 4);
         let mut val = [0; 1];
-        dev.i2c
-            .write_read(dev.addr, &[Self::WRITE_ADDR, out], &mut val)
+        dev.i2c_write_read_async(&[Self::WRITE_ADDR, out], &mut val)
             .await?;
         *self = val[0].into();
+        dev.spu = match (self.strong_pullup(), dev.spu) {
+            (true, StrongPullup::Active) => StrongPullup::Active, // Kept by a read-modify-write
+            (true, _) => StrongPullup::Armed,
+            (false, _) => StrongPullup::Off,
+        };
+        // The strong pullup is one-shot, and is not restored after a device reset
+        dev.config = self.with_strong_pullup(false);
         dev.reset = false; // Clear the reset flag after writing configuration
         Ok(())
     }
 }
 
 impl InteractAsync for OneWirePortConfiguration {
-    async fn async_read<I: I2cAsync<SevenBitAddressAsync>, D: DelayNsAsync>(
+    async fn async_read<I: I2cAsync<SevenBitAddressAsync>, D: DelayNsAsync, W: WaitStrategy>(
         &mut self,
-        dev: &mut Ds2484<I, D>,
+        dev: &mut Ds2484<I, D, W>,
     ) -> Result<(), Ds2484Error<I::Error>> {
         let mut buf = [0; 8];
-        dev.i2c
-            .write_read(dev.addr, &[READ_PTR_CMD, Self::READ_PTR], &mut buf)
+        dev.i2c_write_read_async(&[READ_PTR_CMD, Self::READ_PTR], &mut buf)
             .await?;
         *self = Self::from_bytes(buf);
+        dev.port = *self;
         Ok(())
     }
 
-    async fn async_write<I: I2cAsync<SevenBitAddressAsync>, D: DelayNsAsync>(
+    async fn async_write<I: I2cAsync<SevenBitAddressAsync>, D: DelayNsAsync, W: WaitStrategy>(
         &mut self,
-        dev: &mut Ds2484<I, D>,
+        dev: &mut Ds2484<I, D, W>,
     ) -> Result<(), Ds2484Error<I::Error>> {
         dev.onewire_wait_async().await?;
-        dev.i2c.write(dev.addr, &self.to_bytes()).await?;
+        dev.i2c_write_async(&self.to_bytes()).await?;
         self.async_read(dev).await
     }
 }
@@ -0,0 +1,107 @@
+use crate::{DeviceStatus, Ds2484, Ds2484Error, FixedInterval, WaitStrategy};
+use core::cell::RefCell;
+use embedded_hal::{
+    delay::DelayNs,
+    i2c::{I2c, SevenBitAddress},
+};
+use embedded_onewire::{OneWire, OneWireResult, OneWireStrongPullup};
+
+/// A [`Ds2484`] shared between several 1-Wire device drivers.
+///
+/// Each driver holds its own [`SharedDs2484Handle`], obtained from
+/// [`SharedDs2484::handle`], instead of an exclusive borrow of the bus master. Every 1-Wire
+/// operation borrows the device for its own duration only, so operations from different
+/// handles must not be nested, e.g. from an interrupt handler, or the borrow panics.
+/// Interleaving operations of different drivers is up to the application: a driver that
+/// addresses a device and then reads from it expects no other driver in between.
+pub struct SharedDs2484<I, D, W = FixedInterval> {
+    dev: RefCell<Ds2484<I, D, W>>,
+}
+
+impl<I, D, W> SharedDs2484<I, D, W> {
+    /// Wraps the bus master for sharing.
+    pub fn new(dev: Ds2484<I, D, W>) -> Self {
+        SharedDs2484 {
+            dev: RefCell::new(dev),
+        }
+    }
+
+    /// Get a handle to the bus master that implements [`OneWire`].
+    pub fn handle(&self) -> SharedDs2484Handle<'_, I, D, W> {
+        SharedDs2484Handle { dev: &self.dev }
+    }
+
+    /// Unwraps the bus master.
+    pub fn into_inner(self) -> Ds2484<I, D, W> {
+        self.dev.into_inner()
+    }
+}
+
+/// Handle to a [`SharedDs2484`], see [`SharedDs2484::handle`].
+pub struct SharedDs2484Handle<'a, I, D, W = FixedInterval> {
+    dev: &'a RefCell<Ds2484<I, D, W>>,
+}
+
+impl<I2C: I2c<SevenBitAddress>, D: DelayNs, W: WaitStrategy> OneWire
+    for SharedDs2484Handle<'_, I2C, D, W>
+{
+    type Status = DeviceStatus;
+
+    type BusError = Ds2484Error<I2C::Error>;
+
+    fn reset(&mut self) -> OneWireResult<Self::Status, Self::BusError> {
+        self.dev.borrow_mut().reset()
+    }
+
+    fn address(&mut self, rom: Option<u64>) -> OneWireResult<(), Self::BusError> {
+        self.dev.borrow_mut().address(rom)
+    }
+
+    fn write_byte(&mut self, byte: u8) -> OneWireResult<(), Self::BusError> {
+        self.dev.borrow_mut().write_byte(byte)
+    }
+
+    fn read_byte(&mut self) -> OneWireResult<u8, Self::BusError> {
+        self.dev.borrow_mut().read_byte()
+    }
+
+    fn write_bytes(&mut self, bytes: &[u8]) -> OneWireResult<(), Self::BusError> {
+        self.dev.borrow_mut().write_bytes(bytes)
+    }
+
+    fn read_bytes(&mut self, buf: &mut [u8]) -> OneWireResult<(), Self::BusError> {
+        self.dev.borrow_mut().read_bytes(buf)
+    }
+
+    fn write_bit(&mut self, bit: bool) -> OneWireResult<(), Self::BusError> {
+        self.dev.borrow_mut().write_bit(bit)
+    }
+
+    fn read_bit(&mut self) -> OneWireResult<bool, Self::BusError> {
+        self.dev.borrow_mut().read_bit()
+    }
+
+    #[cfg(feature = "triplet-read")]
+    fn read_triplet(
+        &mut self,
+        direction: bool,
+    ) -> OneWireResult<(bool, bool, bool), Self::BusError> {
+        self.dev.borrow_mut().read_triplet(direction)
+    }
+
+    fn get_overdrive_mode(&mut self) -> bool {
+        self.dev.borrow_mut().get_overdrive_mode()
+    }
+
+    fn set_overdrive_mode(&mut self, enable: bool) -> OneWireResult<(), Self::BusError> {
+        self.dev.borrow_mut().set_overdrive_mode(enable)
+    }
+}
+
+impl<I2C: I2c<SevenBitAddress>, D: DelayNs, W: WaitStrategy> OneWireStrongPullup
+    for SharedDs2484Handle<'_, I2C, D, W>
+{
+    fn write_byte_powered(&mut self, byte: u8) -> OneWireResult<(), Self::BusError> {
+        self.dev.borrow_mut().write_byte_powered(byte)
+    }
+}
@@ -0,0 +1,19 @@
+/// Counters of the operations performed by a [`Ds2484`](crate::Ds2484).
+///
+/// All counters wrap around on overflow.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(not(feature = "stats"), allow(dead_code))]
+pub struct BusStatistics {
+    /// Number of 1-Wire resets issued.
+    pub resets: u32,
+    /// Number of bytes written to the 1-Wire bus.
+    pub bytes_written: u32,
+    /// Number of bytes read from the 1-Wire bus.
+    pub bytes_read: u32,
+    /// Number of status register polls that found the device busy.
+    pub busy_polls: u32,
+    /// Number of operations that failed with [`Ds2484Error::RetriesExceeded`](crate::Ds2484Error::RetriesExceeded).
+    pub retries_exceeded: u32,
+    /// Number of CRC failures reported with [`Ds2484::record_crc_failure`](crate::Ds2484::record_crc_failure).
+    pub crc_failures: u32,
+}
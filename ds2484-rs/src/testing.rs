@@ -0,0 +1,141 @@
+//! Canned I2C transactions of a simulated DS2484.
+//!
+//! The sequences match a [`Ds2484`](crate::Ds2484) built with the default
+//! [`Ds2484Builder`](crate::Ds2484Builder) settings, and a 1-Wire line that is idle
+//! at every status poll. They can be chained to test drivers of 1-Wire devices against
+//! an [`I2cMock`](embedded_hal_mock::eh1::i2c::Mock):
+//!
+//! ```rust
+//! use ds2484::{DeviceConfiguration, Ds2484Builder, OneWire, testing};
+//! use embedded_hal_mock::eh1::{delay::NoopDelay, i2c::Mock as I2cMock};
+//!
+//! let mut expectations = testing::build(DeviceConfiguration::new());
+//! expectations.extend(testing::onewire_reset(true));
+//! expectations.extend(testing::write_byte(0xcc));
+//! let mut i2c = I2cMock::new(&expectations);
+//! let mut ds2484 = Ds2484Builder::default().build(&mut i2c, NoopDelay::new()).unwrap();
+//! ds2484.reset().unwrap();
+//! ds2484.write_byte(0xcc).unwrap();
+//! i2c.done();
+//! ```
+extern crate std;
+
+use crate::{
+    DeviceConfiguration, DeviceStatus,
+    onewire::{ONEWIRE_READ_BYTE, ONEWIRE_READ_DATA_PTR, ONEWIRE_RESET_CMD, ONEWIRE_WRITE_BYTE},
+    registers::{DEVICE_RST_CMD, DEVICE_STATUS_PTR, READ_PTR_CMD},
+};
+use embedded_hal_mock::eh1::i2c::Transaction;
+use std::{vec, vec::Vec};
+
+/// I2C address of the DS2484.
+pub const ADDRESS: u8 = 0x18;
+
+/// Device reset, and the status read that finds it complete.
+pub fn device_reset() -> Vec<Transaction> {
+    vec![
+        Transaction::write(ADDRESS, vec![DEVICE_RST_CMD]),
+        Transaction::write_read(
+            ADDRESS,
+            vec![READ_PTR_CMD, DEVICE_STATUS_PTR],
+            vec![0x10], // RST
+        ),
+    ]
+}
+
+/// Status poll that finds the 1-Wire line idle, with the given status.
+pub fn wait(status: DeviceStatus) -> Vec<Transaction> {
+    vec![
+        Transaction::write(ADDRESS, vec![READ_PTR_CMD, DEVICE_STATUS_PTR]),
+        Transaction::read(ADDRESS, vec![status.into_bits()]),
+    ]
+}
+
+/// Device configuration write, accepted by the device.
+pub fn config_write(config: DeviceConfiguration) -> Vec<Transaction> {
+    let bits = config.into_bits() & 0x0f;
+    let mut t = wait(DeviceStatus::new());
+    t.push(Transaction::write(ADDRESS, vec![0xd2, bits | (!bits << 4)]));
+    t.push(Transaction::read(ADDRESS, vec![bits]));
+    t
+}
+
+/// Transactions of [`Ds2484Builder::build`](crate::Ds2484Builder::build) with the given
+/// device configuration.
+pub fn build(config: DeviceConfiguration) -> Vec<Transaction> {
+    let mut t = device_reset();
+    t.extend(config_write(config));
+    t
+}
+
+/// 1-Wire reset, which finds devices on the bus if `present` is true.
+pub fn onewire_reset(present: bool) -> Vec<Transaction> {
+    let mut t = wait(DeviceStatus::new());
+    t.push(Transaction::write(ADDRESS, vec![ONEWIRE_RESET_CMD]));
+    t.extend(wait(DeviceStatus::new().with_present_pulse_detect(present)));
+    t
+}
+
+/// 1-Wire byte write.
+pub fn write_byte(byte: u8) -> Vec<Transaction> {
+    let mut t = wait(DeviceStatus::new());
+    t.push(Transaction::write(ADDRESS, vec![ONEWIRE_WRITE_BYTE, byte]));
+    t
+}
+
+/// 1-Wire byte read, which reads `byte` from the bus.
+pub fn read_byte(byte: u8) -> Vec<Transaction> {
+    let mut t = wait(DeviceStatus::new());
+    t.push(Transaction::write(ADDRESS, vec![ONEWIRE_READ_BYTE]));
+    t.push(Transaction::read(
+        ADDRESS,
+        vec![DeviceStatus::new().into_bits()],
+    ));
+    t.push(Transaction::write_read(
+        ADDRESS,
+        vec![READ_PTR_CMD, ONEWIRE_READ_DATA_PTR],
+        vec![byte],
+    ));
+    t
+}
+
+/// 1-Wire triplet taking `direction`, which reads `id_bit` and `complement_bit`.
+///
+/// The direction taken follows the datasheet: the bit read if the bits differ,
+/// `direction` if both are 0, and 1 if both are 1. The triplet starts with a status poll,
+/// as for the first triplet after the Search ROM command byte.
+#[cfg(feature = "triplet-read")]
+#[cfg_attr(docsrs, doc(cfg(feature = "triplet-read")))]
+pub fn triplet(direction: bool, id_bit: bool, complement_bit: bool) -> Vec<Transaction> {
+    let mut t = wait(DeviceStatus::new());
+    t.extend(next_triplet(direction, id_bit, complement_bit));
+    t
+}
+
+/// 1-Wire triplet issued right after another triplet, see [`triplet`].
+///
+/// The previous triplet left the 1-Wire line idle, so the driver issues this one
+/// without polling the status first.
+#[cfg(feature = "triplet-read")]
+#[cfg_attr(docsrs, doc(cfg(feature = "triplet-read")))]
+pub fn next_triplet(direction: bool, id_bit: bool, complement_bit: bool) -> Vec<Transaction> {
+    let taken = if id_bit != complement_bit {
+        id_bit
+    } else {
+        direction || id_bit
+    };
+    let status = DeviceStatus::new()
+        .with_single_bit_result(id_bit)
+        .with_triplet_second_bit(complement_bit)
+        .with_branch_dir_taken(taken);
+    vec![
+        Transaction::write(
+            ADDRESS,
+            vec![
+                crate::onewire::ONEWIRE_TRIPLET,
+                if direction { 0xff } else { 0x0 },
+            ],
+        ),
+        Transaction::read(ADDRESS, vec![status.into_bits()]),
+    ]
+}
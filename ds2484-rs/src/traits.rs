@@ -1,4 +1,4 @@
-use crate::{Ds2484, Ds2484Error};
+use crate::{Ds2484, Ds2484Error, WaitStrategy};
 use embedded_hal::{
     delay::DelayNs,
     i2c::{I2c, SevenBitAddress},
@@ -15,13 +15,13 @@ pub trait Addressing {
 /// Trait for interacting with the DS2484 I2C 1-Wire master.
 pub trait Interact: Addressing {
     /// Read the register value from the DS2484.
-    fn read<I: I2c<SevenBitAddress>, D: DelayNs>(
+    fn read<I: I2c<SevenBitAddress>, D: DelayNs, W: WaitStrategy>(
         &mut self,
-        dev: &mut Ds2484<I, D>,
+        dev: &mut Ds2484<I, D, W>,
     ) -> Result<(), Ds2484Error<I::Error>>;
     /// Write the register value to the DS2484.
-    fn write<I: I2c<SevenBitAddress>, D: DelayNs>(
+    fn write<I: I2c<SevenBitAddress>, D: DelayNs, W: WaitStrategy>(
         &mut self,
-        dev: &mut Ds2484<I, D>,
+        dev: &mut Ds2484<I, D, W>,
     ) -> Result<(), Ds2484Error<I::Error>>;
 }
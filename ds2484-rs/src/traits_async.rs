@@ -1,5 +1,5 @@
 #![allow(async_fn_in_trait)]
-use crate::{Ds2484, Ds2484Error, traits::Addressing};
+use crate::{Ds2484, Ds2484Error, WaitStrategy, traits::Addressing};
 use embedded_hal_async::{
     delay::DelayNs,
     i2c::{I2c, SevenBitAddress},
@@ -8,13 +8,13 @@ use embedded_hal_async::{
 /// Trait for interacting with the DS2484 I2C 1-Wire master asynchronously.
 pub trait InteractAsync: Addressing {
     /// Read the register value from the DS2484 asynchronously.
-    async fn async_read<I: I2c<SevenBitAddress>, D: DelayNs>(
+    async fn async_read<I: I2c<SevenBitAddress>, D: DelayNs, W: WaitStrategy>(
         &mut self,
-        dev: &mut Ds2484<I, D>,
+        dev: &mut Ds2484<I, D, W>,
     ) -> Result<(), Ds2484Error<I::Error>>;
     /// Write the register value to the DS2484 asynchronously.
-    async fn async_write<I: I2c<SevenBitAddress>, D: DelayNs>(
+    async fn async_write<I: I2c<SevenBitAddress>, D: DelayNs, W: WaitStrategy>(
         &mut self,
-        dev: &mut Ds2484<I, D>,
+        dev: &mut Ds2484<I, D, W>,
     ) -> Result<(), Ds2484Error<I::Error>>;
 }
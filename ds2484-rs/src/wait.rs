@@ -0,0 +1,83 @@
+/// Strategy used to pace the status register polls while the DS2484 is busy.
+///
+/// [`WaitStrategy::interval_us`] is called every time a poll finds the device busy, and
+/// returns the time to wait, in microseconds, before polling again. Returning `0` polls
+/// again immediately, which lets an implementation yield to a scheduler on its own
+/// instead of blocking in the delay.
+///
+/// Any `FnMut(u32, bool) -> u32` closure can be used as a wait strategy.
+pub trait WaitStrategy {
+    /// Returns the time to wait, in microseconds, before the next poll.
+    ///
+    /// # Arguments
+    /// * `attempt` - The number of polls that found the device busy so far, minus one.
+    /// * `overdrive` - Whether the 1-Wire bus is running at overdrive speed.
+    fn interval_us(&mut self, attempt: u32, overdrive: bool) -> u32;
+}
+
+/// Polls the status register at a fixed interval.
+///
+/// The default polls every 1 ms at standard speed and every 100 µs at overdrive speed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FixedInterval {
+    /// Interval between polls at standard speed, in microseconds.
+    pub standard_us: u32,
+    /// Interval between polls at overdrive speed, in microseconds.
+    pub overdrive_us: u32,
+}
+
+impl Default for FixedInterval {
+    fn default() -> Self {
+        FixedInterval {
+            standard_us: 1000,
+            overdrive_us: 100,
+        }
+    }
+}
+
+impl WaitStrategy for FixedInterval {
+    fn interval_us(&mut self, _attempt: u32, overdrive: bool) -> u32 {
+        if overdrive {
+            self.overdrive_us
+        } else {
+            self.standard_us
+        }
+    }
+}
+
+/// Polls the status register with an exponentially increasing interval.
+///
+/// The interval starts at `initial_us` and doubles after every busy poll, up to `max_us`.
+/// The default starts at 10 µs and is capped at 1 ms.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExponentialBackoff {
+    /// Interval before the second poll, in microseconds.
+    pub initial_us: u32,
+    /// Upper bound of the interval, in microseconds.
+    pub max_us: u32,
+}
+
+impl Default for ExponentialBackoff {
+    fn default() -> Self {
+        ExponentialBackoff {
+            initial_us: 10,
+            max_us: 1000,
+        }
+    }
+}
+
+impl WaitStrategy for ExponentialBackoff {
+    fn interval_us(&mut self, attempt: u32, _overdrive: bool) -> u32 {
+        self.initial_us
+            .checked_shl(attempt)
+            .filter(|&us| us >> attempt == self.initial_us)
+            .unwrap_or(u32::MAX)
+            .min(self.max_us)
+    }
+}
+
+impl<F: FnMut(u32, bool) -> u32> WaitStrategy for F {
+    fn interval_us(&mut self, attempt: u32, overdrive: bool) -> u32 {
+        self(attempt, overdrive)
+    }
+}
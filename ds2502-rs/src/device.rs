@@ -0,0 +1,130 @@
+use crate::{Model, READ_MEMORY_CMD, READ_STATUS_CMD, WRITE_MEMORY_CMD};
+use embedded_onewire::{OneWire, OneWireError, OneWireProgramPulse, OneWireResult};
+
+/// A DS2502 or DS2505 add-only memory on a 1-Wire bus.
+///
+/// The memory is an EPROM: it reads as all ones when blank, and programming a byte can only
+/// clear its bits. Programming requires a bus master implementing [`OneWireProgramPulse`].
+///
+/// The driver owns the bus master, which can be a `&mut` reference to one or a handle to a
+/// shared one. Every operation addresses the device first.
+#[derive(Debug)]
+pub struct Ds2502<O> {
+    pub(crate) bus: O,
+    pub(crate) rom: Option<u64>,
+    pub(crate) model: Model,
+}
+
+impl<O> Ds2502<O> {
+    /// Creates a driver for the device `model` with ROM `rom` on `bus`.
+    ///
+    /// Pass [`None`] to skip ROM addressing on a bus with a single device. The model of an
+    /// addressed device can be found from its ROM with [`Model::from_family_code`].
+    pub fn new(bus: O, rom: Option<u64>, model: Model) -> Self {
+        Ds2502 { bus, rom, model }
+    }
+
+    /// ROM of the device, if it is addressed by ROM.
+    pub fn rom(&self) -> Option<u64> {
+        self.rom
+    }
+
+    /// Model of the device.
+    pub fn model(&self) -> Model {
+        self.model
+    }
+
+    /// Release the bus master.
+    pub fn release(self) -> O {
+        self.bus
+    }
+}
+
+impl<O: OneWire> Ds2502<O> {
+    /// Read the data memory from `address` into `buf`.
+    ///
+    /// # Errors
+    /// Returns [`OneWireError::InvalidValue`] if the read goes past the end of the memory,
+    /// and [`OneWireError::InvalidCrc`] if the CRC of the command sent back by a DS2502 does
+    /// not match.
+    pub fn read_memory(&mut self, address: u16, buf: &mut [u8]) -> OneWireResult<(), O::BusError> {
+        if address as usize + buf.len() > self.model.memory_size() {
+            return Err(OneWireError::InvalidValue("address"));
+        }
+        self.read(READ_MEMORY_CMD, address, buf)
+    }
+
+    /// Read the status memory from `address` into `buf`.
+    ///
+    /// The status memory holds the write protection and the page redirection bytes.
+    ///
+    /// # Errors
+    /// See [`Ds2502::read_memory`].
+    pub fn read_status(&mut self, address: u16, buf: &mut [u8]) -> OneWireResult<(), O::BusError> {
+        if address as usize + buf.len() > self.model.status_size() {
+            return Err(OneWireError::InvalidValue("address"));
+        }
+        self.read(READ_STATUS_CMD, address, buf)
+    }
+
+    fn read(&mut self, cmd: u8, address: u16, buf: &mut [u8]) -> OneWireResult<(), O::BusError> {
+        let [lo, hi] = address.to_le_bytes();
+        let cmd = [cmd, lo, hi];
+        self.bus.address(self.rom)?;
+        self.bus.write_bytes(&cmd)?;
+        // Only the DS2502 sends the CRC of the command, the CRC at the end of the page is
+        // not read.
+        if self.model == Model::Ds2502 {
+            let crc = self.bus.read_byte()?;
+            self.model.check_crc(&cmd, &[crc])?;
+        }
+        self.bus.read_bytes(buf)
+    }
+}
+
+impl<O: OneWireProgramPulse> Ds2502<O> {
+    /// Program `byte` at `address` of the data memory.
+    ///
+    /// The byte is checked with the CRC sent back by the device before the programming
+    /// pulse, and read back after it.
+    ///
+    /// # Errors
+    /// Returns [`OneWireError::InvalidValue`] if `address` is past the end of the memory,
+    /// [`OneWireError::InvalidCrc`] if the CRC sent back does not match, in which case the
+    /// byte is not programmed, and [`OneWireError::VerificationFailed`] if the byte read back
+    /// does not match, e.g. because a bit to set was already programmed to 0.
+    pub fn write_byte(&mut self, address: u16, byte: u8) -> OneWireResult<(), O::BusError> {
+        if address as usize >= self.model.memory_size() {
+            return Err(OneWireError::InvalidValue("address"));
+        }
+        let [lo, hi] = address.to_le_bytes();
+        let cmd = [WRITE_MEMORY_CMD, lo, hi, byte];
+        self.bus.address(self.rom)?;
+        self.bus.write_bytes(&cmd)?;
+        let mut crc = [0; 2];
+        let crc = &mut crc[..self.model.crc_len()];
+        self.bus.read_bytes(crc)?;
+        self.model.check_crc(&cmd, crc)?;
+        self.bus.program_pulse()?;
+        if self.bus.read_byte()? != byte {
+            return Err(OneWireError::VerificationFailed);
+        }
+        Ok(())
+    }
+
+    /// Program `data` at `address` of the data memory, one byte at a time.
+    ///
+    /// # Errors
+    /// Returns [`OneWireError::InvalidValue`] if the write goes past the end of the memory,
+    /// and the errors of [`Ds2502::write_byte`]. The bytes before the failed one are already
+    /// programmed.
+    pub fn write(&mut self, address: u16, data: &[u8]) -> OneWireResult<(), O::BusError> {
+        if address as usize + data.len() > self.model.memory_size() {
+            return Err(OneWireError::InvalidValue("address"));
+        }
+        for (address, &byte) in (address..).zip(data) {
+            self.write_byte(address, byte)?;
+        }
+        Ok(())
+    }
+}
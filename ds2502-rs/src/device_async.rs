@@ -0,0 +1,94 @@
+use crate::{Ds2502, Model, READ_MEMORY_CMD, READ_STATUS_CMD, WRITE_MEMORY_CMD};
+use embedded_onewire::{OneWireAsync, OneWireError, OneWireProgramPulseAsync, OneWireResult};
+
+impl<O: OneWireAsync> Ds2502<O> {
+    /// Read the data memory from `address` into `buf`.
+    ///
+    /// See [`Ds2502::read_memory`].
+    pub async fn read_memory_async(
+        &mut self,
+        address: u16,
+        buf: &mut [u8],
+    ) -> OneWireResult<(), O::BusError> {
+        if address as usize + buf.len() > self.model.memory_size() {
+            return Err(OneWireError::InvalidValue("address"));
+        }
+        self.read_async(READ_MEMORY_CMD, address, buf).await
+    }
+
+    /// Read the status memory from `address` into `buf`.
+    ///
+    /// See [`Ds2502::read_status`].
+    pub async fn read_status_async(
+        &mut self,
+        address: u16,
+        buf: &mut [u8],
+    ) -> OneWireResult<(), O::BusError> {
+        if address as usize + buf.len() > self.model.status_size() {
+            return Err(OneWireError::InvalidValue("address"));
+        }
+        self.read_async(READ_STATUS_CMD, address, buf).await
+    }
+
+    async fn read_async(
+        &mut self,
+        cmd: u8,
+        address: u16,
+        buf: &mut [u8],
+    ) -> OneWireResult<(), O::BusError> {
+        let [lo, hi] = address.to_le_bytes();
+        let cmd = [cmd, lo, hi];
+        OneWireAsync::address(&mut self.bus, self.rom).await?;
+        OneWireAsync::write_bytes(&mut self.bus, &cmd).await?;
+        if self.model == Model::Ds2502 {
+            let crc = OneWireAsync::read_byte(&mut self.bus).await?;
+            self.model.check_crc(&cmd, &[crc])?;
+        }
+        OneWireAsync::read_bytes(&mut self.bus, buf).await
+    }
+}
+
+impl<O: OneWireProgramPulseAsync> Ds2502<O> {
+    /// Program `byte` at `address` of the data memory.
+    ///
+    /// See [`Ds2502::write_byte`].
+    pub async fn write_byte_async(
+        &mut self,
+        address: u16,
+        byte: u8,
+    ) -> OneWireResult<(), O::BusError> {
+        if address as usize >= self.model.memory_size() {
+            return Err(OneWireError::InvalidValue("address"));
+        }
+        let [lo, hi] = address.to_le_bytes();
+        let cmd = [WRITE_MEMORY_CMD, lo, hi, byte];
+        OneWireAsync::address(&mut self.bus, self.rom).await?;
+        OneWireAsync::write_bytes(&mut self.bus, &cmd).await?;
+        let mut crc = [0; 2];
+        let crc = &mut crc[..self.model.crc_len()];
+        OneWireAsync::read_bytes(&mut self.bus, crc).await?;
+        self.model.check_crc(&cmd, crc)?;
+        OneWireProgramPulseAsync::program_pulse(&mut self.bus).await?;
+        if OneWireAsync::read_byte(&mut self.bus).await? != byte {
+            return Err(OneWireError::VerificationFailed);
+        }
+        Ok(())
+    }
+
+    /// Program `data` at `address` of the data memory, one byte at a time.
+    ///
+    /// See [`Ds2502::write`].
+    pub async fn write_async(
+        &mut self,
+        address: u16,
+        data: &[u8],
+    ) -> OneWireResult<(), O::BusError> {
+        if address as usize + data.len() > self.model.memory_size() {
+            return Err(OneWireError::InvalidValue("address"));
+        }
+        for (address, &byte) in (address..).zip(data) {
+            self.write_byte_async(address, byte).await?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,153 @@
+#![no_std]
+#![deny(missing_docs)]
+#![cfg_attr(docsrs, feature(doc_cfg))]
+#![doc = include_str!("../README.md")]
+
+mod device;
+mod device_async;
+
+pub use device::Ds2502;
+use embedded_onewire::{OneWireCrc, OneWireCrc16};
+pub use embedded_onewire::{OneWireError, OneWireResult};
+
+/// Family code of the DS2502, the low byte of its ROM.
+pub const DS2502_FAMILY_CODE: u8 = 0x09;
+/// Family code of the DS2505, the low byte of its ROM.
+pub const DS2505_FAMILY_CODE: u8 = 0x0b;
+
+pub(crate) const READ_MEMORY_CMD: u8 = 0xf0;
+pub(crate) const READ_STATUS_CMD: u8 = 0xaa;
+pub(crate) const WRITE_MEMORY_CMD: u8 = 0x0f;
+
+/// A device of the add-only memory family.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Model {
+    /// DS2502, 1Kb in 4 pages of 32 bytes, with 8 bytes of status memory.
+    Ds2502,
+    /// DS2505, 16Kb in 64 pages of 32 bytes, with 88 bytes of status memory.
+    Ds2505,
+}
+
+impl Model {
+    /// The model with family code `family`, if it is one of the family.
+    pub fn from_family_code(family: u8) -> Option<Self> {
+        match family {
+            DS2502_FAMILY_CODE => Some(Model::Ds2502),
+            DS2505_FAMILY_CODE => Some(Model::Ds2505),
+            _ => None,
+        }
+    }
+
+    /// Size of the data memory in bytes.
+    pub fn memory_size(&self) -> usize {
+        match self {
+            Model::Ds2502 => 128,
+            Model::Ds2505 => 2048,
+        }
+    }
+
+    /// Size of the status memory in bytes.
+    pub fn status_size(&self) -> usize {
+        match self {
+            Model::Ds2502 => 8,
+            Model::Ds2505 => 88,
+        }
+    }
+
+    /// Number of CRC bytes sent by the device after a command: the DS2502 uses the 1-Wire
+    /// CRC-8, and the DS2505 the CRC-16.
+    pub(crate) fn crc_len(&self) -> usize {
+        match self {
+            Model::Ds2502 => 1,
+            Model::Ds2505 => 2,
+        }
+    }
+
+    /// Check the CRC sent by the device after `bytes`.
+    ///
+    /// # Errors
+    /// Returns [`OneWireError::InvalidCrc`] if the CRC does not match.
+    pub(crate) fn check_crc<E>(&self, bytes: &[u8], crc: &[u8]) -> OneWireResult<(), E> {
+        let valid = match self {
+            Model::Ds2502 => {
+                let mut check = OneWireCrc::default();
+                bytes.iter().chain(crc).for_each(|&b| check.update(b));
+                check.value() == 0
+            }
+            Model::Ds2505 => {
+                let mut check = OneWireCrc16::default();
+                check.update_slice(bytes);
+                check.update_slice(crc);
+                check.is_valid()
+            }
+        };
+        if !valid {
+            return Err(OneWireError::InvalidCrc);
+        }
+        Ok(())
+    }
+}
+
+mod test {
+    #[test]
+    fn test_read() {
+        use super::*;
+        use embedded_onewire::testing::{Mock, Transaction};
+        extern crate std;
+
+        let bus = Mock::new(&[
+            Transaction::reset(true),
+            Transaction::skip_rom(),
+            Transaction::write(&[READ_MEMORY_CMD, 0x20, 0x00]),
+            Transaction::read(&[0x4c]),
+            Transaction::read(&[0x01, 0x02, 0x03]),
+            Transaction::reset(true),
+            Transaction::skip_rom(),
+            Transaction::write(&[READ_STATUS_CMD, 0x00, 0x00]),
+            Transaction::read(&[0x9d]),
+        ]);
+        let mut eprom = Ds2502::new(bus, None, Model::Ds2502);
+        let mut buf = [0; 3];
+        eprom.read_memory(0x20, &mut buf).unwrap();
+        assert_eq!(buf, [0x01, 0x02, 0x03]);
+        assert!(matches!(
+            eprom.read_status(0x00, &mut buf[..1]),
+            Err(OneWireError::InvalidCrc)
+        ));
+        assert!(matches!(
+            eprom.read_memory(0x7f, &mut buf),
+            Err(OneWireError::InvalidValue(_))
+        ));
+        eprom.release().done();
+    }
+
+    #[test]
+    fn test_write_byte() {
+        use super::*;
+        use embedded_onewire::testing::{Mock, Transaction};
+        extern crate std;
+
+        let bus = Mock::new(&[
+            Transaction::reset(true),
+            Transaction::skip_rom(),
+            Transaction::write(&[WRITE_MEMORY_CMD, 0x10, 0x04, 0x5a]),
+            Transaction::read(&[0x7f, 0xd5]),
+            Transaction::program_pulse(),
+            Transaction::read(&[0x5a]),
+            // A bit already programmed to 0 cannot be set again
+            Transaction::reset(true),
+            Transaction::skip_rom(),
+            Transaction::write(&[WRITE_MEMORY_CMD, 0x10, 0x04, 0x5a]),
+            Transaction::read(&[0x7f, 0xd5]),
+            Transaction::program_pulse(),
+            Transaction::read(&[0x58]),
+        ]);
+        let mut eprom = Ds2502::new(bus, None, Model::Ds2505);
+        eprom.write_byte(0x0410, 0x5a).unwrap();
+        assert!(matches!(
+            eprom.write_byte(0x0410, 0x5a),
+            Err(OneWireError::VerificationFailed)
+        ));
+        eprom.release().done();
+    }
+}
@@ -0,0 +1,157 @@
+use crate::{
+    Block, COPY_DATA_CMD, COPY_DATA_US, EEPROM_ADDR, LOCK_CMD, MEMORY_SIZE, Measurements, Model,
+    READ_DATA_CMD, RECALL_DATA_CMD, STATUS_ADDR, Status, WRITE_DATA_CMD,
+};
+use embedded_hal::delay::DelayNs;
+use embedded_onewire::{OneWire, OneWireError, OneWireResult};
+
+/// A DS2780 or DS2781 fuel gauge on a 1-Wire bus.
+///
+/// The driver owns the bus master, which can be a `&mut` reference to one or a handle to a
+/// shared one. Every operation addresses the device first.
+#[derive(Debug)]
+pub struct Ds2780<O> {
+    pub(crate) bus: O,
+    pub(crate) rom: Option<u64>,
+    pub(crate) model: Model,
+}
+
+impl<O> Ds2780<O> {
+    /// Creates a driver for the device of `model` with ROM `rom` on `bus`.
+    ///
+    /// Pass [`None`] to skip ROM addressing on a bus with a single device. The model can be
+    /// found from the family code of the ROM with [`Model::from_family`].
+    pub fn new(bus: O, rom: Option<u64>, model: Model) -> Self {
+        Ds2780 { bus, rom, model }
+    }
+
+    /// ROM of the device, if it is addressed by ROM.
+    pub fn rom(&self) -> Option<u64> {
+        self.rom
+    }
+
+    /// Model of the device.
+    pub fn model(&self) -> Model {
+        self.model
+    }
+
+    /// Release the bus master.
+    pub fn release(self) -> O {
+        self.bus
+    }
+}
+
+/// Check that `len` registers from `address` are in the register map.
+pub(crate) fn check_range<E>(address: u8, len: usize) -> OneWireResult<(), E> {
+    if address as usize >= MEMORY_SIZE {
+        return Err(OneWireError::InvalidValue("address"));
+    }
+    if address as usize + len > MEMORY_SIZE {
+        return Err(OneWireError::InvalidValue("length"));
+    }
+    Ok(())
+}
+
+/// Address of `len` registers from `offset` in `block`.
+pub(crate) fn block_address<E>(block: Block, offset: usize, len: usize) -> OneWireResult<u8, E> {
+    if offset + len > block.size() {
+        return Err(OneWireError::InvalidValue("length"));
+    }
+    Ok(block.address() + offset as u8)
+}
+
+impl<O: OneWire> Ds2780<O> {
+    fn command(&mut self, cmd: &[u8]) -> OneWireResult<(), O::BusError> {
+        self.bus.address(self.rom)?;
+        self.bus.write_bytes(cmd)
+    }
+
+    /// Read the registers from `address` into `buf`.
+    ///
+    /// # Errors
+    /// Returns [`OneWireError::InvalidValue`] if the registers are not all in the register
+    /// map.
+    pub fn read(&mut self, address: u8, buf: &mut [u8]) -> OneWireResult<(), O::BusError> {
+        check_range(address, buf.len())?;
+        self.command(&[READ_DATA_CMD, address])?;
+        self.bus.read_bytes(buf)
+    }
+
+    /// Write `data` to the registers from `address`.
+    ///
+    /// Writes to the EEPROM blocks only change their shadow RAM, see
+    /// [`Ds2780::write_block`]. Read-only registers are left unchanged.
+    ///
+    /// # Errors
+    /// Returns [`OneWireError::InvalidValue`] if the registers are not all in the register
+    /// map.
+    pub fn write(&mut self, address: u8, data: &[u8]) -> OneWireResult<(), O::BusError> {
+        check_range(address, data.len())?;
+        self.command(&[WRITE_DATA_CMD, address])?;
+        self.bus.write_bytes(data)
+    }
+
+    /// Read the status register and the measurements.
+    pub fn read_measurements(&mut self) -> OneWireResult<Measurements, O::BusError> {
+        let mut buf = [0; Measurements::LEN];
+        self.read(STATUS_ADDR, &mut buf)?;
+        Ok(Measurements::parse(&buf, self.model))
+    }
+
+    /// Write the status register, to clear its flags.
+    pub fn write_status(&mut self, status: Status) -> OneWireResult<(), O::BusError> {
+        self.write(STATUS_ADDR, &[status.into_bits()])
+    }
+
+    /// Write `data` to the shadow RAM of `block` from `offset`, copy the block to the
+    /// EEPROM and wait for the copy to complete.
+    ///
+    /// # Errors
+    /// Returns [`OneWireError::InvalidValue`] if `data` does not fit in the block from
+    /// `offset`.
+    pub fn write_block<D: DelayNs>(
+        &mut self,
+        block: Block,
+        offset: usize,
+        data: &[u8],
+        delay: &mut D,
+    ) -> OneWireResult<(), O::BusError> {
+        let address = block_address(block, offset, data.len())?;
+        self.write(address, data)?;
+        self.copy_block(block, delay)
+    }
+
+    /// Copy the shadow RAM of `block` to the EEPROM, and wait for the copy to complete.
+    ///
+    /// The copy is ignored if the block is locked.
+    pub fn copy_block<D: DelayNs>(
+        &mut self,
+        block: Block,
+        delay: &mut D,
+    ) -> OneWireResult<(), O::BusError> {
+        self.command(&[COPY_DATA_CMD, block.address()])?;
+        delay.delay_us(COPY_DATA_US);
+        Ok(())
+    }
+
+    /// Recall `block` from the EEPROM to its shadow RAM, discarding any write since the
+    /// last copy.
+    pub fn recall_block(&mut self, block: Block) -> OneWireResult<(), O::BusError> {
+        self.command(&[RECALL_DATA_CMD, block.address()])
+    }
+
+    /// Lock `block`, making it read-only.
+    ///
+    /// The lock is permanent, and only applies if the lock is enabled in the EEPROM
+    /// register.
+    pub fn lock_block(&mut self, block: Block) -> OneWireResult<(), O::BusError> {
+        self.command(&[LOCK_CMD, block.address()])
+    }
+
+    /// Whether `block` is locked.
+    pub fn is_locked(&mut self, block: Block) -> OneWireResult<bool, O::BusError> {
+        let mut eeprom = [0];
+        self.read(EEPROM_ADDR, &mut eeprom)?;
+        Ok(eeprom[0] & block.lock_bit() != 0)
+    }
+}
@@ -0,0 +1,110 @@
+use crate::{
+    Block, COPY_DATA_CMD, COPY_DATA_US, Ds2780, EEPROM_ADDR, LOCK_CMD, Measurements, READ_DATA_CMD,
+    RECALL_DATA_CMD, STATUS_ADDR, Status, WRITE_DATA_CMD,
+    device::{block_address, check_range},
+};
+use embedded_hal_async::delay::DelayNs as DelayNsAsync;
+use embedded_onewire::{OneWireAsync, OneWireResult};
+
+impl<O: OneWireAsync> Ds2780<O> {
+    async fn command_async(&mut self, cmd: &[u8]) -> OneWireResult<(), O::BusError> {
+        OneWireAsync::address(&mut self.bus, self.rom).await?;
+        OneWireAsync::write_bytes(&mut self.bus, cmd).await
+    }
+
+    /// Read the registers from `address` into `buf`.
+    ///
+    /// See [`Ds2780::read`].
+    pub async fn read_async(
+        &mut self,
+        address: u8,
+        buf: &mut [u8],
+    ) -> OneWireResult<(), O::BusError> {
+        check_range(address, buf.len())?;
+        self.command_async(&[READ_DATA_CMD, address]).await?;
+        OneWireAsync::read_bytes(&mut self.bus, buf).await
+    }
+
+    /// Write `data` to the registers from `address`.
+    ///
+    /// See [`Ds2780::write`].
+    pub async fn write_async(
+        &mut self,
+        address: u8,
+        data: &[u8],
+    ) -> OneWireResult<(), O::BusError> {
+        check_range(address, data.len())?;
+        self.command_async(&[WRITE_DATA_CMD, address]).await?;
+        OneWireAsync::write_bytes(&mut self.bus, data).await
+    }
+
+    /// Read the status register and the measurements.
+    ///
+    /// See [`Ds2780::read_measurements`].
+    pub async fn read_measurements_async(&mut self) -> OneWireResult<Measurements, O::BusError> {
+        let mut buf = [0; Measurements::LEN];
+        self.read_async(STATUS_ADDR, &mut buf).await?;
+        Ok(Measurements::parse(&buf, self.model))
+    }
+
+    /// Write the status register, to clear its flags.
+    ///
+    /// See [`Ds2780::write_status`].
+    pub async fn write_status_async(&mut self, status: Status) -> OneWireResult<(), O::BusError> {
+        self.write_async(STATUS_ADDR, &[status.into_bits()]).await
+    }
+
+    /// Write `data` to the shadow RAM of `block` from `offset`, and copy the block to the
+    /// EEPROM.
+    ///
+    /// See [`Ds2780::write_block`].
+    pub async fn write_block_async<D: DelayNsAsync>(
+        &mut self,
+        block: Block,
+        offset: usize,
+        data: &[u8],
+        delay: &mut D,
+    ) -> OneWireResult<(), O::BusError> {
+        let address = block_address(block, offset, data.len())?;
+        self.write_async(address, data).await?;
+        self.copy_block_async(block, delay).await
+    }
+
+    /// Copy the shadow RAM of `block` to the EEPROM, and wait for the copy to complete.
+    ///
+    /// See [`Ds2780::copy_block`].
+    pub async fn copy_block_async<D: DelayNsAsync>(
+        &mut self,
+        block: Block,
+        delay: &mut D,
+    ) -> OneWireResult<(), O::BusError> {
+        self.command_async(&[COPY_DATA_CMD, block.address()])
+            .await?;
+        delay.delay_us(COPY_DATA_US).await;
+        Ok(())
+    }
+
+    /// Recall `block` from the EEPROM to its shadow RAM.
+    ///
+    /// See [`Ds2780::recall_block`].
+    pub async fn recall_block_async(&mut self, block: Block) -> OneWireResult<(), O::BusError> {
+        self.command_async(&[RECALL_DATA_CMD, block.address()])
+            .await
+    }
+
+    /// Lock `block`, making it read-only.
+    ///
+    /// See [`Ds2780::lock_block`].
+    pub async fn lock_block_async(&mut self, block: Block) -> OneWireResult<(), O::BusError> {
+        self.command_async(&[LOCK_CMD, block.address()]).await
+    }
+
+    /// Whether `block` is locked.
+    ///
+    /// See [`Ds2780::is_locked`].
+    pub async fn is_locked_async(&mut self, block: Block) -> OneWireResult<bool, O::BusError> {
+        let mut eeprom = [0];
+        self.read_async(EEPROM_ADDR, &mut eeprom).await?;
+        Ok(eeprom[0] & block.lock_bit() != 0)
+    }
+}
@@ -0,0 +1,323 @@
+#![no_std]
+#![deny(missing_docs)]
+#![cfg_attr(docsrs, feature(doc_cfg))]
+#![doc = include_str!("../README.md")]
+
+mod device;
+mod device_async;
+
+pub use device::Ds2780;
+pub use embedded_onewire::{OneWireError, OneWireResult};
+
+use bitfield_struct::bitfield;
+
+/// Family code of the DS2780, the low byte of its ROM.
+pub const DS2780_FAMILY_CODE: u8 = 0x32;
+/// Family code of the DS2781, the low byte of its ROM.
+pub const DS2781_FAMILY_CODE: u8 = 0x3d;
+
+/// Size of the register map.
+pub const MEMORY_SIZE: usize = 0x80;
+
+pub(crate) const READ_DATA_CMD: u8 = 0x69;
+pub(crate) const WRITE_DATA_CMD: u8 = 0x6c;
+pub(crate) const COPY_DATA_CMD: u8 = 0x48;
+pub(crate) const RECALL_DATA_CMD: u8 = 0xb8;
+pub(crate) const LOCK_CMD: u8 = 0x6a;
+
+/// Address of the status register, the first of the measurement registers.
+pub(crate) const STATUS_ADDR: u8 = 0x01;
+/// Address of the EEPROM register.
+pub(crate) const EEPROM_ADDR: u8 = 0x1f;
+
+/// Maximum time for a block to be copied to the EEPROM, in microseconds.
+pub(crate) const COPY_DATA_US: u32 = 15_000;
+
+/// Model of the fuel gauge, which sets the scale of the voltage register.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Model {
+    /// DS2780, for a single Li+ cell, up to 4.99 V.
+    Ds2780,
+    /// DS2781, for one or two Li+ cells or up to five NiMH cells, up to 9.99 V.
+    Ds2781,
+}
+
+impl Model {
+    /// The model with family code `family`, if any.
+    pub fn from_family(family: u8) -> Option<Self> {
+        match family {
+            DS2780_FAMILY_CODE => Some(Model::Ds2780),
+            DS2781_FAMILY_CODE => Some(Model::Ds2781),
+            _ => None,
+        }
+    }
+
+    /// Weight of the least significant bit of the voltage register, in microvolts.
+    pub(crate) fn voltage_lsb_uv(&self) -> i32 {
+        match self {
+            Model::Ds2780 => 4_880,
+            Model::Ds2781 => 9_760,
+        }
+    }
+}
+
+/// An EEPROM block, shadowed by RAM in the register map.
+///
+/// The registers of a block are read and written in the shadow RAM, which is copied to and
+/// recalled from the EEPROM as a whole.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Block {
+    /// Block 0, 16 bytes of user memory.
+    User,
+    /// Block 1, 32 bytes of parameters of the cell model and the fuel gauge.
+    Parameters,
+}
+
+impl Block {
+    /// Address of the first register of the block.
+    pub fn address(&self) -> u8 {
+        match self {
+            Block::User => 0x20,
+            Block::Parameters => 0x60,
+        }
+    }
+
+    /// Size of the block, in bytes.
+    pub fn size(&self) -> usize {
+        match self {
+            Block::User => 16,
+            Block::Parameters => 32,
+        }
+    }
+
+    /// Bit of the block in the EEPROM register.
+    pub(crate) fn lock_bit(&self) -> u8 {
+        match self {
+            Block::User => 0x01,
+            Block::Parameters => 0x02,
+        }
+    }
+}
+
+#[bitfield(u8)]
+#[derive(PartialEq, Eq)]
+/// # Status register
+pub struct Status {
+    #[bits(1)]
+    __: u8,
+    /// PORF: The device was reset by a power-on. Cleared by writing 0.
+    pub power_on_reset: bool,
+    /// UVF: The voltage fell below the under-voltage threshold. Cleared by writing 0.
+    pub under_voltage: bool,
+    #[bits(1)]
+    __: u8,
+    /// LEARNF: The cell is being charged from empty, so that its full capacity is learned.
+    #[bits(access = RO)]
+    pub learning: bool,
+    /// SEF: The standby capacity is exhausted.
+    #[bits(access = RO)]
+    pub standby_empty: bool,
+    /// AEF: The active capacity is exhausted.
+    #[bits(access = RO)]
+    pub active_empty: bool,
+    /// CHGTF: The charge terminated, the cell is full.
+    #[bits(access = RO)]
+    pub charge_terminated: bool,
+}
+
+/// A temperature reading, in units of 1/256 °C with a resolution of 0.125 °C.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Temperature(i16);
+
+impl Temperature {
+    /// Temperature from the raw register value.
+    pub fn from_raw(raw: i16) -> Self {
+        Temperature(raw)
+    }
+
+    /// The raw register value, in units of 1/256 °C.
+    pub fn raw(&self) -> i16 {
+        self.0
+    }
+
+    /// The temperature in thousandths of a degree Celsius, truncated.
+    pub fn millidegrees(&self) -> i32 {
+        (self.0 >> 5) as i32 * 125
+    }
+
+    /// The temperature in degrees Celsius.
+    pub fn celsius(&self) -> f32 {
+        self.0 as f32 / 256.0
+    }
+}
+
+/// The measurement registers of the fuel gauge.
+///
+/// The currents and the accumulated charge are voltages across the sense resistor, and are
+/// converted with its value, see [`Measurements::current_microamps`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Measurements {
+    /// The status register.
+    pub status: Status,
+    /// RAAC: Remaining capacity at the active empty point, in microampere-hours.
+    pub remaining_active_uah: u32,
+    /// RSAC: Remaining capacity at the standby empty point, in microampere-hours.
+    pub remaining_standby_uah: u32,
+    /// RARC: Remaining capacity at the active empty point, in percent of the full capacity.
+    pub remaining_active_percent: u8,
+    /// RSRC: Remaining capacity at the standby empty point, in percent of the full capacity.
+    pub remaining_standby_percent: u8,
+    /// IAVG: Current averaged over the last 28 s, in units of 1.5625 µV across the sense
+    /// resistor. Positive while charging.
+    pub average_current: i16,
+    /// Temperature of the device.
+    pub temperature: Temperature,
+    /// Voltage of the cell, in microvolts.
+    pub voltage_uv: i32,
+    /// Current in the last 3.5 s, in units of 1.5625 µV across the sense resistor. Positive
+    /// while charging.
+    pub current: i16,
+    /// ACR: Accumulated charge, in units of 6.25 µVh across the sense resistor.
+    pub accumulated_charge: u16,
+}
+
+impl Measurements {
+    /// Number of registers, from the status register to the accumulated charge.
+    pub(crate) const LEN: usize = 17;
+
+    pub(crate) fn parse(bytes: &[u8; Self::LEN], model: Model) -> Self {
+        let word = |addr: usize| {
+            let i = addr - STATUS_ADDR as usize;
+            [bytes[i], bytes[i + 1]]
+        };
+        Measurements {
+            status: Status::from_bits(bytes[0]),
+            remaining_active_uah: u16::from_be_bytes(word(0x02)) as u32 * 1_600,
+            remaining_standby_uah: u16::from_be_bytes(word(0x04)) as u32 * 1_600,
+            remaining_active_percent: bytes[0x06 - STATUS_ADDR as usize],
+            remaining_standby_percent: bytes[0x07 - STATUS_ADDR as usize],
+            average_current: i16::from_be_bytes(word(0x08)),
+            temperature: Temperature(i16::from_be_bytes(word(0x0a)) & !0x1f),
+            // The voltage is left justified
+            voltage_uv: (i16::from_be_bytes(word(0x0c)) >> 5) as i32 * model.voltage_lsb_uv(),
+            current: i16::from_be_bytes(word(0x0e)),
+            accumulated_charge: u16::from_be_bytes(word(0x10)),
+        }
+    }
+
+    /// The current through a sense resistor of `sense_milliohm`, in microamperes.
+    pub fn current_microamps(&self, sense_milliohm: u32) -> i32 {
+        current_microamps(self.current, sense_milliohm)
+    }
+
+    /// The average current through a sense resistor of `sense_milliohm`, in microamperes.
+    pub fn average_current_microamps(&self, sense_milliohm: u32) -> i32 {
+        current_microamps(self.average_current, sense_milliohm)
+    }
+
+    /// The accumulated charge with a sense resistor of `sense_milliohm`, in
+    /// microampere-hours.
+    pub fn accumulated_charge_uah(&self, sense_milliohm: u32) -> u32 {
+        (self.accumulated_charge as u64 * 6_250 / sense_milliohm as u64) as u32
+    }
+}
+
+fn current_microamps(raw: i16, sense_milliohm: u32) -> i32 {
+    (raw as i64 * 3_125 / (2 * sense_milliohm as i64)) as i32
+}
+
+mod test {
+    #[test]
+    fn test_measurements() {
+        use super::*;
+        let bytes = [
+            0x80, 0x02, 0x71, 0x02, 0x80, 0x50, 0x52, 0xfc, 0x00, 0x19, 0x20, 0x63, 0xe0, 0x06,
+            0x40, 0x03, 0x20,
+        ];
+        let m = Measurements::parse(&bytes, Model::Ds2780);
+        assert!(m.status.charge_terminated());
+        assert!(!m.status.power_on_reset());
+        assert_eq!(m.remaining_active_uah, 1_000_000);
+        assert_eq!(m.remaining_standby_uah, 1_024_000);
+        assert_eq!(m.remaining_active_percent, 80);
+        assert_eq!(m.remaining_standby_percent, 82);
+        assert_eq!(m.temperature.celsius(), 25.125);
+        assert_eq!(m.temperature.millidegrees(), 25_125);
+        assert_eq!(m.voltage_uv, 3_899_120);
+        // 20 mOhm sense resistor
+        assert_eq!(m.current_microamps(20), 125_000);
+        assert_eq!(m.average_current_microamps(20), -80_000);
+        assert_eq!(m.accumulated_charge_uah(20), 250_000);
+        let m = Measurements::parse(&bytes, Model::Ds2781);
+        assert_eq!(m.voltage_uv, 7_798_240);
+        assert_eq!(
+            Temperature::from_raw(0xe700_u16 as i16).millidegrees(),
+            -25_000
+        );
+    }
+
+    #[test]
+    fn test_write_block() {
+        use super::*;
+        use embedded_hal_mock::eh1::delay::NoopDelay;
+        use embedded_onewire::testing::{Mock, Transaction};
+        extern crate std;
+
+        let rom = 0x7700_0000_1234_5632;
+        let bus = Mock::new(&[
+            Transaction::reset(true),
+            Transaction::match_rom(rom),
+            Transaction::write(&[WRITE_DATA_CMD, 0x24, 0xde, 0xad]),
+            Transaction::reset(true),
+            Transaction::match_rom(rom),
+            Transaction::write(&[COPY_DATA_CMD, 0x20]),
+            Transaction::reset(true),
+            Transaction::match_rom(rom),
+            Transaction::write(&[READ_DATA_CMD, EEPROM_ADDR]),
+            Transaction::read(&[0x41]),
+            Transaction::reset(true),
+            Transaction::match_rom(rom),
+            Transaction::write(&[READ_DATA_CMD, EEPROM_ADDR]),
+            Transaction::read(&[0x41]),
+        ]);
+        let mut gauge = Ds2780::new(bus, Some(rom), Model::Ds2780);
+        gauge
+            .write_block(Block::User, 4, &[0xde, 0xad], &mut NoopDelay::new())
+            .unwrap();
+        assert!(matches!(
+            gauge.write_block(Block::User, 15, &[0xde, 0xad], &mut NoopDelay::new()),
+            Err(OneWireError::InvalidValue("length"))
+        ));
+        assert!(gauge.is_locked(Block::User).unwrap());
+        assert!(!gauge.is_locked(Block::Parameters).unwrap());
+        gauge.release().done();
+    }
+
+    #[test]
+    fn test_read_measurements() {
+        use super::*;
+        use embedded_onewire::testing::{Mock, Transaction};
+        extern crate std;
+
+        let bus = Mock::new(&[
+            Transaction::reset(true),
+            Transaction::skip_rom(),
+            Transaction::write(&[READ_DATA_CMD, STATUS_ADDR]),
+            Transaction::read(&[
+                0x02, 0x02, 0x71, 0x02, 0x80, 0x50, 0x52, 0xfc, 0x00, 0x19, 0x20, 0x63, 0xe0, 0x06,
+                0x40, 0x03, 0x20,
+            ]),
+            Transaction::reset(true),
+            Transaction::skip_rom(),
+            Transaction::write(&[WRITE_DATA_CMD, STATUS_ADDR, 0x00]),
+        ]);
+        let mut gauge = Ds2780::new(bus, None, Model::Ds2781);
+        let m = gauge.read_measurements().unwrap();
+        assert!(m.status.power_on_reset());
+        assert_eq!(m.voltage_uv, 7_798_240);
+        gauge
+            .write_status(m.status.with_power_on_reset(false))
+            .unwrap();
+        gauge.release().done();
+    }
+}
@@ -0,0 +1,106 @@
+use crate::{
+    Control, DECREMENT_CMD, INCREMENT_CMD, READ_POSITION_CMD, RELEASE_CODE, RELEASE_DONE, State,
+    WRITE_CONTROL_CMD, WRITE_POSITION_CMD,
+};
+use embedded_onewire::{OneWire, OneWireError, OneWireResult};
+
+/// A DS2890 digital potentiometer on a 1-Wire bus.
+///
+/// The driver owns the bus master, which can be a `&mut` reference to one or a handle to a
+/// shared one. Every operation addresses the device first.
+#[derive(Debug)]
+pub struct Ds2890<O> {
+    pub(crate) bus: O,
+    pub(crate) rom: Option<u64>,
+}
+
+impl<O> Ds2890<O> {
+    /// Creates a driver for the device with ROM `rom` on `bus`.
+    ///
+    /// Pass [`None`] to skip ROM addressing on a bus with a single device.
+    pub fn new(bus: O, rom: Option<u64>) -> Self {
+        Ds2890 { bus, rom }
+    }
+
+    /// ROM of the device, if it is addressed by ROM.
+    pub fn rom(&self) -> Option<u64> {
+        self.rom
+    }
+
+    /// Release the bus master.
+    pub fn release(self) -> O {
+        self.bus
+    }
+}
+
+impl<O: OneWire> Ds2890<O> {
+    fn command(&mut self, cmd: &[u8]) -> OneWireResult<(), O::BusError> {
+        self.bus.address(self.rom)?;
+        self.bus.write_bytes(cmd)
+    }
+
+    /// Write `value` with `cmd`, and apply it once the device echoed it.
+    fn write_released(&mut self, cmd: u8, value: u8) -> OneWireResult<(), O::BusError> {
+        self.command(&[cmd, value])?;
+        if self.bus.read_byte()? != value {
+            return Err(OneWireError::VerificationFailed);
+        }
+        self.bus.write_byte(RELEASE_CODE)?;
+        if self.bus.read_byte()? != RELEASE_DONE {
+            return Err(OneWireError::VerificationFailed);
+        }
+        Ok(())
+    }
+
+    /// Read the control register and the position of the wiper.
+    pub fn read_state(&mut self) -> OneWireResult<State, O::BusError> {
+        self.command(&[READ_POSITION_CMD])?;
+        let mut buf = [0; 2];
+        self.bus.read_bytes(&mut buf)?;
+        Ok(State {
+            control: Control::from_bits(buf[0]),
+            position: buf[1],
+        })
+    }
+
+    /// Move the wiper to `position`.
+    ///
+    /// # Errors
+    /// Returns [`OneWireError::VerificationFailed`] if the device does not echo the
+    /// position, in which case the wiper does not move, or does not confirm the move.
+    pub fn write_position(&mut self, position: u8) -> OneWireResult<(), O::BusError> {
+        self.write_released(WRITE_POSITION_CMD, position)
+    }
+
+    /// Write the control register.
+    ///
+    /// # Errors
+    /// See [`Ds2890::write_position`].
+    pub fn write_control(&mut self, control: Control) -> OneWireResult<(), O::BusError> {
+        self.write_released(WRITE_CONTROL_CMD, control.into_bits())
+    }
+
+    /// Turn the charge pump on or off.
+    ///
+    /// # Errors
+    /// See [`Ds2890::write_position`].
+    pub fn set_charge_pump(&mut self, on: bool) -> OneWireResult<(), O::BusError> {
+        self.write_control(Control::single_wiper(on))
+    }
+
+    /// Move the wiper one step up, and return its new position.
+    ///
+    /// The wiper stays at the top position.
+    pub fn increment(&mut self) -> OneWireResult<u8, O::BusError> {
+        self.command(&[INCREMENT_CMD])?;
+        self.bus.read_byte()
+    }
+
+    /// Move the wiper one step down, and return its new position.
+    ///
+    /// The wiper stays at the bottom position.
+    pub fn decrement(&mut self) -> OneWireResult<u8, O::BusError> {
+        self.command(&[DECREMENT_CMD])?;
+        self.bus.read_byte()
+    }
+}
@@ -0,0 +1,79 @@
+use crate::{
+    Control, DECREMENT_CMD, Ds2890, INCREMENT_CMD, READ_POSITION_CMD, RELEASE_CODE, RELEASE_DONE,
+    State, WRITE_CONTROL_CMD, WRITE_POSITION_CMD,
+};
+use embedded_onewire::{OneWireAsync, OneWireError, OneWireResult};
+
+impl<O: OneWireAsync> Ds2890<O> {
+    async fn command_async(&mut self, cmd: &[u8]) -> OneWireResult<(), O::BusError> {
+        OneWireAsync::address(&mut self.bus, self.rom).await?;
+        OneWireAsync::write_bytes(&mut self.bus, cmd).await
+    }
+
+    async fn write_released_async(&mut self, cmd: u8, value: u8) -> OneWireResult<(), O::BusError> {
+        self.command_async(&[cmd, value]).await?;
+        if OneWireAsync::read_byte(&mut self.bus).await? != value {
+            return Err(OneWireError::VerificationFailed);
+        }
+        OneWireAsync::write_byte(&mut self.bus, RELEASE_CODE).await?;
+        if OneWireAsync::read_byte(&mut self.bus).await? != RELEASE_DONE {
+            return Err(OneWireError::VerificationFailed);
+        }
+        Ok(())
+    }
+
+    /// Read the control register and the position of the wiper.
+    ///
+    /// See [`Ds2890::read_state`].
+    pub async fn read_state_async(&mut self) -> OneWireResult<State, O::BusError> {
+        self.command_async(&[READ_POSITION_CMD]).await?;
+        let mut buf = [0; 2];
+        OneWireAsync::read_bytes(&mut self.bus, &mut buf).await?;
+        Ok(State {
+            control: Control::from_bits(buf[0]),
+            position: buf[1],
+        })
+    }
+
+    /// Move the wiper to `position`.
+    ///
+    /// See [`Ds2890::write_position`].
+    pub async fn write_position_async(&mut self, position: u8) -> OneWireResult<(), O::BusError> {
+        self.write_released_async(WRITE_POSITION_CMD, position)
+            .await
+    }
+
+    /// Write the control register.
+    ///
+    /// See [`Ds2890::write_control`].
+    pub async fn write_control_async(
+        &mut self,
+        control: Control,
+    ) -> OneWireResult<(), O::BusError> {
+        self.write_released_async(WRITE_CONTROL_CMD, control.into_bits())
+            .await
+    }
+
+    /// Turn the charge pump on or off.
+    ///
+    /// See [`Ds2890::set_charge_pump`].
+    pub async fn set_charge_pump_async(&mut self, on: bool) -> OneWireResult<(), O::BusError> {
+        self.write_control_async(Control::single_wiper(on)).await
+    }
+
+    /// Move the wiper one step up, and return its new position.
+    ///
+    /// See [`Ds2890::increment`].
+    pub async fn increment_async(&mut self) -> OneWireResult<u8, O::BusError> {
+        self.command_async(&[INCREMENT_CMD]).await?;
+        OneWireAsync::read_byte(&mut self.bus).await
+    }
+
+    /// Move the wiper one step down, and return its new position.
+    ///
+    /// See [`Ds2890::decrement`].
+    pub async fn decrement_async(&mut self) -> OneWireResult<u8, O::BusError> {
+        self.command_async(&[DECREMENT_CMD]).await?;
+        OneWireAsync::read_byte(&mut self.bus).await
+    }
+}
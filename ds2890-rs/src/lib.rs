@@ -0,0 +1,126 @@
+#![no_std]
+#![deny(missing_docs)]
+#![cfg_attr(docsrs, feature(doc_cfg))]
+#![doc = include_str!("../README.md")]
+
+mod device;
+mod device_async;
+
+pub use device::Ds2890;
+pub use embedded_onewire::{OneWireError, OneWireResult};
+
+use bitfield_struct::bitfield;
+
+/// Family code of the DS2890, the low byte of its ROM.
+pub const FAMILY_CODE: u8 = 0x2c;
+
+pub(crate) const READ_POSITION_CMD: u8 = 0xf0;
+pub(crate) const WRITE_POSITION_CMD: u8 = 0x0f;
+pub(crate) const WRITE_CONTROL_CMD: u8 = 0x55;
+pub(crate) const INCREMENT_CMD: u8 = 0xc3;
+pub(crate) const DECREMENT_CMD: u8 = 0x99;
+
+/// Code sent by the master to apply a written value.
+pub(crate) const RELEASE_CODE: u8 = 0x96;
+/// Byte sent by the device after applying a written value.
+pub(crate) const RELEASE_DONE: u8 = 0x00;
+
+#[bitfield(u8)]
+#[derive(PartialEq, Eq)]
+/// # Control register
+pub struct Control {
+    /// Number of the wiper the commands apply to. The DS2890 has a single wiper, 0.
+    #[bits(2)]
+    pub wiper: u8,
+    /// Complement of the wiper number, checked by the device when the register is written.
+    #[bits(2)]
+    pub wiper_complement: u8,
+    #[bits(2)]
+    __: u8,
+    /// CPC: The charge pump is on, which extends the voltage range of the potentiometer
+    /// terminals beyond V<sub>DD</sub> at the cost of its supply current.
+    pub charge_pump: bool,
+    #[bits(1)]
+    __: u8,
+}
+
+impl Control {
+    /// The control register selecting the wiper of the DS2890, with the charge pump on or
+    /// off.
+    pub fn single_wiper(charge_pump: bool) -> Self {
+        Control::new()
+            .with_wiper(0)
+            .with_wiper_complement(0b11)
+            .with_charge_pump(charge_pump)
+    }
+}
+
+/// The state of the DS2890.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct State {
+    /// The control register.
+    pub control: Control,
+    /// Position of the wiper, from 0 to 255.
+    pub position: u8,
+}
+
+mod test {
+    #[test]
+    fn test_write_position() {
+        use super::*;
+        use embedded_onewire::testing::{Mock, Transaction};
+        extern crate std;
+
+        let bus = Mock::new(&[
+            Transaction::reset(true),
+            Transaction::skip_rom(),
+            Transaction::write(&[WRITE_POSITION_CMD, 0x80]),
+            Transaction::read(&[0x80]),
+            Transaction::write(&[RELEASE_CODE]),
+            Transaction::read(&[RELEASE_DONE]),
+            // The echo is corrupted, the value is not released
+            Transaction::reset(true),
+            Transaction::skip_rom(),
+            Transaction::write(&[WRITE_POSITION_CMD, 0x80]),
+            Transaction::read(&[0x81]),
+            Transaction::reset(true),
+            Transaction::skip_rom(),
+            Transaction::write(&[READ_POSITION_CMD]),
+            Transaction::read(&[0x4c, 0x80]),
+            Transaction::reset(true),
+            Transaction::skip_rom(),
+            Transaction::write(&[INCREMENT_CMD]),
+            Transaction::read(&[0x81]),
+        ]);
+        let mut pot = Ds2890::new(bus, None);
+        pot.write_position(0x80).unwrap();
+        assert!(matches!(
+            pot.write_position(0x80),
+            Err(OneWireError::VerificationFailed)
+        ));
+        let state = pot.read_state().unwrap();
+        assert!(state.control.charge_pump());
+        assert_eq!(state.position, 0x80);
+        assert_eq!(pot.increment().unwrap(), 0x81);
+        pot.release().done();
+    }
+
+    #[test]
+    fn test_set_charge_pump() {
+        use super::*;
+        use embedded_onewire::testing::{Mock, Transaction};
+        extern crate std;
+
+        let bus = Mock::new(&[
+            Transaction::reset(true),
+            Transaction::skip_rom(),
+            Transaction::write(&[WRITE_CONTROL_CMD, 0x4c]),
+            Transaction::read(&[0x4c]),
+            Transaction::write(&[RELEASE_CODE]),
+            Transaction::read(&[RELEASE_DONE]),
+        ]);
+        let mut pot = Ds2890::new(bus, None);
+        pot.set_charge_pump(true).unwrap();
+        pot.release().done();
+    }
+}
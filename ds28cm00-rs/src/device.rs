@@ -0,0 +1,56 @@
+use crate::{ADDRESS, CONTROL_REG, Mode, ROM_REG};
+use embedded_hal::i2c::I2c;
+use embedded_onewire::{OneWireCrc, OneWireError, OneWireResult};
+
+/// A DS28CM00 I2C silicon serial number.
+///
+/// The driver owns the I2C bus, which can be a `&mut` reference to one, or a device wrapper
+/// from [`embedded-hal-bus`](https://crates.io/crates/embedded-hal-bus) to share it with other
+/// devices. Errors of the bus are reported as [`OneWireError::Other`], so that the ROM is
+/// checked and reported like the ROM of a 1-Wire device.
+#[derive(Debug)]
+pub struct Ds28cm00<I> {
+    pub(crate) i2c: I,
+}
+
+impl<I> Ds28cm00<I> {
+    /// Creates a driver for the DS28CM00 on `i2c`.
+    pub fn new(i2c: I) -> Self {
+        Ds28cm00 { i2c }
+    }
+
+    /// Release the I2C bus.
+    pub fn release(self) -> I {
+        self.i2c
+    }
+}
+
+impl<I: I2c> Ds28cm00<I> {
+    /// Read the ROM, with the family code in the low byte, in the format of the ROM of a
+    /// 1-Wire device.
+    ///
+    /// # Errors
+    /// Returns [`OneWireError::InvalidCrc`] if the CRC-8 of the ROM does not match.
+    pub fn read_rom(&mut self) -> OneWireResult<u64, I::Error> {
+        let mut rom = [0; 8];
+        self.i2c.write_read(ADDRESS, &[ROM_REG], &mut rom)?;
+        if !OneWireCrc::validate(&rom) {
+            return Err(OneWireError::InvalidCrc);
+        }
+        Ok(u64::from_le_bytes(rom))
+    }
+
+    /// Read the mode of the I2C interface.
+    pub fn mode(&mut self) -> OneWireResult<Mode, I::Error> {
+        let mut control = [0];
+        self.i2c.write_read(ADDRESS, &[CONTROL_REG], &mut control)?;
+        Ok(Mode::parse(control[0]))
+    }
+
+    /// Set the mode of the I2C interface. The mode is not retained when the device is
+    /// powered off.
+    pub fn set_mode(&mut self, mode: Mode) -> OneWireResult<(), I::Error> {
+        self.i2c.write(ADDRESS, &[CONTROL_REG, mode as u8])?;
+        Ok(())
+    }
+}
@@ -0,0 +1,36 @@
+use crate::{ADDRESS, CONTROL_REG, Ds28cm00, Mode, ROM_REG};
+use embedded_hal_async::i2c::I2c as I2cAsync;
+use embedded_onewire::{OneWireCrc, OneWireError, OneWireResult};
+
+impl<I: I2cAsync> Ds28cm00<I> {
+    /// Read the ROM, with the family code in the low byte.
+    ///
+    /// See [`Ds28cm00::read_rom`].
+    pub async fn read_rom_async(&mut self) -> OneWireResult<u64, I::Error> {
+        let mut rom = [0; 8];
+        self.i2c.write_read(ADDRESS, &[ROM_REG], &mut rom).await?;
+        if !OneWireCrc::validate(&rom) {
+            return Err(OneWireError::InvalidCrc);
+        }
+        Ok(u64::from_le_bytes(rom))
+    }
+
+    /// Read the mode of the I2C interface.
+    ///
+    /// See [`Ds28cm00::mode`].
+    pub async fn mode_async(&mut self) -> OneWireResult<Mode, I::Error> {
+        let mut control = [0];
+        self.i2c
+            .write_read(ADDRESS, &[CONTROL_REG], &mut control)
+            .await?;
+        Ok(Mode::parse(control[0]))
+    }
+
+    /// Set the mode of the I2C interface.
+    ///
+    /// See [`Ds28cm00::set_mode`].
+    pub async fn set_mode_async(&mut self, mode: Mode) -> OneWireResult<(), I::Error> {
+        self.i2c.write(ADDRESS, &[CONTROL_REG, mode as u8]).await?;
+        Ok(())
+    }
+}
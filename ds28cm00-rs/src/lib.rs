@@ -0,0 +1,70 @@
+#![no_std]
+#![deny(missing_docs)]
+#![cfg_attr(docsrs, feature(doc_cfg))]
+#![doc = include_str!("../README.md")]
+
+mod device;
+mod device_async;
+
+pub use device::Ds28cm00;
+pub use embedded_onewire::{OneWireError, OneWireResult};
+
+/// Family code of the DS28CM00, the low byte of its ROM.
+pub const FAMILY_CODE: u8 = 0x70;
+
+/// I2C address of the DS28CM00.
+pub const ADDRESS: u8 = 0x50;
+
+/// Address of the first byte of the ROM.
+pub(crate) const ROM_REG: u8 = 0x00;
+/// Address of the control register.
+pub(crate) const CONTROL_REG: u8 = 0x08;
+
+/// Mode of the I2C interface, selected by bit 0 of the control register.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    /// I2C mode, where the clock can be held low indefinitely.
+    I2c = 0,
+    /// SMBus mode, where the interface times out when the clock is held low.
+    Smbus = 1,
+}
+
+impl Mode {
+    /// Parse the control register.
+    pub(crate) fn parse(control: u8) -> Self {
+        if control & 1 == 0 {
+            Mode::I2c
+        } else {
+            Mode::Smbus
+        }
+    }
+}
+
+mod test {
+    #[test]
+    fn test_ds28cm00() {
+        use super::*;
+        use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+        extern crate std;
+
+        const ROM: [u8; 8] = [0x70, 0x12, 0x34, 0x56, 0x78, 0x9a, 0x00, 0x42];
+        let mut garbled = ROM;
+        garbled[3] ^= 0x01;
+        let i2c = I2cMock::new(&[
+            I2cTransaction::write_read(ADDRESS, std::vec![ROM_REG], ROM.to_vec()),
+            I2cTransaction::write_read(ADDRESS, std::vec![ROM_REG], garbled.to_vec()),
+            I2cTransaction::write_read(ADDRESS, std::vec![CONTROL_REG], std::vec![0x00]),
+            I2cTransaction::write(ADDRESS, std::vec![CONTROL_REG, 0x01]),
+            I2cTransaction::write_read(ADDRESS, std::vec![CONTROL_REG], std::vec![0x01]),
+        ]);
+        let mut ds28cm00 = Ds28cm00::new(i2c);
+        let rom = ds28cm00.read_rom().unwrap();
+        assert_eq!(rom, u64::from_le_bytes(ROM));
+        assert_eq!(rom as u8, FAMILY_CODE);
+        assert!(matches!(ds28cm00.read_rom(), Err(OneWireError::InvalidCrc)));
+        assert_eq!(ds28cm00.mode().unwrap(), Mode::I2c);
+        ds28cm00.set_mode(Mode::Smbus).unwrap();
+        assert_eq!(ds28cm00.mode().unwrap(), Mode::Smbus);
+        ds28cm00.release().done();
+    }
+}
@@ -0,0 +1,207 @@
+use crate::{
+    CONFIRMATION, MATCH_ROM_CMD, MEMORY_END, PAGE_SIZE, PAGES, PROG_TIME_US, Protection,
+    READ_MEMORY_CMD, RELEASE, SEGMENT_SIZE, SKIP_ROM_CMD, WRITE_MEMORY_CMD, protection_bits,
+    segment_range,
+};
+use embedded_hal::delay::DelayNs;
+use embedded_onewire::{OneWire, OneWireError, OneWireResult};
+use onewire_eeprom::OneWireEeprom;
+
+/// A DS28E05 112-byte EEPROM on a 1-Wire bus.
+///
+/// The driver owns the bus master, which can be a `&mut` reference to one or a handle to a
+/// shared one. Every operation addresses the device first.
+///
+/// The DS28E05 only communicates at overdrive speed, and only understands the standard
+/// ROM commands sent at that speed. The driver switches the bus master to overdrive before
+/// every operation, and leaves it there: devices that communicate at standard speed need the
+/// bus master switched back and a reset at standard speed.
+#[derive(Debug)]
+pub struct Ds28e05<O> {
+    pub(crate) bus: O,
+    pub(crate) rom: Option<u64>,
+}
+
+impl<O> Ds28e05<O> {
+    /// Creates a driver for the device with ROM `rom` on `bus`.
+    ///
+    /// Pass [`None`] to skip ROM addressing on a bus with a single device.
+    pub fn new(bus: O, rom: Option<u64>) -> Self {
+        Ds28e05 { bus, rom }
+    }
+
+    /// ROM of the device, if it is addressed by ROM.
+    pub fn rom(&self) -> Option<u64> {
+        self.rom
+    }
+
+    /// Release the bus master.
+    pub fn release(self) -> O {
+        self.bus
+    }
+}
+
+impl<O: OneWire> Ds28e05<O> {
+    fn command(&mut self, cmd: &[u8]) -> OneWireResult<(), O::BusError> {
+        if !self.bus.get_overdrive_mode() {
+            self.bus.set_overdrive_mode(true)?;
+        }
+        // The overdrive ROM commands of `OneWire::address` are not supported
+        self.bus.reset()?;
+        match self.rom {
+            Some(rom) => {
+                self.bus.write_byte(MATCH_ROM_CMD)?;
+                self.bus.write_bytes(&rom.to_le_bytes())?;
+            }
+            None => self.bus.write_byte(SKIP_ROM_CMD)?,
+        }
+        self.bus.write_bytes(cmd)
+    }
+
+    /// Read the memory from `address` into `buf`.
+    ///
+    /// The user memory and the protection bytes can be read, up to address `0x7f`.
+    ///
+    /// # Errors
+    /// Returns [`OneWireError::InvalidValue`] if the read goes past the end of the memory.
+    pub fn read(&mut self, address: u16, buf: &mut [u8]) -> OneWireResult<(), O::BusError> {
+        if address as usize + buf.len() > MEMORY_END {
+            return Err(OneWireError::InvalidValue("address"));
+        }
+        let [lo, hi] = address.to_le_bytes();
+        self.command(&[READ_MEMORY_CMD, lo, hi])?;
+        self.bus.read_bytes(buf)
+    }
+
+    /// Program the segments of `data` from the segment at `address`, within a page.
+    fn write_segments<D: DelayNs>(
+        &mut self,
+        address: usize,
+        data: &[u8],
+        delay: &mut D,
+    ) -> OneWireResult<(), O::BusError> {
+        let [lo, hi] = (address as u16).to_le_bytes();
+        self.command(&[WRITE_MEMORY_CMD, lo, hi])?;
+        for segment in data.chunks(SEGMENT_SIZE) {
+            self.bus.write_bytes(segment)?;
+            let mut echo = [0; SEGMENT_SIZE];
+            self.bus.read_bytes(&mut echo)?;
+            if echo != segment {
+                self.bus.reset()?;
+                return Err(OneWireError::VerificationFailed);
+            }
+            self.bus.write_byte(RELEASE)?;
+            delay.delay_us(PROG_TIME_US);
+            if self.bus.read_byte()? != CONFIRMATION {
+                self.bus.reset()?;
+                return Err(OneWireError::VerificationFailed);
+            }
+        }
+        self.bus.reset()?;
+        Ok(())
+    }
+
+    /// Write `data` to the memory at `address`.
+    ///
+    /// The memory is programmed by segments of two bytes, one page per command. Segments
+    /// that are only partially covered by `data` are read first, so that their other byte is
+    /// written back unchanged.
+    ///
+    /// # Errors
+    /// Returns [`OneWireError::InvalidValue`] if the write goes past the end of the memory,
+    /// and [`OneWireError::VerificationFailed`] if a segment is corrupted or the device does
+    /// not confirm its programming, e.g. because the page is write-protected. The segments
+    /// before the failed one are already programmed.
+    pub fn write<D: DelayNs>(
+        &mut self,
+        address: u16,
+        data: &[u8],
+        delay: &mut D,
+    ) -> OneWireResult<(), O::BusError> {
+        if address as usize + data.len() > MEMORY_END {
+            return Err(OneWireError::InvalidValue("address"));
+        }
+        let mut address = address as usize;
+        let mut data = data;
+        while !data.is_empty() {
+            let len = data.len().min(PAGE_SIZE - address % PAGE_SIZE);
+            let range = segment_range(address, len);
+            let mut buf = [0; PAGE_SIZE];
+            let buf = &mut buf[..range.len()];
+            if range.start != address {
+                self.read(range.start as u16, &mut buf[..SEGMENT_SIZE])?;
+            }
+            if range.end != address + len {
+                let last = range.len() - SEGMENT_SIZE;
+                self.read((range.start + last) as u16, &mut buf[last..])?;
+            }
+            let offset = address - range.start;
+            buf[offset..offset + len].copy_from_slice(&data[..len]);
+            self.write_segments(range.start, buf, delay)?;
+            address += len;
+            data = &data[len..];
+        }
+        Ok(())
+    }
+
+    /// Read the write protection of `page` of the user memory.
+    ///
+    /// # Errors
+    /// Returns [`OneWireError::InvalidValue`] if `page` does not exist.
+    pub fn protection(&mut self, page: u8) -> OneWireResult<Protection, O::BusError> {
+        if page >= PAGES {
+            return Err(OneWireError::InvalidValue("page"));
+        }
+        let (address, shift) = protection_bits(page);
+        let mut byte = [0];
+        self.read(address, &mut byte)?;
+        Ok(Protection::from_bits(byte[0] >> shift))
+    }
+
+    /// Set the write protection of `page` of the user memory.
+    ///
+    /// Once set, a protection can not be removed.
+    ///
+    /// # Errors
+    /// Returns [`OneWireError::InvalidValue`] if `page` does not exist, and
+    /// [`OneWireError::VerificationFailed`] if the device does not confirm the programming,
+    /// e.g. because the protection bytes are write-protected.
+    pub fn set_protection<D: DelayNs>(
+        &mut self,
+        page: u8,
+        protection: Protection,
+        delay: &mut D,
+    ) -> OneWireResult<(), O::BusError> {
+        if page >= PAGES {
+            return Err(OneWireError::InvalidValue("page"));
+        }
+        let (address, shift) = protection_bits(page);
+        let range = segment_range(address as usize, 1);
+        let mut segment = [0; SEGMENT_SIZE];
+        self.read(range.start as u16, &mut segment)?;
+        let byte = &mut segment[address as usize - range.start];
+        *byte = (*byte & !(0b11 << shift)) | (protection.into_bits() << shift);
+        self.write_segments(range.start, &segment, delay)
+    }
+}
+
+impl<O: OneWire> OneWireEeprom for Ds28e05<O> {
+    type BusError = O::BusError;
+
+    const CAPACITY: usize = PAGES as usize * PAGE_SIZE;
+    const PAGE_SIZE: usize = PAGE_SIZE;
+    const SCRATCHPAD_SIZE: usize = SEGMENT_SIZE;
+
+    fn read(&mut self, address: u16, buf: &mut [u8]) -> OneWireResult<(), Self::BusError> {
+        Ds28e05::read(self, address, buf)
+    }
+
+    fn write<D: DelayNs>(
+        &mut self,
+        address: u16,
+        data: &[u8],
+        delay: &mut D,
+    ) -> OneWireResult<(), Self::BusError> {
+        Ds28e05::write(self, address, data, delay)
+    }
+}
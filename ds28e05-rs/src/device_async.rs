@@ -0,0 +1,161 @@
+use crate::{
+    CONFIRMATION, Ds28e05, MATCH_ROM_CMD, MEMORY_END, PAGE_SIZE, PAGES, PROG_TIME_US, Protection,
+    READ_MEMORY_CMD, RELEASE, SEGMENT_SIZE, SKIP_ROM_CMD, WRITE_MEMORY_CMD, protection_bits,
+    segment_range,
+};
+use embedded_hal_async::delay::DelayNs as DelayNsAsync;
+use embedded_onewire::{OneWireAsync, OneWireError, OneWireResult};
+use onewire_eeprom::OneWireEepromAsync;
+
+impl<O: OneWireAsync> Ds28e05<O> {
+    async fn command_async(&mut self, cmd: &[u8]) -> OneWireResult<(), O::BusError> {
+        if !OneWireAsync::get_overdrive_mode(&mut self.bus) {
+            OneWireAsync::set_overdrive_mode(&mut self.bus, true).await?;
+        }
+        OneWireAsync::reset(&mut self.bus).await?;
+        match self.rom {
+            Some(rom) => {
+                OneWireAsync::write_byte(&mut self.bus, MATCH_ROM_CMD).await?;
+                OneWireAsync::write_bytes(&mut self.bus, &rom.to_le_bytes()).await?;
+            }
+            None => OneWireAsync::write_byte(&mut self.bus, SKIP_ROM_CMD).await?,
+        }
+        OneWireAsync::write_bytes(&mut self.bus, cmd).await
+    }
+
+    /// Read the memory from `address` into `buf`.
+    ///
+    /// See [`Ds28e05::read`].
+    pub async fn read_async(
+        &mut self,
+        address: u16,
+        buf: &mut [u8],
+    ) -> OneWireResult<(), O::BusError> {
+        if address as usize + buf.len() > MEMORY_END {
+            return Err(OneWireError::InvalidValue("address"));
+        }
+        let [lo, hi] = address.to_le_bytes();
+        self.command_async(&[READ_MEMORY_CMD, lo, hi]).await?;
+        OneWireAsync::read_bytes(&mut self.bus, buf).await
+    }
+
+    async fn write_segments_async<D: DelayNsAsync>(
+        &mut self,
+        address: usize,
+        data: &[u8],
+        delay: &mut D,
+    ) -> OneWireResult<(), O::BusError> {
+        let [lo, hi] = (address as u16).to_le_bytes();
+        self.command_async(&[WRITE_MEMORY_CMD, lo, hi]).await?;
+        for segment in data.chunks(SEGMENT_SIZE) {
+            OneWireAsync::write_bytes(&mut self.bus, segment).await?;
+            let mut echo = [0; SEGMENT_SIZE];
+            OneWireAsync::read_bytes(&mut self.bus, &mut echo).await?;
+            if echo != segment {
+                OneWireAsync::reset(&mut self.bus).await?;
+                return Err(OneWireError::VerificationFailed);
+            }
+            OneWireAsync::write_byte(&mut self.bus, RELEASE).await?;
+            delay.delay_us(PROG_TIME_US).await;
+            if OneWireAsync::read_byte(&mut self.bus).await? != CONFIRMATION {
+                OneWireAsync::reset(&mut self.bus).await?;
+                return Err(OneWireError::VerificationFailed);
+            }
+        }
+        OneWireAsync::reset(&mut self.bus).await?;
+        Ok(())
+    }
+
+    /// Write `data` to the memory at `address`.
+    ///
+    /// See [`Ds28e05::write`].
+    pub async fn write_async<D: DelayNsAsync>(
+        &mut self,
+        address: u16,
+        data: &[u8],
+        delay: &mut D,
+    ) -> OneWireResult<(), O::BusError> {
+        if address as usize + data.len() > MEMORY_END {
+            return Err(OneWireError::InvalidValue("address"));
+        }
+        let mut address = address as usize;
+        let mut data = data;
+        while !data.is_empty() {
+            let len = data.len().min(PAGE_SIZE - address % PAGE_SIZE);
+            let range = segment_range(address, len);
+            let mut buf = [0; PAGE_SIZE];
+            let buf = &mut buf[..range.len()];
+            if range.start != address {
+                self.read_async(range.start as u16, &mut buf[..SEGMENT_SIZE])
+                    .await?;
+            }
+            if range.end != address + len {
+                let last = range.len() - SEGMENT_SIZE;
+                self.read_async((range.start + last) as u16, &mut buf[last..])
+                    .await?;
+            }
+            let offset = address - range.start;
+            buf[offset..offset + len].copy_from_slice(&data[..len]);
+            self.write_segments_async(range.start, buf, delay).await?;
+            address += len;
+            data = &data[len..];
+        }
+        Ok(())
+    }
+
+    /// Read the write protection of `page` of the user memory.
+    ///
+    /// See [`Ds28e05::protection`].
+    pub async fn protection_async(&mut self, page: u8) -> OneWireResult<Protection, O::BusError> {
+        if page >= PAGES {
+            return Err(OneWireError::InvalidValue("page"));
+        }
+        let (address, shift) = protection_bits(page);
+        let mut byte = [0];
+        self.read_async(address, &mut byte).await?;
+        Ok(Protection::from_bits(byte[0] >> shift))
+    }
+
+    /// Set the write protection of `page` of the user memory.
+    ///
+    /// See [`Ds28e05::set_protection`].
+    pub async fn set_protection_async<D: DelayNsAsync>(
+        &mut self,
+        page: u8,
+        protection: Protection,
+        delay: &mut D,
+    ) -> OneWireResult<(), O::BusError> {
+        if page >= PAGES {
+            return Err(OneWireError::InvalidValue("page"));
+        }
+        let (address, shift) = protection_bits(page);
+        let range = segment_range(address as usize, 1);
+        let mut segment = [0; SEGMENT_SIZE];
+        self.read_async(range.start as u16, &mut segment).await?;
+        let byte = &mut segment[address as usize - range.start];
+        *byte = (*byte & !(0b11 << shift)) | (protection.into_bits() << shift);
+        self.write_segments_async(range.start, &segment, delay)
+            .await
+    }
+}
+
+impl<O: OneWireAsync> OneWireEepromAsync for Ds28e05<O> {
+    type BusError = O::BusError;
+
+    const CAPACITY: usize = PAGES as usize * PAGE_SIZE;
+    const PAGE_SIZE: usize = PAGE_SIZE;
+    const SCRATCHPAD_SIZE: usize = SEGMENT_SIZE;
+
+    async fn read(&mut self, address: u16, buf: &mut [u8]) -> OneWireResult<(), Self::BusError> {
+        self.read_async(address, buf).await
+    }
+
+    async fn write<D: DelayNsAsync>(
+        &mut self,
+        address: u16,
+        data: &[u8],
+        delay: &mut D,
+    ) -> OneWireResult<(), Self::BusError> {
+        self.write_async(address, data, delay).await
+    }
+}
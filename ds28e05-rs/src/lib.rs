@@ -0,0 +1,172 @@
+#![no_std]
+#![deny(missing_docs)]
+#![cfg_attr(docsrs, feature(doc_cfg))]
+#![doc = include_str!("../README.md")]
+
+mod device;
+mod device_async;
+
+pub use device::Ds28e05;
+pub use embedded_onewire::{OneWireError, OneWireResult};
+
+/// Family code of the DS28E05, the low byte of its ROM.
+pub const FAMILY_CODE: u8 = 0x0d;
+
+/// Size of a page of the memory.
+pub const PAGE_SIZE: usize = 16;
+/// Number of pages of the user memory.
+pub const PAGES: u8 = 7;
+/// Size of a segment, the unit of programming.
+pub const SEGMENT_SIZE: usize = 2;
+
+/// Match ROM, sent at overdrive speed.
+pub(crate) const MATCH_ROM_CMD: u8 = 0x55;
+/// Skip ROM, sent at overdrive speed.
+pub(crate) const SKIP_ROM_CMD: u8 = 0xcc;
+pub(crate) const READ_MEMORY_CMD: u8 = 0xf0;
+pub(crate) const WRITE_MEMORY_CMD: u8 = 0x55;
+/// Release byte sent by the master to start programming a segment.
+pub(crate) const RELEASE: u8 = 0xff;
+/// Byte sent by the device after programming a segment successfully.
+pub(crate) const CONFIRMATION: u8 = 0xaa;
+
+/// Address of the protection bytes, after the user memory.
+pub(crate) const PROTECTION_ADDR: u16 = 0x70;
+/// End of the memory, including the protection bytes.
+pub(crate) const MEMORY_END: usize = 0x80;
+
+/// Programming time of a segment, in microseconds.
+pub(crate) const PROG_TIME_US: u32 = 16_000;
+
+/// Write protection of a page of the user memory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Protection {
+    /// The page can be written freely.
+    Open,
+    /// The page is read-only.
+    WriteProtected,
+    /// EPROM emulation: bits of the page can only be changed from 1 to 0.
+    Eprom,
+}
+
+impl Protection {
+    pub(crate) fn into_bits(self) -> u8 {
+        match self {
+            Protection::Open => 0b00,
+            Protection::WriteProtected => 0b01,
+            Protection::Eprom => 0b10,
+        }
+    }
+
+    pub(crate) fn from_bits(bits: u8) -> Self {
+        match bits & 0b11 {
+            0b01 => Protection::WriteProtected,
+            0b10 => Protection::Eprom,
+            _ => Protection::Open,
+        }
+    }
+}
+
+/// Address and bit offset of the protection of `page` in the protection bytes.
+///
+/// Each byte holds the protection of four pages, two bits per page from the lowest.
+pub(crate) fn protection_bits(page: u8) -> (u16, u8) {
+    (PROTECTION_ADDR + page as u16 / 4, 2 * (page % 4))
+}
+
+/// Segment-aligned range of the memory covering `len` bytes from `address`.
+pub(crate) fn segment_range(address: usize, len: usize) -> core::ops::Range<usize> {
+    let start = address - address % SEGMENT_SIZE;
+    let end = (address + len).next_multiple_of(SEGMENT_SIZE);
+    start..end
+}
+
+mod test {
+    #[test]
+    fn test_write_partial_segments() {
+        use super::*;
+        use embedded_hal_mock::eh1::delay::NoopDelay;
+        use embedded_onewire::testing::{Mock, Transaction};
+        extern crate std;
+
+        let bus = Mock::new(&[
+            // The partial segments are read first
+            Transaction::reset(true),
+            Transaction::skip_rom(),
+            Transaction::write(&[READ_MEMORY_CMD, 0x0e, 0x00]),
+            Transaction::read(&[0x11, 0x22]),
+            Transaction::reset(true),
+            Transaction::skip_rom(),
+            Transaction::write(&[WRITE_MEMORY_CMD, 0x0e, 0x00]),
+            Transaction::write(&[0x11, 0xaa]),
+            Transaction::read(&[0x11, 0xaa]),
+            Transaction::write(&[RELEASE]),
+            Transaction::read(&[CONFIRMATION]),
+            Transaction::reset(true),
+            // The next page takes a new command
+            Transaction::reset(true),
+            Transaction::skip_rom(),
+            Transaction::write(&[READ_MEMORY_CMD, 0x10, 0x00]),
+            Transaction::read(&[0x33, 0x44]),
+            Transaction::reset(true),
+            Transaction::skip_rom(),
+            Transaction::write(&[WRITE_MEMORY_CMD, 0x10, 0x00]),
+            Transaction::write(&[0xbb, 0x44]),
+            Transaction::read(&[0xbb, 0x44]),
+            Transaction::write(&[RELEASE]),
+            Transaction::read(&[CONFIRMATION]),
+            Transaction::reset(true),
+        ]);
+        let mut eeprom = Ds28e05::new(bus, None);
+        eeprom
+            .write(0x0f, &[0xaa, 0xbb], &mut NoopDelay::new())
+            .unwrap();
+        assert!(matches!(
+            eeprom.write(0x7f, &[0; 2], &mut NoopDelay::new()),
+            Err(OneWireError::InvalidValue("address"))
+        ));
+        let mut bus = eeprom.release();
+        assert!(embedded_onewire::OneWire::get_overdrive_mode(&mut bus));
+        bus.done();
+    }
+
+    #[test]
+    fn test_protection() {
+        use super::*;
+        use embedded_hal_mock::eh1::delay::NoopDelay;
+        use embedded_onewire::testing::{Mock, Transaction};
+        extern crate std;
+
+        let rom = 0x3100_0000_1234_560d;
+        let bus = Mock::new(&[
+            Transaction::reset(true),
+            Transaction::match_rom(rom),
+            Transaction::write(&[READ_MEMORY_CMD, 0x70, 0x00]),
+            Transaction::read(&[0x00, 0x00]),
+            Transaction::reset(true),
+            Transaction::match_rom(rom),
+            Transaction::write(&[WRITE_MEMORY_CMD, 0x70, 0x00]),
+            Transaction::write(&[0x00, 0x04]),
+            // The page is already protected
+            Transaction::read(&[0x00, 0x04]),
+            Transaction::write(&[RELEASE]),
+            Transaction::read(&[0x00]),
+            Transaction::reset(true),
+            Transaction::reset(true),
+            Transaction::match_rom(rom),
+            Transaction::write(&[READ_MEMORY_CMD, 0x71, 0x00]),
+            Transaction::read(&[0x04]),
+        ]);
+        let mut eeprom = Ds28e05::new(bus, Some(rom));
+        assert!(matches!(
+            eeprom.set_protection(5, Protection::WriteProtected, &mut NoopDelay::new()),
+            Err(OneWireError::VerificationFailed)
+        ));
+        assert_eq!(eeprom.protection(5).unwrap(), Protection::WriteProtected);
+        assert!(matches!(
+            eeprom.protection(PAGES),
+            Err(OneWireError::InvalidValue("page"))
+        ));
+        eeprom.release().done();
+    }
+}
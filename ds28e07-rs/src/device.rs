@@ -0,0 +1,67 @@
+use crate::PROG_TIME_US;
+use core::ops::{Deref, DerefMut};
+use ds2431::Ds2431;
+use embedded_hal::delay::DelayNs;
+use embedded_onewire::{OneWireResult, OneWireStrongPullup};
+use onewire_eeprom::OneWireEeprom;
+
+/// A DS28E07 1024-bit EEPROM on a 1-Wire bus.
+///
+/// The DS28E07 is operated like a DS2431: the driver wraps a [`Ds2431`] driver, whose
+/// methods are available through [`Deref`], and waits for the longer copy of the
+/// scratchpad of the DS28E07.
+#[derive(Debug)]
+pub struct Ds28e07<O> {
+    pub(crate) inner: Ds2431<O>,
+}
+
+impl<O> Ds28e07<O> {
+    /// Creates a driver for the device with ROM `rom` on `bus`.
+    ///
+    /// See [`Ds2431::new`].
+    pub fn new(bus: O, rom: Option<u64>) -> Self {
+        Ds28e07 {
+            inner: Ds2431::with_prog_time(bus, rom, PROG_TIME_US),
+        }
+    }
+
+    /// Release the bus master.
+    pub fn release(self) -> O {
+        self.inner.release()
+    }
+}
+
+impl<O> Deref for Ds28e07<O> {
+    type Target = Ds2431<O>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+impl<O> DerefMut for Ds28e07<O> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.inner
+    }
+}
+
+impl<O: OneWireStrongPullup> OneWireEeprom for Ds28e07<O> {
+    type BusError = O::BusError;
+
+    const CAPACITY: usize = <Ds2431<O> as OneWireEeprom>::CAPACITY;
+    const PAGE_SIZE: usize = <Ds2431<O> as OneWireEeprom>::PAGE_SIZE;
+    const SCRATCHPAD_SIZE: usize = <Ds2431<O> as OneWireEeprom>::SCRATCHPAD_SIZE;
+
+    fn read(&mut self, address: u16, buf: &mut [u8]) -> OneWireResult<(), Self::BusError> {
+        self.inner.read(address, buf)
+    }
+
+    fn write<D: DelayNs>(
+        &mut self,
+        address: u16,
+        data: &[u8],
+        delay: &mut D,
+    ) -> OneWireResult<(), Self::BusError> {
+        self.inner.write(address, data, delay)
+    }
+}
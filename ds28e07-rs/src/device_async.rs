@@ -0,0 +1,26 @@
+use crate::Ds28e07;
+use ds2431::Ds2431;
+use embedded_hal_async::delay::DelayNs;
+use embedded_onewire::{OneWireResult, OneWireStrongPullupAsync};
+use onewire_eeprom::OneWireEepromAsync;
+
+impl<O: OneWireStrongPullupAsync> OneWireEepromAsync for Ds28e07<O> {
+    type BusError = O::BusError;
+
+    const CAPACITY: usize = <Ds2431<O> as OneWireEepromAsync>::CAPACITY;
+    const PAGE_SIZE: usize = <Ds2431<O> as OneWireEepromAsync>::PAGE_SIZE;
+    const SCRATCHPAD_SIZE: usize = <Ds2431<O> as OneWireEepromAsync>::SCRATCHPAD_SIZE;
+
+    async fn read(&mut self, address: u16, buf: &mut [u8]) -> OneWireResult<(), Self::BusError> {
+        self.inner.read_async(address, buf).await
+    }
+
+    async fn write<D: DelayNs>(
+        &mut self,
+        address: u16,
+        data: &[u8],
+        delay: &mut D,
+    ) -> OneWireResult<(), Self::BusError> {
+        self.inner.write_async(address, data, delay).await
+    }
+}
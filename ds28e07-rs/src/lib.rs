@@ -0,0 +1,56 @@
+#![no_std]
+#![deny(missing_docs)]
+#![cfg_attr(docsrs, feature(doc_cfg))]
+#![doc = include_str!("../README.md")]
+
+mod device;
+mod device_async;
+
+pub use device::Ds28e07;
+pub use ds2431::{Ds2431, PAGE_SIZE, PAGES, Protection, ROW_SIZE};
+pub use embedded_onewire::{OneWireError, OneWireResult};
+
+/// Family code of the DS28E07, the low byte of its ROM. The DS2431 shares it.
+pub const FAMILY_CODE: u8 = 0x2d;
+
+/// Time to copy the scratchpad to the memory, in microseconds. Longer than the 10 ms of
+/// the DS2431.
+pub(crate) const PROG_TIME_US: u32 = 12_500;
+
+mod test {
+    #[test]
+    fn test_write() {
+        use super::*;
+        use embedded_hal_mock::eh1::delay::NoopDelay;
+        use embedded_onewire::testing::{Mock, Transaction};
+        extern crate std;
+
+        let row = [0x00, 0x55, 0x00, 0x00, 0x00, 0x55, 0x12, 0x34];
+        let bus = Mock::new(&[
+            Transaction::reset(true),
+            Transaction::skip_rom(),
+            Transaction::write(&[0x0f, 0x80, 0x00]),
+            Transaction::write(&row),
+            Transaction::read(&[0xd0, 0x61]),
+            Transaction::reset(true),
+            Transaction::skip_rom(),
+            Transaction::write(&[0xaa]),
+            Transaction::read(&[0x80, 0x00, 0x07]),
+            Transaction::read(&row),
+            Transaction::read(&[0xf3, 0xb6]),
+            Transaction::reset(true),
+            Transaction::skip_rom(),
+            Transaction::write(&[0x55, 0x80, 0x00]),
+            Transaction::write_powered(0x07),
+            Transaction::read(&[0xaa]),
+            Transaction::reset(true),
+            Transaction::skip_rom(),
+            Transaction::write(&[0xf0, 0x81, 0x00]),
+            Transaction::read(&[0x55]),
+        ]);
+        let mut eeprom = Ds28e07::new(bus, None);
+        eeprom.write_row(0x80, &row, &mut NoopDelay::new()).unwrap();
+        assert_eq!(eeprom.protection(1).unwrap(), Protection::WriteProtected);
+        eeprom.release().done();
+    }
+}
@@ -0,0 +1,178 @@
+use crate::{
+    ENABLE_SLEEP_CMD, Error, FRAME_SIZE, MAX_LEN, MAX_POLLS, READ_CONFIG_CMD, READ_REVISION_CMD,
+    READ_STOP_CMD, Speed, WRITE_CONFIG_CMD, WRITE_READ_STOP_CMD, check_status, frame, plan,
+    scatter, write_cmd,
+};
+use embedded_hal::i2c::{ErrorType, I2c, Operation, SevenBitAddress};
+use embedded_onewire::{OneWire, OneWireResult};
+
+/// A DS28E17 1-Wire-to-I2C bridge on a 1-Wire bus.
+///
+/// The driver owns the bus master, which can be a `&mut` reference to one or a handle to a
+/// shared one. Every operation addresses the device first.
+///
+/// The driver implements [`I2c`], so that drivers of I2C devices can talk to the targets on
+/// the I2C bus of the bridge. Each I2C command of the bridge is sent with its CRC-16, and the
+/// driver waits for the bridge to complete it on the I2C bus before reading back its status.
+#[derive(Debug)]
+pub struct Ds28e17<O> {
+    pub(crate) bus: O,
+    pub(crate) rom: Option<u64>,
+}
+
+impl<O> Ds28e17<O> {
+    /// Creates a driver for the device with ROM `rom` on `bus`.
+    ///
+    /// Pass [`None`] to skip ROM addressing on a bus with a single device.
+    pub fn new(bus: O, rom: Option<u64>) -> Self {
+        Ds28e17 { bus, rom }
+    }
+
+    /// ROM of the device, if it is addressed by ROM.
+    pub fn rom(&self) -> Option<u64> {
+        self.rom
+    }
+
+    /// Release the bus master.
+    pub fn release(self) -> O {
+        self.bus
+    }
+}
+
+impl<O: OneWire> Ds28e17<O> {
+    fn command(&mut self, cmd: &[u8]) -> OneWireResult<(), O::BusError> {
+        self.bus.address(self.rom)?;
+        self.bus.write_bytes(cmd)
+    }
+
+    /// Send an I2C command, wait for the device to complete it, and check its status.
+    fn i2c_command(&mut self, cmd: &[u8], writes: bool) -> Result<(), Error<O::BusError>> {
+        self.command(cmd)?;
+        let mut polls = 0;
+        while self.bus.read_bit()? {
+            polls += 1;
+            if polls == MAX_POLLS {
+                return Err(Error::Timeout);
+            }
+        }
+        let status = self.bus.read_byte()?;
+        let write_status = if writes { self.bus.read_byte()? } else { 0 };
+        check_status(status, write_status)
+    }
+
+    /// Set the speed of the I2C bus.
+    pub fn set_speed(&mut self, speed: Speed) -> OneWireResult<(), O::BusError> {
+        self.command(&[WRITE_CONFIG_CMD, speed.into_bits()])
+    }
+
+    /// Read the speed of the I2C bus.
+    pub fn speed(&mut self) -> OneWireResult<Speed, O::BusError> {
+        self.command(&[READ_CONFIG_CMD])?;
+        Ok(Speed::from_bits(self.bus.read_byte()?))
+    }
+
+    /// Put the device to sleep, until the next activity on the 1-Wire bus.
+    pub fn enable_sleep(&mut self) -> OneWireResult<(), O::BusError> {
+        self.command(&[ENABLE_SLEEP_CMD])
+    }
+
+    /// Read the revision of the device.
+    pub fn revision(&mut self) -> OneWireResult<u8, O::BusError> {
+        self.command(&[READ_REVISION_CMD])?;
+        self.bus.read_byte()
+    }
+
+    fn write_chunk(
+        &mut self,
+        address: u8,
+        data: &[u8],
+        start: bool,
+        stop: bool,
+    ) -> Result<(), Error<O::BusError>> {
+        let mut buf = [0; FRAME_SIZE];
+        let cmd = [write_cmd(start, stop)];
+        let addr = [address << 1];
+        let addr: &[u8] = if start { &addr } else { &[] };
+        let len = frame(&mut buf, &[&cmd, addr, &[data.len() as u8], data]);
+        self.i2c_command(&buf[..len], true)
+    }
+
+    fn read_chunk(&mut self, address: u8, buf: &mut [u8]) -> Result<(), Error<O::BusError>> {
+        let mut cmd = [0; FRAME_SIZE];
+        let len = frame(
+            &mut cmd,
+            &[&[READ_STOP_CMD, address << 1 | 1, buf.len() as u8]],
+        );
+        self.i2c_command(&cmd[..len], false)?;
+        Ok(self.bus.read_bytes(buf)?)
+    }
+
+    fn write_read_chunk(
+        &mut self,
+        address: u8,
+        write: &[u8],
+        read: &mut [u8],
+    ) -> Result<(), Error<O::BusError>> {
+        let mut cmd = [0; FRAME_SIZE];
+        let len = frame(
+            &mut cmd,
+            &[
+                &[WRITE_READ_STOP_CMD, address << 1, write.len() as u8],
+                write,
+                &[read.len() as u8],
+            ],
+        );
+        self.i2c_command(&cmd[..len], true)?;
+        Ok(self.bus.read_bytes(read)?)
+    }
+}
+
+impl<O: OneWire> ErrorType for Ds28e17<O>
+where
+    O::BusError: core::fmt::Debug,
+{
+    type Error = Error<O::BusError>;
+}
+
+impl<O: OneWire> I2c<SevenBitAddress> for Ds28e17<O>
+where
+    O::BusError: core::fmt::Debug,
+{
+    /// Run `operations` on the I2C bus of the bridge.
+    ///
+    /// A write followed by a read, as in [`I2c::write_read`], is a single command. Otherwise,
+    /// writes are sent in chunks of up to [`MAX_LEN`] bytes, and all the reads in one final
+    /// command.
+    fn transaction(
+        &mut self,
+        address: SevenBitAddress,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        if operations.is_empty() {
+            return Ok(());
+        }
+        let (mut written, read) = plan(operations)?;
+        if let [Operation::Write(write), Operation::Read(buf)] = operations
+            && (1..=MAX_LEN).contains(&write.len())
+            && !buf.is_empty()
+        {
+            return self.write_read_chunk(address, write, buf);
+        }
+        let mut start = true;
+        for op in operations.iter() {
+            if let Operation::Write(data) = op {
+                for chunk in data.chunks(MAX_LEN) {
+                    written -= chunk.len();
+                    self.write_chunk(address, chunk, start, written == 0 && read == 0)?;
+                    start = false;
+                }
+            }
+        }
+        if read > 0 {
+            let mut buf = [0; MAX_LEN];
+            self.read_chunk(address, &mut buf[..read])?;
+            scatter(&buf[..read], operations);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,190 @@
+use crate::{
+    Ds28e17, ENABLE_SLEEP_CMD, Error, FRAME_SIZE, MAX_LEN, MAX_POLLS, READ_CONFIG_CMD,
+    READ_REVISION_CMD, READ_STOP_CMD, Speed, WRITE_CONFIG_CMD, WRITE_READ_STOP_CMD, check_status,
+    frame, plan, scatter, write_cmd,
+};
+use embedded_hal::i2c::{ErrorType, Operation, SevenBitAddress};
+use embedded_hal_async::i2c::I2c;
+use embedded_onewire::{OneWireAsync, OneWireResult};
+
+/// A DS28E17 on an asynchronous 1-Wire bus, implementing the asynchronous [`I2c`].
+///
+/// The error type of the I2C traits is shared by their blocking and asynchronous
+/// versions, so the asynchronous one is implemented by this wrapper. Create it with
+/// [`Ds28e17::into_async`].
+#[derive(Debug)]
+pub struct Ds28e17Async<O>(Ds28e17<O>);
+
+impl<O> Ds28e17<O> {
+    /// Wrap the driver to use it as an asynchronous [`I2c`].
+    pub fn into_async(self) -> Ds28e17Async<O> {
+        Ds28e17Async(self)
+    }
+}
+
+impl<O> Ds28e17Async<O> {
+    /// Unwrap the driver.
+    pub fn into_inner(self) -> Ds28e17<O> {
+        self.0
+    }
+}
+
+impl<O: OneWireAsync> Ds28e17<O> {
+    async fn command_async(&mut self, cmd: &[u8]) -> OneWireResult<(), O::BusError> {
+        OneWireAsync::address(&mut self.bus, self.rom).await?;
+        OneWireAsync::write_bytes(&mut self.bus, cmd).await
+    }
+
+    async fn i2c_command_async(
+        &mut self,
+        cmd: &[u8],
+        writes: bool,
+    ) -> Result<(), Error<O::BusError>> {
+        self.command_async(cmd).await?;
+        let mut polls = 0;
+        while OneWireAsync::read_bit(&mut self.bus).await? {
+            polls += 1;
+            if polls == MAX_POLLS {
+                return Err(Error::Timeout);
+            }
+        }
+        let status = OneWireAsync::read_byte(&mut self.bus).await?;
+        let write_status = if writes {
+            OneWireAsync::read_byte(&mut self.bus).await?
+        } else {
+            0
+        };
+        check_status(status, write_status)
+    }
+
+    /// Set the speed of the I2C bus.
+    ///
+    /// See [`Ds28e17::set_speed`].
+    pub async fn set_speed_async(&mut self, speed: Speed) -> OneWireResult<(), O::BusError> {
+        self.command_async(&[WRITE_CONFIG_CMD, speed.into_bits()])
+            .await
+    }
+
+    /// Read the speed of the I2C bus.
+    ///
+    /// See [`Ds28e17::speed`].
+    pub async fn speed_async(&mut self) -> OneWireResult<Speed, O::BusError> {
+        self.command_async(&[READ_CONFIG_CMD]).await?;
+        Ok(Speed::from_bits(
+            OneWireAsync::read_byte(&mut self.bus).await?,
+        ))
+    }
+
+    /// Put the device to sleep, until the next activity on the 1-Wire bus.
+    ///
+    /// See [`Ds28e17::enable_sleep`].
+    pub async fn enable_sleep_async(&mut self) -> OneWireResult<(), O::BusError> {
+        self.command_async(&[ENABLE_SLEEP_CMD]).await
+    }
+
+    /// Read the revision of the device.
+    ///
+    /// See [`Ds28e17::revision`].
+    pub async fn revision_async(&mut self) -> OneWireResult<u8, O::BusError> {
+        self.command_async(&[READ_REVISION_CMD]).await?;
+        OneWireAsync::read_byte(&mut self.bus).await
+    }
+
+    async fn write_chunk_async(
+        &mut self,
+        address: u8,
+        data: &[u8],
+        start: bool,
+        stop: bool,
+    ) -> Result<(), Error<O::BusError>> {
+        let mut buf = [0; FRAME_SIZE];
+        let cmd = [write_cmd(start, stop)];
+        let addr = [address << 1];
+        let addr: &[u8] = if start { &addr } else { &[] };
+        let len = frame(&mut buf, &[&cmd, addr, &[data.len() as u8], data]);
+        self.i2c_command_async(&buf[..len], true).await
+    }
+
+    async fn read_chunk_async(
+        &mut self,
+        address: u8,
+        buf: &mut [u8],
+    ) -> Result<(), Error<O::BusError>> {
+        let mut cmd = [0; FRAME_SIZE];
+        let len = frame(
+            &mut cmd,
+            &[&[READ_STOP_CMD, address << 1 | 1, buf.len() as u8]],
+        );
+        self.i2c_command_async(&cmd[..len], false).await?;
+        Ok(OneWireAsync::read_bytes(&mut self.bus, buf).await?)
+    }
+
+    async fn write_read_chunk_async(
+        &mut self,
+        address: u8,
+        write: &[u8],
+        read: &mut [u8],
+    ) -> Result<(), Error<O::BusError>> {
+        let mut cmd = [0; FRAME_SIZE];
+        let len = frame(
+            &mut cmd,
+            &[
+                &[WRITE_READ_STOP_CMD, address << 1, write.len() as u8],
+                write,
+                &[read.len() as u8],
+            ],
+        );
+        self.i2c_command_async(&cmd[..len], true).await?;
+        Ok(OneWireAsync::read_bytes(&mut self.bus, read).await?)
+    }
+}
+
+impl<O: OneWireAsync> ErrorType for Ds28e17Async<O>
+where
+    O::BusError: core::fmt::Debug,
+{
+    type Error = Error<O::BusError>;
+}
+
+impl<O: OneWireAsync> I2c<SevenBitAddress> for Ds28e17Async<O>
+where
+    O::BusError: core::fmt::Debug,
+{
+    /// Run `operations` on the I2C bus of the bridge.
+    ///
+    /// See the blocking implementation on [`Ds28e17`].
+    async fn transaction(
+        &mut self,
+        address: SevenBitAddress,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        if operations.is_empty() {
+            return Ok(());
+        }
+        let (mut written, read) = plan(operations)?;
+        if let [Operation::Write(write), Operation::Read(buf)] = operations
+            && (1..=MAX_LEN).contains(&write.len())
+            && !buf.is_empty()
+        {
+            return self.0.write_read_chunk_async(address, write, buf).await;
+        }
+        let mut start = true;
+        for op in operations.iter() {
+            if let Operation::Write(data) = op {
+                for chunk in data.chunks(MAX_LEN) {
+                    written -= chunk.len();
+                    self.0
+                        .write_chunk_async(address, chunk, start, written == 0 && read == 0)
+                        .await?;
+                    start = false;
+                }
+            }
+        }
+        if read > 0 {
+            let mut buf = [0; MAX_LEN];
+            self.0.read_chunk_async(address, &mut buf[..read]).await?;
+            scatter(&buf[..read], operations);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,259 @@
+#![no_std]
+#![deny(missing_docs)]
+#![cfg_attr(docsrs, feature(doc_cfg))]
+#![doc = include_str!("../README.md")]
+
+mod device;
+mod device_async;
+
+pub use device::Ds28e17;
+pub use device_async::Ds28e17Async;
+pub use embedded_onewire::{OneWireError, OneWireResult};
+
+use embedded_hal::i2c::{ErrorKind, NoAcknowledgeSource, Operation};
+use embedded_onewire::OneWireCrc16;
+
+/// Family code of the DS28E17, the low byte of its ROM.
+pub const FAMILY_CODE: u8 = 0x19;
+/// Largest number of bytes the device writes or reads in one I2C command.
+pub const MAX_LEN: usize = 255;
+
+pub(crate) const WRITE_STOP_CMD: u8 = 0x4b;
+pub(crate) const WRITE_NO_STOP_CMD: u8 = 0x5a;
+pub(crate) const WRITE_ONLY_CMD: u8 = 0x69;
+pub(crate) const WRITE_ONLY_STOP_CMD: u8 = 0x78;
+pub(crate) const READ_STOP_CMD: u8 = 0x87;
+pub(crate) const WRITE_READ_STOP_CMD: u8 = 0x2d;
+pub(crate) const WRITE_CONFIG_CMD: u8 = 0xd2;
+pub(crate) const READ_CONFIG_CMD: u8 = 0xe1;
+pub(crate) const ENABLE_SLEEP_CMD: u8 = 0x1e;
+pub(crate) const READ_REVISION_CMD: u8 = 0xc3;
+
+/// Size of the longest command: the Write, Read Data With Stop command with its CRC-16.
+pub(crate) const FRAME_SIZE: usize = MAX_LEN + 6;
+/// Number of read slots to wait for an I2C command to complete, above the 25 ms a
+/// 255-byte transfer takes at 100 kHz.
+pub(crate) const MAX_POLLS: u32 = 2_000;
+
+/// Status bit set if the CRC-16 of the command did not match.
+const STATUS_CRC: u8 = 0x01;
+/// Status bit set if the I2C target did not acknowledge its address.
+const STATUS_ADDRESS_NACK: u8 = 0x02;
+/// Status bit set if the device could not issue the start condition.
+const STATUS_START: u8 = 0x08;
+
+/// Speed of the I2C bus.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Speed {
+    /// 100 kHz
+    Standard,
+    /// 400 kHz, the speed after power-up.
+    Fast,
+    /// 900 kHz
+    FastPlus,
+}
+
+impl Speed {
+    pub(crate) fn into_bits(self) -> u8 {
+        match self {
+            Speed::Standard => 0b00,
+            Speed::Fast => 0b01,
+            Speed::FastPlus => 0b10,
+        }
+    }
+
+    pub(crate) fn from_bits(bits: u8) -> Self {
+        match bits & 0b11 {
+            0b00 => Speed::Standard,
+            0b01 => Speed::Fast,
+            _ => Speed::FastPlus,
+        }
+    }
+}
+
+/// Errors of the I2C transactions tunneled through the DS28E17.
+#[derive(Debug)]
+pub enum Error<E> {
+    /// The 1-Wire transfer failed, or the device received a corrupted command.
+    OneWire(OneWireError<E>),
+    /// The I2C target did not acknowledge its address.
+    AddressNack,
+    /// The I2C target did not acknowledge a written byte.
+    DataNack,
+    /// The device could not issue the start condition, e.g. because SDA or SCL is held
+    /// low.
+    Start,
+    /// The device did not complete the command in time.
+    Timeout,
+    /// The operations can not be tunneled: reads must come after all writes and add up to
+    /// at most [`MAX_LEN`] bytes, and at least one byte must be transferred.
+    Unsupported,
+}
+
+impl<E> From<OneWireError<E>> for Error<E> {
+    fn from(e: OneWireError<E>) -> Self {
+        Error::OneWire(e)
+    }
+}
+
+impl<E: core::fmt::Debug> embedded_hal::i2c::Error for Error<E> {
+    fn kind(&self) -> ErrorKind {
+        match self {
+            Error::AddressNack => ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address),
+            Error::DataNack => ErrorKind::NoAcknowledge(NoAcknowledgeSource::Data),
+            Error::Start => ErrorKind::Bus,
+            _ => ErrorKind::Other,
+        }
+    }
+}
+
+/// Check the status of an I2C command, and the write status of a command that writes.
+pub(crate) fn check_status<E>(status: u8, write_status: u8) -> Result<(), Error<E>> {
+    if status & STATUS_CRC != 0 {
+        Err(Error::OneWire(OneWireError::InvalidCrc))
+    } else if status & STATUS_ADDRESS_NACK != 0 {
+        Err(Error::AddressNack)
+    } else if status & STATUS_START != 0 {
+        Err(Error::Start)
+    } else if write_status != 0 {
+        Err(Error::DataNack)
+    } else {
+        Ok(())
+    }
+}
+
+/// Assemble `parts` into a command followed by its CRC-16, and return its length.
+pub(crate) fn frame(buf: &mut [u8; FRAME_SIZE], parts: &[&[u8]]) -> usize {
+    let mut len = 0;
+    for part in parts {
+        buf[len..len + part.len()].copy_from_slice(part);
+        len += part.len();
+    }
+    let mut crc = OneWireCrc16::default();
+    crc.update_slice(&buf[..len]);
+    buf[len..len + 2].copy_from_slice(&(!crc.value()).to_le_bytes());
+    len + 2
+}
+
+/// The Write Data command for a chunk of a write, issuing the start and stop conditions as
+/// requested.
+pub(crate) fn write_cmd(start: bool, stop: bool) -> u8 {
+    match (start, stop) {
+        (true, true) => WRITE_STOP_CMD,
+        (true, false) => WRITE_NO_STOP_CMD,
+        (false, false) => WRITE_ONLY_CMD,
+        (false, true) => WRITE_ONLY_STOP_CMD,
+    }
+}
+
+/// Check that `operations` can be tunneled, and return the number of bytes written and read.
+pub(crate) fn plan<E>(operations: &[Operation<'_>]) -> Result<(usize, usize), Error<E>> {
+    let (mut written, mut read) = (0, 0);
+    for op in operations {
+        match op {
+            Operation::Write(_) if read > 0 => return Err(Error::Unsupported),
+            Operation::Write(data) => written += data.len(),
+            Operation::Read(buf) => read += buf.len(),
+        }
+    }
+    if read > MAX_LEN || written + read == 0 {
+        return Err(Error::Unsupported);
+    }
+    Ok((written, read))
+}
+
+/// Copy the bytes read in one command to the buffers of the read operations.
+pub(crate) fn scatter(data: &[u8], operations: &mut [Operation<'_>]) {
+    let mut data = data;
+    for op in operations {
+        if let Operation::Read(buf) = op {
+            let (head, tail) = data.split_at(buf.len());
+            buf.copy_from_slice(head);
+            data = tail;
+        }
+    }
+}
+
+mod test {
+    #[test]
+    fn test_write_read() {
+        use super::*;
+        use embedded_hal::i2c::I2c;
+        use embedded_onewire::testing::{Mock, Transaction};
+        extern crate std;
+
+        let bus = Mock::new(&[
+            Transaction::reset(true),
+            Transaction::skip_rom(),
+            Transaction::write(&[WRITE_READ_STOP_CMD, 0xec, 0x01, 0xd0, 0x01, 0xaa, 0xa9]),
+            // The device is busy on the I2C bus
+            Transaction::read_bit(true),
+            Transaction::read_bit(false),
+            Transaction::read(&[0x00, 0x00, 0x60]),
+        ]);
+        let mut bridge = Ds28e17::new(bus, None);
+        let mut id = [0];
+        bridge.write_read(0x76, &[0xd0], &mut id).unwrap();
+        assert_eq!(id, [0x60]);
+        bridge.release().done();
+    }
+
+    #[test]
+    fn test_transaction() {
+        use super::*;
+        use embedded_hal::i2c::{Error as _, I2c};
+        use embedded_onewire::testing::{Mock, Transaction};
+        extern crate std;
+
+        let bus = Mock::new(&[
+            Transaction::reset(true),
+            Transaction::skip_rom(),
+            Transaction::write(&[WRITE_NO_STOP_CMD, 0xa0, 0x02, 0x01, 0x02, 0xa4, 0x63]),
+            Transaction::read_bit(false),
+            Transaction::read(&[0x00, 0x00]),
+            Transaction::reset(true),
+            Transaction::skip_rom(),
+            Transaction::write(&[WRITE_ONLY_CMD, 0x01, 0x03, 0x6e, 0x72]),
+            Transaction::read_bit(false),
+            Transaction::read(&[0x00, 0x00]),
+            Transaction::reset(true),
+            Transaction::skip_rom(),
+            Transaction::write(&[READ_STOP_CMD, 0xa1, 0x03, 0x76, 0x47]),
+            Transaction::read_bit(false),
+            Transaction::read(&[0x00, 0x0a, 0x0b, 0x0c]),
+            // No target at the address
+            Transaction::reset(true),
+            Transaction::skip_rom(),
+            Transaction::write(&[WRITE_STOP_CMD, 0xa0, 0x01, 0x00, 0xe9, 0xa9]),
+            Transaction::read_bit(false),
+            Transaction::read(&[0x02, 0x00]),
+        ]);
+        let mut bridge = Ds28e17::new(bus, None);
+        let (mut a, mut b) = ([0; 2], [0; 1]);
+        bridge
+            .transaction(
+                0x50,
+                &mut [
+                    Operation::Write(&[0x01, 0x02]),
+                    Operation::Write(&[0x03]),
+                    Operation::Read(&mut a),
+                    Operation::Read(&mut b),
+                ],
+            )
+            .unwrap();
+        assert_eq!((a, b), ([0x0a, 0x0b], [0x0c]));
+        let err = bridge.write(0x50, &[0x00]).unwrap_err();
+        assert_eq!(
+            err.kind(),
+            ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address)
+        );
+        assert!(matches!(
+            bridge.transaction(
+                0x50,
+                &mut [Operation::Read(&mut a), Operation::Write(&[0x01])]
+            ),
+            Err(Error::Unsupported)
+        ));
+        bridge.release().done();
+    }
+}
@@ -0,0 +1,309 @@
+use crate::{
+    COMMAND_START, Configuration, DEVICE_STATUS_CMD, Error, MAX_CHUNK, OP_TIME_US, PACKET_SIZE,
+    READ_CONFIG_CMD, READ_SEQUENCER_CMD, RELEASE, RUN_SEQUENCER_CMD, SEQUENCER_SIZE, Sequence,
+    Speed, WRITE_CONFIG_CMD, WRITE_SEQUENCER_CMD, check_packet_crc, packet, parse_response,
+};
+use embedded_hal::delay::DelayNs;
+use embedded_onewire::{OneWireError, OneWireStrongPullup};
+
+/// A DS28E18 1-Wire-to-I2C/SPI bridge on a 1-Wire bus.
+///
+/// The driver owns the bus master, which can be a `&mut` reference to one or a handle to a
+/// shared one. Every operation addresses the device first.
+///
+/// The device runs sequences of I2C or SPI commands stored in its sequencer memory. Every
+/// command is sent in a packet checked with a CRC-16, after which the bus is held at the
+/// strong pullup to power the device while it executes the command.
+#[derive(Debug)]
+pub struct Ds28e18<O> {
+    pub(crate) bus: O,
+    pub(crate) rom: Option<u64>,
+    /// Speed of the remote bus, to estimate the run time of sequences.
+    pub(crate) speed: Speed,
+}
+
+impl<O> Ds28e18<O> {
+    /// Creates a driver for the device with ROM `rom` on `bus`.
+    ///
+    /// Pass [`None`] to skip ROM addressing on a bus with a single device.
+    pub fn new(bus: O, rom: Option<u64>) -> Self {
+        Ds28e18 {
+            bus,
+            rom,
+            speed: Speed::K100,
+        }
+    }
+
+    /// ROM of the device, if it is addressed by ROM.
+    pub fn rom(&self) -> Option<u64> {
+        self.rom
+    }
+
+    /// Release the bus master.
+    pub fn release(self) -> O {
+        self.bus
+    }
+}
+
+impl<O: OneWireStrongPullup> Ds28e18<O> {
+    /// Run a command, powering the device for `op_us` microseconds, and copy the data of
+    /// the response into `out`.
+    fn execute<D: DelayNs>(
+        &mut self,
+        cmd: u8,
+        params: &[u8],
+        data: &[u8],
+        out: &mut [u8],
+        delay: &mut D,
+        op_us: u32,
+    ) -> Result<(), Error<O::BusError>> {
+        let mut buf = [0; PACKET_SIZE];
+        let len = packet(&mut buf, cmd, params, data);
+        self.bus.address(self.rom)?;
+        self.bus.write_byte(COMMAND_START)?;
+        self.bus.write_bytes(&buf[..len])?;
+        let mut crc = [0; 2];
+        self.bus.read_bytes(&mut crc)?;
+        check_packet_crc(&buf[..len], &crc)?;
+        self.bus.write_byte_powered(RELEASE)?;
+        delay.delay_us(op_us);
+        // A dummy byte precedes the length of the response
+        self.bus.read_byte()?;
+        let len = self.bus.read_byte()?;
+        if len as usize > buf.len() {
+            return Err(Error::OneWire(OneWireError::InvalidCrc));
+        }
+        let response = &mut buf[..len as usize];
+        self.bus.read_bytes(response)?;
+        self.bus.read_bytes(&mut crc)?;
+        let data = parse_response(len, response, &crc)?;
+        let n = data.len().min(out.len());
+        out[..n].copy_from_slice(&data[..n]);
+        Ok(())
+    }
+
+    /// Write `data` to the sequencer memory at `address`.
+    ///
+    /// # Errors
+    /// Returns [`OneWireError::InvalidValue`] if the write goes past the end of the
+    /// sequencer memory.
+    pub fn write_sequencer<D: DelayNs>(
+        &mut self,
+        address: u16,
+        data: &[u8],
+        delay: &mut D,
+    ) -> Result<(), Error<O::BusError>> {
+        check_span(address, data.len())?;
+        for (i, chunk) in data.chunks(MAX_CHUNK).enumerate() {
+            let [lo, hi] = (address + (i * MAX_CHUNK) as u16).to_le_bytes();
+            self.execute(
+                WRITE_SEQUENCER_CMD,
+                &[lo, hi & 0x01],
+                chunk,
+                &mut [],
+                delay,
+                OP_TIME_US,
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Read the sequencer memory at `address` into `buf`.
+    ///
+    /// # Errors
+    /// See [`Ds28e18::write_sequencer`].
+    pub fn read_sequencer<D: DelayNs>(
+        &mut self,
+        address: u16,
+        buf: &mut [u8],
+        delay: &mut D,
+    ) -> Result<(), Error<O::BusError>> {
+        check_span(address, buf.len())?;
+        for (i, chunk) in buf.chunks_mut(MAX_CHUNK).enumerate() {
+            let params = read_params(address + (i * MAX_CHUNK) as u16, chunk.len());
+            self.execute(READ_SEQUENCER_CMD, &params, &[], chunk, delay, OP_TIME_US)?;
+        }
+        Ok(())
+    }
+
+    /// Run the `len` bytes of commands at `address` of the sequencer memory, powering the
+    /// device for `run_us` microseconds.
+    ///
+    /// The data read by the sequence replaces the room reserved for it in the sequencer
+    /// memory.
+    ///
+    /// # Errors
+    /// Returns [`Error::PowerOnReset`] until the status of the device is read after a reset,
+    /// and [`Error::Nack`] or [`Error::Execution`] if the sequence fails.
+    pub fn run_sequencer<D: DelayNs>(
+        &mut self,
+        address: u16,
+        len: usize,
+        delay: &mut D,
+        run_us: u32,
+    ) -> Result<(), Error<O::BusError>> {
+        check_span(address, len)?;
+        let params = run_params(address, len);
+        self.execute(RUN_SEQUENCER_CMD, &params, &[], &mut [], delay, run_us)
+    }
+
+    /// Write `sequence` at the start of the sequencer memory and run it.
+    ///
+    /// # Errors
+    /// See [`Ds28e18::run_sequencer`].
+    pub fn run<D: DelayNs>(
+        &mut self,
+        sequence: &Sequence,
+        delay: &mut D,
+    ) -> Result<(), Error<O::BusError>> {
+        let commands = sequence.as_bytes();
+        self.write_sequencer(0, commands, delay)?;
+        self.run_sequencer(0, commands.len(), delay, sequence.run_time_us(self.speed))
+    }
+
+    /// Write the configuration register.
+    pub fn write_configuration<D: DelayNs>(
+        &mut self,
+        configuration: Configuration,
+        delay: &mut D,
+    ) -> Result<(), Error<O::BusError>> {
+        self.execute(
+            WRITE_CONFIG_CMD,
+            &[configuration.into_bits()],
+            &[],
+            &mut [],
+            delay,
+            OP_TIME_US,
+        )?;
+        self.speed = configuration.speed();
+        Ok(())
+    }
+
+    /// Read the configuration register.
+    pub fn configuration<D: DelayNs>(
+        &mut self,
+        delay: &mut D,
+    ) -> Result<Configuration, Error<O::BusError>> {
+        let mut config = [0];
+        self.execute(READ_CONFIG_CMD, &[], &[], &mut config, delay, OP_TIME_US)?;
+        let config = Configuration::from_bits(config[0]);
+        self.speed = config.speed();
+        Ok(config)
+    }
+
+    /// Read the status of the device, which clears the reset flag that blocks the
+    /// sequencer after power-up.
+    pub fn device_status<D: DelayNs>(&mut self, delay: &mut D) -> Result<u8, Error<O::BusError>> {
+        let mut status = [0];
+        self.execute(DEVICE_STATUS_CMD, &[], &[], &mut status, delay, OP_TIME_US)?;
+        Ok(status[0])
+    }
+
+    /// Write `write` to the I2C target at the 7-bit `address`, then read `read` from it.
+    ///
+    /// Either part can be empty. The transaction ends with a stop condition.
+    ///
+    /// # Errors
+    /// Returns [`OneWireError::InvalidValue`] if a part is longer than
+    /// [`MAX_TRANSFER`](crate::MAX_TRANSFER) bytes, and the errors of
+    /// [`Ds28e18::run_sequencer`].
+    pub fn i2c_write_read<D: DelayNs>(
+        &mut self,
+        address: u8,
+        write: &[u8],
+        read: &mut [u8],
+        delay: &mut D,
+    ) -> Result<(), Error<O::BusError>> {
+        let mut sequence = Sequence::new();
+        let slot = i2c_sequence(&mut sequence, address, write, read.len())?;
+        self.run(&sequence, delay)?;
+        match slot {
+            Some(slot) => self.read_sequencer(slot.offset, read, delay),
+            None => Ok(()),
+        }
+    }
+
+    /// Select the SPI target, write `write`, read `read`, and deselect the target.
+    ///
+    /// # Errors
+    /// See [`Ds28e18::i2c_write_read`].
+    pub fn spi_write_read<D: DelayNs>(
+        &mut self,
+        write: &[u8],
+        read: &mut [u8],
+        delay: &mut D,
+    ) -> Result<(), Error<O::BusError>> {
+        let mut sequence = Sequence::new();
+        let slot = spi_sequence(&mut sequence, write, read.len())?;
+        self.run(&sequence, delay)?;
+        if read.is_empty() {
+            return Ok(());
+        }
+        self.read_sequencer(slot.offset, read, delay)
+    }
+}
+
+pub(crate) fn check_span<E>(address: u16, len: usize) -> Result<(), Error<E>> {
+    if address as usize + len > SEQUENCER_SIZE {
+        return Err(Error::OneWire(OneWireError::InvalidValue("address")));
+    }
+    Ok(())
+}
+
+/// Parameters of the Read Sequencer command: the 9-bit address, and the 7-bit length where
+/// 0 stands for 128 bytes.
+pub(crate) fn read_params(address: u16, len: usize) -> [u8; 2] {
+    let [lo, hi] = address.to_le_bytes();
+    [lo, ((len as u8 & 0x7f) << 1) | (hi & 0x01)]
+}
+
+/// Parameters of the Run Sequencer command: the 9-bit address and the 10-bit length.
+pub(crate) fn run_params(address: u16, len: usize) -> [u8; 3] {
+    let [lo, hi] = address.to_le_bytes();
+    [
+        lo,
+        ((len as u8 & 0x7f) << 1) | (hi & 0x01),
+        (len >> 7) as u8 & 0x07,
+    ]
+}
+
+/// Build the sequence of an I2C write and read, and return where the data read lands.
+pub(crate) fn i2c_sequence<E>(
+    sequence: &mut Sequence,
+    address: u8,
+    write: &[u8],
+    read: usize,
+) -> Result<Option<crate::ReadSlot>, Error<E>> {
+    let invalid = || Error::OneWire(OneWireError::InvalidValue("length"));
+    if !write.is_empty() || read == 0 {
+        sequence.i2c_start().ok_or_else(invalid)?;
+        sequence.i2c_write(&[address << 1]).ok_or_else(invalid)?;
+        if !write.is_empty() {
+            sequence.i2c_write(write).ok_or_else(invalid)?;
+        }
+    }
+    let slot = if read > 0 {
+        sequence.i2c_start().ok_or_else(invalid)?;
+        sequence
+            .i2c_write(&[address << 1 | 1])
+            .ok_or_else(invalid)?;
+        Some(sequence.i2c_read_nack_end(read).ok_or_else(invalid)?)
+    } else {
+        None
+    };
+    sequence.i2c_stop().ok_or_else(invalid)?;
+    Ok(slot)
+}
+
+/// Build the sequence of an SPI write and read, and return where the data read lands.
+pub(crate) fn spi_sequence<E>(
+    sequence: &mut Sequence,
+    write: &[u8],
+    read: usize,
+) -> Result<crate::ReadSlot, Error<E>> {
+    let invalid = || Error::OneWire(OneWireError::InvalidValue("length"));
+    sequence.spi_select().ok_or_else(invalid)?;
+    let slot = sequence.spi_write_read(write, read).ok_or_else(invalid)?;
+    sequence.spi_deselect().ok_or_else(invalid)?;
+    Ok(slot)
+}
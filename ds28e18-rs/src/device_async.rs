@@ -0,0 +1,203 @@
+use crate::{
+    COMMAND_START, Configuration, DEVICE_STATUS_CMD, Ds28e18, Error, MAX_CHUNK, OP_TIME_US,
+    PACKET_SIZE, READ_CONFIG_CMD, READ_SEQUENCER_CMD, RELEASE, RUN_SEQUENCER_CMD, Sequence,
+    WRITE_CONFIG_CMD, WRITE_SEQUENCER_CMD, check_packet_crc,
+    device::{check_span, i2c_sequence, read_params, run_params, spi_sequence},
+    packet, parse_response,
+};
+use embedded_hal_async::delay::DelayNs;
+use embedded_onewire::{OneWireAsync, OneWireError, OneWireStrongPullupAsync};
+
+impl<O: OneWireStrongPullupAsync> Ds28e18<O> {
+    async fn execute_async<D: DelayNs>(
+        &mut self,
+        cmd: u8,
+        params: &[u8],
+        data: &[u8],
+        out: &mut [u8],
+        delay: &mut D,
+        op_us: u32,
+    ) -> Result<(), Error<O::BusError>> {
+        let mut buf = [0; PACKET_SIZE];
+        let len = packet(&mut buf, cmd, params, data);
+        OneWireAsync::address(&mut self.bus, self.rom).await?;
+        OneWireAsync::write_byte(&mut self.bus, COMMAND_START).await?;
+        OneWireAsync::write_bytes(&mut self.bus, &buf[..len]).await?;
+        let mut crc = [0; 2];
+        OneWireAsync::read_bytes(&mut self.bus, &mut crc).await?;
+        check_packet_crc(&buf[..len], &crc)?;
+        OneWireStrongPullupAsync::write_byte_powered(&mut self.bus, RELEASE).await?;
+        delay.delay_us(op_us).await;
+        OneWireAsync::read_byte(&mut self.bus).await?;
+        let len = OneWireAsync::read_byte(&mut self.bus).await?;
+        if len as usize > buf.len() {
+            return Err(Error::OneWire(OneWireError::InvalidCrc));
+        }
+        let response = &mut buf[..len as usize];
+        OneWireAsync::read_bytes(&mut self.bus, response).await?;
+        OneWireAsync::read_bytes(&mut self.bus, &mut crc).await?;
+        let data = parse_response(len, response, &crc)?;
+        let n = data.len().min(out.len());
+        out[..n].copy_from_slice(&data[..n]);
+        Ok(())
+    }
+
+    /// Write `data` to the sequencer memory at `address`.
+    ///
+    /// See [`Ds28e18::write_sequencer`].
+    pub async fn write_sequencer_async<D: DelayNs>(
+        &mut self,
+        address: u16,
+        data: &[u8],
+        delay: &mut D,
+    ) -> Result<(), Error<O::BusError>> {
+        check_span(address, data.len())?;
+        for (i, chunk) in data.chunks(MAX_CHUNK).enumerate() {
+            let [lo, hi] = (address + (i * MAX_CHUNK) as u16).to_le_bytes();
+            self.execute_async(
+                WRITE_SEQUENCER_CMD,
+                &[lo, hi & 0x01],
+                chunk,
+                &mut [],
+                delay,
+                OP_TIME_US,
+            )
+            .await?;
+        }
+        Ok(())
+    }
+
+    /// Read the sequencer memory at `address` into `buf`.
+    ///
+    /// See [`Ds28e18::read_sequencer`].
+    pub async fn read_sequencer_async<D: DelayNs>(
+        &mut self,
+        address: u16,
+        buf: &mut [u8],
+        delay: &mut D,
+    ) -> Result<(), Error<O::BusError>> {
+        check_span(address, buf.len())?;
+        for (i, chunk) in buf.chunks_mut(MAX_CHUNK).enumerate() {
+            let params = read_params(address + (i * MAX_CHUNK) as u16, chunk.len());
+            self.execute_async(READ_SEQUENCER_CMD, &params, &[], chunk, delay, OP_TIME_US)
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Run the `len` bytes of commands at `address` of the sequencer memory.
+    ///
+    /// See [`Ds28e18::run_sequencer`].
+    pub async fn run_sequencer_async<D: DelayNs>(
+        &mut self,
+        address: u16,
+        len: usize,
+        delay: &mut D,
+        run_us: u32,
+    ) -> Result<(), Error<O::BusError>> {
+        check_span(address, len)?;
+        let params = run_params(address, len);
+        self.execute_async(RUN_SEQUENCER_CMD, &params, &[], &mut [], delay, run_us)
+            .await
+    }
+
+    /// Write `sequence` at the start of the sequencer memory and run it.
+    ///
+    /// See [`Ds28e18::run`].
+    pub async fn run_async<D: DelayNs>(
+        &mut self,
+        sequence: &Sequence,
+        delay: &mut D,
+    ) -> Result<(), Error<O::BusError>> {
+        let commands = sequence.as_bytes();
+        self.write_sequencer_async(0, commands, delay).await?;
+        self.run_sequencer_async(0, commands.len(), delay, sequence.run_time_us(self.speed))
+            .await
+    }
+
+    /// Write the configuration register.
+    ///
+    /// See [`Ds28e18::write_configuration`].
+    pub async fn write_configuration_async<D: DelayNs>(
+        &mut self,
+        configuration: Configuration,
+        delay: &mut D,
+    ) -> Result<(), Error<O::BusError>> {
+        self.execute_async(
+            WRITE_CONFIG_CMD,
+            &[configuration.into_bits()],
+            &[],
+            &mut [],
+            delay,
+            OP_TIME_US,
+        )
+        .await?;
+        self.speed = configuration.speed();
+        Ok(())
+    }
+
+    /// Read the configuration register.
+    ///
+    /// See [`Ds28e18::configuration`].
+    pub async fn configuration_async<D: DelayNs>(
+        &mut self,
+        delay: &mut D,
+    ) -> Result<Configuration, Error<O::BusError>> {
+        let mut config = [0];
+        self.execute_async(READ_CONFIG_CMD, &[], &[], &mut config, delay, OP_TIME_US)
+            .await?;
+        let config = Configuration::from_bits(config[0]);
+        self.speed = config.speed();
+        Ok(config)
+    }
+
+    /// Read the status of the device.
+    ///
+    /// See [`Ds28e18::device_status`].
+    pub async fn device_status_async<D: DelayNs>(
+        &mut self,
+        delay: &mut D,
+    ) -> Result<u8, Error<O::BusError>> {
+        let mut status = [0];
+        self.execute_async(DEVICE_STATUS_CMD, &[], &[], &mut status, delay, OP_TIME_US)
+            .await?;
+        Ok(status[0])
+    }
+
+    /// Write `write` to the I2C target at the 7-bit `address`, then read `read` from it.
+    ///
+    /// See [`Ds28e18::i2c_write_read`].
+    pub async fn i2c_write_read_async<D: DelayNs>(
+        &mut self,
+        address: u8,
+        write: &[u8],
+        read: &mut [u8],
+        delay: &mut D,
+    ) -> Result<(), Error<O::BusError>> {
+        let mut sequence = Sequence::new();
+        let slot = i2c_sequence(&mut sequence, address, write, read.len())?;
+        self.run_async(&sequence, delay).await?;
+        match slot {
+            Some(slot) => self.read_sequencer_async(slot.offset, read, delay).await,
+            None => Ok(()),
+        }
+    }
+
+    /// Select the SPI target, write `write`, read `read`, and deselect the target.
+    ///
+    /// See [`Ds28e18::spi_write_read`].
+    pub async fn spi_write_read_async<D: DelayNs>(
+        &mut self,
+        write: &[u8],
+        read: &mut [u8],
+        delay: &mut D,
+    ) -> Result<(), Error<O::BusError>> {
+        let mut sequence = Sequence::new();
+        let slot = spi_sequence(&mut sequence, write, read.len())?;
+        self.run_async(&sequence, delay).await?;
+        if read.is_empty() {
+            return Ok(());
+        }
+        self.read_sequencer_async(slot.offset, read, delay).await
+    }
+}
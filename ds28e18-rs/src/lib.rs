@@ -0,0 +1,304 @@
+#![no_std]
+#![deny(missing_docs)]
+#![cfg_attr(docsrs, feature(doc_cfg))]
+#![doc = include_str!("../README.md")]
+
+mod device;
+mod device_async;
+mod sequence;
+
+pub use device::Ds28e18;
+pub use embedded_onewire::{OneWireError, OneWireResult};
+pub use sequence::{MAX_TRANSFER, ReadSlot, Sequence};
+
+use bitfield_struct::bitfield;
+use embedded_onewire::OneWireCrc16;
+
+/// Family code of the DS28E18, the low byte of its ROM.
+pub const FAMILY_CODE: u8 = 0x56;
+/// Size of the sequencer memory.
+pub const SEQUENCER_SIZE: usize = 512;
+
+/// Most bytes of the sequencer memory written or read by a single command.
+pub(crate) const MAX_CHUNK: usize = 128;
+/// Size of the largest packet: the length, command, parameters and a chunk of data.
+pub(crate) const PACKET_SIZE: usize = MAX_CHUNK + 4;
+
+pub(crate) const COMMAND_START: u8 = 0x66;
+pub(crate) const RELEASE: u8 = 0xaa;
+
+pub(crate) const WRITE_SEQUENCER_CMD: u8 = 0x11;
+pub(crate) const READ_SEQUENCER_CMD: u8 = 0x22;
+pub(crate) const RUN_SEQUENCER_CMD: u8 = 0x33;
+pub(crate) const WRITE_CONFIG_CMD: u8 = 0x55;
+pub(crate) const READ_CONFIG_CMD: u8 = 0x6a;
+pub(crate) const DEVICE_STATUS_CMD: u8 = 0x7a;
+
+const RESULT_SUCCESS: u8 = 0xaa;
+const RESULT_POR: u8 = 0x44;
+const RESULT_EXECUTION_ERROR: u8 = 0x55;
+const RESULT_INVALID_PARAMETER: u8 = 0x77;
+const RESULT_NACK: u8 = 0x88;
+
+/// Time for the device to process a command, with the bus at the strong pullup.
+pub(crate) const OP_TIME_US: u32 = 1_000;
+
+/// Speed of the remote I2C or SPI bus.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Speed {
+    /// 100 kHz
+    K100,
+    /// 400 kHz
+    K400,
+    /// 1 MHz
+    K1000,
+    /// 2.3 MHz
+    K2300,
+}
+
+impl Speed {
+    /// The clock frequency in kHz.
+    pub fn khz(&self) -> u32 {
+        match self {
+            Speed::K100 => 100,
+            Speed::K400 => 400,
+            Speed::K1000 => 1000,
+            Speed::K2300 => 2300,
+        }
+    }
+
+    const fn into_bits(self) -> u8 {
+        self as u8
+    }
+
+    const fn from_bits(bits: u8) -> Self {
+        match bits & 0b11 {
+            0 => Speed::K100,
+            1 => Speed::K400,
+            2 => Speed::K1000,
+            _ => Speed::K2300,
+        }
+    }
+}
+
+/// Protocol of the remote bus.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Protocol {
+    /// I2C
+    I2c,
+    /// SPI
+    Spi,
+}
+
+impl Protocol {
+    const fn into_bits(self) -> u8 {
+        self as u8
+    }
+
+    const fn from_bits(bits: u8) -> Self {
+        match bits & 0b11 {
+            1 => Protocol::Spi,
+            _ => Protocol::I2c,
+        }
+    }
+}
+
+/// Clock polarity and phase of the SPI bus.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpiMode {
+    /// CPOL = 0, CPHA = 0
+    Mode0,
+    /// CPOL = 1, CPHA = 1
+    Mode3,
+}
+
+impl SpiMode {
+    const fn into_bits(self) -> u8 {
+        match self {
+            SpiMode::Mode0 => 0b00,
+            SpiMode::Mode3 => 0b11,
+        }
+    }
+
+    const fn from_bits(bits: u8) -> Self {
+        match bits & 0b11 {
+            0b11 => SpiMode::Mode3,
+            _ => SpiMode::Mode0,
+        }
+    }
+}
+
+#[bitfield(u8)]
+#[derive(PartialEq, Eq)]
+/// # Configuration register
+pub struct Configuration {
+    /// SPD: Speed of the remote bus.
+    #[bits(2)]
+    pub speed: Speed,
+    #[bits(1)]
+    __: u8,
+    /// INACK: Continue the sequence when an I2C target does not acknowledge a byte.
+    pub ignore_nack: bool,
+    /// PROT: Protocol of the remote bus.
+    #[bits(2)]
+    pub protocol: Protocol,
+    /// SPI_MODE: Clock polarity and phase of the SPI bus.
+    #[bits(2)]
+    pub spi_mode: SpiMode,
+}
+
+/// Errors of the DS28E18 commands.
+#[derive(Debug)]
+pub enum Error<E> {
+    /// The 1-Wire transfer failed.
+    OneWire(OneWireError<E>),
+    /// The device was reset since its status was last read with
+    /// [`Ds28e18::device_status`], and refuses to run the sequencer.
+    PowerOnReset,
+    /// The device rejected the parameters of the command.
+    InvalidParameter,
+    /// An I2C target did not acknowledge a byte written by the command at this offset of
+    /// the sequence.
+    Nack(u16),
+    /// The sequencer stopped on an invalid command at this offset of the sequence.
+    Execution(u16),
+}
+
+impl<E> From<OneWireError<E>> for Error<E> {
+    fn from(e: OneWireError<E>) -> Self {
+        Error::OneWire(e)
+    }
+}
+
+/// Assemble the packet of a command, starting with its length.
+pub(crate) fn packet(buf: &mut [u8; PACKET_SIZE], cmd: u8, params: &[u8], data: &[u8]) -> usize {
+    let len = 1 + params.len() + data.len();
+    buf[0] = len as u8;
+    buf[1] = cmd;
+    buf[2..2 + params.len()].copy_from_slice(params);
+    buf[2 + params.len()..1 + len].copy_from_slice(data);
+    1 + len
+}
+
+/// Check the CRC-16 sent back by the device for `packet`.
+pub(crate) fn check_packet_crc<E>(packet: &[u8], crc: &[u8; 2]) -> Result<(), Error<E>> {
+    let mut check = OneWireCrc16::default();
+    check.update_slice(packet);
+    check.update_slice(crc);
+    if !check.is_valid() {
+        return Err(Error::OneWire(OneWireError::InvalidCrc));
+    }
+    Ok(())
+}
+
+/// Check the response of the device, made of its length, the result byte and the data, and
+/// return the data.
+pub(crate) fn parse_response<'a, E>(
+    len: u8,
+    response: &'a [u8],
+    crc: &[u8; 2],
+) -> Result<&'a [u8], Error<E>> {
+    let mut check = OneWireCrc16::default();
+    check.update(len);
+    check.update_slice(response);
+    check.update_slice(crc);
+    if response.is_empty() || !check.is_valid() {
+        return Err(Error::OneWire(OneWireError::InvalidCrc));
+    }
+    let data = &response[1..];
+    let offset = || {
+        u16::from_le_bytes([
+            data.first().copied().unwrap_or(0),
+            data.get(1).copied().unwrap_or(0),
+        ])
+    };
+    match response[0] {
+        RESULT_SUCCESS => Ok(data),
+        RESULT_POR => Err(Error::PowerOnReset),
+        RESULT_INVALID_PARAMETER => Err(Error::InvalidParameter),
+        RESULT_NACK => Err(Error::Nack(offset())),
+        RESULT_EXECUTION_ERROR => Err(Error::Execution(offset())),
+        _ => Err(Error::OneWire(OneWireError::VerificationFailed)),
+    }
+}
+
+mod test {
+    #[test]
+    fn test_sequence() {
+        use super::*;
+
+        let mut sequence = Sequence::new();
+        sequence.i2c_start().unwrap().i2c_write(&[0x80]).unwrap();
+        let slot = sequence.i2c_read_nack_end(2).unwrap();
+        sequence.i2c_stop().unwrap();
+        assert_eq!(
+            sequence.as_bytes(),
+            [0x02, 0xe3, 0x01, 0x80, 0xd3, 0x02, 0xff, 0xff, 0x03]
+        );
+        assert_eq!(slot, ReadSlot { offset: 6, len: 2 });
+        assert!(sequence.i2c_write(&[0; 256]).is_none());
+        assert!(sequence.delay(16).is_none());
+        let mut sequence = Sequence::new();
+        assert!(sequence.spi_write_read(&[0; 255], 255).is_none());
+        assert!(sequence.spi_write_read(&[0; 250], 255).is_some());
+        assert!(sequence.spi_write_read(&[], 0).is_none());
+    }
+
+    #[test]
+    fn test_i2c_write_read() {
+        use super::*;
+        use embedded_hal_mock::eh1::delay::NoopDelay;
+        use embedded_onewire::testing::{Mock, Transaction};
+        extern crate std;
+
+        let bus = Mock::new(&[
+            Transaction::reset(true),
+            Transaction::skip_rom(),
+            Transaction::write(&[
+                COMMAND_START,
+                0x13,
+                WRITE_SEQUENCER_CMD,
+                0x00,
+                0x00,
+                0x02,
+                0xe3,
+                0x01,
+                0x80,
+                0xe3,
+                0x01,
+                0xe3,
+                0x02,
+                0xe3,
+                0x01,
+                0x81,
+                0xd3,
+                0x02,
+                0xff,
+                0xff,
+                0x03,
+            ]),
+            Transaction::read(&[0xc7, 0x50]),
+            Transaction::write_powered(RELEASE),
+            Transaction::read(&[0xff, 0x01, 0xaa, 0x7e, 0x10]),
+            Transaction::reset(true),
+            Transaction::skip_rom(),
+            Transaction::write(&[COMMAND_START, 0x04, RUN_SEQUENCER_CMD, 0x00, 0x20, 0x00]),
+            Transaction::read(&[0x18, 0xbb]),
+            Transaction::write_powered(RELEASE),
+            Transaction::read(&[0xff, 0x01, 0xaa, 0x7e, 0x10]),
+            Transaction::reset(true),
+            Transaction::skip_rom(),
+            Transaction::write(&[COMMAND_START, 0x03, READ_SEQUENCER_CMD, 0x0d, 0x04]),
+            Transaction::read(&[0x5a, 0xe2]),
+            Transaction::write_powered(RELEASE),
+            Transaction::read(&[0xff, 0x03, 0xaa, 0x12, 0x34, 0xd2, 0xec]),
+        ]);
+        let mut bridge = Ds28e18::new(bus, None);
+        let mut buf = [0; 2];
+        bridge
+            .i2c_write_read(0x40, &[0xe3], &mut buf, &mut NoopDelay::new())
+            .unwrap();
+        assert_eq!(buf, [0x12, 0x34]);
+        bridge.release().done();
+    }
+}
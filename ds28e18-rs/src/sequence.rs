@@ -0,0 +1,191 @@
+use crate::{SEQUENCER_SIZE, Speed};
+
+pub(crate) const I2C_START: u8 = 0x02;
+pub(crate) const I2C_STOP: u8 = 0x03;
+pub(crate) const I2C_WRITE: u8 = 0xe3;
+pub(crate) const I2C_READ: u8 = 0xd4;
+pub(crate) const I2C_READ_NACK_END: u8 = 0xd3;
+pub(crate) const SPI_WRITE_READ: u8 = 0xc0;
+pub(crate) const SPI_SS_HIGH: u8 = 0x01;
+pub(crate) const SPI_SS_LOW: u8 = 0x80;
+pub(crate) const DELAY: u8 = 0xdd;
+pub(crate) const SENS_VDD_ON: u8 = 0xcc;
+pub(crate) const SENS_VDD_OFF: u8 = 0xbb;
+
+/// Largest number of bytes a single sequencer command writes or reads.
+pub const MAX_TRANSFER: usize = 255;
+
+/// Where the data read by a sequencer command lands in the sequencer memory once the
+/// sequence ran.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReadSlot {
+    /// Offset of the data from the start of the sequence.
+    pub offset: u16,
+    /// Number of bytes read.
+    pub len: u16,
+}
+
+/// A sequence of commands for the sequencer of the DS28E18.
+///
+/// The sequence is written to the sequencer memory and run with
+/// [`Ds28e18::run_sequencer`](crate::Ds28e18::run_sequencer). The commands that read
+/// reserve room in the sequence, which the device fills with the data read: the returned
+/// [`ReadSlot`] locates it.
+///
+/// The builder methods return [`None`] if the command does not fit in the sequencer memory
+/// or transfers too many bytes.
+#[derive(Debug, Clone)]
+pub struct Sequence {
+    buf: [u8; SEQUENCER_SIZE],
+    len: usize,
+    /// Bytes clocked on the remote bus, to estimate the time the sequence takes.
+    transferred: usize,
+    /// Time spent in delay commands.
+    delay_ms: u32,
+}
+
+impl Default for Sequence {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Sequence {
+    /// An empty sequence.
+    pub fn new() -> Self {
+        Sequence {
+            buf: [0; SEQUENCER_SIZE],
+            len: 0,
+            transferred: 0,
+            delay_ms: 0,
+        }
+    }
+
+    /// The commands of the sequence.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.buf[..self.len]
+    }
+
+    /// Whether the sequence has no command.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Remove all the commands.
+    pub fn clear(&mut self) {
+        *self = Self::new();
+    }
+
+    /// An estimate of the time the sequence takes to run at `speed`, in microseconds.
+    pub fn run_time_us(&self, speed: Speed) -> u32 {
+        // Nine clock cycles per byte on the I2C bus, and some slack for the sequencer
+        let clocks = self.transferred as u32 * 9 + self.len as u32;
+        1_000 + clocks * 1_000 / speed.khz() + self.delay_ms * 1_000
+    }
+
+    fn push(&mut self, bytes: &[&[u8]], reserve: usize) -> Option<u16> {
+        let len: usize = bytes.iter().map(|b| b.len()).sum();
+        if self.len + len + reserve > SEQUENCER_SIZE {
+            return None;
+        }
+        for b in bytes {
+            self.buf[self.len..self.len + b.len()].copy_from_slice(b);
+            self.len += b.len();
+        }
+        let offset = self.len as u16;
+        self.buf[self.len..self.len + reserve].fill(0xff);
+        self.len += reserve;
+        Some(offset)
+    }
+
+    fn transfer_len(len: usize) -> Option<u8> {
+        (1..=MAX_TRANSFER).contains(&len).then_some(len as u8)
+    }
+
+    /// Issue an I2C start condition, or a repeated start.
+    pub fn i2c_start(&mut self) -> Option<&mut Self> {
+        self.push(&[&[I2C_START]], 0)?;
+        Some(self)
+    }
+
+    /// Issue an I2C stop condition.
+    pub fn i2c_stop(&mut self) -> Option<&mut Self> {
+        self.push(&[&[I2C_STOP]], 0)?;
+        Some(self)
+    }
+
+    /// Write `data` on the I2C bus, including the address byte after a start.
+    pub fn i2c_write(&mut self, data: &[u8]) -> Option<&mut Self> {
+        let len = Self::transfer_len(data.len())?;
+        self.push(&[&[I2C_WRITE, len], data], 0)?;
+        self.transferred += data.len();
+        Some(self)
+    }
+
+    /// Read `len` bytes on the I2C bus, acknowledging them all.
+    pub fn i2c_read(&mut self, len: usize) -> Option<ReadSlot> {
+        self.read(I2C_READ, len)
+    }
+
+    /// Read `len` bytes on the I2C bus, leaving the last one unacknowledged as expected
+    /// before a stop.
+    pub fn i2c_read_nack_end(&mut self, len: usize) -> Option<ReadSlot> {
+        self.read(I2C_READ_NACK_END, len)
+    }
+
+    fn read(&mut self, cmd: u8, len: usize) -> Option<ReadSlot> {
+        let n = Self::transfer_len(len)?;
+        let offset = self.push(&[&[cmd, n]], len)?;
+        self.transferred += len;
+        Some(ReadSlot {
+            offset,
+            len: len as u16,
+        })
+    }
+
+    /// Drive the SPI slave select low.
+    pub fn spi_select(&mut self) -> Option<&mut Self> {
+        self.push(&[&[SPI_SS_LOW]], 0)?;
+        Some(self)
+    }
+
+    /// Drive the SPI slave select high.
+    pub fn spi_deselect(&mut self) -> Option<&mut Self> {
+        self.push(&[&[SPI_SS_HIGH]], 0)?;
+        Some(self)
+    }
+
+    /// Write `write` on the SPI bus, then read `read` bytes.
+    ///
+    /// The slot is empty if nothing is read.
+    pub fn spi_write_read(&mut self, write: &[u8], read: usize) -> Option<ReadSlot> {
+        if write.len() > MAX_TRANSFER || read > MAX_TRANSFER || write.len() + read == 0 {
+            return None;
+        }
+        let offset = self.push(
+            &[&[SPI_WRITE_READ, write.len() as u8, read as u8], write],
+            read,
+        )?;
+        self.transferred += write.len() + read;
+        Some(ReadSlot {
+            offset,
+            len: read as u16,
+        })
+    }
+
+    /// Wait for 2<sup>`exponent`</sup> ms, up to 32768 ms.
+    pub fn delay(&mut self, exponent: u8) -> Option<&mut Self> {
+        if exponent > 15 {
+            return None;
+        }
+        self.push(&[&[DELAY, exponent]], 0)?;
+        self.delay_ms += 1 << exponent;
+        Some(self)
+    }
+
+    /// Turn the supply of the remote sensors on or off.
+    pub fn sens_vdd(&mut self, on: bool) -> Option<&mut Self> {
+        self.push(&[&[if on { SENS_VDD_ON } else { SENS_VDD_OFF }]], 0)?;
+        Some(self)
+    }
+}
@@ -0,0 +1,147 @@
+use crate::{DATA_PAGES, EXTENDED_READ_MEMORY_CMD, LAYOUT, MEMORY_END, PAGE_SIZE};
+use embedded_hal::delay::DelayNs;
+use embedded_onewire::{OneWire, OneWireCrc16, OneWireError, OneWireResult, OneWireStrongPullup};
+use onewire_eeprom::OneWireEeprom;
+
+/// A DS28EC20 20Kb EEPROM on a 1-Wire bus.
+///
+/// The driver owns the bus master, which can be a `&mut` reference to one or a handle to a
+/// shared one. Every operation addresses the device first.
+#[derive(Debug)]
+pub struct Ds28ec20<O> {
+    pub(crate) bus: O,
+    pub(crate) rom: Option<u64>,
+}
+
+impl<O> Ds28ec20<O> {
+    /// Creates a driver for the device with ROM `rom` on `bus`.
+    ///
+    /// Pass [`None`] to skip ROM addressing on a bus with a single device.
+    pub fn new(bus: O, rom: Option<u64>) -> Self {
+        Ds28ec20 { bus, rom }
+    }
+
+    /// ROM of the device, if it is addressed by ROM.
+    pub fn rom(&self) -> Option<u64> {
+        self.rom
+    }
+
+    /// Release the bus master.
+    pub fn release(self) -> O {
+        self.bus
+    }
+}
+
+impl<O: OneWire> Ds28ec20<O> {
+    /// Read the memory from `address` into `buf`, with the Read Memory command.
+    ///
+    /// The data memory and the register page can be read, up to address `0x0a1f`. The
+    /// transfer is not checked, see [`Ds28ec20::read_checked`].
+    ///
+    /// # Errors
+    /// Returns [`OneWireError::InvalidValue`] if the read goes past the end of the memory.
+    pub fn read(&mut self, address: u16, buf: &mut [u8]) -> OneWireResult<(), O::BusError> {
+        LAYOUT.read(&mut self.bus, self.rom, address, buf)
+    }
+
+    /// Read the memory from `address` into `buf`, with the Extended Read Memory command.
+    ///
+    /// The device sends the CRC-16 of every page it reads, so the read always continues
+    /// to the end of the last page touched by `buf`.
+    ///
+    /// # Errors
+    /// Returns [`OneWireError::InvalidValue`] if the read goes past the end of the memory,
+    /// and [`OneWireError::InvalidCrc`] if the CRC-16 of a page does not match.
+    pub fn read_checked(&mut self, address: u16, buf: &mut [u8]) -> OneWireResult<(), O::BusError> {
+        if address as usize + buf.len() > MEMORY_END {
+            return Err(OneWireError::InvalidValue("address"));
+        }
+        let [lo, hi] = address.to_le_bytes();
+        let cmd = [EXTENDED_READ_MEMORY_CMD, lo, hi];
+        self.bus.address(self.rom)?;
+        self.bus.write_bytes(&cmd)?;
+        // The CRC of the first page includes the command and the address
+        let mut crc = OneWireCrc16::default();
+        crc.update_slice(&cmd);
+        let mut offset = address as usize % PAGE_SIZE;
+        let mut buf = buf;
+        while !buf.is_empty() {
+            let mut page = [0; PAGE_SIZE];
+            let page = &mut page[offset..];
+            self.bus.read_bytes(page)?;
+            let mut check = [0; 2];
+            self.bus.read_bytes(&mut check)?;
+            crc.update_slice(page);
+            crc.update_slice(&check);
+            if !crc.is_valid() {
+                return Err(OneWireError::InvalidCrc);
+            }
+            let len = page.len().min(buf.len());
+            buf[..len].copy_from_slice(&page[..len]);
+            buf = &mut buf[len..];
+            crc = OneWireCrc16::default();
+            offset = 0;
+        }
+        Ok(())
+    }
+}
+
+impl<O: OneWireStrongPullup> Ds28ec20<O> {
+    /// Program the page at `address` with `page`.
+    ///
+    /// The bus is held at the strong pullup while the page is copied from the scratchpad to
+    /// the memory.
+    ///
+    /// # Errors
+    /// Returns [`OneWireError::InvalidValue`] if `address` is not the start of a page,
+    /// [`OneWireError::InvalidCrc`] if a transfer of the scratchpad is corrupted, and
+    /// [`OneWireError::VerificationFailed`] if the scratchpad read back does not match, e.g.
+    /// because the page is write-protected, or the device does not confirm the copy.
+    pub fn write_page<D: DelayNs>(
+        &mut self,
+        address: u16,
+        page: &[u8; PAGE_SIZE],
+        delay: &mut D,
+    ) -> OneWireResult<(), O::BusError> {
+        LAYOUT.write_block(&mut self.bus, self.rom, address, page, delay)
+    }
+
+    /// Write `data` to the memory at `address`.
+    ///
+    /// Every page touched by `data` is programmed in full: pages that are only partially
+    /// covered are read first, so that the rest of their bytes are written back unchanged.
+    ///
+    /// # Errors
+    /// Returns [`OneWireError::InvalidValue`] if the write goes past the end of the memory,
+    /// and the errors of [`Ds28ec20::write_page`]. The pages before the failed one are
+    /// already programmed.
+    pub fn write<D: DelayNs>(
+        &mut self,
+        address: u16,
+        data: &[u8],
+        delay: &mut D,
+    ) -> OneWireResult<(), O::BusError> {
+        LAYOUT.write(&mut self.bus, self.rom, address, data, delay)
+    }
+}
+
+impl<O: OneWireStrongPullup> OneWireEeprom for Ds28ec20<O> {
+    type BusError = O::BusError;
+
+    const CAPACITY: usize = DATA_PAGES * PAGE_SIZE;
+    const PAGE_SIZE: usize = PAGE_SIZE;
+    const SCRATCHPAD_SIZE: usize = PAGE_SIZE;
+
+    fn read(&mut self, address: u16, buf: &mut [u8]) -> OneWireResult<(), Self::BusError> {
+        Ds28ec20::read(self, address, buf)
+    }
+
+    fn write<D: DelayNs>(
+        &mut self,
+        address: u16,
+        data: &[u8],
+        delay: &mut D,
+    ) -> OneWireResult<(), Self::BusError> {
+        Ds28ec20::write(self, address, data, delay)
+    }
+}
@@ -0,0 +1,111 @@
+use crate::{DATA_PAGES, Ds28ec20, EXTENDED_READ_MEMORY_CMD, LAYOUT, MEMORY_END, PAGE_SIZE};
+use embedded_hal_async::delay::DelayNs;
+use embedded_onewire::{
+    OneWireAsync, OneWireCrc16, OneWireError, OneWireResult, OneWireStrongPullupAsync,
+};
+use onewire_eeprom::OneWireEepromAsync;
+
+impl<O: OneWireAsync> Ds28ec20<O> {
+    /// Read the memory from `address` into `buf`, with the Read Memory command.
+    ///
+    /// See [`Ds28ec20::read`].
+    pub async fn read_async(
+        &mut self,
+        address: u16,
+        buf: &mut [u8],
+    ) -> OneWireResult<(), O::BusError> {
+        LAYOUT
+            .read_async(&mut self.bus, self.rom, address, buf)
+            .await
+    }
+
+    /// Read the memory from `address` into `buf`, with the Extended Read Memory command.
+    ///
+    /// See [`Ds28ec20::read_checked`].
+    pub async fn read_checked_async(
+        &mut self,
+        address: u16,
+        buf: &mut [u8],
+    ) -> OneWireResult<(), O::BusError> {
+        if address as usize + buf.len() > MEMORY_END {
+            return Err(OneWireError::InvalidValue("address"));
+        }
+        let [lo, hi] = address.to_le_bytes();
+        let cmd = [EXTENDED_READ_MEMORY_CMD, lo, hi];
+        OneWireAsync::address(&mut self.bus, self.rom).await?;
+        OneWireAsync::write_bytes(&mut self.bus, &cmd).await?;
+        let mut crc = OneWireCrc16::default();
+        crc.update_slice(&cmd);
+        let mut offset = address as usize % PAGE_SIZE;
+        let mut buf = buf;
+        while !buf.is_empty() {
+            let mut page = [0; PAGE_SIZE];
+            let page = &mut page[offset..];
+            OneWireAsync::read_bytes(&mut self.bus, page).await?;
+            let mut check = [0; 2];
+            OneWireAsync::read_bytes(&mut self.bus, &mut check).await?;
+            crc.update_slice(page);
+            crc.update_slice(&check);
+            if !crc.is_valid() {
+                return Err(OneWireError::InvalidCrc);
+            }
+            let len = page.len().min(buf.len());
+            buf[..len].copy_from_slice(&page[..len]);
+            buf = &mut buf[len..];
+            crc = OneWireCrc16::default();
+            offset = 0;
+        }
+        Ok(())
+    }
+}
+
+impl<O: OneWireStrongPullupAsync> Ds28ec20<O> {
+    /// Program the page at `address` with `page`.
+    ///
+    /// See [`Ds28ec20::write_page`].
+    pub async fn write_page_async<D: DelayNs>(
+        &mut self,
+        address: u16,
+        page: &[u8; PAGE_SIZE],
+        delay: &mut D,
+    ) -> OneWireResult<(), O::BusError> {
+        LAYOUT
+            .write_block_async(&mut self.bus, self.rom, address, page, delay)
+            .await
+    }
+
+    /// Write `data` to the memory at `address`.
+    ///
+    /// See [`Ds28ec20::write`].
+    pub async fn write_async<D: DelayNs>(
+        &mut self,
+        address: u16,
+        data: &[u8],
+        delay: &mut D,
+    ) -> OneWireResult<(), O::BusError> {
+        LAYOUT
+            .write_async(&mut self.bus, self.rom, address, data, delay)
+            .await
+    }
+}
+
+impl<O: OneWireStrongPullupAsync> OneWireEepromAsync for Ds28ec20<O> {
+    type BusError = O::BusError;
+
+    const CAPACITY: usize = DATA_PAGES * PAGE_SIZE;
+    const PAGE_SIZE: usize = PAGE_SIZE;
+    const SCRATCHPAD_SIZE: usize = PAGE_SIZE;
+
+    async fn read(&mut self, address: u16, buf: &mut [u8]) -> OneWireResult<(), Self::BusError> {
+        self.read_async(address, buf).await
+    }
+
+    async fn write<D: DelayNs>(
+        &mut self,
+        address: u16,
+        data: &[u8],
+        delay: &mut D,
+    ) -> OneWireResult<(), Self::BusError> {
+        self.write_async(address, data, delay).await
+    }
+}
@@ -0,0 +1,103 @@
+#![no_std]
+#![deny(missing_docs)]
+#![cfg_attr(docsrs, feature(doc_cfg))]
+#![doc = include_str!("../README.md")]
+
+mod device;
+mod device_async;
+
+pub use device::Ds28ec20;
+pub use embedded_onewire::{OneWireError, OneWireResult};
+
+use onewire_eeprom::Layout;
+
+/// Family code of the DS28EC20, the low byte of its ROM.
+pub const FAMILY_CODE: u8 = 0x43;
+
+/// Size of a page of the memory, and of the scratchpad.
+pub const PAGE_SIZE: usize = 32;
+
+/// Number of pages of the data memory.
+pub const DATA_PAGES: usize = 80;
+
+/// End of the memory: the data memory and the register page.
+pub(crate) const MEMORY_END: usize = (DATA_PAGES + 1) * PAGE_SIZE;
+
+/// Programming time of a page, in microseconds.
+pub(crate) const PROG_TIME_US: u32 = 10_000;
+
+/// Layout of the memory for the scratchpad engine.
+pub(crate) const LAYOUT: Layout<PAGE_SIZE> = Layout::new(MEMORY_END, MEMORY_END, PROG_TIME_US);
+
+pub(crate) const EXTENDED_READ_MEMORY_CMD: u8 = 0xa5;
+
+mod test {
+    #[test]
+    fn test_read_checked() {
+        use super::*;
+        use embedded_onewire::testing::{Mock, Transaction};
+        extern crate std;
+
+        let page: std::vec::Vec<u8> = (0..32).collect();
+        let bus = Mock::new(&[
+            Transaction::reset(true),
+            Transaction::skip_rom(),
+            Transaction::write(&[EXTENDED_READ_MEMORY_CMD, 0x10, 0x00]),
+            Transaction::read(&page[16..]),
+            Transaction::read(&[0x2e, 0x85]),
+            // The second page is read to its end for its CRC
+            Transaction::read(&page),
+            Transaction::read(&[0x95, 0x3c]),
+            Transaction::reset(true),
+            Transaction::skip_rom(),
+            Transaction::write(&[EXTENDED_READ_MEMORY_CMD, 0x20, 0x00]),
+            Transaction::read(&page),
+            Transaction::read(&[0x95, 0x3d]),
+        ]);
+        let mut eeprom = Ds28ec20::new(bus, None);
+        let mut buf = [0; 20];
+        eeprom.read_checked(0x10, &mut buf).unwrap();
+        assert_eq!(&buf[..16], &page[16..]);
+        assert_eq!(&buf[16..], &page[..4]);
+        assert!(matches!(
+            eeprom.read_checked(0x20, &mut buf),
+            Err(OneWireError::InvalidCrc)
+        ));
+        assert!(matches!(
+            eeprom.read_checked(0x0a10, &mut buf),
+            Err(OneWireError::InvalidValue(_))
+        ));
+        eeprom.release().done();
+    }
+
+    #[test]
+    fn test_write_page() {
+        use super::*;
+        use embedded_hal_mock::eh1::delay::NoopDelay;
+        use embedded_onewire::testing::{Mock, Transaction};
+        extern crate std;
+
+        let page: std::vec::Vec<u8> = (0..32).collect();
+        let bus = Mock::new(&[
+            Transaction::reset(true),
+            Transaction::skip_rom(),
+            Transaction::write(&[0x0f, 0x20, 0x00]),
+            Transaction::write(&page),
+            Transaction::read(&[0x33, 0x5d]),
+            Transaction::reset(true),
+            Transaction::skip_rom(),
+            Transaction::write(&[0xaa]),
+            Transaction::read(&[0x20, 0x00, 0x1f]),
+            Transaction::read(&page),
+            Transaction::read(&[0x03, 0x30]),
+            Transaction::reset(true),
+            Transaction::skip_rom(),
+            Transaction::write(&[0x55, 0x20, 0x00]),
+            Transaction::write_powered(0x1f),
+            Transaction::read(&[0xaa]),
+        ]);
+        let mut eeprom = Ds28ec20::new(bus, None);
+        eeprom.write(0x20, &page, &mut NoopDelay::new()).unwrap();
+        eeprom.release().done();
+    }
+}
@@ -23,6 +23,8 @@ pub enum OneWireError<E> {
     InvalidCrc,
     /// Invalid value
     InvalidValue(&'static str),
+    /// Data read back from a device does not match the data written to it.
+    VerificationFailed,
 }
 
 impl<E> From<E> for OneWireError<E> {
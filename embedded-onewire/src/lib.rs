@@ -7,15 +7,18 @@ pub mod consts;
 mod error;
 mod search;
 mod search_async;
+#[cfg(feature = "testing")]
+#[cfg_attr(docsrs, doc(cfg(feature = "testing")))]
+pub mod testing;
 mod traits;
 mod traits_async;
 mod utils;
 pub use error::OneWireError;
 pub use search::{OneWireSearch, OneWireSearchKind};
 pub use search_async::OneWireSearchAsync;
-pub use traits::{OneWire, OneWireStatus};
-pub use traits_async::OneWireAsync;
-pub use utils::OneWireCrc;
+pub use traits::{OneWire, OneWireProgramPulse, OneWireStatus, OneWireStrongPullup};
+pub use traits_async::{OneWireAsync, OneWireProgramPulseAsync, OneWireStrongPullupAsync};
+pub use utils::{OneWireCrc, OneWireCrc16};
 
 /// Error type for 1-Wire operations.
 pub type OneWireResult<T, E> = Result<T, OneWireError<E>>;
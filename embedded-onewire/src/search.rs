@@ -126,12 +126,18 @@ impl<T: OneWire> OneWireSearch<'_, T> {
         // Search ROM command
         self.onewire.write_byte(self.cmd)?;
         let res = loop {
-            // Read the id_bit and the complement_bit using triplet if available
-            // and if this is not the first spin of the loop.
+            // Direction to take if both the id_bit and the complement_bit are 0,
+            // from the ROM found in the previous pass
+            let idir = if id_bit_num < self.last_discrepancy {
+                self.rom[idx] & rom_mask > 0
+            } else {
+                id_bit_num == self.last_discrepancy
+            };
+            // Read the id_bit and the complement_bit using triplet if available.
             // If triplet is not implemented, fallback to reading bits, and let
             // the write flag indicate if we need to write the direction bit later.
             #[cfg(feature = "triplet-read")]
-            let (id_bit, complement_bit, dir) = { self.onewire.read_triplet()? };
+            let (id_bit, complement_bit, dir) = { self.onewire.read_triplet(idir)? };
             #[cfg(not(feature = "triplet-read"))]
             let (id_bit, complement_bit) = {
                 let id_bit = self.onewire.read_bit()?;
@@ -146,32 +152,17 @@ impl<T: OneWire> OneWireSearch<'_, T> {
                 // The bits are different, use the id_bit
                 id_bit
             } else {
-                #[cfg(not(feature = "triplet-read"))]
-                {
-                    // Both bits are 0, use the direction from the ROM
-                    let idir = if id_bit_num < self.last_discrepancy {
-                        self.rom[idx] & rom_mask > 0
-                    } else {
-                        id_bit_num == self.last_discrepancy
-                    };
-                    if !idir {
-                        last_zero = id_bit_num;
-                        if last_zero < 9 {
-                            self.last_family_discrepancy = last_zero;
-                        }
-                    }
-                    idir
-                }
+                // Both bits are 0, use the direction from the ROM, as taken by the
+                // bus master if triplet is implemented
                 #[cfg(feature = "triplet-read")]
-                {
-                    if !dir {
-                        last_zero = id_bit_num;
-                        if last_zero < 9 {
-                            self.last_family_discrepancy = last_zero;
-                        }
+                let idir = dir;
+                if !idir {
+                    last_zero = id_bit_num;
+                    if last_zero < 9 {
+                        self.last_family_discrepancy = last_zero;
                     }
-                    dir
                 }
+                idir
             };
             if set {
                 self.rom[idx] |= rom_mask; // Set the bit in the ROM
@@ -0,0 +1,260 @@
+//! A scripted 1-Wire bus for testing device drivers.
+//!
+//! [`Mock`] implements [`OneWire`] and [`OneWireStrongPullup`] and checks every operation
+//! against a list of expected [`Transaction`]s, in the manner of
+//! [`embedded-hal-mock`](https://crates.io/crates/embedded-hal-mock).
+//!
+//! ```rust
+//! use embedded_onewire::{OneWire, testing::{Mock, Transaction}};
+//!
+//! let mut bus = Mock::new(&[
+//!     Transaction::reset(true),
+//!     Transaction::skip_rom(),
+//!     Transaction::write(&[0xbe]),
+//!     Transaction::read(&[0x50, 0x05]),
+//! ]);
+//! bus.address(None).unwrap();
+//! bus.write_byte(0xbe).unwrap();
+//! assert_eq!(bus.read_byte().unwrap(), 0x50);
+//! assert_eq!(bus.read_byte().unwrap(), 0x05);
+//! bus.done();
+//! ```
+extern crate alloc;
+
+use crate::{
+    OneWire, OneWireError, OneWireProgramPulse, OneWireResult, OneWireStatus, OneWireStrongPullup,
+    consts::{ONEWIRE_MATCH_ROM_CMD, ONEWIRE_SKIP_ROM_CMD},
+};
+use alloc::{collections::VecDeque, vec::Vec};
+use core::convert::Infallible;
+
+/// An operation expected on the [`Mock`] bus.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Transaction {
+    /// A reset, answered with a presence pulse if `true`.
+    Reset(bool),
+    /// A reset that finds the bus held low, by a short circuit or a device signaling an
+    /// interrupt.
+    ShortCircuit,
+    /// Bytes written with consecutive [`OneWire::write_byte`] calls.
+    Write(Vec<u8>),
+    /// A byte written with [`OneWireStrongPullup::write_byte_powered`].
+    WritePowered(u8),
+    /// A programming pulse applied with [`OneWireProgramPulse::program_pulse`].
+    ProgramPulse,
+    /// Bytes returned by consecutive [`OneWire::read_byte`] calls.
+    Read(Vec<u8>),
+    /// A bit written with [`OneWire::write_bit`].
+    WriteBit(bool),
+    /// A bit returned by [`OneWire::read_bit`].
+    ReadBit(bool),
+}
+
+impl Transaction {
+    /// A reset, answered with a presence pulse if `presence` is true.
+    pub fn reset(presence: bool) -> Self {
+        Transaction::Reset(presence)
+    }
+
+    /// A reset that finds the bus held low.
+    pub fn short_circuit() -> Self {
+        Transaction::ShortCircuit
+    }
+
+    /// Bytes written to the bus.
+    pub fn write(bytes: &[u8]) -> Self {
+        Transaction::Write(bytes.to_vec())
+    }
+
+    /// A byte written to the bus, followed by a strong pullup.
+    pub fn write_powered(byte: u8) -> Self {
+        Transaction::WritePowered(byte)
+    }
+
+    /// A programming pulse applied to the bus.
+    pub fn program_pulse() -> Self {
+        Transaction::ProgramPulse
+    }
+
+    /// Bytes read from the bus.
+    pub fn read(bytes: &[u8]) -> Self {
+        Transaction::Read(bytes.to_vec())
+    }
+
+    /// A bit written to the bus.
+    pub fn write_bit(bit: bool) -> Self {
+        Transaction::WriteBit(bit)
+    }
+
+    /// A bit read from the bus.
+    pub fn read_bit(bit: bool) -> Self {
+        Transaction::ReadBit(bit)
+    }
+
+    /// The Skip ROM command written by [`OneWire::address`] after its reset.
+    pub fn skip_rom() -> Self {
+        Transaction::Write([ONEWIRE_SKIP_ROM_CMD].to_vec())
+    }
+
+    /// The Match ROM command and ROM written by [`OneWire::address`] after its reset.
+    pub fn match_rom(rom: u64) -> Self {
+        let mut bytes = [ONEWIRE_MATCH_ROM_CMD].to_vec();
+        bytes.extend_from_slice(&rom.to_le_bytes());
+        Transaction::Write(bytes)
+    }
+}
+
+/// Status reported by a reset of the [`Mock`] bus.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MockStatus {
+    presence: bool,
+}
+
+impl OneWireStatus for MockStatus {
+    fn presence(&self) -> bool {
+        self.presence
+    }
+
+    fn shortcircuit(&self) -> bool {
+        false
+    }
+}
+
+/// A 1-Wire bus that checks the operations performed against a list of expectations.
+///
+/// Every operation panics if it does not match the next expected [`Transaction`]. A reset
+/// without a presence pulse fails with [`OneWireError::NoDevicePresent`]. The overdrive
+/// mode is only recorded.
+#[derive(Debug)]
+pub struct Mock {
+    expected: VecDeque<Transaction>,
+    offset: usize,
+    overdrive: bool,
+}
+
+impl Mock {
+    /// Creates a bus expecting `expected`, in order.
+    pub fn new(expected: &[Transaction]) -> Self {
+        Mock {
+            expected: expected.iter().cloned().collect(),
+            offset: 0,
+            overdrive: false,
+        }
+    }
+
+    /// Checks that every expected transaction has been performed.
+    pub fn done(&mut self) {
+        assert!(
+            self.expected.is_empty(),
+            "transactions not performed: {:?}",
+            self.expected
+        );
+    }
+
+    fn next(&mut self, op: &str) -> Transaction {
+        match self.expected.pop_front() {
+            Some(t) => t,
+            None => panic!("unexpected {op}, no more transactions expected"),
+        }
+    }
+
+    /// Next byte of a multi-byte transaction, `write` selecting the kind.
+    fn next_byte(&mut self, write: bool) -> u8 {
+        let (bytes, kind) = match self.expected.front() {
+            Some(Transaction::Write(bytes)) if write => (bytes, "write"),
+            Some(Transaction::Read(bytes)) if !write => (bytes, "read"),
+            other => panic!(
+                "unexpected byte {}, expected {other:?}",
+                if write { "write" } else { "read" }
+            ),
+        };
+        let Some(&byte) = bytes.get(self.offset) else {
+            panic!("empty {kind} transaction");
+        };
+        self.offset += 1;
+        if self.offset == bytes.len() {
+            self.offset = 0;
+            self.expected.pop_front();
+        }
+        byte
+    }
+}
+
+impl OneWire for Mock {
+    type Status = MockStatus;
+
+    type BusError = Infallible;
+
+    fn reset(&mut self) -> OneWireResult<Self::Status, Self::BusError> {
+        match self.next("reset") {
+            Transaction::Reset(true) => Ok(MockStatus { presence: true }),
+            Transaction::Reset(false) => Err(OneWireError::NoDevicePresent),
+            Transaction::ShortCircuit => Err(OneWireError::ShortCircuit),
+            other => panic!("unexpected reset, expected {other:?}"),
+        }
+    }
+
+    fn write_byte(&mut self, byte: u8) -> OneWireResult<(), Self::BusError> {
+        let expected = self.next_byte(true);
+        assert_eq!(byte, expected, "unexpected byte written");
+        Ok(())
+    }
+
+    fn read_byte(&mut self) -> OneWireResult<u8, Self::BusError> {
+        Ok(self.next_byte(false))
+    }
+
+    fn write_bit(&mut self, bit: bool) -> OneWireResult<(), Self::BusError> {
+        match self.next("bit write") {
+            Transaction::WriteBit(expected) => assert_eq!(bit, expected, "unexpected bit written"),
+            other => panic!("unexpected bit write, expected {other:?}"),
+        }
+        Ok(())
+    }
+
+    fn read_bit(&mut self) -> OneWireResult<bool, Self::BusError> {
+        match self.next("bit read") {
+            Transaction::ReadBit(bit) => Ok(bit),
+            other => panic!("unexpected bit read, expected {other:?}"),
+        }
+    }
+
+    #[cfg(feature = "triplet-read")]
+    fn read_triplet(
+        &mut self,
+        _direction: bool,
+    ) -> OneWireResult<(bool, bool, bool), Self::BusError> {
+        Err(OneWireError::Unimplemented)
+    }
+
+    fn get_overdrive_mode(&mut self) -> bool {
+        self.overdrive
+    }
+
+    fn set_overdrive_mode(&mut self, enable: bool) -> OneWireResult<(), Self::BusError> {
+        self.overdrive = enable;
+        Ok(())
+    }
+}
+
+impl OneWireStrongPullup for Mock {
+    fn write_byte_powered(&mut self, byte: u8) -> OneWireResult<(), Self::BusError> {
+        match self.next("powered write") {
+            Transaction::WritePowered(expected) => {
+                assert_eq!(byte, expected, "unexpected byte written")
+            }
+            other => panic!("unexpected powered write, expected {other:?}"),
+        }
+        Ok(())
+    }
+}
+
+impl OneWireProgramPulse for Mock {
+    fn program_pulse(&mut self) -> OneWireResult<(), Self::BusError> {
+        match self.next("programming pulse") {
+            Transaction::ProgramPulse => {}
+            other => panic!("unexpected programming pulse, expected {other:?}"),
+        }
+        Ok(())
+    }
+}
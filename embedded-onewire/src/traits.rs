@@ -64,9 +64,7 @@ pub trait OneWire {
         self.reset()?; // Reset the bus before addressing
         self.write_byte(cmd)?; // Send the match ROM command
         if let Some(rom) = rom {
-            for &b in rom.to_le_bytes().iter() {
-                self.write_byte(b)?; // Write each byte of the ROM address
-            }
+            self.write_bytes(&rom.to_le_bytes())?; // Write the ROM address
         }
         Ok(())
     }
@@ -95,6 +93,40 @@ pub trait OneWire {
     /// This method returns an error if the read operation fails.
     fn read_byte(&mut self) -> OneWireResult<u8, Self::BusError>;
 
+    /// Writes a sequence of bytes to the device addressed using [`OneWire::address`] on the 1-Wire bus.
+    ///
+    /// The default implementation calls [`OneWire::write_byte`] for every byte. Bus masters that can
+    /// schedule multi-byte transfers more efficiently should override this method.
+    ///
+    /// # Arguments
+    /// * `bytes` - The bytes to write to the bus.
+    ///
+    /// # Errors
+    /// This method returns an error if any of the write operations fail.
+    fn write_bytes(&mut self, bytes: &[u8]) -> OneWireResult<(), Self::BusError> {
+        for &byte in bytes.iter() {
+            self.write_byte(byte)?;
+        }
+        Ok(())
+    }
+
+    /// Reads a sequence of bytes from the device addressed using [`OneWire::address`] on the 1-Wire bus.
+    ///
+    /// The default implementation calls [`OneWire::read_byte`] for every byte. Bus masters that can
+    /// schedule multi-byte transfers more efficiently should override this method.
+    ///
+    /// # Arguments
+    /// * `buf` - The buffer to fill with the bytes read from the bus.
+    ///
+    /// # Errors
+    /// This method returns an error if any of the read operations fail.
+    fn read_bytes(&mut self, buf: &mut [u8]) -> OneWireResult<(), Self::BusError> {
+        for byte in buf.iter_mut() {
+            *byte = self.read_byte()?;
+        }
+        Ok(())
+    }
+
     /// Write a single bit to the device addressed using [`OneWire::address`] on the 1-Wire bus.
     /// Multiple bits can be written in succession after addressing the device.
     /// # Arguments
@@ -133,18 +165,22 @@ pub trait OneWire {
     ///
     ///
     /// # Arguments
-    /// * `direction` - A boolean indicating the direction of the search. If true, the search is in the forward direction; if false, it is in the backward direction.
+    /// * `direction` - The direction V to take if both read time slots are 0.
     ///
     /// # Returns
-    /// A result containing a tuple of two booleans:
+    /// A result containing a tuple of three booleans:
     /// * The first boolean indicates the id bit read from the bus.
     /// * The second boolean indicates the complement bit read from the bus.
+    /// * The third boolean indicates the direction taken, i.e. the bit written to the bus.
     ///
     /// # Errors
     /// This method returns an error if the triplet read operation is not implemented or if any other error occurs.
     #[cfg(feature = "triplet-read")]
     #[cfg_attr(docsrs, doc(cfg(feature = "triplet-read")))]
-    fn read_triplet(&mut self) -> OneWireResult<(bool, bool, bool), Self::BusError>;
+    fn read_triplet(
+        &mut self,
+        direction: bool,
+    ) -> OneWireResult<(bool, bool, bool), Self::BusError>;
 
     /// Check if the 1-Wire bus is in overdrive mode.
     /// # Returns
@@ -158,3 +194,102 @@ pub trait OneWire {
     /// A result indicating the success or failure of the operation.
     fn set_overdrive_mode(&mut self, enable: bool) -> OneWireResult<(), Self::BusError>;
 }
+
+/// Forwards to the bus master, so that drivers taking the bus master by value can be
+/// given a mutable reference to it.
+impl<T: OneWire + ?Sized> OneWire for &mut T {
+    type Status = T::Status;
+    type BusError = T::BusError;
+
+    fn reset(&mut self) -> OneWireResult<Self::Status, Self::BusError> {
+        T::reset(self)
+    }
+
+    fn address(&mut self, rom: Option<u64>) -> OneWireResult<(), Self::BusError> {
+        T::address(self, rom)
+    }
+
+    fn write_byte(&mut self, byte: u8) -> OneWireResult<(), Self::BusError> {
+        T::write_byte(self, byte)
+    }
+
+    fn read_byte(&mut self) -> OneWireResult<u8, Self::BusError> {
+        T::read_byte(self)
+    }
+
+    fn write_bytes(&mut self, bytes: &[u8]) -> OneWireResult<(), Self::BusError> {
+        T::write_bytes(self, bytes)
+    }
+
+    fn read_bytes(&mut self, buf: &mut [u8]) -> OneWireResult<(), Self::BusError> {
+        T::read_bytes(self, buf)
+    }
+
+    fn write_bit(&mut self, bit: bool) -> OneWireResult<(), Self::BusError> {
+        T::write_bit(self, bit)
+    }
+
+    fn read_bit(&mut self) -> OneWireResult<bool, Self::BusError> {
+        T::read_bit(self)
+    }
+
+    #[cfg(feature = "triplet-read")]
+    fn read_triplet(
+        &mut self,
+        direction: bool,
+    ) -> OneWireResult<(bool, bool, bool), Self::BusError> {
+        T::read_triplet(self, direction)
+    }
+
+    fn get_overdrive_mode(&mut self) -> bool {
+        T::get_overdrive_mode(self)
+    }
+
+    fn set_overdrive_mode(&mut self, enable: bool) -> OneWireResult<(), Self::BusError> {
+        T::set_overdrive_mode(self, enable)
+    }
+}
+
+/// Trait for 1-Wire bus masters that can power the bus with a strong pullup.
+///
+/// Parasitically powered devices draw their supply current from the 1-Wire line during
+/// e.g. a temperature conversion or an EEPROM copy, which the weak pullup of the bus
+/// master cannot provide.
+pub trait OneWireStrongPullup: OneWire {
+    /// Writes a byte to the device addressed using [`OneWire::address`], and powers the
+    /// 1-Wire bus with a strong pullup right after it.
+    ///
+    /// The strong pullup lasts until the next operation on the bus. The caller waits for
+    /// the time the device needs, then ends the strong pullup with e.g. [`OneWire::reset`].
+    ///
+    /// # Arguments
+    /// * `byte` - The byte to write to the bus.
+    ///
+    /// # Errors
+    /// This method returns an error if the write operation fails.
+    fn write_byte_powered(&mut self, byte: u8) -> OneWireResult<(), Self::BusError>;
+}
+
+impl<T: OneWireStrongPullup + ?Sized> OneWireStrongPullup for &mut T {
+    fn write_byte_powered(&mut self, byte: u8) -> OneWireResult<(), Self::BusError> {
+        T::write_byte_powered(self, byte)
+    }
+}
+
+/// Trait for 1-Wire bus masters that can apply the EPROM programming pulse.
+///
+/// Add-only memories such as the DS2502 and DS2505 program a byte when the bus master
+/// raises the 1-Wire line to the programming voltage, typically 12 V, for 480 µs.
+pub trait OneWireProgramPulse: OneWire {
+    /// Applies the programming pulse to the 1-Wire bus.
+    ///
+    /// # Errors
+    /// This method returns an error if the bus master fails to apply the pulse.
+    fn program_pulse(&mut self) -> OneWireResult<(), Self::BusError>;
+}
+
+impl<T: OneWireProgramPulse + ?Sized> OneWireProgramPulse for &mut T {
+    fn program_pulse(&mut self) -> OneWireResult<(), Self::BusError> {
+        T::program_pulse(self)
+    }
+}
@@ -46,9 +46,7 @@ pub trait OneWireAsync {
         self.reset().await?; // Reset the bus before addressing
         self.write_byte(cmd).await?; // Send the match ROM command
         if let Some(rom) = rom {
-            for &b in rom.to_le_bytes().iter() {
-                self.write_byte(b).await?; // Write each byte of the ROM address
-            }
+            self.write_bytes(&rom.to_le_bytes()).await?; // Write the ROM address
         }
         Ok(())
     }
@@ -77,6 +75,40 @@ pub trait OneWireAsync {
     /// This method returns an error if the read operation fails.
     async fn read_byte(&mut self) -> OneWireResult<u8, Self::BusError>;
 
+    /// Writes a sequence of bytes to the device addressed using [`OneWireAsync::address`] on the 1-Wire bus.
+    ///
+    /// The default implementation calls [`OneWireAsync::write_byte`] for every byte. Bus masters that can
+    /// schedule multi-byte transfers more efficiently should override this method.
+    ///
+    /// # Arguments
+    /// * `bytes` - The bytes to write to the bus.
+    ///
+    /// # Errors
+    /// This method returns an error if any of the write operations fail.
+    async fn write_bytes(&mut self, bytes: &[u8]) -> OneWireResult<(), Self::BusError> {
+        for &byte in bytes.iter() {
+            self.write_byte(byte).await?;
+        }
+        Ok(())
+    }
+
+    /// Reads a sequence of bytes from the device addressed using [`OneWireAsync::address`] on the 1-Wire bus.
+    ///
+    /// The default implementation calls [`OneWireAsync::read_byte`] for every byte. Bus masters that can
+    /// schedule multi-byte transfers more efficiently should override this method.
+    ///
+    /// # Arguments
+    /// * `buf` - The buffer to fill with the bytes read from the bus.
+    ///
+    /// # Errors
+    /// This method returns an error if any of the read operations fail.
+    async fn read_bytes(&mut self, buf: &mut [u8]) -> OneWireResult<(), Self::BusError> {
+        for byte in buf.iter_mut() {
+            *byte = self.read_byte().await?;
+        }
+        Ok(())
+    }
+
     /// Write a single bit to the device addressed using [`OneWireAsync::address`] on the 1-Wire bus.
     /// Multiple bits can be written in succession after addressing the device.
     /// # Arguments
@@ -115,18 +147,22 @@ pub trait OneWireAsync {
     ///
     ///
     /// # Arguments
-    /// * `direction` - A boolean indicating the direction of the search. If true, the search is in the forward direction; if false, it is in the backward direction.
+    /// * `direction` - The direction V to take if both read time slots are 0.
     ///
     /// # Returns
-    /// A result containing a tuple of two booleans:
+    /// A result containing a tuple of three booleans:
     /// * The first boolean indicates the id bit read from the bus.
     /// * The second boolean indicates the complement bit read from the bus.
+    /// * The third boolean indicates the direction taken, i.e. the bit written to the bus.
     ///
     /// # Errors
     /// This method returns an error if the triplet read operation is not implemented or if any other error occurs.
     #[cfg(feature = "triplet-read")]
     #[cfg_attr(docsrs, doc(cfg(feature = "triplet-read")))]
-    async fn read_triplet(&mut self) -> OneWireResult<(bool, bool, bool), Self::BusError>;
+    async fn read_triplet(
+        &mut self,
+        direction: bool,
+    ) -> OneWireResult<(bool, bool, bool), Self::BusError>;
 
     /// Check if the 1-Wire bus is in overdrive mode.
     /// # Returns
@@ -140,3 +176,99 @@ pub trait OneWireAsync {
     /// A result indicating the success or failure of the operation.
     async fn set_overdrive_mode(&mut self, enable: bool) -> OneWireResult<(), Self::BusError>;
 }
+
+/// Forwards to the bus master, so that drivers taking the bus master by value can be
+/// given a mutable reference to it.
+impl<T: OneWireAsync + ?Sized> OneWireAsync for &mut T {
+    type Status = T::Status;
+    type BusError = T::BusError;
+
+    async fn reset(&mut self) -> OneWireResult<Self::Status, Self::BusError> {
+        T::reset(self).await
+    }
+
+    async fn address(&mut self, rom: Option<u64>) -> OneWireResult<(), Self::BusError> {
+        T::address(self, rom).await
+    }
+
+    async fn write_byte(&mut self, byte: u8) -> OneWireResult<(), Self::BusError> {
+        T::write_byte(self, byte).await
+    }
+
+    async fn read_byte(&mut self) -> OneWireResult<u8, Self::BusError> {
+        T::read_byte(self).await
+    }
+
+    async fn write_bytes(&mut self, bytes: &[u8]) -> OneWireResult<(), Self::BusError> {
+        T::write_bytes(self, bytes).await
+    }
+
+    async fn read_bytes(&mut self, buf: &mut [u8]) -> OneWireResult<(), Self::BusError> {
+        T::read_bytes(self, buf).await
+    }
+
+    async fn write_bit(&mut self, bit: bool) -> OneWireResult<(), Self::BusError> {
+        T::write_bit(self, bit).await
+    }
+
+    async fn read_bit(&mut self) -> OneWireResult<bool, Self::BusError> {
+        T::read_bit(self).await
+    }
+
+    #[cfg(feature = "triplet-read")]
+    async fn read_triplet(
+        &mut self,
+        direction: bool,
+    ) -> OneWireResult<(bool, bool, bool), Self::BusError> {
+        T::read_triplet(self, direction).await
+    }
+
+    fn get_overdrive_mode(&mut self) -> bool {
+        T::get_overdrive_mode(self)
+    }
+
+    async fn set_overdrive_mode(&mut self, enable: bool) -> OneWireResult<(), Self::BusError> {
+        T::set_overdrive_mode(self, enable).await
+    }
+}
+
+/// Trait for 1-Wire bus masters that can power the bus with a strong pullup.
+///
+/// See [`OneWireStrongPullup`](crate::OneWireStrongPullup).
+pub trait OneWireStrongPullupAsync: OneWireAsync {
+    /// Writes a byte to the device addressed using [`OneWireAsync::address`], and powers
+    /// the 1-Wire bus with a strong pullup right after it.
+    ///
+    /// The strong pullup lasts until the next operation on the bus. The caller waits for
+    /// the time the device needs, then ends the strong pullup with e.g. [`OneWireAsync::reset`].
+    ///
+    /// # Arguments
+    /// * `byte` - The byte to write to the bus.
+    ///
+    /// # Errors
+    /// This method returns an error if the write operation fails.
+    async fn write_byte_powered(&mut self, byte: u8) -> OneWireResult<(), Self::BusError>;
+}
+
+impl<T: OneWireStrongPullupAsync + ?Sized> OneWireStrongPullupAsync for &mut T {
+    async fn write_byte_powered(&mut self, byte: u8) -> OneWireResult<(), Self::BusError> {
+        T::write_byte_powered(self, byte).await
+    }
+}
+
+/// Trait for asynchronous 1-Wire bus masters that can apply the EPROM programming pulse.
+///
+/// See [`OneWireProgramPulse`](crate::OneWireProgramPulse).
+pub trait OneWireProgramPulseAsync: OneWireAsync {
+    /// Applies the programming pulse to the 1-Wire bus.
+    ///
+    /// # Errors
+    /// This method returns an error if the bus master fails to apply the pulse.
+    async fn program_pulse(&mut self) -> OneWireResult<(), Self::BusError>;
+}
+
+impl<T: OneWireProgramPulseAsync + ?Sized> OneWireProgramPulseAsync for &mut T {
+    async fn program_pulse(&mut self) -> OneWireResult<(), Self::BusError> {
+        T::program_pulse(self).await
+    }
+}
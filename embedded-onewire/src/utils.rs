@@ -76,6 +76,60 @@ impl OneWireCrc {
     }
 }
 
+#[derive(Debug, Default)]
+/// Calculate CRC-16 used by 1-Wire memory and I/O devices.
+///
+/// The devices send the inverted CRC-16 of the command, address and data, least
+/// significant byte first.
+pub struct OneWireCrc16(u16);
+
+impl OneWireCrc16 {
+    /// Get the current CRC value
+    pub fn value(&self) -> u16 {
+        self.0
+    }
+
+    /// Update the CRC with the incoming byte.
+    ///
+    /// # Arguments
+    /// * `byte` - The byte to update the CRC with.
+    pub fn update(&mut self, byte: u8) {
+        let mut crc = self.0 ^ byte as u16;
+        for _ in 0..8 {
+            if crc & 0x0001 == 0x0001 {
+                crc = (crc >> 1) ^ 0xA001; // Polynomial: x^16 + x^15 + x^2 + 1
+            } else {
+                crc >>= 1;
+            }
+        }
+        self.0 = crc;
+    }
+
+    /// Update the CRC with a sequence of bytes.
+    pub fn update_slice(&mut self, bytes: &[u8]) {
+        for &byte in bytes.iter() {
+            self.update(byte);
+        }
+    }
+
+    /// Whether the bytes seen so far end with their inverted 1-Wire CRC-16, least
+    /// significant byte first.
+    ///
+    /// # Note
+    /// For such a sequence, the CRC should be `0xB001`.
+    pub fn is_valid(&self) -> bool {
+        self.0 == 0xB001
+    }
+
+    /// Validate a sequence of bytes where the last two bytes are the inverted 1-Wire CRC-16
+    /// of the previous bytes, least significant byte first.
+    pub fn validate(sequence: &[u8]) -> bool {
+        let mut crc = OneWireCrc16(0);
+        crc.update_slice(sequence);
+        crc.is_valid()
+    }
+}
+
 mod test {
     #[test]
     fn test_crc_update() {
@@ -101,4 +155,21 @@ mod test {
         std::println!("CRC after table: {calc:#04x}");
         assert_eq!(table, calc, "CRC values do not match");
     }
+
+    #[test]
+    fn test_crc16() {
+        use super::OneWireCrc16;
+        let mut crc = OneWireCrc16::default();
+        crc.update_slice(b"123456789");
+        assert_eq!(crc.value(), 0xbb3d);
+        // Read Memory of a DS2431 from address 0, followed by the inverted CRC
+        let mut seq = [0xf0, 0x00, 0x00, 0x00, 0x00];
+        let mut crc = OneWireCrc16::default();
+        crc.update_slice(&seq[..3]);
+        let inverted = !crc.value();
+        seq[3..].copy_from_slice(&inverted.to_le_bytes());
+        assert!(OneWireCrc16::validate(&seq));
+        seq[1] = 0x01;
+        assert!(!OneWireCrc16::validate(&seq));
+    }
 }
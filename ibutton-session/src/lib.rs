@@ -0,0 +1,314 @@
+#![no_std]
+#![deny(missing_docs)]
+#![cfg_attr(docsrs, feature(doc_cfg))]
+#![doc = include_str!("../README.md")]
+
+mod queue;
+mod session;
+mod session_async;
+
+pub use embedded_onewire::{OneWireError, OneWireResult};
+pub use queue::{EventQueue, EventSink};
+pub use session::Session;
+
+pub(crate) const READ_ROM_CMD: u8 = 0x33;
+
+/// Debouncing parameters of a [`Session`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SessionConfig {
+    /// Consecutive presence pulses required before the ROM is read.
+    pub presence_samples: u8,
+    /// Consecutive identical ROM reads required to report an arrival.
+    pub confirm_reads: u8,
+    /// Consecutive polls without a presence pulse required to report a departure.
+    pub departure_samples: u8,
+    /// Family code of the accepted iButtons, or `None` to accept any family.
+    pub family: Option<u8>,
+}
+
+impl Default for SessionConfig {
+    fn default() -> Self {
+        SessionConfig {
+            presence_samples: 2,
+            confirm_reads: 2,
+            departure_samples: 3,
+            family: None,
+        }
+    }
+}
+
+/// A change of the iButton touching the probe.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Event {
+    /// An iButton with this ROM arrived on the probe.
+    Arrived(u64),
+    /// The iButton with this ROM departed from the probe.
+    Departed(u64),
+}
+
+/// What a poll of the probe found.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Sample {
+    /// No presence pulse, or an iButton of another family.
+    Absent,
+    /// A presence pulse, without reading the ROM.
+    Contact,
+    /// A short circuit, or a ROM garbled by the contact bouncing.
+    Garbled,
+    /// A presence pulse, and a valid ROM.
+    Rom(u64),
+}
+
+/// State of the probe.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum State {
+    /// No iButton confirmed on the probe. `candidate` is the ROM being confirmed, read
+    /// `reads` times in a row.
+    Absent {
+        presences: u8,
+        candidate: Option<u64>,
+        reads: u8,
+    },
+    /// The iButton with `rom` is on the probe, and missed the last `misses` polls.
+    Present { rom: u64, misses: u8 },
+}
+
+/// Debounces the samples of the probe into arrivals and departures.
+///
+/// [`Session`] samples the probe with the Read ROM command. Readers that sample it in
+/// another way, e.g. with a search, feed their samples to a debouncer of their own.
+#[derive(Debug)]
+pub struct Debouncer {
+    pub(crate) config: SessionConfig,
+    pub(crate) state: State,
+}
+
+impl Debouncer {
+    /// Creates a debouncer with no iButton on the probe.
+    pub fn new(config: SessionConfig) -> Self {
+        Debouncer {
+            config,
+            state: State::Absent {
+                presences: 0,
+                candidate: None,
+                reads: 0,
+            },
+        }
+    }
+
+    /// Debouncing parameters.
+    pub fn config(&self) -> &SessionConfig {
+        &self.config
+    }
+
+    /// ROM of the iButton confirmed on the probe.
+    pub fn current(&self) -> Option<u64> {
+        match self.state {
+            State::Present { rom, .. } => Some(rom),
+            State::Absent { .. } => None,
+        }
+    }
+
+    /// Whether the ROM is read after a presence pulse.
+    ///
+    /// The ROM of a new iButton is read once its presence is debounced, and the ROM of the
+    /// iButton on the probe is read on every poll, to catch an iButton swapped for another.
+    pub fn wants_rom(&self) -> bool {
+        match self.state {
+            State::Absent { presences, .. } => {
+                presences.saturating_add(1) >= self.config.presence_samples
+            }
+            State::Present { .. } => true,
+        }
+    }
+
+    /// Whether `rom` is accepted by the family filter.
+    pub fn accepts(&self, rom: u64) -> bool {
+        self.config.family.is_none_or(|family| rom as u8 == family)
+    }
+
+    /// Record a sample, and return the change it confirms.
+    pub fn sample(&mut self, sample: Sample) -> Option<Event> {
+        match (self.state, sample) {
+            // A garbled sample means that the contact is not stable yet
+            (State::Absent { .. }, Sample::Absent | Sample::Garbled) => {
+                self.state = State::Absent {
+                    presences: 0,
+                    candidate: None,
+                    reads: 0,
+                };
+                None
+            }
+            (
+                State::Absent {
+                    presences,
+                    candidate,
+                    reads,
+                },
+                Sample::Contact,
+            ) => {
+                self.state = State::Absent {
+                    presences: presences.saturating_add(1),
+                    candidate,
+                    reads,
+                };
+                None
+            }
+            (
+                State::Absent {
+                    presences,
+                    candidate,
+                    reads,
+                },
+                Sample::Rom(rom),
+            ) => {
+                let reads = if candidate == Some(rom) {
+                    reads.saturating_add(1)
+                } else {
+                    1
+                };
+                if reads >= self.config.confirm_reads {
+                    self.state = State::Present { rom, misses: 0 };
+                    Some(Event::Arrived(rom))
+                } else {
+                    self.state = State::Absent {
+                        presences: presences.saturating_add(1),
+                        candidate: Some(rom),
+                        reads,
+                    };
+                    None
+                }
+            }
+            (State::Present { rom, misses }, Sample::Absent) => {
+                let misses = misses.saturating_add(1);
+                if misses >= self.config.departure_samples {
+                    self.state = State::Absent {
+                        presences: 0,
+                        candidate: None,
+                        reads: 0,
+                    };
+                    Some(Event::Departed(rom))
+                } else {
+                    self.state = State::Present { rom, misses };
+                    None
+                }
+            }
+            (State::Present { rom, .. }, Sample::Contact) => {
+                self.state = State::Present { rom, misses: 0 };
+                None
+            }
+            // A bouncing contact neither confirms nor denies the iButton
+            (State::Present { .. }, Sample::Garbled) => None,
+            (State::Present { rom, .. }, Sample::Rom(read)) => {
+                if read == rom {
+                    self.state = State::Present { rom, misses: 0 };
+                    None
+                } else {
+                    // Swapped for another iButton, which is confirmed from here on
+                    self.state = State::Absent {
+                        presences: self.config.presence_samples,
+                        candidate: Some(read),
+                        reads: 1,
+                    };
+                    Some(Event::Departed(rom))
+                }
+            }
+        }
+    }
+}
+
+mod test {
+    #[test]
+    fn test_debouncer() {
+        use super::*;
+
+        let mut debouncer = Debouncer::new(SessionConfig::default());
+        assert!(!debouncer.wants_rom());
+        assert_eq!(debouncer.sample(Sample::Contact), None);
+        assert!(debouncer.wants_rom());
+        assert_eq!(debouncer.sample(Sample::Rom(1)), None);
+        // A garbled read restarts the debouncing
+        assert_eq!(debouncer.sample(Sample::Garbled), None);
+        assert!(!debouncer.wants_rom());
+        assert_eq!(debouncer.sample(Sample::Contact), None);
+        assert_eq!(debouncer.sample(Sample::Rom(1)), None);
+        assert_eq!(debouncer.sample(Sample::Rom(1)), Some(Event::Arrived(1)));
+        assert_eq!(debouncer.current(), Some(1));
+        // Short losses of contact are tolerated
+        assert_eq!(debouncer.sample(Sample::Absent), None);
+        assert_eq!(debouncer.sample(Sample::Absent), None);
+        assert_eq!(debouncer.sample(Sample::Garbled), None);
+        assert_eq!(debouncer.sample(Sample::Rom(1)), None);
+        assert_eq!(debouncer.sample(Sample::Absent), None);
+        assert_eq!(debouncer.sample(Sample::Absent), None);
+        assert_eq!(debouncer.sample(Sample::Absent), Some(Event::Departed(1)));
+        assert_eq!(debouncer.current(), None);
+        // A swapped iButton
+        assert_eq!(debouncer.sample(Sample::Contact), None);
+        assert_eq!(debouncer.sample(Sample::Rom(1)), None);
+        assert_eq!(debouncer.sample(Sample::Rom(1)), Some(Event::Arrived(1)));
+        assert_eq!(debouncer.sample(Sample::Rom(2)), Some(Event::Departed(1)));
+        assert!(debouncer.wants_rom());
+        assert_eq!(debouncer.sample(Sample::Rom(2)), Some(Event::Arrived(2)));
+    }
+
+    #[test]
+    fn test_poll() {
+        use super::*;
+        use embedded_onewire::testing::{Mock, Transaction};
+        extern crate std;
+
+        const ROM: [u8; 8] = [0x01, 0x5a, 0x3c, 0x12, 0x00, 0x00, 0x00, 0x46];
+        let bus = Mock::new(&[
+            Transaction::reset(true),
+            Transaction::reset(true),
+            Transaction::write(&[READ_ROM_CMD]),
+            Transaction::read(&ROM),
+            Transaction::short_circuit(),
+            Transaction::reset(true),
+            Transaction::reset(true),
+            Transaction::write(&[READ_ROM_CMD]),
+            Transaction::read(&ROM),
+            Transaction::reset(true),
+            Transaction::write(&[READ_ROM_CMD]),
+            Transaction::read(&ROM),
+            Transaction::reset(false),
+            Transaction::reset(false),
+        ]);
+        let rom = u64::from_le_bytes(ROM);
+        let config = SessionConfig {
+            departure_samples: 2,
+            family: Some(0x01),
+            ..Default::default()
+        };
+        let mut session = Session::new(bus, config);
+        let mut queue = EventQueue::<4>::new();
+        for _ in 0..6 {
+            session.poll_into(&mut queue).unwrap();
+        }
+        assert_eq!(queue.pop(), Some(Event::Arrived(rom)));
+        assert_eq!(queue.pop(), None);
+        assert_eq!(session.current(), Some(rom));
+        let mut events = std::vec::Vec::new();
+        session.poll_into(&mut |event| events.push(event)).unwrap();
+        session.poll_into(&mut |event| events.push(event)).unwrap();
+        assert_eq!(events, [Event::Departed(rom)]);
+        session.release().done();
+    }
+
+    #[test]
+    fn test_queue() {
+        use super::*;
+
+        let mut queue = EventQueue::<2>::new();
+        assert!(queue.is_empty());
+        queue.push(Event::Arrived(1));
+        queue.push(Event::Departed(1));
+        queue.push(Event::Arrived(2));
+        assert_eq!(queue.len(), 2);
+        assert_eq!(queue.dropped(), 1);
+        assert_eq!(queue.pop(), Some(Event::Departed(1)));
+        assert_eq!(queue.pop(), Some(Event::Arrived(2)));
+        assert_eq!(queue.pop(), None);
+    }
+}
@@ -0,0 +1,87 @@
+use crate::Event;
+
+/// A receiver of the events of a [`Session`](crate::Session).
+///
+/// Implemented for closures taking an [`Event`], and for [`EventQueue`].
+pub trait EventSink {
+    /// Receive an event.
+    fn push(&mut self, event: Event);
+}
+
+impl<F: FnMut(Event)> EventSink for F {
+    fn push(&mut self, event: Event) {
+        self(event)
+    }
+}
+
+/// A fixed-capacity queue of events, e.g. filled by polling in an interrupt handler and
+/// drained by the application.
+///
+/// When the queue is full, the oldest event is dropped, so the queue always ends with the
+/// latest state of the probe.
+#[derive(Debug, Clone)]
+pub struct EventQueue<const N: usize> {
+    events: [Option<Event>; N],
+    head: usize,
+    len: usize,
+    dropped: usize,
+}
+
+impl<const N: usize> Default for EventQueue<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> EventQueue<N> {
+    /// Creates an empty queue.
+    pub const fn new() -> Self {
+        EventQueue {
+            events: [None; N],
+            head: 0,
+            len: 0,
+            dropped: 0,
+        }
+    }
+
+    /// Number of events in the queue.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether the queue is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Number of events dropped because the queue was full.
+    pub fn dropped(&self) -> usize {
+        self.dropped
+    }
+
+    /// Remove the oldest event from the queue.
+    pub fn pop(&mut self) -> Option<Event> {
+        if self.len == 0 {
+            return None;
+        }
+        let event = self.events[self.head].take();
+        self.head = (self.head + 1) % N;
+        self.len -= 1;
+        event
+    }
+}
+
+impl<const N: usize> EventSink for EventQueue<N> {
+    fn push(&mut self, event: Event) {
+        if N == 0 {
+            self.dropped += 1;
+            return;
+        }
+        if self.len == N {
+            self.pop();
+            self.dropped += 1;
+        }
+        self.events[(self.head + self.len) % N] = Some(event);
+        self.len += 1;
+    }
+}
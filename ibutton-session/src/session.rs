@@ -0,0 +1,99 @@
+use crate::{Debouncer, Event, EventSink, READ_ROM_CMD, Sample, SessionConfig};
+use embedded_onewire::{OneWire, OneWireCrc, OneWireError, OneWireResult, OneWireStatus};
+
+/// A session manager for iButtons touching a probe on a 1-Wire bus.
+///
+/// The contact of an iButton with a probe is intermittent. Every call to [`Session::poll`]
+/// samples the probe: a new iButton is reported once its presence pulse was seen
+/// [`SessionConfig::presence_samples`] times in a row, and its ROM was read
+/// [`SessionConfig::confirm_reads`] times in a row without errors. It is reported to
+/// depart once it missed [`SessionConfig::departure_samples`] polls in a row, or was
+/// replaced by another iButton.
+///
+/// The probe must be the only device on the bus, as the ROM is read with the Read ROM
+/// command. The session owns the bus master, which can be a `&mut` reference to one or a
+/// handle to a shared one.
+#[derive(Debug)]
+pub struct Session<O> {
+    pub(crate) bus: O,
+    pub(crate) debouncer: Debouncer,
+}
+
+impl<O> Session<O> {
+    /// Creates a session for the probe on `bus`.
+    pub fn new(bus: O, config: SessionConfig) -> Self {
+        Session {
+            bus,
+            debouncer: Debouncer::new(config),
+        }
+    }
+
+    /// Debouncing parameters of the session.
+    pub fn config(&self) -> &SessionConfig {
+        &self.debouncer.config
+    }
+
+    /// ROM of the iButton on the probe, once its arrival was reported.
+    pub fn current(&self) -> Option<u64> {
+        self.debouncer.current()
+    }
+
+    /// Release the bus master.
+    pub fn release(self) -> O {
+        self.bus
+    }
+
+    /// Parse the ROM read from the probe.
+    pub(crate) fn parse_rom(&self, rom: [u8; 8]) -> Sample {
+        let value = u64::from_le_bytes(rom);
+        if value == 0 || !OneWireCrc::validate(&rom) {
+            Sample::Garbled
+        } else if !self.debouncer.accepts(value) {
+            Sample::Absent
+        } else {
+            Sample::Rom(value)
+        }
+    }
+}
+
+impl<O: OneWire> Session<O> {
+    /// Sample the probe.
+    fn sample(&mut self) -> OneWireResult<Sample, O::BusError> {
+        match self.bus.reset() {
+            Ok(status) if status.presence() => {}
+            Ok(_) | Err(OneWireError::NoDevicePresent) => return Ok(Sample::Absent),
+            Err(OneWireError::ShortCircuit) => return Ok(Sample::Garbled),
+            Err(e) => return Err(e),
+        }
+        if !self.debouncer.wants_rom() {
+            return Ok(Sample::Contact);
+        }
+        self.bus.write_byte(READ_ROM_CMD)?;
+        let mut rom = [0; 8];
+        self.bus.read_bytes(&mut rom)?;
+        Ok(self.parse_rom(rom))
+    }
+
+    /// Sample the probe, and return the arrival or departure it confirms.
+    ///
+    /// Samples garbled by the contact bouncing are discarded.
+    ///
+    /// # Errors
+    /// Returns the errors of the bus master, other than a missing presence pulse or a short
+    /// circuit.
+    pub fn poll(&mut self) -> OneWireResult<Option<Event>, O::BusError> {
+        let sample = self.sample()?;
+        Ok(self.debouncer.sample(sample))
+    }
+
+    /// Sample the probe, and push the arrival or departure it confirms to `sink`.
+    ///
+    /// # Errors
+    /// See [`Session::poll`].
+    pub fn poll_into<S: EventSink>(&mut self, sink: &mut S) -> OneWireResult<(), O::BusError> {
+        if let Some(event) = self.poll()? {
+            sink.push(event);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,41 @@
+use crate::{Event, EventSink, READ_ROM_CMD, Sample, Session};
+use embedded_onewire::{OneWireAsync, OneWireError, OneWireResult, OneWireStatus};
+
+impl<O: OneWireAsync> Session<O> {
+    async fn sample_async(&mut self) -> OneWireResult<Sample, O::BusError> {
+        match OneWireAsync::reset(&mut self.bus).await {
+            Ok(status) if status.presence() => {}
+            Ok(_) | Err(OneWireError::NoDevicePresent) => return Ok(Sample::Absent),
+            Err(OneWireError::ShortCircuit) => return Ok(Sample::Garbled),
+            Err(e) => return Err(e),
+        }
+        if !self.debouncer.wants_rom() {
+            return Ok(Sample::Contact);
+        }
+        OneWireAsync::write_byte(&mut self.bus, READ_ROM_CMD).await?;
+        let mut rom = [0; 8];
+        OneWireAsync::read_bytes(&mut self.bus, &mut rom).await?;
+        Ok(self.parse_rom(rom))
+    }
+
+    /// Sample the probe, and return the arrival or departure it confirms.
+    ///
+    /// See [`Session::poll`].
+    pub async fn poll_async(&mut self) -> OneWireResult<Option<Event>, O::BusError> {
+        let sample = self.sample_async().await?;
+        Ok(self.debouncer.sample(sample))
+    }
+
+    /// Sample the probe, and push the arrival or departure it confirms to `sink`.
+    ///
+    /// See [`Session::poll_into`].
+    pub async fn poll_into_async<S: EventSink>(
+        &mut self,
+        sink: &mut S,
+    ) -> OneWireResult<(), O::BusError> {
+        if let Some(event) = self.poll_async().await? {
+            sink.push(event);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,117 @@
+use crate::{
+    CONVERSION_TIME_US, CONVERT_T_CMD, READ_POWER_SUPPLY_CMD, READ_SCRATCHPAD_CMD, Scratchpad,
+};
+use embedded_hal::delay::DelayNs;
+use embedded_onewire::{OneWire, OneWireResult, OneWireStrongPullup};
+
+/// A MAX31850 or MAX31851 thermocouple-to-digital converter on a 1-Wire bus.
+///
+/// The driver owns the bus master, which can be a `&mut` reference to one or a handle to a
+/// shared one. Every operation addresses the device first.
+#[derive(Debug)]
+pub struct Max31850<O> {
+    pub(crate) bus: O,
+    pub(crate) rom: Option<u64>,
+}
+
+impl<O> Max31850<O> {
+    /// Creates a driver for the device with ROM `rom` on `bus`.
+    ///
+    /// Pass [`None`] to skip ROM addressing on a bus with a single device. On a bus with
+    /// several devices, this addresses all of them at once, which is only useful to start
+    /// a conversion on all of them.
+    pub fn new(bus: O, rom: Option<u64>) -> Self {
+        Max31850 { bus, rom }
+    }
+
+    /// ROM of the device, if it is addressed by ROM.
+    pub fn rom(&self) -> Option<u64> {
+        self.rom
+    }
+
+    /// Release the bus master.
+    pub fn release(self) -> O {
+        self.bus
+    }
+}
+
+impl<O: OneWire> Max31850<O> {
+    fn command(&mut self, cmd: u8) -> OneWireResult<(), O::BusError> {
+        self.bus.address(self.rom)?;
+        self.bus.write_byte(cmd)
+    }
+
+    /// Start a temperature conversion.
+    ///
+    /// An externally powered device signals the end of the conversion in the read time
+    /// slots that follow, see [`Max31850::conversion_done`]. A parasitically powered device
+    /// needs [`Max31850::start_conversion_powered`] instead.
+    pub fn start_conversion(&mut self) -> OneWireResult<(), O::BusError> {
+        self.command(CONVERT_T_CMD)
+    }
+
+    /// Whether the conversion started by [`Max31850::start_conversion`] is complete.
+    ///
+    /// Only valid for an externally powered device, right after the conversion is started
+    /// and before any other operation on the bus.
+    pub fn conversion_done(&mut self) -> OneWireResult<bool, O::BusError> {
+        self.bus.read_bit()
+    }
+
+    /// Read the scratchpad, holding the result of the last conversion.
+    ///
+    /// # Errors
+    /// Returns [`OneWireError::InvalidCrc`](embedded_onewire::OneWireError::InvalidCrc) if
+    /// the CRC of the scratchpad does not match, e.g. because no device responded.
+    pub fn read_scratchpad(&mut self) -> OneWireResult<Scratchpad, O::BusError> {
+        self.command(READ_SCRATCHPAD_CMD)?;
+        let mut buf = [0; Scratchpad::LEN];
+        self.bus.read_bytes(&mut buf)?;
+        Scratchpad::parse(&buf)
+    }
+
+    /// Start a temperature conversion, wait for it to complete and read the scratchpad.
+    ///
+    /// The thermocouple temperature is only valid if no fault is reported, see
+    /// [`Scratchpad::temperature`].
+    pub fn measure<D: DelayNs>(&mut self, delay: &mut D) -> OneWireResult<Scratchpad, O::BusError> {
+        self.start_conversion()?;
+        delay.delay_us(CONVERSION_TIME_US);
+        self.read_scratchpad()
+    }
+
+    /// Whether the device is parasitically powered.
+    ///
+    /// When the device is addressed with [`None`], this is true if any device on the bus
+    /// is parasitically powered.
+    pub fn is_parasite_powered(&mut self) -> OneWireResult<bool, O::BusError> {
+        self.command(READ_POWER_SUPPLY_CMD)?;
+        // Parasitically powered devices pull the bus low
+        Ok(!self.bus.read_bit()?)
+    }
+}
+
+impl<O: OneWireStrongPullup> Max31850<O> {
+    /// Start a temperature conversion, powering a parasitically powered device with the
+    /// strong pullup of the bus master.
+    ///
+    /// The strong pullup lasts until the next operation on the bus, which must wait for
+    /// [`CONVERSION_TIME_US`].
+    pub fn start_conversion_powered(&mut self) -> OneWireResult<(), O::BusError> {
+        self.bus.address(self.rom)?;
+        self.bus.write_byte_powered(CONVERT_T_CMD)
+    }
+
+    /// Start a temperature conversion with the strong pullup, wait for it to complete and
+    /// read the scratchpad.
+    ///
+    /// See [`Max31850::measure`] and [`Max31850::start_conversion_powered`].
+    pub fn measure_powered<D: DelayNs>(
+        &mut self,
+        delay: &mut D,
+    ) -> OneWireResult<Scratchpad, O::BusError> {
+        self.start_conversion_powered()?;
+        delay.delay_us(CONVERSION_TIME_US);
+        self.read_scratchpad()
+    }
+}
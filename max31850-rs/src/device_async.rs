@@ -0,0 +1,81 @@
+use crate::{
+    CONVERSION_TIME_US, CONVERT_T_CMD, Max31850, READ_POWER_SUPPLY_CMD, READ_SCRATCHPAD_CMD,
+    Scratchpad,
+};
+use embedded_hal_async::delay::DelayNs as DelayNsAsync;
+use embedded_onewire::{OneWireAsync, OneWireResult, OneWireStrongPullupAsync};
+
+impl<O: OneWireAsync> Max31850<O> {
+    async fn command_async(&mut self, cmd: u8) -> OneWireResult<(), O::BusError> {
+        OneWireAsync::address(&mut self.bus, self.rom).await?;
+        OneWireAsync::write_byte(&mut self.bus, cmd).await
+    }
+
+    /// Start a temperature conversion.
+    ///
+    /// See [`Max31850::start_conversion`].
+    pub async fn start_conversion_async(&mut self) -> OneWireResult<(), O::BusError> {
+        self.command_async(CONVERT_T_CMD).await
+    }
+
+    /// Whether the conversion started by [`Max31850::start_conversion_async`] is complete.
+    ///
+    /// See [`Max31850::conversion_done`].
+    pub async fn conversion_done_async(&mut self) -> OneWireResult<bool, O::BusError> {
+        OneWireAsync::read_bit(&mut self.bus).await
+    }
+
+    /// Read the scratchpad, holding the result of the last conversion.
+    ///
+    /// See [`Max31850::read_scratchpad`].
+    pub async fn read_scratchpad_async(&mut self) -> OneWireResult<Scratchpad, O::BusError> {
+        self.command_async(READ_SCRATCHPAD_CMD).await?;
+        let mut buf = [0; Scratchpad::LEN];
+        OneWireAsync::read_bytes(&mut self.bus, &mut buf).await?;
+        Scratchpad::parse(&buf)
+    }
+
+    /// Start a temperature conversion, wait for it to complete and read the scratchpad.
+    ///
+    /// See [`Max31850::measure`].
+    pub async fn measure_async<D: DelayNsAsync>(
+        &mut self,
+        delay: &mut D,
+    ) -> OneWireResult<Scratchpad, O::BusError> {
+        self.start_conversion_async().await?;
+        delay.delay_us(CONVERSION_TIME_US).await;
+        self.read_scratchpad_async().await
+    }
+
+    /// Whether the device is parasitically powered.
+    ///
+    /// See [`Max31850::is_parasite_powered`].
+    pub async fn is_parasite_powered_async(&mut self) -> OneWireResult<bool, O::BusError> {
+        self.command_async(READ_POWER_SUPPLY_CMD).await?;
+        Ok(!OneWireAsync::read_bit(&mut self.bus).await?)
+    }
+}
+
+impl<O: OneWireStrongPullupAsync> Max31850<O> {
+    /// Start a temperature conversion, powering a parasitically powered device with the
+    /// strong pullup of the bus master.
+    ///
+    /// See [`Max31850::start_conversion_powered`].
+    pub async fn start_conversion_powered_async(&mut self) -> OneWireResult<(), O::BusError> {
+        OneWireAsync::address(&mut self.bus, self.rom).await?;
+        OneWireStrongPullupAsync::write_byte_powered(&mut self.bus, CONVERT_T_CMD).await
+    }
+
+    /// Start a temperature conversion with the strong pullup, wait for it to complete and
+    /// read the scratchpad.
+    ///
+    /// See [`Max31850::measure_powered`].
+    pub async fn measure_powered_async<D: DelayNsAsync>(
+        &mut self,
+        delay: &mut D,
+    ) -> OneWireResult<Scratchpad, O::BusError> {
+        self.start_conversion_powered_async().await?;
+        delay.delay_us(CONVERSION_TIME_US).await;
+        self.read_scratchpad_async().await
+    }
+}
@@ -0,0 +1,168 @@
+#![no_std]
+#![deny(missing_docs)]
+#![cfg_attr(docsrs, feature(doc_cfg))]
+#![doc = include_str!("../README.md")]
+
+mod device;
+mod device_async;
+
+pub use device::Max31850;
+pub use ds18b20::Temperature;
+pub use embedded_onewire::{OneWireError, OneWireResult};
+
+use bitfield_struct::bitfield;
+
+/// Family code of the MAX31850 and MAX31851, the low byte of their ROM.
+pub const FAMILY_CODE: u8 = 0x3b;
+
+pub(crate) const CONVERT_T_CMD: u8 = 0x44;
+pub(crate) const READ_SCRATCHPAD_CMD: u8 = 0xbe;
+pub(crate) const READ_POWER_SUPPLY_CMD: u8 = 0xb4;
+
+/// Maximum conversion time, in microseconds.
+pub const CONVERSION_TIME_US: u32 = 100_000;
+
+#[bitfield(u8)]
+#[derive(PartialEq, Eq)]
+/// # Thermocouple faults
+///
+/// Detected during the last conversion. The thermocouple temperature is not valid if any is
+/// set.
+pub struct Faults {
+    /// OC: The thermocouple is open.
+    pub open_circuit: bool,
+    /// SCG: The thermocouple is shorted to GND.
+    pub short_to_gnd: bool,
+    /// SCV: The thermocouple is shorted to V<sub>DD</sub>.
+    pub short_to_vdd: bool,
+    #[bits(5)]
+    __: u8,
+}
+
+impl Faults {
+    /// Whether any fault was detected.
+    pub fn any(&self) -> bool {
+        self.into_bits() != 0
+    }
+}
+
+/// Contents of the MAX31850 scratchpad.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Scratchpad {
+    /// Cold-junction compensated thermocouple temperature, with a resolution of 0.25 °C.
+    pub thermocouple: Temperature,
+    /// Temperature of the cold junction, the device itself, with a resolution of 0.0625 °C.
+    pub cold_junction: Temperature,
+    /// Faults detected during the last conversion.
+    pub faults: Faults,
+    /// Hardware address set by the AD0 to AD3 pins, to tell apart the devices on a bus.
+    pub address: u8,
+}
+
+impl Scratchpad {
+    /// Length of the scratchpad including its CRC.
+    pub(crate) const LEN: usize = 9;
+
+    /// Parse the scratchpad read from the device.
+    ///
+    /// # Errors
+    /// Returns [`OneWireError::InvalidCrc`] if the CRC does not match the contents.
+    pub(crate) fn parse<E>(bytes: &[u8; Self::LEN]) -> OneWireResult<Self, E> {
+        if !embedded_onewire::OneWireCrc::validate(bytes) {
+            return Err(OneWireError::InvalidCrc);
+        }
+        // The low bits hold the fault bits, the temperatures are left justified
+        let thermocouple = i16::from_le_bytes([bytes[0], bytes[1]]) & !0x3;
+        let cold_junction = (i16::from_le_bytes([bytes[2], bytes[3]]) & !0xf) >> 4;
+        Ok(Scratchpad {
+            thermocouple: Temperature::from_raw(thermocouple),
+            cold_junction: Temperature::from_raw(cold_junction),
+            faults: Faults::from_bits(bytes[2] & 0x7),
+            address: bytes[4] & 0xf,
+        })
+    }
+
+    /// The thermocouple temperature, or [`None`] if a fault was detected.
+    pub fn temperature(&self) -> Option<Temperature> {
+        (!self.faults.any()).then_some(self.thermocouple)
+    }
+}
+
+mod test {
+    #[test]
+    fn test_scratchpad() {
+        use super::*;
+        let bytes = [0x44, 0x06, 0x10, 0x19, 0xf1, 0xff, 0xff, 0xff, 0x9e];
+        let scratchpad = Scratchpad::parse::<()>(&bytes).unwrap();
+        assert_eq!(scratchpad.thermocouple.millidegrees(), 100_250);
+        assert_eq!(scratchpad.cold_junction.millidegrees(), 25_062);
+        assert!(!scratchpad.faults.any());
+        assert_eq!(scratchpad.address, 1);
+        assert_eq!(scratchpad.temperature(), Some(scratchpad.thermocouple));
+        // Below 0 °C
+        let bytes = [0x30, 0xfc, 0x00, 0xfe, 0xf0, 0xff, 0xff, 0xff, 0xae];
+        let scratchpad = Scratchpad::parse::<()>(&bytes).unwrap();
+        assert_eq!(scratchpad.thermocouple.celsius(), -61.0);
+        assert_eq!(scratchpad.cold_junction.celsius(), -2.0);
+        let mut bytes = bytes;
+        bytes[0] = 0x34;
+        assert!(matches!(
+            Scratchpad::parse::<()>(&bytes),
+            Err(OneWireError::InvalidCrc)
+        ));
+    }
+
+    #[test]
+    fn test_measure() {
+        use super::*;
+        use embedded_hal_mock::eh1::delay::NoopDelay;
+        use embedded_onewire::testing::{Mock, Transaction};
+        extern crate std;
+
+        let rom = 0x4a00_0000_1234_563b;
+        let bus = Mock::new(&[
+            Transaction::reset(true),
+            Transaction::match_rom(rom),
+            Transaction::write(&[CONVERT_T_CMD]),
+            Transaction::reset(true),
+            Transaction::match_rom(rom),
+            Transaction::write(&[READ_SCRATCHPAD_CMD]),
+            // Open thermocouple
+            Transaction::read(&[0x01, 0x00, 0x91, 0xfe, 0xf0, 0xff, 0xff, 0xff, 0xd6]),
+        ]);
+        let mut sensor = Max31850::new(bus, Some(rom));
+        let scratchpad = sensor.measure(&mut NoopDelay::new()).unwrap();
+        assert!(scratchpad.faults.open_circuit());
+        assert!(!scratchpad.faults.short_to_gnd());
+        assert_eq!(scratchpad.temperature(), None);
+        assert_eq!(scratchpad.cold_junction.millidegrees(), -1_437);
+        sensor.release().done();
+    }
+
+    #[test]
+    fn test_measure_powered() {
+        use super::*;
+        use embedded_hal_mock::eh1::delay::NoopDelay;
+        use embedded_onewire::testing::{Mock, Transaction};
+        extern crate std;
+
+        let bus = Mock::new(&[
+            Transaction::reset(true),
+            Transaction::skip_rom(),
+            Transaction::write(&[READ_POWER_SUPPLY_CMD]),
+            Transaction::read_bit(false),
+            Transaction::reset(true),
+            Transaction::skip_rom(),
+            Transaction::write_powered(CONVERT_T_CMD),
+            Transaction::reset(true),
+            Transaction::skip_rom(),
+            Transaction::write(&[READ_SCRATCHPAD_CMD]),
+            Transaction::read(&[0x44, 0x06, 0x10, 0x19, 0xf1, 0xff, 0xff, 0xff, 0x9e]),
+        ]);
+        let mut sensor = Max31850::new(bus, None);
+        assert!(sensor.is_parasite_powered().unwrap());
+        let scratchpad = sensor.measure_powered(&mut NoopDelay::new()).unwrap();
+        assert_eq!(scratchpad.temperature().unwrap().celsius(), 100.25);
+        sensor.release().done();
+    }
+}
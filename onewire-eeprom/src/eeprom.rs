@@ -0,0 +1,42 @@
+use embedded_hal::delay::DelayNs;
+use embedded_onewire::OneWireResult;
+
+/// A 1-Wire EEPROM, to write code that works with whichever memory part is present.
+///
+/// The data memory starts at address `0x0000` and spans [`OneWireEeprom::CAPACITY`] bytes.
+/// The registers of a part, such as its write protection, are not part of the data memory,
+/// and are managed through the methods of its driver.
+pub trait OneWireEeprom {
+    /// Error of the bus master.
+    type BusError;
+
+    /// Size of the data memory.
+    const CAPACITY: usize;
+    /// Size of a page, the unit of the write protection or of the checked reads of the part.
+    const PAGE_SIZE: usize;
+    /// Size of the scratchpad, the unit of programming. Writes that only partially cover a
+    /// block of this size read the rest of it first.
+    const SCRATCHPAD_SIZE: usize;
+
+    /// Read the memory from `address` into `buf`.
+    ///
+    /// # Errors
+    /// Returns [`OneWireError::InvalidValue`](embedded_onewire::OneWireError::InvalidValue)
+    /// if the read goes past the end of the memory of the part, and the errors of the bus.
+    fn read(&mut self, address: u16, buf: &mut [u8]) -> OneWireResult<(), Self::BusError>;
+
+    /// Write `data` to the memory at `address`.
+    ///
+    /// # Errors
+    /// Returns [`OneWireError::InvalidValue`](embedded_onewire::OneWireError::InvalidValue)
+    /// if the write goes past the end of the writable memory of the part, and
+    /// [`OneWireError::VerificationFailed`](embedded_onewire::OneWireError::VerificationFailed)
+    /// if the part does not confirm the programming, e.g. because the memory is
+    /// write-protected. The blocks before the failed one are already programmed.
+    fn write<D: DelayNs>(
+        &mut self,
+        address: u16,
+        data: &[u8],
+        delay: &mut D,
+    ) -> OneWireResult<(), Self::BusError>;
+}
@@ -0,0 +1,33 @@
+#![allow(async_fn_in_trait)]
+use embedded_hal_async::delay::DelayNs;
+use embedded_onewire::OneWireResult;
+
+/// A 1-Wire EEPROM, accessed asynchronously.
+///
+/// See [`OneWireEeprom`](crate::OneWireEeprom).
+pub trait OneWireEepromAsync {
+    /// Error of the bus master.
+    type BusError;
+
+    /// Size of the data memory.
+    const CAPACITY: usize;
+    /// Size of a page, the unit of the write protection or of the checked reads of the part.
+    const PAGE_SIZE: usize;
+    /// Size of the scratchpad, the unit of programming.
+    const SCRATCHPAD_SIZE: usize;
+
+    /// Read the memory from `address` into `buf`.
+    ///
+    /// See [`OneWireEeprom::read`](crate::OneWireEeprom::read).
+    async fn read(&mut self, address: u16, buf: &mut [u8]) -> OneWireResult<(), Self::BusError>;
+
+    /// Write `data` to the memory at `address`.
+    ///
+    /// See [`OneWireEeprom::write`](crate::OneWireEeprom::write).
+    async fn write<D: DelayNs>(
+        &mut self,
+        address: u16,
+        data: &[u8],
+        delay: &mut D,
+    ) -> OneWireResult<(), Self::BusError>;
+}
@@ -0,0 +1,91 @@
+use crate::scratchpad;
+use embedded_hal::delay::DelayNs;
+use embedded_onewire::{OneWire, OneWireError, OneWireResult, OneWireStrongPullup};
+
+/// Memory layout and timing of an EEPROM programmed through a scratchpad of `N` bytes.
+///
+/// The layout checks the bounds of the accesses, and runs them with the [`scratchpad`]
+/// flow, so that a driver only has to describe its part.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Layout<const N: usize> {
+    pub(crate) memory_end: usize,
+    pub(crate) writable_end: usize,
+    pub(crate) prog_time_us: u32,
+}
+
+impl<const N: usize> Layout<N> {
+    /// Layout of a memory readable up to `memory_end` and writable up to `writable_end`,
+    /// whose scratchpad takes `prog_time_us` microseconds to be copied.
+    pub const fn new(memory_end: usize, writable_end: usize, prog_time_us: u32) -> Self {
+        Layout {
+            memory_end,
+            writable_end,
+            prog_time_us,
+        }
+    }
+
+    /// End of the readable memory.
+    pub const fn memory_end(&self) -> usize {
+        self.memory_end
+    }
+
+    /// End of the writable memory.
+    pub const fn writable_end(&self) -> usize {
+        self.writable_end
+    }
+
+    /// Read the memory from `address` into `buf`.
+    ///
+    /// # Errors
+    /// Returns [`OneWireError::InvalidValue`] if the read goes past the end of the memory.
+    pub fn read<O: OneWire>(
+        &self,
+        bus: &mut O,
+        rom: Option<u64>,
+        address: u16,
+        buf: &mut [u8],
+    ) -> OneWireResult<(), O::BusError> {
+        if address as usize + buf.len() > self.memory_end {
+            return Err(OneWireError::InvalidValue("address"));
+        }
+        scratchpad::read_memory(bus, rom, address, buf)
+    }
+
+    /// Program the block at `address` with `block`.
+    ///
+    /// # Errors
+    /// Returns [`OneWireError::InvalidValue`] if `address` is not the start of a writable
+    /// block, and the errors of [`scratchpad::write_block`].
+    pub fn write_block<O: OneWireStrongPullup, D: DelayNs>(
+        &self,
+        bus: &mut O,
+        rom: Option<u64>,
+        address: u16,
+        block: &[u8; N],
+        delay: &mut D,
+    ) -> OneWireResult<(), O::BusError> {
+        if !(address as usize).is_multiple_of(N) || address as usize >= self.writable_end {
+            return Err(OneWireError::InvalidValue("address"));
+        }
+        scratchpad::write_block(bus, rom, address, block, delay, self.prog_time_us)
+    }
+
+    /// Write `data` to the memory at `address`.
+    ///
+    /// # Errors
+    /// Returns [`OneWireError::InvalidValue`] if the write goes past the end of the writable
+    /// memory, and the errors of [`scratchpad::write_memory`].
+    pub fn write<O: OneWireStrongPullup, D: DelayNs>(
+        &self,
+        bus: &mut O,
+        rom: Option<u64>,
+        address: u16,
+        data: &[u8],
+        delay: &mut D,
+    ) -> OneWireResult<(), O::BusError> {
+        if address as usize + data.len() > self.writable_end {
+            return Err(OneWireError::InvalidValue("address"));
+        }
+        scratchpad::write_memory::<_, _, N>(bus, rom, address, data, delay, self.prog_time_us)
+    }
+}
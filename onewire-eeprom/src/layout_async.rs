@@ -0,0 +1,56 @@
+use crate::{Layout, scratchpad};
+use embedded_hal_async::delay::DelayNs;
+use embedded_onewire::{OneWireAsync, OneWireError, OneWireResult, OneWireStrongPullupAsync};
+
+impl<const N: usize> Layout<N> {
+    /// Read the memory from `address` into `buf`.
+    ///
+    /// See [`Layout::read`].
+    pub async fn read_async<O: OneWireAsync>(
+        &self,
+        bus: &mut O,
+        rom: Option<u64>,
+        address: u16,
+        buf: &mut [u8],
+    ) -> OneWireResult<(), O::BusError> {
+        if address as usize + buf.len() > self.memory_end {
+            return Err(OneWireError::InvalidValue("address"));
+        }
+        scratchpad::read_memory_async(bus, rom, address, buf).await
+    }
+
+    /// Program the block at `address` with `block`.
+    ///
+    /// See [`Layout::write_block`].
+    pub async fn write_block_async<O: OneWireStrongPullupAsync, D: DelayNs>(
+        &self,
+        bus: &mut O,
+        rom: Option<u64>,
+        address: u16,
+        block: &[u8; N],
+        delay: &mut D,
+    ) -> OneWireResult<(), O::BusError> {
+        if !(address as usize).is_multiple_of(N) || address as usize >= self.writable_end {
+            return Err(OneWireError::InvalidValue("address"));
+        }
+        scratchpad::write_block_async(bus, rom, address, block, delay, self.prog_time_us).await
+    }
+
+    /// Write `data` to the memory at `address`.
+    ///
+    /// See [`Layout::write`].
+    pub async fn write_async<O: OneWireStrongPullupAsync, D: DelayNs>(
+        &self,
+        bus: &mut O,
+        rom: Option<u64>,
+        address: u16,
+        data: &[u8],
+        delay: &mut D,
+    ) -> OneWireResult<(), O::BusError> {
+        if address as usize + data.len() > self.writable_end {
+            return Err(OneWireError::InvalidValue("address"));
+        }
+        scratchpad::write_memory_async::<_, _, N>(bus, rom, address, data, delay, self.prog_time_us)
+            .await
+    }
+}
@@ -0,0 +1,69 @@
+#![no_std]
+#![deny(missing_docs)]
+#![cfg_attr(docsrs, feature(doc_cfg))]
+#![doc = include_str!("../README.md")]
+
+mod eeprom;
+mod eeprom_async;
+mod layout;
+mod layout_async;
+pub mod scratchpad;
+mod scratchpad_async;
+
+pub use eeprom::OneWireEeprom;
+pub use eeprom_async::OneWireEepromAsync;
+pub use embedded_onewire::{OneWireError, OneWireResult};
+pub use layout::Layout;
+
+mod test {
+    #[test]
+    fn test_layout() {
+        use super::*;
+        use embedded_hal_mock::eh1::delay::NoopDelay;
+        use embedded_onewire::testing::{Mock, Transaction};
+        extern crate std;
+
+        // The memory of a DS2431
+        const LAYOUT: Layout<8> = Layout::new(0x90, 0x88, 10_000);
+        let row = [0x11, 0x22, 0x33, 0xaa, 0x55, 0x66, 0x77, 0x88];
+        let mut bus = Mock::new(&[
+            Transaction::reset(true),
+            Transaction::skip_rom(),
+            Transaction::write(&[0xf0, 0x00, 0x00]),
+            Transaction::read(&[0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77, 0x88]),
+            Transaction::reset(true),
+            Transaction::skip_rom(),
+            Transaction::write(&[0x0f, 0x00, 0x00]),
+            Transaction::write(&row),
+            Transaction::read(&[0xc6, 0xb7]),
+            Transaction::reset(true),
+            Transaction::skip_rom(),
+            Transaction::write(&[0xaa]),
+            Transaction::read(&[0x00, 0x00, 0x07]),
+            Transaction::read(&row),
+            Transaction::read(&[0x4b, 0x4a]),
+            Transaction::reset(true),
+            Transaction::skip_rom(),
+            Transaction::write(&[0x55, 0x00, 0x00]),
+            Transaction::write_powered(0x07),
+            Transaction::read(&[0xaa]),
+        ]);
+        LAYOUT
+            .write(&mut bus, None, 0x03, &[0xaa], &mut NoopDelay::new())
+            .unwrap();
+        // Past the writable memory, and unaligned
+        assert!(matches!(
+            LAYOUT.write(&mut bus, None, 0x80, &[0; 9], &mut NoopDelay::new()),
+            Err(OneWireError::InvalidValue(_))
+        ));
+        assert!(matches!(
+            LAYOUT.write_block(&mut bus, None, 0x04, &row, &mut NoopDelay::new()),
+            Err(OneWireError::InvalidValue(_))
+        ));
+        assert!(matches!(
+            LAYOUT.read(&mut bus, None, 0x8f, &mut [0; 2]),
+            Err(OneWireError::InvalidValue(_))
+        ));
+        bus.done();
+    }
+}
@@ -0,0 +1,220 @@
+//! The scratchpad programming flow shared by the 1-Wire EEPROMs.
+//!
+//! Devices such as the DS2431, DS2433 and DS28EC20 are programmed one scratchpad at a time:
+//! the data is written to the scratchpad, read back with its target address and the ending
+//! offset, and copied to the memory after the master proves with the address and the ending
+//! offset that it read the scratchpad. The bus must be powered during the copy, which a
+//! parasitically powered device cannot do from the weak pullup.
+//!
+//! The functions are generic over the size of the scratchpad `N`, and expect the target
+//! address to be aligned to it. The asynchronous twins are suffixed with `_async`.
+use embedded_hal::delay::DelayNs;
+use embedded_onewire::{OneWire, OneWireCrc16, OneWireError, OneWireResult, OneWireStrongPullup};
+
+pub use crate::scratchpad_async::{
+    copy_scratchpad_async, read_memory_async, read_scratchpad_async, write_block_async,
+    write_memory_async, write_scratchpad_async,
+};
+
+pub(crate) const WRITE_SCRATCHPAD_CMD: u8 = 0x0f;
+pub(crate) const READ_SCRATCHPAD_CMD: u8 = 0xaa;
+pub(crate) const COPY_SCRATCHPAD_CMD: u8 = 0x55;
+pub(crate) const READ_MEMORY_CMD: u8 = 0xf0;
+
+/// Pattern of alternating ones and zeros sent by the device after a successful copy.
+pub(crate) const COPY_DONE: u8 = 0xaa;
+
+/// Partial flag of the E/S byte, set if the scratchpad was not written to its end.
+pub(crate) const PARTIAL_FLAG: u8 = 0x20;
+
+/// Contents of the scratchpad, with its target address and the ending offset.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Scratchpad<const N: usize> {
+    /// Target address of the scratchpad.
+    pub address: u16,
+    /// The E/S byte: the ending offset in the low bits, and status flags.
+    pub es: u8,
+    /// The data.
+    pub data: [u8; N],
+}
+
+/// Write `data` to the scratchpad with target address `address`.
+///
+/// # Errors
+/// Returns [`OneWireError::InvalidCrc`] if the CRC-16 sent back by the device does not
+/// match the command, address and data.
+pub fn write_scratchpad<O: OneWire, const N: usize>(
+    bus: &mut O,
+    rom: Option<u64>,
+    address: u16,
+    data: &[u8; N],
+) -> OneWireResult<(), O::BusError> {
+    let [lo, hi] = address.to_le_bytes();
+    let cmd = [WRITE_SCRATCHPAD_CMD, lo, hi];
+    bus.address(rom)?;
+    bus.write_bytes(&cmd)?;
+    bus.write_bytes(data)?;
+    let mut crc = [0; 2];
+    bus.read_bytes(&mut crc)?;
+    check_write_crc(&cmd, data, &crc)
+}
+
+/// Read the scratchpad.
+///
+/// # Errors
+/// Returns [`OneWireError::InvalidCrc`] if the CRC-16 of the scratchpad does not match.
+pub fn read_scratchpad<O: OneWire, const N: usize>(
+    bus: &mut O,
+    rom: Option<u64>,
+) -> OneWireResult<Scratchpad<N>, O::BusError> {
+    bus.address(rom)?;
+    bus.write_byte(READ_SCRATCHPAD_CMD)?;
+    let mut header = [0; 3];
+    bus.read_bytes(&mut header)?;
+    let mut data = [0; N];
+    bus.read_bytes(&mut data)?;
+    let mut crc = [0; 2];
+    bus.read_bytes(&mut crc)?;
+    parse_scratchpad(header, data, &crc)
+}
+
+/// Copy the scratchpad read as `scratchpad` to the memory, powering the bus with the
+/// strong pullup for `prog_us` microseconds.
+///
+/// # Errors
+/// Returns [`OneWireError::VerificationFailed`] if the device does not confirm the copy.
+pub fn copy_scratchpad<O: OneWireStrongPullup, D: DelayNs, const N: usize>(
+    bus: &mut O,
+    rom: Option<u64>,
+    scratchpad: &Scratchpad<N>,
+    delay: &mut D,
+    prog_us: u32,
+) -> OneWireResult<(), O::BusError> {
+    let [lo, hi] = scratchpad.address.to_le_bytes();
+    bus.address(rom)?;
+    bus.write_bytes(&[COPY_SCRATCHPAD_CMD, lo, hi])?;
+    bus.write_byte_powered(scratchpad.es)?;
+    delay.delay_us(prog_us);
+    if bus.read_byte()? != COPY_DONE {
+        return Err(OneWireError::VerificationFailed);
+    }
+    Ok(())
+}
+
+/// Program `data` at the scratchpad-aligned `address`: write the scratchpad, read it back
+/// to verify it, and copy it to the memory.
+///
+/// # Errors
+/// Returns [`OneWireError::VerificationFailed`] if the scratchpad read back does not match
+/// the data, or if the device does not confirm the copy.
+pub fn write_block<O: OneWireStrongPullup, D: DelayNs, const N: usize>(
+    bus: &mut O,
+    rom: Option<u64>,
+    address: u16,
+    data: &[u8; N],
+    delay: &mut D,
+    prog_us: u32,
+) -> OneWireResult<(), O::BusError> {
+    write_scratchpad(bus, rom, address, data)?;
+    let scratchpad = read_scratchpad::<_, N>(bus, rom)?;
+    verify_scratchpad(&scratchpad, address, data)?;
+    copy_scratchpad(bus, rom, &scratchpad, delay, prog_us)
+}
+
+/// Write `data` to the memory at `address`, programming every scratchpad-sized block it
+/// touches in full.
+///
+/// The blocks only partially covered by `data` are read first, so that the rest of their
+/// bytes are written back unchanged. The blocks before a failed one are already programmed.
+pub fn write_memory<O: OneWireStrongPullup, D: DelayNs, const N: usize>(
+    bus: &mut O,
+    rom: Option<u64>,
+    address: u16,
+    data: &[u8],
+    delay: &mut D,
+    prog_us: u32,
+) -> OneWireResult<(), O::BusError> {
+    let mut address = address as usize;
+    let mut data = data;
+    while !data.is_empty() {
+        let (start, offset, len) = block_span::<N>(address, data.len());
+        let mut block = [0; N];
+        if len != N {
+            read_memory(bus, rom, start, &mut block)?;
+        }
+        block[offset..offset + len].copy_from_slice(&data[..len]);
+        write_block(bus, rom, start, &block, delay, prog_us)?;
+        address += len;
+        data = &data[len..];
+    }
+    Ok(())
+}
+
+/// Read the memory from `address` into `buf`.
+///
+/// The Read Memory command sends no CRC.
+pub fn read_memory<O: OneWire>(
+    bus: &mut O,
+    rom: Option<u64>,
+    address: u16,
+    buf: &mut [u8],
+) -> OneWireResult<(), O::BusError> {
+    let [lo, hi] = address.to_le_bytes();
+    bus.address(rom)?;
+    bus.write_bytes(&[READ_MEMORY_CMD, lo, hi])?;
+    bus.read_bytes(buf)
+}
+
+pub(crate) fn check_write_crc<E>(cmd: &[u8], data: &[u8], crc: &[u8; 2]) -> OneWireResult<(), E> {
+    let mut check = OneWireCrc16::default();
+    check.update_slice(cmd);
+    check.update_slice(data);
+    check.update_slice(crc);
+    if !check.is_valid() {
+        return Err(OneWireError::InvalidCrc);
+    }
+    Ok(())
+}
+
+pub(crate) fn parse_scratchpad<E, const N: usize>(
+    header: [u8; 3],
+    data: [u8; N],
+    crc: &[u8; 2],
+) -> OneWireResult<Scratchpad<N>, E> {
+    let mut check = OneWireCrc16::default();
+    check.update(READ_SCRATCHPAD_CMD);
+    check.update_slice(&header);
+    check.update_slice(&data);
+    check.update_slice(crc);
+    if !check.is_valid() {
+        return Err(OneWireError::InvalidCrc);
+    }
+    Ok(Scratchpad {
+        address: u16::from_le_bytes([header[0], header[1]]),
+        es: header[2],
+        data,
+    })
+}
+
+pub(crate) fn verify_scratchpad<E, const N: usize>(
+    scratchpad: &Scratchpad<N>,
+    address: u16,
+    data: &[u8; N],
+) -> OneWireResult<(), E> {
+    let ending = (N - 1) as u8;
+    if scratchpad.address != address
+        || scratchpad.es & PARTIAL_FLAG != 0
+        || scratchpad.es & ending != ending
+        || scratchpad.data != *data
+    {
+        return Err(OneWireError::VerificationFailed);
+    }
+    Ok(())
+}
+
+/// Start address of the block of size `N` containing `address`, and the offset and length
+/// of the part of `len` bytes from `address` that falls in it.
+pub(crate) fn block_span<const N: usize>(address: usize, len: usize) -> (u16, usize, usize) {
+    let offset = address % N;
+    ((address - offset) as u16, offset, (N - offset).min(len))
+}
@@ -0,0 +1,125 @@
+use crate::scratchpad::{
+    COPY_DONE, COPY_SCRATCHPAD_CMD, READ_MEMORY_CMD, READ_SCRATCHPAD_CMD, Scratchpad,
+    WRITE_SCRATCHPAD_CMD, block_span, check_write_crc, parse_scratchpad, verify_scratchpad,
+};
+use embedded_hal_async::delay::DelayNs;
+use embedded_onewire::{OneWireAsync, OneWireError, OneWireResult, OneWireStrongPullupAsync};
+
+/// Write `data` to the scratchpad with target address `address`.
+///
+/// See [`write_scratchpad`](crate::scratchpad::write_scratchpad).
+pub async fn write_scratchpad_async<O: OneWireAsync, const N: usize>(
+    bus: &mut O,
+    rom: Option<u64>,
+    address: u16,
+    data: &[u8; N],
+) -> OneWireResult<(), O::BusError> {
+    let [lo, hi] = address.to_le_bytes();
+    let cmd = [WRITE_SCRATCHPAD_CMD, lo, hi];
+    OneWireAsync::address(bus, rom).await?;
+    OneWireAsync::write_bytes(bus, &cmd).await?;
+    OneWireAsync::write_bytes(bus, data).await?;
+    let mut crc = [0; 2];
+    OneWireAsync::read_bytes(bus, &mut crc).await?;
+    check_write_crc(&cmd, data, &crc)
+}
+
+/// Read the scratchpad.
+///
+/// See [`read_scratchpad`](crate::scratchpad::read_scratchpad).
+pub async fn read_scratchpad_async<O: OneWireAsync, const N: usize>(
+    bus: &mut O,
+    rom: Option<u64>,
+) -> OneWireResult<Scratchpad<N>, O::BusError> {
+    OneWireAsync::address(bus, rom).await?;
+    OneWireAsync::write_byte(bus, READ_SCRATCHPAD_CMD).await?;
+    let mut header = [0; 3];
+    OneWireAsync::read_bytes(bus, &mut header).await?;
+    let mut data = [0; N];
+    OneWireAsync::read_bytes(bus, &mut data).await?;
+    let mut crc = [0; 2];
+    OneWireAsync::read_bytes(bus, &mut crc).await?;
+    parse_scratchpad(header, data, &crc)
+}
+
+/// Copy the scratchpad read as `scratchpad` to the memory, powering the bus with the
+/// strong pullup for `prog_us` microseconds.
+///
+/// See [`copy_scratchpad`](crate::scratchpad::copy_scratchpad).
+pub async fn copy_scratchpad_async<O: OneWireStrongPullupAsync, D: DelayNs, const N: usize>(
+    bus: &mut O,
+    rom: Option<u64>,
+    scratchpad: &Scratchpad<N>,
+    delay: &mut D,
+    prog_us: u32,
+) -> OneWireResult<(), O::BusError> {
+    let [lo, hi] = scratchpad.address.to_le_bytes();
+    OneWireAsync::address(bus, rom).await?;
+    OneWireAsync::write_bytes(bus, &[COPY_SCRATCHPAD_CMD, lo, hi]).await?;
+    OneWireStrongPullupAsync::write_byte_powered(bus, scratchpad.es).await?;
+    delay.delay_us(prog_us).await;
+    if OneWireAsync::read_byte(bus).await? != COPY_DONE {
+        return Err(OneWireError::VerificationFailed);
+    }
+    Ok(())
+}
+
+/// Program `data` at the scratchpad-aligned `address`.
+///
+/// See [`write_block`](crate::scratchpad::write_block).
+pub async fn write_block_async<O: OneWireStrongPullupAsync, D: DelayNs, const N: usize>(
+    bus: &mut O,
+    rom: Option<u64>,
+    address: u16,
+    data: &[u8; N],
+    delay: &mut D,
+    prog_us: u32,
+) -> OneWireResult<(), O::BusError> {
+    write_scratchpad_async(bus, rom, address, data).await?;
+    let scratchpad = read_scratchpad_async::<_, N>(bus, rom).await?;
+    verify_scratchpad(&scratchpad, address, data)?;
+    copy_scratchpad_async(bus, rom, &scratchpad, delay, prog_us).await
+}
+
+/// Write `data` to the memory at `address`, programming every scratchpad-sized block it
+/// touches in full.
+///
+/// See [`write_memory`](crate::scratchpad::write_memory).
+pub async fn write_memory_async<O: OneWireStrongPullupAsync, D: DelayNs, const N: usize>(
+    bus: &mut O,
+    rom: Option<u64>,
+    address: u16,
+    data: &[u8],
+    delay: &mut D,
+    prog_us: u32,
+) -> OneWireResult<(), O::BusError> {
+    let mut address = address as usize;
+    let mut data = data;
+    while !data.is_empty() {
+        let (start, offset, len) = block_span::<N>(address, data.len());
+        let mut block = [0; N];
+        if len != N {
+            read_memory_async(bus, rom, start, &mut block).await?;
+        }
+        block[offset..offset + len].copy_from_slice(&data[..len]);
+        write_block_async(bus, rom, start, &block, delay, prog_us).await?;
+        address += len;
+        data = &data[len..];
+    }
+    Ok(())
+}
+
+/// Read the memory from `address` into `buf`.
+///
+/// See [`read_memory`](crate::scratchpad::read_memory).
+pub async fn read_memory_async<O: OneWireAsync>(
+    bus: &mut O,
+    rom: Option<u64>,
+    address: u16,
+    buf: &mut [u8],
+) -> OneWireResult<(), O::BusError> {
+    let [lo, hi] = address.to_le_bytes();
+    OneWireAsync::address(bus, rom).await?;
+    OneWireAsync::write_bytes(bus, &[READ_MEMORY_CMD, lo, hi]).await?;
+    OneWireAsync::read_bytes(bus, buf).await
+}
@@ -0,0 +1,265 @@
+use crate::netlink::{
+    self, Reply, W1_CMD_ALARM_SEARCH, W1_CMD_READ, W1_CMD_RESET, W1_CMD_SEARCH, W1_CMD_WRITE,
+};
+use crate::{CHUNK, ResetStatus, sysfs};
+use embedded_onewire::{OneWire, OneWireError, OneWireResult, OneWireSearchKind};
+use std::io;
+use std::os::fd::{AsRawFd, FromRawFd, OwnedFd};
+use std::time::Duration;
+
+/// Size of the buffer the replies of the kernel are received in.
+const RECV_LEN: usize = 16384;
+
+/// A 1-Wire bus master of the Linux kernel, accessed through the w1 netlink interface.
+///
+/// Every operation on the bus is a request to the kernel, which holds the bus for the
+/// duration of the request. The kernel may search the bus between two requests, which
+/// breaks up transactions with the devices. The automatic search should be disabled with
+/// [`LinuxW1::disable_automatic_search`], or the `search_count=0` parameter of the `wire`
+/// kernel module.
+///
+/// Communicating with the kernel requires the `CAP_NET_ADMIN` capability.
+#[derive(Debug)]
+pub struct LinuxW1 {
+    pub(crate) socket: OwnedFd,
+    pub(crate) master: u32,
+    pub(crate) seq: u32,
+    pub(crate) buf: Vec<u8>,
+}
+
+impl LinuxW1 {
+    /// Opens the bus master `w1_bus_master<master>`.
+    ///
+    /// # Errors
+    /// Returns an error if the netlink socket could not be opened. A bus master that does
+    /// not exist is reported by the first operation on the bus.
+    pub fn new(master: u32) -> io::Result<Self> {
+        // SAFETY: The arguments are valid, and the returned descriptor is checked.
+        let fd = unsafe {
+            libc::socket(
+                libc::AF_NETLINK,
+                libc::SOCK_DGRAM | libc::SOCK_CLOEXEC,
+                libc::NETLINK_CONNECTOR,
+            )
+        };
+        if fd < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        // SAFETY: The descriptor was just opened, and is not owned by anything else.
+        let socket = unsafe { OwnedFd::from_raw_fd(fd) };
+        // SAFETY: An all-zero `sockaddr_nl` is valid.
+        let mut addr: libc::sockaddr_nl = unsafe { core::mem::zeroed() };
+        addr.nl_family = libc::AF_NETLINK as libc::sa_family_t;
+        // SAFETY: `addr` is a valid `sockaddr_nl` of the given size.
+        let res = unsafe {
+            libc::bind(
+                socket.as_raw_fd(),
+                &addr as *const libc::sockaddr_nl as *const libc::sockaddr,
+                core::mem::size_of::<libc::sockaddr_nl>() as libc::socklen_t,
+            )
+        };
+        if res < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        let mut bus = LinuxW1 {
+            socket,
+            master,
+            seq: 0,
+            buf: vec![0; RECV_LEN],
+        };
+        bus.set_timeout(Some(Duration::from_secs(10)))?;
+        Ok(bus)
+    }
+
+    /// Number of the bus master.
+    pub fn master(&self) -> u32 {
+        self.master
+    }
+
+    /// Sets how long to wait for the kernel to reply to a request, or `None` to wait
+    /// indefinitely. Defaults to 10 seconds.
+    ///
+    /// # Errors
+    /// Returns an error if the timeout could not be set on the socket.
+    pub fn set_timeout(&mut self, timeout: Option<Duration>) -> io::Result<()> {
+        let timeout = timeout.unwrap_or_default();
+        let tv = libc::timeval {
+            tv_sec: timeout.as_secs() as libc::time_t,
+            tv_usec: timeout.subsec_micros() as libc::suseconds_t,
+        };
+        // SAFETY: `tv` is a valid `timeval` of the given size.
+        let res = unsafe {
+            libc::setsockopt(
+                self.socket.as_raw_fd(),
+                libc::SOL_SOCKET,
+                libc::SO_RCVTIMEO,
+                &tv as *const libc::timeval as *const libc::c_void,
+                core::mem::size_of::<libc::timeval>() as libc::socklen_t,
+            )
+        };
+        if res < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(())
+    }
+
+    /// Disables the periodic search of the bus by the kernel, so that it does not interrupt
+    /// transactions with the devices.
+    ///
+    /// # Errors
+    /// Returns an error if the sysfs attribute of the bus master could not be written, e.g.
+    /// because of missing permissions.
+    pub fn disable_automatic_search(&self) -> io::Result<()> {
+        sysfs::set_search_count(self.master, 0)
+    }
+
+    /// Searches the ROM codes of the devices on the bus with the search algorithm of the
+    /// kernel.
+    ///
+    /// The generic [`OneWireSearch`](embedded_onewire::OneWireSearch) needs single bit
+    /// operations, which the kernel does not provide.
+    ///
+    /// # Errors
+    /// Returns an error if the search failed.
+    pub fn search(&mut self, kind: OneWireSearchKind) -> io::Result<Vec<u64>> {
+        let cmd = match kind {
+            OneWireSearchKind::Normal => W1_CMD_SEARCH,
+            OneWireSearchKind::Alarmed => W1_CMD_ALARM_SEARCH,
+        };
+        let mut data = Vec::new();
+        self.command(cmd, &[], &mut data)?;
+        Ok(data
+            .chunks_exact(8)
+            .map(|rom| u64::from_ne_bytes(rom.try_into().unwrap()))
+            .collect())
+    }
+
+    /// Sends the request for `cmd` with `data`, collects the returned data in `out`, and
+    /// returns the status of the command.
+    pub(crate) fn transact(&mut self, cmd: u8, data: &[u8], out: &mut Vec<u8>) -> io::Result<u8> {
+        self.seq = self.seq.wrapping_add(1);
+        let req = netlink::request(self.seq, self.master, cmd, data);
+        // SAFETY: `req` is a valid buffer of the given length.
+        let res = unsafe {
+            libc::send(
+                self.socket.as_raw_fd(),
+                req.as_ptr() as *const libc::c_void,
+                req.len(),
+                0,
+            )
+        };
+        if res < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        loop {
+            // SAFETY: `self.buf` is a valid buffer of the given length.
+            let len = unsafe {
+                libc::recv(
+                    self.socket.as_raw_fd(),
+                    self.buf.as_mut_ptr() as *mut libc::c_void,
+                    self.buf.len(),
+                    0,
+                )
+            };
+            if len < 0 {
+                return Err(io::Error::last_os_error());
+            }
+            for reply in netlink::replies(&self.buf[..len as usize], self.seq) {
+                match reply {
+                    Reply::Data(data) => out.extend_from_slice(data),
+                    Reply::Status(status) => return Ok(status),
+                }
+            }
+        }
+    }
+
+    /// Sends the request for `cmd` with `data`, and fails if the command failed.
+    pub(crate) fn command(&mut self, cmd: u8, data: &[u8], out: &mut Vec<u8>) -> io::Result<()> {
+        match self.transact(cmd, data, out)? {
+            0 => Ok(()),
+            errno => Err(io::Error::from_raw_os_error(errno as i32)),
+        }
+    }
+}
+
+impl OneWire for LinuxW1 {
+    type Status = ResetStatus;
+    type BusError = io::Error;
+
+    fn reset(&mut self) -> OneWireResult<Self::Status, Self::BusError> {
+        match self.transact(W1_CMD_RESET, &[], &mut Vec::new())? {
+            0 => Ok(ResetStatus),
+            // The kernel reports the absence of a presence pulse as 1, i.e. a status of -1
+            0xff => Err(OneWireError::NoDevicePresent),
+            errno => Err(OneWireError::Other(io::Error::from_raw_os_error(
+                errno as i32,
+            ))),
+        }
+    }
+
+    fn write_byte(&mut self, byte: u8) -> OneWireResult<(), Self::BusError> {
+        self.write_bytes(&[byte])
+    }
+
+    fn read_byte(&mut self) -> OneWireResult<u8, Self::BusError> {
+        let mut buf = [0];
+        self.read_bytes(&mut buf)?;
+        Ok(buf[0])
+    }
+
+    fn write_bytes(&mut self, bytes: &[u8]) -> OneWireResult<(), Self::BusError> {
+        for chunk in bytes.chunks(CHUNK) {
+            self.command(W1_CMD_WRITE, chunk, &mut Vec::new())?;
+        }
+        Ok(())
+    }
+
+    fn read_bytes(&mut self, buf: &mut [u8]) -> OneWireResult<(), Self::BusError> {
+        let mut data = Vec::with_capacity(CHUNK);
+        for chunk in buf.chunks_mut(CHUNK) {
+            data.clear();
+            // The kernel reads as many bytes as the command carries
+            self.command(W1_CMD_READ, &vec![0xff; chunk.len()], &mut data)?;
+            if data.len() != chunk.len() {
+                return Err(OneWireError::Other(io::Error::new(
+                    io::ErrorKind::UnexpectedEof,
+                    "short read from the w1 bus master",
+                )));
+            }
+            chunk.copy_from_slice(&data);
+        }
+        Ok(())
+    }
+
+    /// Not provided by the w1 netlink interface.
+    fn write_bit(&mut self, _bit: bool) -> OneWireResult<(), Self::BusError> {
+        Err(OneWireError::Unimplemented)
+    }
+
+    /// Not provided by the w1 netlink interface.
+    fn read_bit(&mut self) -> OneWireResult<bool, Self::BusError> {
+        Err(OneWireError::Unimplemented)
+    }
+
+    /// Not provided by the w1 netlink interface, see [`LinuxW1::search`].
+    #[cfg(feature = "triplet-read")]
+    fn read_triplet(
+        &mut self,
+        _direction: bool,
+    ) -> OneWireResult<(bool, bool, bool), Self::BusError> {
+        Err(OneWireError::Unimplemented)
+    }
+
+    fn get_overdrive_mode(&mut self) -> bool {
+        false
+    }
+
+    /// The bus masters of the kernel only communicate at the standard speed.
+    fn set_overdrive_mode(&mut self, enable: bool) -> OneWireResult<(), Self::BusError> {
+        if enable {
+            Err(OneWireError::BusInvalidSpeed)
+        } else {
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,88 @@
+#![deny(missing_docs)]
+#![cfg_attr(docsrs, feature(doc_cfg))]
+#![doc = include_str!("../README.md")]
+
+pub use embedded_onewire::{OneWire, OneWireError, OneWireResult, OneWireSearchKind};
+mod device;
+mod netlink;
+mod sysfs;
+
+pub use device::LinuxW1;
+pub use sysfs::{masters, slaves};
+
+/// Largest number of bytes written or read in a single request.
+pub(crate) const CHUNK: usize = 1024;
+
+/// Status of a reset of the bus, with a presence pulse.
+///
+/// The kernel does not report short circuits, and a reset without a presence pulse fails
+/// with [`OneWireError::NoDevicePresent`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ResetStatus;
+
+impl embedded_onewire::OneWireStatus for ResetStatus {
+    fn presence(&self) -> bool {
+        true
+    }
+
+    fn shortcircuit(&self) -> bool {
+        false
+    }
+}
+
+mod test {
+    #[test]
+    fn test_request() {
+        use super::netlink::*;
+
+        let req = request(7, 1, W1_CMD_WRITE, &[0xcc, 0x44]);
+        assert_eq!(req.len(), 54);
+        assert_eq!(&req[..4], &54u32.to_ne_bytes());
+        assert_eq!(&req[4..6], &NLMSG_DONE.to_ne_bytes());
+        assert_eq!(&req[8..12], &7u32.to_ne_bytes());
+        // Connector message
+        assert_eq!(&req[16..20], &CN_W1_IDX.to_ne_bytes());
+        assert_eq!(&req[20..24], &CN_W1_VAL.to_ne_bytes());
+        assert_eq!(&req[24..28], &7u32.to_ne_bytes());
+        assert_eq!(&req[32..34], &18u16.to_ne_bytes());
+        // w1 message
+        assert_eq!(req[36], W1_MASTER_CMD);
+        assert_eq!(&req[38..40], &6u16.to_ne_bytes());
+        assert_eq!(&req[40..44], &1u32.to_ne_bytes());
+        // w1 command
+        assert_eq!(req[48], W1_CMD_WRITE);
+        assert_eq!(&req[50..52], &2u16.to_ne_bytes());
+        assert_eq!(&req[52..], &[0xcc, 0x44]);
+    }
+
+    #[test]
+    fn test_replies() {
+        use super::netlink::*;
+
+        // The kernel echoes the command with the data read, and then its status
+        let status = |seq, status| {
+            let mut reply = request(seq, 1, W1_CMD_READ, &[]);
+            reply[W1_MSG_OFFSET + 1] = status;
+            reply
+        };
+        let mut buf = request(3, 1, W1_CMD_READ, &[0x12, 0x34, 0x56]);
+        buf.push(0); // Padding
+        buf.extend(status(2, 0));
+        buf.extend(status(3, 0));
+        assert_eq!(
+            replies(&buf, 3),
+            [Reply::Data(&[0x12, 0x34, 0x56]), Reply::Status(0)]
+        );
+        assert_eq!(replies(&status(4, 19), 4), [Reply::Status(19)]);
+        assert!(replies(&buf[..20], 3).is_empty());
+    }
+
+    #[test]
+    fn test_parse_slave() {
+        use super::sysfs::parse_slave;
+
+        assert_eq!(parse_slave("28-000001020304\n"), Some(0x2000000102030428));
+        assert_eq!(parse_slave("28-0102"), None);
+        assert_eq!(parse_slave("w1_bus_master1"), None);
+    }
+}
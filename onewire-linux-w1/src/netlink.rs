@@ -0,0 +1,139 @@
+//! Messages of the w1 netlink protocol, see `Documentation/w1/w1-netlink.rst` in the Linux
+//! kernel sources.
+//!
+//! A request is a netlink message carrying a connector message, which carries a w1 message
+//! addressed to a bus master, which in turn carries a w1 command. The kernel replies with the
+//! data of read commands, followed by a status message for every command. All fields are in
+//! the native byte order.
+
+/// Size of the netlink message header.
+pub(crate) const NLMSG_HDR_LEN: usize = 16;
+/// Size of the connector message header.
+pub(crate) const CN_MSG_LEN: usize = 20;
+/// Size of the w1 message header.
+pub(crate) const W1_MSG_LEN: usize = 12;
+/// Size of the w1 command header.
+pub(crate) const W1_CMD_LEN: usize = 4;
+/// Offset of the first w1 message in a netlink message.
+pub(crate) const W1_MSG_OFFSET: usize = NLMSG_HDR_LEN + CN_MSG_LEN;
+
+/// Netlink message type of a message that is not part of a multipart message.
+pub(crate) const NLMSG_DONE: u16 = 3;
+/// Connector index of the w1 subsystem.
+pub(crate) const CN_W1_IDX: u32 = 3;
+/// Connector value of the w1 subsystem.
+pub(crate) const CN_W1_VAL: u32 = 1;
+
+/// w1 message addressed to a bus master.
+pub(crate) const W1_MASTER_CMD: u8 = 4;
+
+/// Read bytes from the bus.
+pub(crate) const W1_CMD_READ: u8 = 0;
+/// Write bytes to the bus.
+pub(crate) const W1_CMD_WRITE: u8 = 1;
+/// Search the ROM codes of the devices on the bus.
+pub(crate) const W1_CMD_SEARCH: u8 = 2;
+/// Search the ROM codes of the devices in an alarm state.
+pub(crate) const W1_CMD_ALARM_SEARCH: u8 = 3;
+/// Reset the bus.
+pub(crate) const W1_CMD_RESET: u8 = 5;
+
+/// Build the request for `cmd` with `data` to the bus master `master`.
+///
+/// The sequence number `seq` identifies the replies of the kernel to the request.
+pub(crate) fn request(seq: u32, master: u32, cmd: u8, data: &[u8]) -> Vec<u8> {
+    let cmd_len = W1_CMD_LEN + data.len();
+    let msg_len = W1_MSG_LEN + cmd_len;
+    let cn_len = CN_MSG_LEN + msg_len;
+    let len = NLMSG_HDR_LEN + cn_len;
+    let mut buf = Vec::with_capacity(len);
+    // Netlink message header
+    buf.extend_from_slice(&(len as u32).to_ne_bytes());
+    buf.extend_from_slice(&NLMSG_DONE.to_ne_bytes());
+    buf.extend_from_slice(&0u16.to_ne_bytes()); // flags
+    buf.extend_from_slice(&seq.to_ne_bytes());
+    buf.extend_from_slice(&0u32.to_ne_bytes()); // port, filled in by the kernel
+    // Connector message header
+    buf.extend_from_slice(&CN_W1_IDX.to_ne_bytes());
+    buf.extend_from_slice(&CN_W1_VAL.to_ne_bytes());
+    buf.extend_from_slice(&seq.to_ne_bytes());
+    buf.extend_from_slice(&0u32.to_ne_bytes()); // ack
+    buf.extend_from_slice(&(msg_len as u16).to_ne_bytes());
+    buf.extend_from_slice(&0u16.to_ne_bytes()); // flags
+    // w1 message header
+    buf.push(W1_MASTER_CMD);
+    buf.push(0); // status
+    buf.extend_from_slice(&(cmd_len as u16).to_ne_bytes());
+    buf.extend_from_slice(&master.to_ne_bytes());
+    buf.extend_from_slice(&0u32.to_ne_bytes()); // reserved
+    // w1 command
+    buf.push(cmd);
+    buf.push(0); // reserved
+    buf.extend_from_slice(&(data.len() as u16).to_ne_bytes());
+    buf.extend_from_slice(data);
+    buf
+}
+
+/// A reply of the kernel to a request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Reply<'a> {
+    /// Data of a read or search command.
+    Data(&'a [u8]),
+    /// Completion of a command, with the error number of the failure, or 0 on success.
+    Status(u8),
+}
+
+fn u16_at(buf: &[u8], offset: usize) -> Option<u16> {
+    Some(u16::from_ne_bytes(
+        buf.get(offset..offset + 2)?.try_into().ok()?,
+    ))
+}
+
+fn u32_at(buf: &[u8], offset: usize) -> Option<u32> {
+    Some(u32::from_ne_bytes(
+        buf.get(offset..offset + 4)?.try_into().ok()?,
+    ))
+}
+
+/// Parse the replies to the request with the sequence number `seq` in the datagram `buf`.
+///
+/// Replies to other requests, and messages of other connector users, are skipped.
+pub(crate) fn replies(buf: &[u8], seq: u32) -> Vec<Reply<'_>> {
+    let mut out = Vec::new();
+    let mut rest = buf;
+    while let Some(len) = u32_at(rest, 0) {
+        let len = len as usize;
+        if len < NLMSG_HDR_LEN || len > rest.len() {
+            break;
+        }
+        let (msg, next) = rest.split_at(len);
+        // Netlink messages are aligned to 4 bytes
+        rest = next.get(len.next_multiple_of(4) - len..).unwrap_or(&[]);
+        if u32_at(msg, NLMSG_HDR_LEN) != Some(CN_W1_IDX)
+            || u32_at(msg, NLMSG_HDR_LEN + 4) != Some(CN_W1_VAL)
+            || u32_at(msg, NLMSG_HDR_LEN + 8) != Some(seq)
+        {
+            continue;
+        }
+        let Some(cn_len) = u16_at(msg, NLMSG_HDR_LEN + 16) else {
+            continue;
+        };
+        let end = (W1_MSG_OFFSET + cn_len as usize).min(msg.len());
+        let mut w1 = &msg[W1_MSG_OFFSET.min(end)..end];
+        while w1.len() >= W1_MSG_LEN {
+            let status = w1[1];
+            let w1_len = u16_at(w1, 2).unwrap_or_default() as usize;
+            let data = &w1[W1_MSG_LEN..(W1_MSG_LEN + w1_len).min(w1.len())];
+            w1 = &w1[W1_MSG_LEN + data.len()..];
+            match u16_at(data, 2) {
+                // The status of a command carries the command header without its data
+                Some(0) | None => out.push(Reply::Status(status)),
+                Some(cmd_len) => {
+                    let end = (W1_CMD_LEN + cmd_len as usize).min(data.len());
+                    out.push(Reply::Data(&data[W1_CMD_LEN..end]));
+                }
+            }
+        }
+    }
+    out
+}
@@ -0,0 +1,73 @@
+//! Bus masters and devices listed by the w1 subsystem in sysfs.
+
+use embedded_onewire::OneWireCrc;
+use std::io;
+use std::path::PathBuf;
+
+/// Directory of the w1 bus masters and devices.
+pub(crate) const W1_DEVICES: &str = "/sys/bus/w1/devices";
+/// Prefix of the bus master directories.
+pub(crate) const MASTER_PREFIX: &str = "w1_bus_master";
+
+fn master_dir(master: u32) -> PathBuf {
+    PathBuf::from(W1_DEVICES).join(format!("{MASTER_PREFIX}{master}"))
+}
+
+/// Lists the numbers of the w1 bus masters registered with the kernel, in ascending order.
+///
+/// # Errors
+/// Returns an error if the w1 subsystem is not available, e.g. because the `wire` kernel
+/// module is not loaded.
+pub fn masters() -> io::Result<Vec<u32>> {
+    let mut masters = std::fs::read_dir(W1_DEVICES)?
+        .filter_map(|entry| {
+            entry
+                .ok()?
+                .file_name()
+                .to_str()?
+                .strip_prefix(MASTER_PREFIX)?
+                .parse()
+                .ok()
+        })
+        .collect::<Vec<u32>>();
+    masters.sort_unstable();
+    Ok(masters)
+}
+
+/// Lists the ROM codes of the devices the kernel found on the bus master `master` during
+/// its last search.
+///
+/// # Errors
+/// Returns an error if the bus master does not exist.
+pub fn slaves(master: u32) -> io::Result<Vec<u64>> {
+    let list = std::fs::read_to_string(master_dir(master).join("w1_master_slaves"))?;
+    Ok(list.lines().filter_map(parse_slave).collect())
+}
+
+/// Sets the number of times the kernel searches the bus master `master`, where `-1` searches
+/// continuously and `0` disables the search.
+pub(crate) fn set_search_count(master: u32, count: i32) -> io::Result<()> {
+    std::fs::write(
+        master_dir(master).join("w1_master_search"),
+        count.to_string(),
+    )
+}
+
+/// Parses the ROM code of a device from its sysfs name `ff-dddddddddddd`, of the family code
+/// and the serial number in hexadecimal.
+///
+/// The CRC is not part of the name, and is computed.
+pub(crate) fn parse_slave(name: &str) -> Option<u64> {
+    let (family, serial) = name.trim().split_once('-')?;
+    if family.len() != 2 || serial.len() != 12 {
+        return None;
+    }
+    let family = u8::from_str_radix(family, 16).ok()?;
+    let serial = u64::from_str_radix(serial, 16).ok()?;
+    let rom = (serial << 8) | family as u64;
+    let mut crc = OneWireCrc::default();
+    rom.to_le_bytes()[..7]
+        .iter()
+        .for_each(|&byte| crc.update(byte));
+    Some(rom | (crc.value() as u64) << 56)
+}
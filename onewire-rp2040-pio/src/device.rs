@@ -0,0 +1,225 @@
+use crate::{
+    OVERDRIVE_HZ, RESET_WORD, ResetStatus, STANDARD_HZ, bits_read, bits_word, divisor, program,
+};
+use core::convert::Infallible;
+use embedded_onewire::{
+    OneWire, OneWireError, OneWireResult, OneWireStatus, OneWireStrongPullup,
+    consts::ONEWIRE_SKIP_ROM_CMD_OD,
+};
+use pio::{Instruction, InstructionOperands, SetDestination};
+use rp2040_hal::gpio::{DynPinId, DynPullType, Pin, PinId, PullType};
+use rp2040_hal::pio::{
+    InstallError, PIO, PIOBuilder, PIOExt, PinDir, PinState, Running, Rx, ShiftDirection,
+    StateMachine, StateMachineIndex, Tx, UninitStateMachine,
+};
+
+/// The 1-Wire pin, with its type erased.
+pub type OneWirePin<P> = Pin<DynPinId, <P as PIOExt>::PinFunction, DynPullType>;
+
+/// A 1-Wire bus master running on a PIO state machine of the RP2040.
+///
+/// The state machine generates the timing of the resets and the time slots, so the CPU only
+/// exchanges words with it through the FIFOs, and interrupts do not disturb the timing. The
+/// line is driven low by switching the pin to an output, and released by switching it back
+/// to an input, so an external pullup resistor is required.
+pub struct PioOneWire<P: PIOExt, SM: StateMachineIndex> {
+    pub(crate) sm: StateMachine<(P, SM), Running>,
+    pub(crate) rx: Rx<(P, SM)>,
+    pub(crate) tx: Tx<(P, SM)>,
+    pub(crate) pin: OneWirePin<P>,
+    pub(crate) standard: (u16, u8),
+    pub(crate) fast: (u16, u8),
+    pub(crate) overdrive: bool,
+    pub(crate) pulse: bool,
+}
+
+impl<P: PIOExt, SM: StateMachineIndex> PioOneWire<P, SM> {
+    /// Installs the 1-Wire program on `pio`, and starts it on the state machine `sm` with the
+    /// 1-Wire line on `pin`.
+    ///
+    /// # Arguments
+    /// * `pio` - The PIO block of the state machine.
+    /// * `sm` - The state machine.
+    /// * `pin` - The pin of the 1-Wire line, set to the function of the PIO block.
+    /// * `sys_clk_hz` - Frequency of the system clock, in Hz.
+    ///
+    /// # Errors
+    /// Returns an error if the PIO block does not have space for the program, which takes
+    /// 30 of the 32 instructions.
+    pub fn new<I: PinId, PU: PullType>(
+        pio: &mut PIO<P>,
+        sm: UninitStateMachine<(P, SM)>,
+        pin: Pin<I, P::PinFunction, PU>,
+        sys_clk_hz: u32,
+    ) -> Result<Self, InstallError> {
+        let installed = pio.install(&program())?;
+        let pin = pin.into_dyn_pin().into_pull_type::<DynPullType>();
+        let id = pin.id().num;
+        let standard = divisor(sys_clk_hz, STANDARD_HZ);
+        let (mut sm, rx, tx) = PIOBuilder::from_installed_program(installed)
+            .set_pins(id, 1)
+            .side_set_pin_base(id)
+            .in_pin_base(id)
+            .in_shift_direction(ShiftDirection::Right)
+            .out_shift_direction(ShiftDirection::Right)
+            .clock_divisor_fixed_point(standard.0, standard.1)
+            .build(sm);
+        sm.set_pins([(id, PinState::Low)]);
+        sm.set_pindirs([(id, PinDir::Input)]);
+        Ok(PioOneWire {
+            sm: sm.start(),
+            rx,
+            tx,
+            pin,
+            standard,
+            fast: divisor(sys_clk_hz, OVERDRIVE_HZ),
+            overdrive: false,
+            pulse: false,
+        })
+    }
+
+    /// Stops the state machine, and uninstalls the program.
+    ///
+    /// # Returns
+    /// The state machine and the pin.
+    pub fn release(mut self, pio: &mut PIO<P>) -> (UninitStateMachine<(P, SM)>, OneWirePin<P>) {
+        self.end_pulse();
+        let (sm, installed) = self.sm.stop().uninit(self.rx, self.tx);
+        pio.uninstall(installed);
+        (sm, self.pin)
+    }
+
+    /// Executes a `set` instruction on the state machine, stalled on an empty FIFO.
+    pub(crate) fn set(&mut self, destination: SetDestination, data: u8) {
+        self.sm.exec_instruction(Instruction {
+            operands: InstructionOperands::SET { destination, data },
+            delay: 0,
+            side_set: None,
+        });
+    }
+
+    /// Drives the line high.
+    pub(crate) fn start_pulse(&mut self) {
+        self.set(SetDestination::PINS, 1);
+        self.set(SetDestination::PINDIRS, 1);
+        self.pulse = true;
+    }
+
+    /// Releases the line if it is driven high.
+    pub(crate) fn end_pulse(&mut self) {
+        if self.pulse {
+            self.set(SetDestination::PINDIRS, 0);
+            self.set(SetDestination::PINS, 0);
+            self.pulse = false;
+        }
+    }
+
+    /// Sets the clock divisor for the speed of the bus.
+    pub(crate) fn set_speed(&mut self, overdrive: bool) {
+        let (int, frac) = if overdrive { self.fast } else { self.standard };
+        self.sm.clock_divisor_fixed_point(int, frac);
+        self.overdrive = overdrive;
+    }
+
+    /// Writes `word` to the state machine, and returns its response.
+    fn transfer(&mut self, word: u32) -> u32 {
+        self.end_pulse();
+        while !self.tx.write(word) {}
+        loop {
+            if let Some(word) = self.rx.read() {
+                return word;
+            }
+        }
+    }
+
+    /// Runs `count` time slots writing `bits`, and returns the bits read.
+    pub(crate) fn bits(&mut self, bits: u8, count: u32) -> u8 {
+        bits_read(self.transfer(bits_word(bits, count)), count)
+    }
+}
+
+impl<P: PIOExt, SM: StateMachineIndex> OneWire for PioOneWire<P, SM> {
+    type Status = ResetStatus;
+
+    type BusError = Infallible;
+
+    fn reset(&mut self) -> OneWireResult<Self::Status, Self::BusError> {
+        let status = ResetStatus::parse(self.transfer(RESET_WORD));
+        if status.shortcircuit() {
+            Err(OneWireError::ShortCircuit)
+        } else if !status.presence() {
+            Err(OneWireError::NoDevicePresent)
+        } else {
+            Ok(status)
+        }
+    }
+
+    fn write_byte(&mut self, byte: u8) -> OneWireResult<(), Self::BusError> {
+        self.bits(byte, 8);
+        Ok(())
+    }
+
+    fn read_byte(&mut self) -> OneWireResult<u8, Self::BusError> {
+        Ok(self.bits(0xff, 8))
+    }
+
+    fn write_bit(&mut self, bit: bool) -> OneWireResult<(), Self::BusError> {
+        self.bits(bit as u8, 1);
+        Ok(())
+    }
+
+    fn read_bit(&mut self) -> OneWireResult<bool, Self::BusError> {
+        Ok(self.bits(1, 1) != 0)
+    }
+
+    #[cfg(feature = "triplet-read")]
+    fn read_triplet(
+        &mut self,
+        direction: bool,
+    ) -> OneWireResult<(bool, bool, bool), Self::BusError> {
+        // Both read slots in one word
+        let read = self.bits(0b11, 2);
+        let id_bit = read & 0b01 != 0;
+        let complement_bit = read & 0b10 != 0;
+        if id_bit && complement_bit {
+            // No device responded
+            return Ok((true, true, true));
+        }
+        let taken = if id_bit != complement_bit {
+            id_bit
+        } else {
+            direction
+        };
+        self.bits(taken as u8, 1);
+        Ok((id_bit, complement_bit, taken))
+    }
+
+    fn get_overdrive_mode(&mut self) -> bool {
+        self.overdrive
+    }
+
+    fn set_overdrive_mode(&mut self, enable: bool) -> OneWireResult<(), Self::BusError> {
+        if enable == self.overdrive {
+            return Ok(()); // No change needed
+        }
+        if enable {
+            self.reset()?;
+            self.write_byte(ONEWIRE_SKIP_ROM_CMD_OD)?;
+            self.set_speed(true);
+        } else {
+            self.set_speed(false);
+            // A reset at standard speed returns the devices to standard speed
+            self.reset()?;
+        }
+        Ok(())
+    }
+}
+
+impl<P: PIOExt, SM: StateMachineIndex> OneWireStrongPullup for PioOneWire<P, SM> {
+    fn write_byte_powered(&mut self, byte: u8) -> OneWireResult<(), Self::BusError> {
+        self.bits(byte, 8);
+        // The line is driven high until the next operation
+        self.start_pulse();
+        Ok(())
+    }
+}
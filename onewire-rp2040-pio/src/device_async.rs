@@ -0,0 +1,121 @@
+use crate::{PioOneWire, RESET_WORD, ResetStatus, bits_read, bits_word};
+use core::convert::Infallible;
+use core::future::poll_fn;
+use core::task::Poll;
+use embedded_onewire::{
+    OneWireAsync, OneWireError, OneWireResult, OneWireStatus, OneWireStrongPullupAsync,
+    consts::ONEWIRE_SKIP_ROM_CMD_OD,
+};
+use rp2040_hal::pio::{PIOExt, StateMachineIndex};
+
+impl<P: PIOExt, SM: StateMachineIndex> PioOneWire<P, SM> {
+    /// Writes `word` to the state machine, and returns its response.
+    ///
+    /// Yields to the executor while the state machine is busy.
+    async fn transfer_async(&mut self, word: u32) -> u32 {
+        self.end_pulse();
+        poll_fn(|cx| {
+            if self.tx.write(word) {
+                Poll::Ready(())
+            } else {
+                cx.waker().wake_by_ref();
+                Poll::Pending
+            }
+        })
+        .await;
+        poll_fn(|cx| match self.rx.read() {
+            Some(word) => Poll::Ready(word),
+            None => {
+                cx.waker().wake_by_ref();
+                Poll::Pending
+            }
+        })
+        .await
+    }
+
+    async fn bits_async(&mut self, bits: u8, count: u32) -> u8 {
+        bits_read(self.transfer_async(bits_word(bits, count)).await, count)
+    }
+}
+
+impl<P: PIOExt, SM: StateMachineIndex> OneWireAsync for PioOneWire<P, SM> {
+    type Status = ResetStatus;
+
+    type BusError = Infallible;
+
+    async fn reset(&mut self) -> OneWireResult<Self::Status, Self::BusError> {
+        let status = ResetStatus::parse(self.transfer_async(RESET_WORD).await);
+        if status.shortcircuit() {
+            Err(OneWireError::ShortCircuit)
+        } else if !status.presence() {
+            Err(OneWireError::NoDevicePresent)
+        } else {
+            Ok(status)
+        }
+    }
+
+    async fn write_byte(&mut self, byte: u8) -> OneWireResult<(), Self::BusError> {
+        self.bits_async(byte, 8).await;
+        Ok(())
+    }
+
+    async fn read_byte(&mut self) -> OneWireResult<u8, Self::BusError> {
+        Ok(self.bits_async(0xff, 8).await)
+    }
+
+    async fn write_bit(&mut self, bit: bool) -> OneWireResult<(), Self::BusError> {
+        self.bits_async(bit as u8, 1).await;
+        Ok(())
+    }
+
+    async fn read_bit(&mut self) -> OneWireResult<bool, Self::BusError> {
+        Ok(self.bits_async(1, 1).await != 0)
+    }
+
+    #[cfg(feature = "triplet-read")]
+    async fn read_triplet(
+        &mut self,
+        direction: bool,
+    ) -> OneWireResult<(bool, bool, bool), Self::BusError> {
+        let read = self.bits_async(0b11, 2).await;
+        let id_bit = read & 0b01 != 0;
+        let complement_bit = read & 0b10 != 0;
+        if id_bit && complement_bit {
+            return Ok((true, true, true));
+        }
+        let taken = if id_bit != complement_bit {
+            id_bit
+        } else {
+            direction
+        };
+        self.bits_async(taken as u8, 1).await;
+        Ok((id_bit, complement_bit, taken))
+    }
+
+    fn get_overdrive_mode(&mut self) -> bool {
+        self.overdrive
+    }
+
+    async fn set_overdrive_mode(&mut self, enable: bool) -> OneWireResult<(), Self::BusError> {
+        if enable == self.overdrive {
+            return Ok(());
+        }
+        if enable {
+            OneWireAsync::reset(self).await?;
+            OneWireAsync::write_byte(self, ONEWIRE_SKIP_ROM_CMD_OD).await?;
+            self.set_speed(true);
+        } else {
+            self.set_speed(false);
+            OneWireAsync::reset(self).await?;
+        }
+        Ok(())
+    }
+}
+
+impl<P: PIOExt, SM: StateMachineIndex> OneWireStrongPullupAsync for PioOneWire<P, SM> {
+    async fn write_byte_powered(&mut self, byte: u8) -> OneWireResult<(), Self::BusError> {
+        self.bits_async(byte, 8).await;
+        self.start_pulse();
+        Ok(())
+    }
+}
@@ -0,0 +1,169 @@
+#![no_std]
+#![deny(missing_docs)]
+#![cfg_attr(docsrs, feature(doc_cfg))]
+#![doc = include_str!("../README.md")]
+
+pub use embedded_onewire::{
+    OneWire, OneWireAsync, OneWireError, OneWireResult, OneWireStrongPullup,
+    OneWireStrongPullupAsync,
+};
+mod device;
+mod device_async;
+
+pub use device::PioOneWire;
+
+/// Frequency of the state machine at standard speed, in Hz. A cycle lasts 1 µs.
+pub(crate) const STANDARD_HZ: u32 = 1_000_000;
+/// Frequency of the state machine at overdrive speed, in Hz. A cycle lasts 1/7 µs.
+pub(crate) const OVERDRIVE_HZ: u32 = 7_000_000;
+
+/// The 1-Wire program of the state machine.
+///
+/// The program drives the line low by switching the pin to an output, with its output
+/// value held low, and releases the line by switching the pin back to an input. Timings are
+/// in cycles, which last 1 µs at standard speed and 1/7 µs at overdrive speed:
+///
+/// | Timing                   | Cycles | Standard | Overdrive |
+/// |--------------------------|--------|----------|-----------|
+/// | Reset low                | 480    | 480 µs   | 68.6 µs   |
+/// | Presence sample          | 68     | 68 µs    | 9.7 µs    |
+/// | Reset high               | 487    | 487 µs   | 69.6 µs   |
+/// | Write 1, read low        | 8      | 8 µs     | 1.1 µs    |
+/// | Read sample              | 13     | 13 µs    | 1.9 µs    |
+/// | Write 0 low              | 61     | 61 µs    | 8.7 µs    |
+/// | Time slot                | 75     | 75 µs    | 10.7 µs   |
+///
+/// Every word written to the state machine is a reset if bit 0 is set. Otherwise, bits 1-5
+/// hold the number of time slots minus one, and the bits to write follow from bit 6, least
+/// significant bit first. A bit is read in every time slot, and the bits read are returned
+/// in the most significant bits of a word. A reset returns the presence sample in bit 30,
+/// and the level of the line at its end in bit 31.
+pub(crate) fn program() -> pio::Program<{ pio::RP2040_MAX_PROGRAM_SIZE }> {
+    pio::pio_asm!(
+        ".side_set 1 opt pindirs",
+        ".wrap_target",
+        "start:",
+        "    pull block",
+        "    out x, 1",
+        "    jmp !x bits",
+        "    set x, 31          side 1 [7]",
+        "reset_low:",
+        "    jmp x-- reset_low         [7]",
+        "    set x, 25                 [7]",
+        "reset_low2:",
+        "    jmp x-- reset_low2        [7]",
+        "    set x, 6           side 0 [7]",
+        "presence:",
+        "    jmp x-- presence          [7]",
+        "    nop                       [3]",
+        "    in pins, 1",
+        "    set x, 31                 [7]",
+        "reset_high:",
+        "    jmp x-- reset_high        [7]",
+        "    set x, 17                 [7]",
+        "reset_high2:",
+        "    jmp x-- reset_high2       [7]",
+        "    in pins, 1",
+        "    push",
+        "    jmp start",
+        "bits:",
+        "    out y, 5",
+        "bit:",
+        "    out x, 1           side 1 [6]",
+        "    jmp !x zero        side 1",
+        "    nop                side 0 [4]",
+        "sample:",
+        "    in pins, 1                [7]",
+        "    set x, 5                  [3]",
+        "slot:",
+        "    jmp x-- slot              [5]",
+        "    nop                side 0 [7]",
+        "    jmp y-- bit               [5]",
+        "    push",
+        ".wrap",
+        "zero:",
+        "    nop                side 1 [3]",
+        "    jmp sample         side 1",
+    )
+    .program
+}
+
+/// Word requesting a reset of the bus.
+pub(crate) const RESET_WORD: u32 = 1;
+
+/// Word requesting `count` time slots, writing the bits of `bits`, least significant bit
+/// first. Read time slots write 1.
+pub(crate) fn bits_word(bits: u8, count: u32) -> u32 {
+    ((count - 1) << 1) | ((bits as u32) << 6)
+}
+
+/// Bits read in `count` time slots, from the word returned by the state machine.
+pub(crate) fn bits_read(word: u32, count: u32) -> u8 {
+    (word >> (32 - count)) as u8
+}
+
+/// Clock divisor of the state machine running at `freq` from a system clock at `sys_hz`,
+/// as the integer and fractional parts of a 16.8 fixed point number.
+pub(crate) fn divisor(sys_hz: u32, freq: u32) -> (u16, u8) {
+    let div = ((sys_hz as u64) << 8) / freq as u64;
+    ((div >> 8) as u16, div as u8)
+}
+
+/// Status of a reset of the bus.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ResetStatus {
+    presence: bool,
+    short: bool,
+}
+
+impl ResetStatus {
+    /// Parse the word returned by the state machine for a reset.
+    pub(crate) fn parse(word: u32) -> Self {
+        ResetStatus {
+            presence: word & (1 << 30) == 0,
+            short: word & (1 << 31) == 0,
+        }
+    }
+}
+
+impl embedded_onewire::OneWireStatus for ResetStatus {
+    fn presence(&self) -> bool {
+        self.presence
+    }
+
+    fn shortcircuit(&self) -> bool {
+        self.short
+    }
+}
+
+mod test {
+    #[test]
+    fn test_program() {
+        use super::*;
+
+        let program = program();
+        assert!(program.code.len() <= pio::RP2040_MAX_PROGRAM_SIZE);
+        assert_eq!(program.wrap.source as usize, program.code.len() - 3);
+        assert_eq!(program.wrap.target, 0);
+    }
+
+    #[test]
+    fn test_words() {
+        use super::*;
+
+        assert_eq!(bits_word(0x44, 8), 0x110e);
+        assert_eq!(bits_word(1, 1), 0x40);
+        assert_eq!(bits_word(0b11, 2), 0xc2);
+        assert_eq!(bits_read(0x5a00_0000, 8), 0x5a);
+        assert_eq!(bits_read(0x8000_0000, 1), 1);
+        assert_eq!(bits_read(0x4000_0000, 2), 0b01);
+        assert_eq!(divisor(125_000_000, STANDARD_HZ), (125, 0));
+        assert_eq!(divisor(125_000_000, OVERDRIVE_HZ), (17, 219));
+        let status = ResetStatus::parse(0x8000_0000);
+        assert!(status.presence && !status.short);
+        let status = ResetStatus::parse(0xc000_0000);
+        assert!(!status.presence && !status.short);
+        let status = ResetStatus::parse(0);
+        assert!(status.short);
+    }
+}